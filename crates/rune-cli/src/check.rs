@@ -1,6 +1,6 @@
-use crate::{visitor, Config, ExitCode, Io, SharedFlags};
+use crate::{visitor, Config, DiagnosticFormat, ExitCode, Io, SharedFlags};
 use anyhow::{Context, Result};
-use rune::compile::FileSourceLoader;
+use rune::compile::{BuildTimings, FileSourceLoader};
 use rune::{Diagnostics, Options, Source, Sources};
 use std::io::Write;
 use std::path::Path;
@@ -23,7 +23,10 @@ pub(crate) fn run(
     options: &Options,
     path: &Path,
 ) -> Result<ExitCode> {
-    writeln!(io.stdout, "Checking: {}", path.display())?;
+    // Keep stdout parseable when diagnostics are emitted as JSON.
+    if flags.shared.diagnostic_format == DiagnosticFormat::Human {
+        writeln!(io.stdout, "Checking: {}", path.display())?;
+    }
 
     let context = flags.shared.context(c)?;
 
@@ -43,15 +46,28 @@ pub(crate) fn run(
     let mut test_finder = visitor::FunctionVisitor::new(visitor::Attribute::None);
     let mut source_loader = FileSourceLoader::new();
 
-    let _ = rune::prepare(&mut sources)
+    let mut timings = BuildTimings::default();
+
+    let mut build = rune::prepare(&mut sources)
         .with_context(&context)
         .with_diagnostics(&mut diagnostics)
         .with_options(options)
         .with_visitor(&mut test_finder)
-        .with_source_loader(&mut source_loader)
-        .build();
+        .with_source_loader(&mut source_loader);
+
+    if flags.shared.timings {
+        build = build.with_progress(&mut timings);
+    }
 
-    diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+    let result = build.build();
+
+    flags
+        .shared
+        .emit_diagnostics(&mut io.stdout.lock(), &diagnostics, &sources)?;
+
+    if flags.shared.timings && result.is_ok() {
+        write!(io.stdout, "{}", timings)?;
+    }
 
     if diagnostics.has_error() || flags.warnings_are_errors && diagnostics.has_warning() {
         Ok(ExitCode::Failure)
@@ -1,12 +1,12 @@
 use crate::{visitor, Args, Io};
 use anyhow::{anyhow, Context as _, Result};
-use rune::compile::{FileSourceLoader, Item};
+use rune::compile::{BuildTimings, FileSourceLoader, Item};
 use rune::Diagnostics;
 use rune::{Context, Hash, Options, Source, Sources, Unit};
 use std::collections::VecDeque;
 use std::ffi::OsStr;
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::{path::Path, sync::Arc};
 use tracing::{error, trace};
 
@@ -69,17 +69,28 @@ pub(crate) fn load(
             let mut functions = visitor::FunctionVisitor::new(attribute);
             let mut source_loader = FileSourceLoader::new();
 
-            let result = rune::prepare(&mut sources)
+            let mut timings = BuildTimings::default();
+
+            let mut build = rune::prepare(&mut sources)
                 .with_context(context)
                 .with_diagnostics(&mut diagnostics)
                 .with_options(options)
                 .with_visitor(&mut functions)
-                .with_source_loader(&mut source_loader)
-                .build();
+                .with_source_loader(&mut source_loader);
+
+            if shared.timings {
+                build = build.with_progress(&mut timings);
+            }
 
-            diagnostics.emit(io.stdout, &sources)?;
+            let result = build.build();
+
+            shared.emit_diagnostics(io.stdout, &diagnostics, &sources)?;
             let unit = result?;
 
+            if shared.timings {
+                write!(io.stdout, "{}", timings)?;
+            }
+
             if options.bytecode {
                 trace!("serializing cache: {}", bytecode_path.display());
                 let f = fs::File::create(&bytecode_path)?;
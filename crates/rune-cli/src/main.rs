@@ -51,13 +51,15 @@
 
 use anyhow::{anyhow, Result};
 use rune::compile::ParseOptionError;
+use rune::diagnostics::EmitError;
 use rune::termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 use rune::workspace::WorkspaceFilter;
-use rune::{Context, ContextError, Options};
+use rune::{Context, ContextError, Diagnostics, Options, Sources};
 use rune_modules::capture_io::CaptureIo;
 use std::error::Error;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use structopt::StructOpt;
 use tracing_subscriber::filter::EnvFilter;
 
@@ -194,6 +196,18 @@ struct SharedFlags {
     #[structopt(long)]
     warnings: bool,
 
+    /// Display a summary of where time was spent while building.
+    #[structopt(long)]
+    timings: bool,
+
+    /// The format to report diagnostics in.
+    ///
+    /// Valid options are:
+    /// * `human` - formatted diagnostics with source snippets.
+    /// * `json` - a single machine-readable JSON document.
+    #[structopt(long, default_value = "human")]
+    diagnostic_format: DiagnosticFormat,
+
     /// Set the given compiler option (see `--help` for available options).
     ///
     /// memoize-instance-fn[=<true/false>] - Inline the lookup of an instance function where appropriate.
@@ -204,6 +218,8 @@ struct SharedFlags {
     ///
     /// macros[=<true/false>] - Enable or disable macros (experimental).
     ///
+    /// const-big-integers[=<true/false>] - Evaluate constant integer arithmetic with arbitrary precision.
+    ///
     /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental).
     #[structopt(name = "option", short = "O", number_of_values = 1)]
     compiler_options: Vec<String>,
@@ -234,6 +250,25 @@ struct SharedFlags {
     paths: Vec<PathBuf>,
 }
 
+/// The format diagnostics are reported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiagnosticFormat {
+    Human,
+    Json,
+}
+
+impl FromStr for DiagnosticFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unsupported diagnostic format `{}`", s)),
+        }
+    }
+}
+
 struct Package {
     /// The name of the package the path belongs to.
     name: Box<str>,
@@ -272,6 +307,22 @@ impl SharedFlags {
         Ok(context)
     }
 
+    /// Emit diagnostics in the format requested by the user.
+    fn emit_diagnostics<O>(
+        &self,
+        out: &mut O,
+        diagnostics: &Diagnostics,
+        sources: &Sources,
+    ) -> Result<(), EmitError>
+    where
+        O: WriteColor,
+    {
+        match self.diagnostic_format {
+            DiagnosticFormat::Human => diagnostics.emit(out, sources),
+            DiagnosticFormat::Json => diagnostics.emit_json(out, sources),
+        }
+    }
+
     /// Setup a context that captures output.
     fn context_with_capture(&self, c: &Config, io: &CaptureIo) -> Result<Context, ContextError> {
         let mut context = rune_modules::with_config(false)?;
@@ -156,14 +156,14 @@ pub(crate) fn expand_install_with(
                     fields.push(name);
                 }
             }
+
+            installers.push(quote! {
+                module.struct_meta::<Self>(&[#(#fields),*][..])?;
+            });
         }
-        syn::Data::Enum(..) => {
-            ctx.errors.push(syn::Error::new_spanned(
-                input,
-                "`Any` not supported on enums",
-            ));
-            return None;
-        }
+        // NB: the variants of enums are registered through
+        // `Module::enum_meta`, typically using `install_with`.
+        syn::Data::Enum(..) => {}
         syn::Data::Union(..) => {
             ctx.errors.push(syn::Error::new_spanned(
                 input,
@@ -173,10 +173,6 @@ pub(crate) fn expand_install_with(
         }
     }
 
-    installers.push(quote! {
-        module.struct_meta::<Self>(&[#(#fields),*][..])?;
-    });
-
     Some(quote! {
         #(#installers)*
         Ok(())
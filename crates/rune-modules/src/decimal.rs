@@ -0,0 +1,421 @@
+//! The native `decimal` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! Provides an exact fixed-point [Decimal] type backed by [rust_decimal],
+//! suitable for things like monetary calculations where floating point
+//! rounding errors are unacceptable.
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.11.0", features = ["decimal"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> rune::Result<()> {
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(&rune_modules::decimal::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use decimal::Decimal;
+//!
+//! fn main() {
+//!     let a = Decimal::parse("0.1")?;
+//!     let b = Decimal::parse("0.2")?;
+//!     let total = (a + b) * 3;
+//!     println(`Total: ${total}`);
+//! }
+//! ```
+//!
+//! ## Semantics
+//!
+//! * Integers are promoted to decimals when mixed with them in arithmetic and
+//!   comparisons, so `Decimal::from_int(1) + 2` works as expected. Integers on
+//!   the left hand side, like `2 + d`, are only supported if the opt-in
+//!   [integer_module] is installed, since it adds operators to the builtin
+//!   integer type.
+//! * Operations which overflow raise an overflow error, or an underflow error
+//!   if the result would have been negative.
+//! * Floats are never implicitly mixed with decimals, since doing so would
+//!   silently reintroduce rounding errors. Convert explicitly with
+//!   `Decimal::parse` or `to_float()` instead.
+//! * Division by zero raises an error rather than producing a value.
+//! * Division results which can't be represented exactly are rounded using
+//!   banker's rounding (round half to even), which is also the strategy used
+//!   by `round_dp`.
+//!
+//! Decimals can be used as keys in a `HashMap` or `HashSet`, where
+//! numerically equal decimals like `1.5` and `1.50` are the same key. The key
+//! is a copy of the decimal, so changing a decimal after it has been inserted
+//! doesn't affect the collection.
+//!
+//! ## Interoperability
+//!
+//! On the Rust side, [Decimal] converts losslessly to and from
+//! [rust_decimal::Decimal] through [From], and implements [ToValue] and
+//! [FromValue] like any other [Any] type. It also implements serde's
+//! `Serialize` and `Deserialize`, matching the representation of the wrapped
+//! [rust_decimal::Decimal].
+//!
+//! ```rust
+//! use rune::runtime::{FromValue, ToValue};
+//! use rune_modules::decimal::Decimal;
+//!
+//! # fn main() -> rune::Result<()> {
+//! let value = Decimal::from(rust_decimal::Decimal::new(1050, 2)).to_value()?;
+//! let decimal = rust_decimal::Decimal::from(Decimal::from_value(value)?);
+//! assert_eq!(decimal.to_string(), "10.50");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [ToValue]: rune::runtime::ToValue
+//! [FromValue]: rune::runtime::FromValue
+//! [Any]: rune::Any
+
+use rune::runtime::{FromValue, Protocol, Ref, Value, VmError, VmErrorKind};
+use rune::{Any, ContextError, Module};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::RoundingStrategy;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Write as _;
+
+/// Construct the `decimal` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("decimal");
+
+    module.ty::<Decimal>()?;
+    module.ty::<DecimalError>()?;
+
+    module.function(&["Decimal", "parse"], Decimal::parse)?;
+    module.function(&["Decimal", "from_int"], Decimal::from_int)?;
+
+    module.inst_fn(Protocol::ADD, Decimal::add)?;
+    module.inst_fn(Protocol::SUB, Decimal::sub)?;
+    module.inst_fn(Protocol::MUL, Decimal::mul)?;
+    module.inst_fn(Protocol::DIV, Decimal::div)?;
+    module.inst_fn(Protocol::REM, Decimal::rem)?;
+    module.inst_fn(Protocol::EQ, Decimal::eq)?;
+    module.inst_fn(Protocol::CMP, Decimal::cmp)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Decimal::string_display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Decimal::string_debug)?;
+    module.key::<Decimal>()?;
+
+    module.inst_fn("round_dp", Decimal::round_dp)?;
+    module.inst_fn("round_dp_with", Decimal::round_dp_with)?;
+    module.inst_fn("normalize", Decimal::normalize)?;
+    module.inst_fn("to_string", to_string)?;
+    module.inst_fn("to_float", to_float)?;
+    module.inst_fn("abs", Decimal::abs)?;
+    module.inst_fn("min", Decimal::min)?;
+    module.inst_fn("max", Decimal::max)?;
+
+    module.inst_fn(Protocol::STRING_DISPLAY, DecimalError::string_display)?;
+    Ok(module)
+}
+
+/// Construct a module which promotes integers to decimals when they are on the
+/// left hand side of an operation with a decimal, like `2 + d`.
+///
+/// This is opt-in, since it registers operators on the builtin integer type
+/// which would conflict with any other module that does the same. Install it
+/// alongside [module] to enable it.
+///
+/// ```rust
+/// # fn main() -> rune::Result<()> {
+/// let mut context = rune::Context::with_default_modules()?;
+/// context.install(&rune_modules::decimal::module(true)?)?;
+/// context.install(&rune_modules::decimal::integer_module()?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn integer_module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("decimal");
+    module.inst_fn(Protocol::ADD, int_add)?;
+    module.inst_fn(Protocol::SUB, int_sub)?;
+    module.inst_fn(Protocol::MUL, int_mul)?;
+    module.inst_fn(Protocol::DIV, int_div)?;
+    module.inst_fn(Protocol::REM, int_rem)?;
+    module.inst_fn(Protocol::EQ, int_eq)?;
+    module.inst_fn(Protocol::CMP, int_cmp)?;
+    Ok(module)
+}
+
+/// An exact fixed-point decimal number.
+///
+/// This is a thin wrapper around [rust_decimal::Decimal] which can be passed
+/// into and out of Rune.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Any)]
+#[serde(transparent)]
+pub struct Decimal {
+    inner: rust_decimal::Decimal,
+}
+
+impl Decimal {
+    /// Parse a decimal from a string, like `"10.50"`.
+    fn parse(s: &str) -> Result<Self, DecimalError> {
+        let inner = s.parse::<rust_decimal::Decimal>().map_err(DecimalError)?;
+        Ok(Self { inner })
+    }
+
+    /// Construct a decimal from an integer.
+    fn from_int(n: i64) -> Self {
+        Self { inner: n.into() }
+    }
+
+    fn add(&self, rhs: Value) -> Result<Self, VmError> {
+        add(self.inner, operand(rhs, Protocol::ADD)?)
+    }
+
+    fn sub(&self, rhs: Value) -> Result<Self, VmError> {
+        sub(self.inner, operand(rhs, Protocol::SUB)?)
+    }
+
+    fn mul(&self, rhs: Value) -> Result<Self, VmError> {
+        mul(self.inner, operand(rhs, Protocol::MUL)?)
+    }
+
+    fn div(&self, rhs: Value) -> Result<Self, VmError> {
+        div(self.inner, operand(rhs, Protocol::DIV)?)
+    }
+
+    fn rem(&self, rhs: Value) -> Result<Self, VmError> {
+        rem(self.inner, operand(rhs, Protocol::REM)?)
+    }
+
+    fn eq(&self, rhs: Value) -> Result<bool, VmError> {
+        Ok(self.inner == operand(rhs, Protocol::EQ)?)
+    }
+
+    fn cmp(&self, rhs: Value) -> Result<Ordering, VmError> {
+        Ok(self.inner.cmp(&operand(rhs, Protocol::CMP)?))
+    }
+
+    fn string_display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+
+    fn string_debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+
+    /// Round to the given number of decimal places using banker's rounding.
+    fn round_dp(&self, dp: u32) -> Self {
+        Self {
+            inner: self.inner.round_dp(dp),
+        }
+    }
+
+    /// Round to the given number of decimal places using the named rounding
+    /// strategy.
+    fn round_dp_with(&self, dp: u32, strategy: &str) -> Result<Self, VmError> {
+        let strategy = match strategy {
+            "half_even" => RoundingStrategy::MidpointNearestEven,
+            "half_up" => RoundingStrategy::MidpointAwayFromZero,
+            "half_down" => RoundingStrategy::MidpointTowardZero,
+            "up" => RoundingStrategy::AwayFromZero,
+            "down" => RoundingStrategy::ToZero,
+            "ceil" => RoundingStrategy::ToPositiveInfinity,
+            "floor" => RoundingStrategy::ToNegativeInfinity,
+            other => {
+                return Err(VmError::panic(format!(
+                    "unsupported rounding strategy `{}`, expected one of `half_even`, `half_up`, `half_down`, `up`, `down`, `ceil` or `floor`",
+                    other
+                )))
+            }
+        };
+
+        Ok(Self {
+            inner: self.inner.round_dp_with_strategy(dp, strategy),
+        })
+    }
+
+    /// Strip trailing zeros, so that numerically equal decimals have the same
+    /// string representation.
+    fn normalize(&self) -> Self {
+        Self {
+            inner: self.inner.normalize(),
+        }
+    }
+
+    fn abs(&self) -> Self {
+        Self {
+            inner: self.inner.abs(),
+        }
+    }
+
+    fn min(&self, other: Value) -> Result<Self, VmError> {
+        let other = operand(other, Protocol::CMP)?;
+        Ok(Self {
+            inner: self.inner.min(other),
+        })
+    }
+
+    fn max(&self, other: Value) -> Result<Self, VmError> {
+        let other = operand(other, Protocol::CMP)?;
+        Ok(Self {
+            inner: self.inner.max(other),
+        })
+    }
+}
+
+/// Convert a decimal to a string.
+fn to_string(decimal: &Decimal) -> String {
+    decimal.inner.to_string()
+}
+
+/// Convert a decimal to a float.
+///
+/// Note that this is lossy, since most decimals can't be exactly represented
+/// as a float.
+fn to_float(decimal: &Decimal) -> f64 {
+    decimal.inner.to_f64().unwrap_or(f64::NAN)
+}
+
+impl From<rust_decimal::Decimal> for Decimal {
+    fn from(inner: rust_decimal::Decimal) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<Decimal> for rust_decimal::Decimal {
+    fn from(decimal: Decimal) -> Self {
+        decimal.inner
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+/// An error raised when parsing a decimal.
+#[derive(Debug, Any)]
+pub struct DecimalError(rust_decimal::Error);
+
+impl DecimalError {
+    fn string_display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.0)
+    }
+}
+
+/// Convert the right hand side of an operation into a decimal.
+fn operand(value: Value, protocol: Protocol) -> Result<rust_decimal::Decimal, VmError> {
+    match value {
+        Value::Integer(n) => Ok(n.into()),
+        Value::Float(..) => Err(mixed_float(protocol)),
+        value => Ok(Ref::<Decimal>::from_value(value)?.inner),
+    }
+}
+
+fn add(lhs: rust_decimal::Decimal, rhs: rust_decimal::Decimal) -> Result<Decimal, VmError> {
+    checked(lhs.checked_add(rhs), rhs.is_sign_negative())
+}
+
+fn sub(lhs: rust_decimal::Decimal, rhs: rust_decimal::Decimal) -> Result<Decimal, VmError> {
+    checked(lhs.checked_sub(rhs), !rhs.is_sign_negative())
+}
+
+fn mul(lhs: rust_decimal::Decimal, rhs: rust_decimal::Decimal) -> Result<Decimal, VmError> {
+    let negative = lhs.is_sign_negative() != rhs.is_sign_negative();
+    checked(lhs.checked_mul(rhs), negative)
+}
+
+fn div(lhs: rust_decimal::Decimal, rhs: rust_decimal::Decimal) -> Result<Decimal, VmError> {
+    let negative = lhs.is_sign_negative() != rhs.is_sign_negative();
+    checked_div(lhs, rhs, negative, rust_decimal::Decimal::checked_div)
+}
+
+fn rem(lhs: rust_decimal::Decimal, rhs: rust_decimal::Decimal) -> Result<Decimal, VmError> {
+    checked_div(lhs, rhs, lhs.is_sign_negative(), rust_decimal::Decimal::checked_rem)
+}
+
+/// Convert the result of a checked operation, raising an underflow if the
+/// result would have been `negative` and an overflow otherwise.
+fn checked(value: Option<rust_decimal::Decimal>, negative: bool) -> Result<Decimal, VmError> {
+    match value {
+        Some(inner) => Ok(Decimal { inner }),
+        None if negative => Err(VmError::from(VmErrorKind::Underflow)),
+        None => Err(VmError::from(VmErrorKind::Overflow)),
+    }
+}
+
+fn checked_div(
+    lhs: rust_decimal::Decimal,
+    rhs: rust_decimal::Decimal,
+    negative: bool,
+    op: fn(rust_decimal::Decimal, rust_decimal::Decimal) -> Option<rust_decimal::Decimal>,
+) -> Result<Decimal, VmError> {
+    if rhs.is_zero() {
+        return Err(VmError::from(VmErrorKind::DivideByZero));
+    }
+
+    checked(op(lhs, rhs), negative)
+}
+
+fn mixed_float(protocol: Protocol) -> VmError {
+    VmError::panic(format!(
+        "cannot use `{}` with a decimal and a float, convert explicitly using `Decimal::parse` or `to_float()`",
+        protocol.name
+    ))
+}
+
+/// Coerce the right hand side of an operation where the left hand side is an
+/// integer into a decimal, raising the usual unsupported operation error if
+/// it's something else.
+fn decimal_rhs(lhs: i64, rhs: Value, protocol: Protocol) -> Result<rust_decimal::Decimal, VmError> {
+    if let Value::Any(..) = &rhs {
+        if let Ok(rhs) = Ref::<Decimal>::from_value(rhs.clone()) {
+            return Ok(rhs.inner);
+        }
+    }
+
+    Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+        op: protocol.name,
+        lhs: Value::Integer(lhs).type_info()?,
+        rhs: rhs.type_info()?,
+    }))
+}
+
+fn int_add(lhs: i64, rhs: Value) -> Result<Decimal, VmError> {
+    add(lhs.into(), decimal_rhs(lhs, rhs, Protocol::ADD)?)
+}
+
+fn int_sub(lhs: i64, rhs: Value) -> Result<Decimal, VmError> {
+    sub(lhs.into(), decimal_rhs(lhs, rhs, Protocol::SUB)?)
+}
+
+fn int_mul(lhs: i64, rhs: Value) -> Result<Decimal, VmError> {
+    mul(lhs.into(), decimal_rhs(lhs, rhs, Protocol::MUL)?)
+}
+
+fn int_div(lhs: i64, rhs: Value) -> Result<Decimal, VmError> {
+    div(lhs.into(), decimal_rhs(lhs, rhs, Protocol::DIV)?)
+}
+
+fn int_rem(lhs: i64, rhs: Value) -> Result<Decimal, VmError> {
+    rem(lhs.into(), decimal_rhs(lhs, rhs, Protocol::REM)?)
+}
+
+fn int_eq(lhs: i64, rhs: Value) -> Result<bool, VmError> {
+    let rhs = decimal_rhs(lhs, rhs, Protocol::EQ)?;
+    Ok(rust_decimal::Decimal::from(lhs) == rhs)
+}
+
+fn int_cmp(lhs: i64, rhs: Value) -> Result<Ordering, VmError> {
+    let rhs = decimal_rhs(lhs, rhs, Protocol::CMP)?;
+    Ok(rust_decimal::Decimal::from(lhs).cmp(&rhs))
+}
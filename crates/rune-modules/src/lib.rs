@@ -46,6 +46,7 @@
 //!
 //! See each module for documentation:
 //! * [core]
+//! * [decimal]
 //! * [experiments]
 //! * [fmt]
 //! * [fs]
@@ -63,6 +64,7 @@
 //! ## Features
 //!
 //! * `core` for the [core module][toml]
+//! * `decimal` for the [decimal module][decimal]
 //! * `experiments` for the [experiments module][experiments]
 //! * `fmt` for the [fmt module][fmt]
 //! * `fs` for the [fs module][fs]
@@ -106,6 +108,9 @@ pub mod capture_io;
 #[cfg(feature = "disable-io")]
 pub mod disable_io;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 macro_rules! modules {
     ($($ident:ident, $name:literal),* $(,)?) => {
         $(
@@ -139,6 +144,7 @@ macro_rules! modules {
 
 modules! {
     core, "core",
+    decimal, "decimal",
     fmt, "fmt",
     fs, "fs",
     http, "http",
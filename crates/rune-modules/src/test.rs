@@ -63,7 +63,10 @@ pub(crate) fn assert_macro(ctx: &mut MacroContext<'_>, stream: &TokenStream) ->
     Ok(output.into_token_stream(ctx))
 }
 
-/// Implementation for the `assert!` macro.
+/// Implementation for the `assert_eq!` macro.
+///
+/// On failure, both operands are formatted with their debug representation,
+/// which for external types dispatches through `Protocol::STRING_DEBUG`.
 pub(crate) fn assert_eq_macro(ctx: &mut MacroContext<'_>, stream: &TokenStream) -> rune::Result<TokenStream> {
     let mut p = Parser::from_token_stream(stream, ctx.stream_span());
     let left = p.parse::<ast::Expr>()?;
@@ -76,32 +79,44 @@ pub(crate) fn assert_eq_macro(ctx: &mut MacroContext<'_>, stream: &TokenStream)
         None
     };
 
+    let debug = ctx.ident("debug");
+    let left_label = ctx.lit("\nleft: ");
+    let right_label = ctx.lit("\nright: ");
+
     let output = if let Some(message) = &message {
         let message = message.expand(ctx)?;
+        let prefix = ctx.lit("assertion failed (left == right): ");
 
         quote! {{
             let left = #left;
             let right = #right;
 
             if !(left == right) {
-                let message = #message;
-                message += format!("\nleft: {:?}", left);
-                message += format!("\nright: {:?}", right);
-                panic("assertion failed (left == right): " + message);
+                panic(#[builtin] template!(
+                    #prefix,
+                    #message,
+                    #left_label,
+                    #[builtin] format!(left, type = #debug),
+                    #right_label,
+                    #[builtin] format!(right, type = #debug)
+                ));
             }
         }}
     } else {
-        let message = ctx.lit("assertion failed (left == right):");
+        let prefix = ctx.lit("assertion failed (left == right):");
 
         quote! {{
             let left = #left;
             let right = #right;
 
             if !(left == right) {
-                let message = String::from_str(#message);
-                message += format!("\nleft: {:?}", left);
-                message += format!("\nright: {:?}", right);
-                panic(message);
+                panic(#[builtin] template!(
+                    #prefix,
+                    #left_label,
+                    #[builtin] format!(left, type = #debug),
+                    #right_label,
+                    #[builtin] format!(right, type = #debug)
+                ));
             }
         }}
     };
@@ -0,0 +1,391 @@
+//! Record and replay calls to native functions.
+//!
+//! This makes it possible to test scripts which depend on functions with side
+//! effects, like fetching the time or performing HTTP requests. Calls are
+//! first recorded against the real implementation into a [Cassette], which can
+//! be serialized and stored alongside the test. The cassette is then replayed
+//! in place of the real implementation.
+//!
+//! Only the arguments and return values of intercepted calls are recorded, so
+//! they have to be plain data which can be represented as a [ConstValue].
+//! Asynchronous functions and protocol functions are not supported.
+//!
+//! ```
+//! use rune::compile::Item;
+//! use rune_modules::testing::{Recorder, Replayer};
+//!
+//! # fn main() -> Result<(), rune::ContextError> {
+//! let context = rune_modules::default_context()?;
+//! let (context, recorder) = Recorder::wrap(context, [Item::with_crate("time")]);
+//! // .. run scripts using `context` ..
+//! let cassette = recorder.cassette();
+//!
+//! let context = rune_modules::default_context()?;
+//! let (context, replayer) = Replayer::install(context, cassette);
+//! // .. run scripts using `context` ..
+//! assert_eq!(replayer.remaining(), 0);
+//! # Ok(()) }
+//! ```
+
+use rune::compile::{ContextSignature, Item};
+use rune::runtime::{ConstValue, FunctionHandler, Stack, ToValue, VmError};
+use rune::{Context, InstFnKind, Value};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+/// A single recorded call to a native function.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCall {
+    /// The name of the function that was called.
+    pub function: String,
+    /// The arguments the function was called with.
+    pub args: Vec<ConstValue>,
+    /// The value returned by the function.
+    pub output: ConstValue,
+}
+
+/// A collection of recorded calls, in the order they were made.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    /// The prefixes of the functions which were intercepted.
+    pub prefixes: Vec<Item>,
+    /// The recorded calls.
+    pub calls: Vec<RecordedCall>,
+}
+
+/// How calls are matched against a [Cassette] when replaying.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Matching {
+    /// Calls must be made in exactly the order they were recorded.
+    #[default]
+    Ordered,
+    /// Calls are matched against the first unused recording with the same
+    /// function and arguments, regardless of order.
+    Keyed,
+}
+
+/// Records calls made to native functions.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    cassette: Arc<Mutex<Cassette>>,
+}
+
+impl Recorder {
+    /// Wrap every function in the `context` whose item starts with one of the
+    /// given `prefixes`, so that calls to them are recorded.
+    pub fn wrap<I>(mut context: Context, prefixes: I) -> (Context, Self)
+    where
+        I: IntoIterator<Item = Item>,
+    {
+        let prefixes = prefixes.into_iter().collect::<Vec<_>>();
+
+        let cassette = Arc::new(Mutex::new(Cassette {
+            prefixes: prefixes.clone(),
+            calls: Vec::new(),
+        }));
+
+        context.wrap_functions(|signature, handler| {
+            let function = intercepted_name(signature, &prefixes)?;
+            let handler = handler.clone();
+            let cassette = cassette.clone();
+
+            let wrapped: Arc<FunctionHandler> = Arc::new(move |stack: &mut Stack, count| {
+                let args = read_args(stack, count, &function)?;
+                handler(stack, count)?;
+
+                let output = match stack.last()? {
+                    Value::Future(..) => {
+                        return Err(VmError::panic(format!(
+                            "cannot record asynchronous function `{}`",
+                            function
+                        )));
+                    }
+                    value => to_const(value.clone(), &function)?,
+                };
+
+                lock(&cassette).calls.push(RecordedCall {
+                    function: function.clone(),
+                    args,
+                    output,
+                });
+
+                Ok(())
+            });
+
+            Some(wrapped)
+        });
+
+        (context, Self { cassette })
+    }
+
+    /// Get a copy of all calls recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        lock(&self.cassette).clone()
+    }
+}
+
+/// Replays calls to native functions from a [Cassette].
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    state: Arc<Mutex<ReplayState>>,
+}
+
+#[derive(Debug)]
+struct ReplayState {
+    matching: Matching,
+    calls: Vec<Option<RecordedCall>>,
+    next: usize,
+}
+
+impl Replayer {
+    /// Replace every function in the `context` which was intercepted when the
+    /// `cassette` was recorded, matching calls in the order they were made.
+    pub fn install(context: Context, cassette: Cassette) -> (Context, Self) {
+        Self::install_with(context, cassette, Matching::Ordered)
+    }
+
+    /// Replace every function in the `context` which was intercepted when the
+    /// `cassette` was recorded, using the given `matching` strategy.
+    pub fn install_with(
+        mut context: Context,
+        cassette: Cassette,
+        matching: Matching,
+    ) -> (Context, Self) {
+        let Cassette { prefixes, calls } = cassette;
+
+        let state = Arc::new(Mutex::new(ReplayState {
+            matching,
+            calls: calls.into_iter().map(Some).collect(),
+            next: 0,
+        }));
+
+        context.wrap_functions(|signature, _| {
+            let function = intercepted_name(signature, &prefixes)?;
+            let state = state.clone();
+
+            let wrapped: Arc<FunctionHandler> = Arc::new(move |stack: &mut Stack, count| {
+                let args = read_args(stack, count, &function)?;
+                let output = lock(&state).replay(&function, &args)?;
+                drop(stack.drain(count)?);
+                stack.push(output.to_value()?);
+                Ok(())
+            });
+
+            Some(wrapped)
+        });
+
+        (context, Self { state })
+    }
+
+    /// The number of recorded calls which have not been replayed yet.
+    pub fn remaining(&self) -> usize {
+        lock(&self.state).calls.iter().flatten().count()
+    }
+}
+
+impl ReplayState {
+    /// Find and consume the recording matching the given call.
+    fn replay(&mut self, function: &str, args: &[ConstValue]) -> Result<ConstValue, VmError> {
+        let args_key = format!("{:?}", args);
+
+        match self.matching {
+            Matching::Ordered => {
+                let index = self.next;
+
+                let call = match self.calls.get_mut(index).and_then(Option::take) {
+                    Some(call) => call,
+                    None => {
+                        return Err(VmError::panic(format!(
+                            "unexpected call to `{}` at position {}, the cassette only contains {} calls",
+                            function,
+                            index,
+                            self.calls.len()
+                        )));
+                    }
+                };
+
+                self.next += 1;
+
+                if call.function != function || format!("{:?}", call.args) != args_key {
+                    return Err(VmError::panic(divergence(index, &call, function, args)));
+                }
+
+                Ok(call.output)
+            }
+            Matching::Keyed => {
+                let slot = self.calls.iter_mut().find(|slot| match slot {
+                    Some(call) => {
+                        call.function == function && format!("{:?}", call.args) == args_key
+                    }
+                    None => false,
+                });
+
+                match slot.and_then(Option::take) {
+                    Some(call) => Ok(call.output),
+                    None => Err(VmError::panic(format!(
+                        "no unused recording of `{}` with the arguments {:?}",
+                        function, args
+                    ))),
+                }
+            }
+        }
+    }
+}
+
+/// Describe how a call diverged from the one which was recorded, as a line
+/// diff of their serialized forms.
+fn divergence(index: usize, call: &RecordedCall, function: &str, args: &[ConstValue]) -> String {
+    let expected = format!("{}{:#?}", call.function, call.args);
+    let actual = format!("{}{:#?}", function, args);
+
+    let mut out = format!(
+        "call at position {} diverged from the cassette (- expected, + actual):",
+        index
+    );
+
+    let mut expected = expected.lines();
+    let mut actual = actual.lines();
+
+    loop {
+        match (expected.next(), actual.next()) {
+            (Some(e), Some(a)) if e == a => {
+                let _ = write!(out, "\n  {}", e);
+            }
+            (e, a) => {
+                if e.is_none() && a.is_none() {
+                    break;
+                }
+
+                if let Some(e) = e {
+                    let _ = write!(out, "\n- {}", e);
+                }
+
+                if let Some(a) = a {
+                    let _ = write!(out, "\n+ {}", a);
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Get the name to record calls to the function with the given signature
+/// under, or `None` if it should not be intercepted.
+fn intercepted_name(signature: &ContextSignature, prefixes: &[Item]) -> Option<String> {
+    let (item, name) = match signature {
+        ContextSignature::Function { item, .. } => (item, None),
+        ContextSignature::Instance { item, name, .. } => match name {
+            InstFnKind::Instance(name) => (item, Some(name)),
+            _ => return None,
+        },
+    };
+
+    if !prefixes.iter().any(|prefix| item.starts_with(prefix)) {
+        return None;
+    }
+
+    Some(match name {
+        Some(name) => format!("{}::{}", item, name),
+        None => item.to_string(),
+    })
+}
+
+/// Read the arguments of a call from the top of the stack without consuming
+/// them.
+fn read_args(stack: &Stack, count: usize, function: &str) -> Result<Vec<ConstValue>, VmError> {
+    let start = stack
+        .len()
+        .checked_sub(count)
+        .ok_or_else(|| VmError::panic(format!("missing arguments in call to `{}`", function)))?;
+
+    let mut args = Vec::with_capacity(count);
+
+    for value in stack.get(start..).into_iter().flatten() {
+        args.push(to_const(value.clone(), function)?);
+    }
+
+    Ok(args)
+}
+
+fn to_const(value: Value, function: &str) -> Result<ConstValue, VmError> {
+    match snapshot(&value)? {
+        Some(value) => Ok(value),
+        None => Err(VmError::panic(format!(
+            "cannot record value of type `{}` in call to `{}`",
+            value.type_info()?,
+            function
+        ))),
+    }
+}
+
+/// Copy the given value into a [ConstValue] without taking it, since the
+/// arguments being recorded are still needed by the function being called.
+///
+/// Returns `None` if the value is not plain data.
+fn snapshot(value: &Value) -> Result<Option<ConstValue>, VmError> {
+    Ok(Some(match value {
+        Value::Unit => ConstValue::Unit,
+        Value::Byte(b) => ConstValue::Byte(*b),
+        Value::Char(c) => ConstValue::Char(*c),
+        Value::Bool(b) => ConstValue::Bool(*b),
+        Value::Integer(n) => ConstValue::Integer(*n),
+        Value::Float(f) => ConstValue::Float(*f),
+        Value::String(s) => ConstValue::String(s.borrow_ref()?.clone()),
+        Value::StaticString(s) => ConstValue::StaticString(s.clone()),
+        Value::Bytes(b) => ConstValue::Bytes(b.borrow_ref()?.clone()),
+        Value::Option(option) => ConstValue::Option(match &*option.borrow_ref()? {
+            Some(some) => match snapshot(some)? {
+                Some(some) => Some(Box::new(some)),
+                None => return Ok(None),
+            },
+            None => None,
+        }),
+        Value::Vec(vec) => match snapshot_all(vec.borrow_ref()?.iter())? {
+            Some(values) => ConstValue::Vec(values),
+            None => return Ok(None),
+        },
+        Value::Tuple(tuple) => match snapshot_all(tuple.borrow_ref()?.iter())? {
+            Some(values) => ConstValue::Tuple(values.into_boxed_slice()),
+            None => return Ok(None),
+        },
+        Value::Object(object) => {
+            let object = object.borrow_ref()?;
+            let mut out = Vec::with_capacity(object.len());
+
+            for (key, value) in object.iter() {
+                match snapshot(value)? {
+                    Some(value) => out.push((key.clone(), value)),
+                    None => return Ok(None),
+                }
+            }
+
+            ConstValue::Object(out.into_iter().collect())
+        }
+        _ => return Ok(None),
+    }))
+}
+
+fn snapshot_all<'a, I>(values: I) -> Result<Option<Vec<ConstValue>>, VmError>
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    let mut out = Vec::new();
+
+    for value in values {
+        match snapshot(value)? {
+            Some(value) => out.push(value),
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(out))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(error) => error.into_inner(),
+    }
+}
@@ -30,32 +30,46 @@
 //!     println("Message after 10 seconds!");
 //! }
 //! ```
+//!
+//! Host functions can take and return [`std::time::Duration`] and
+//! [`std::time::SystemTime`] directly, since these are the types used by this
+//! module.
 
-use rune::{Any, ContextError, Module};
+use rune::runtime::{VmError, VmErrorKind};
+use rune::{ContextError, Module};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Construct the `time` module.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     let mut module = Module::with_crate("time");
+    module.ty::<Duration>()?;
     module.function(&["Duration", "from_secs"], Duration::from_secs)?;
+    module.function(&["Duration", "from_millis"], Duration::from_millis)?;
+    module.inst_fn("as_secs", Duration::as_secs)?;
+    module.inst_fn("as_millis", duration_as_millis)?;
+    module.ty::<SystemTime>()?;
+    module.function(&["SystemTime", "now"], SystemTime::now)?;
+    module.inst_fn("unix_timestamp", system_time_unix_timestamp)?;
     module.async_function(&["sleep"], sleep)?;
     Ok(module)
 }
 
-#[derive(Debug, Clone, Copy, Any)]
-struct Duration {
-    inner: tokio::time::Duration,
+/// Get the number of whole milliseconds in the duration, raising an overflow
+/// if it doesn't fit in a `u64`.
+fn duration_as_millis(duration: &Duration) -> Result<u64, VmError> {
+    u64::try_from(duration.as_millis()).map_err(|_| VmError::from(VmErrorKind::Overflow))
 }
 
-impl Duration {
-    /// Construct a duration from seconds.
-    fn from_secs(secs: u64) -> Self {
-        Self {
-            inner: tokio::time::Duration::from_secs(secs),
-        }
+/// Get the number of whole seconds since the unix epoch, which is negative
+/// for times before it.
+fn system_time_unix_timestamp(time: &SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(error) => -(error.duration().as_secs() as i64),
     }
 }
 
-/// Convert any value to a json string.
+/// Sleep for the given duration.
 async fn sleep(duration: &Duration) {
-    tokio::time::sleep(duration.inner).await;
+    tokio::time::sleep(*duration).await;
 }
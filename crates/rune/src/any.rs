@@ -31,3 +31,7 @@ crate::__internal_impl_any!(std::fmt::Error);
 crate::__internal_impl_any!(std::io::Error);
 crate::__internal_impl_any!(anyhow::Error);
 crate::__internal_impl_any!(std::cmp::Ordering);
+#[cfg(feature = "std")]
+crate::__internal_impl_any!(std::time::Duration);
+#[cfg(feature = "std")]
+crate::__internal_impl_any!(std::time::SystemTime);
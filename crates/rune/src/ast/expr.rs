@@ -125,6 +125,8 @@ pub enum Expr {
     Tuple(ast::ExprTuple),
     /// A vec literal
     Vec(ast::ExprVec),
+    /// A repeat vector expression.
+    Repeat(ast::ExprRepeat),
     /// A range expression.
     Range(ast::ExprRange),
 }
@@ -164,6 +166,7 @@ impl Expr {
             Self::Range(expr) => &expr.attributes,
             Self::Tuple(expr) => &expr.attributes,
             Self::Vec(expr) => &expr.attributes,
+            Self::Repeat(expr) => &expr.attributes,
         }
     }
 
@@ -230,38 +233,12 @@ impl Expr {
             Self::Object(expr) => take(&mut expr.attributes),
             Self::Range(expr) => take(&mut expr.attributes),
             Self::Vec(expr) => take(&mut expr.attributes),
+            Self::Repeat(expr) => take(&mut expr.attributes),
             Self::Tuple(expr) => take(&mut expr.attributes),
             Self::MacroCall(expr) => take(&mut expr.attributes),
         }
     }
 
-    /// Check if this expression is a literal expression.
-    ///
-    /// There are exactly two kinds of literal expressions:
-    /// * Ones that are ExprLit
-    /// * Unary expressions which are the negate operation.
-    pub(crate) fn is_lit(&self) -> bool {
-        match self {
-            Self::Lit(..) => return true,
-            Self::Unary(ast::ExprUnary {
-                op: ast::UnOp::Neg(..),
-                expr,
-                ..
-            }) => {
-                return matches!(
-                    &**expr,
-                    Self::Lit(ast::ExprLit {
-                        lit: ast::Lit::Number(..),
-                        ..
-                    })
-                );
-            }
-            _ => (),
-        }
-
-        false
-    }
-
     /// Internal function to construct a literal expression.
     pub(crate) fn from_lit(lit: ast::Lit) -> Self {
         Self::Lit(ast::ExprLit {
@@ -519,12 +496,13 @@ fn base(
         K![let] => Expr::Let(ast::ExprLet::parse_with_meta(p, take(attributes))?),
         K![if] => Expr::If(ast::ExprIf::parse_with_meta(p, take(attributes))?),
         K![match] => Expr::Match(ast::ExprMatch::parse_with_attributes(p, take(attributes))?),
-        K!['['] => Expr::Vec(ast::ExprVec::parse_with_meta(p, take(attributes))?),
+        K!['['] => bracket_group(p, take(attributes))?,
         ast::Kind::Open(ast::Delimiter::Empty) => empty_group(p, take(attributes))?,
         K!['('] => paren_group(p, take(attributes))?,
         K!['{'] => Expr::Block(ast::ExprBlock::parse_with_meta(
             p,
             take(attributes),
+            take(&mut label),
             take(&mut async_token),
             take(&mut const_token),
             take(&mut move_token),
@@ -792,6 +770,36 @@ fn paren_group(p: &mut Parser<'_>, attributes: Vec<ast::Attribute>) -> Result<Ex
     )?))
 }
 
+/// Parsing something that opens with a bracket.
+fn bracket_group(p: &mut Parser<'_>, attributes: Vec<ast::Attribute>) -> Result<Expr, ParseError> {
+    // Empty vector.
+    if let (K!['['], K![']']) = (p.nth(0)?, p.nth(1)?) {
+        return Ok(Expr::Vec(ast::ExprVec::parse_with_meta(p, attributes)?));
+    }
+
+    let open = p.parse::<T!['[']>()?;
+    let item = p.parse::<ast::ExprVecItem>()?;
+
+    // Repeat expression like `[0; 10]`.
+    let item = match item {
+        ast::ExprVecItem::Expr(expr) if p.peek::<T![;]>()? => {
+            return Ok(Expr::Repeat(ast::ExprRepeat {
+                attributes,
+                open,
+                expr: Box::new(expr),
+                semi: p.parse()?,
+                count: p.parse()?,
+                close: p.parse()?,
+            }));
+        }
+        item => item,
+    };
+
+    Ok(Expr::Vec(ast::ExprVec::parse_from_first_expr(
+        p, attributes, open, item,
+    )?))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ast;
@@ -16,6 +16,9 @@ use crate::ast::prelude::*;
 /// let expr = testing::roundtrip::<ast::ExprBlock>("#[retry] async { 42 }");
 /// assert_eq!(expr.block.statements.len(), 1);
 /// assert_eq!(expr.attributes.len(), 1);
+///
+/// let expr = testing::roundtrip::<ast::ExprBlock>("'label: { break 'label 42; }");
+/// assert!(expr.label.is_some());
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
 #[rune(parse = "meta_only")]
@@ -24,6 +27,9 @@ pub struct ExprBlock {
     /// The attributes for the block.
     #[rune(iter, meta)]
     pub attributes: Vec<ast::Attribute>,
+    /// A label followed by a colon.
+    #[rune(iter, meta)]
+    pub label: Option<(ast::Label, T![:])>,
     /// The optional async token.
     #[rune(iter, meta)]
     pub async_token: Option<T![async]>,
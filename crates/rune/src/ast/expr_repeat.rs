@@ -0,0 +1,36 @@
+use crate::ast::prelude::*;
+
+/// A repeat vector expression `[<expr>; <count>]`.
+///
+/// The expression is evaluated once, and every element of the vector is a deep
+/// copy of the resulting value. Native values which don't implement the
+/// `clone` protocol can't be copied, so they are shared between the elements.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{ast, testing};
+///
+/// testing::roundtrip::<ast::ExprRepeat>("[0; 10]");
+/// testing::roundtrip::<ast::ExprRepeat>("[foo(); N]");
+/// testing::roundtrip::<ast::ExprRepeat>("[[1, 2]; 2 + 2]");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct ExprRepeat {
+    /// Attributes associated with the expression.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The open bracket.
+    pub open: ast::OpenBracket,
+    /// The expression being repeated.
+    pub expr: Box<ast::Expr>,
+    /// The semicolon separating the expression from the count.
+    pub semi: T![;],
+    /// The number of times to repeat the expression.
+    pub count: Box<ast::Expr>,
+    /// The close bracket.
+    pub close: ast::CloseBracket,
+}
+
+expr_parse!(Repeat, ExprRepeat, "repeat expression");
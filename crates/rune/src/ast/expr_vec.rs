@@ -10,6 +10,8 @@ use crate::ast::prelude::*;
 /// testing::roundtrip::<ast::ExprVec>("[1, \"two\"]");
 /// testing::roundtrip::<ast::ExprVec>("[1, 2,]");
 /// testing::roundtrip::<ast::ExprVec>("[1, 2, foo()]");
+/// testing::roundtrip::<ast::ExprVec>("[1, ..foo(), 2]");
+/// testing::roundtrip::<ast::ExprVec>("[..a, ..b]");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
 #[non_exhaustive]
@@ -18,5 +20,51 @@ pub struct ExprVec {
     #[rune(iter, meta)]
     pub attributes: Vec<ast::Attribute>,
     /// Items in the vector.
-    pub items: ast::Bracketed<ast::Expr, T![,]>,
+    pub items: ast::Bracketed<ExprVecItem, T![,]>,
+}
+
+impl ExprVec {
+    /// Start parsing literal vector from the middle of an expression.
+    pub(crate) fn parse_from_first_expr(
+        parser: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+        open: ast::OpenBracket,
+        item: ExprVecItem,
+    ) -> Result<Self, ParseError> {
+        Ok(Self {
+            attributes,
+            items: ast::Bracketed::parse_from_first(parser, open, item)?,
+        })
+    }
+}
+
+/// A single item in a literal vector.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{ast, testing};
+///
+/// testing::roundtrip::<ast::ExprVecItem>("foo()");
+/// testing::roundtrip::<ast::ExprVecItem>("..foo()");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub enum ExprVecItem {
+    /// A single element.
+    Expr(ast::Expr),
+    /// A spread item like `..iter`, which inserts every value produced by
+    /// iterating over the expression.
+    Spread(T![..], ast::Expr),
+}
+
+impl Parse for ExprVecItem {
+    fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        // NB: a lone `..` is still a full range expression.
+        Ok(match (p.nth(0)?, p.nth(1)?) {
+            (K![..], K![,] | K![']'] | K![;]) => Self::Expr(p.parse()?),
+            (K![..], _) => Self::Spread(p.parse()?, p.parse()?),
+            _ => Self::Expr(p.parse()?),
+        })
+    }
 }
@@ -24,7 +24,7 @@ impl Parse for FnArg {
     fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
         Ok(match p.nth(0)? {
             K![self] => Self::SelfValue(p.parse()?),
-            _ => Self::Pat(p.parse()?),
+            _ => Self::Pat(ast::Pat::parse_one(p)?),
         })
     }
 }
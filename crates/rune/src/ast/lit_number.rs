@@ -24,6 +24,8 @@ pub struct LitNumber {
 /// testing::roundtrip::<ast::LitNumber>("42.42");
 /// testing::roundtrip::<ast::LitNumber>("0.42");
 /// testing::roundtrip::<ast::LitNumber>("0.42e10");
+/// testing::roundtrip::<ast::LitNumber>("42u8");
+/// testing::roundtrip::<ast::LitNumber>("3f64");
 /// ```
 impl Parse for LitNumber {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
@@ -39,6 +41,16 @@ impl Parse for LitNumber {
     }
 }
 
+impl LitNumber {
+    /// Get the type suffix of the number literal, if any.
+    pub fn suffix(&self) -> Option<ast::NumberSuffix> {
+        match self.source {
+            ast::NumberSource::Text(text) => text.suffix,
+            ast::NumberSource::Synthetic(..) => None,
+        }
+    }
+}
+
 impl<'a> Resolve<'a> for LitNumber {
     type Output = ast::Number;
 
@@ -66,8 +78,22 @@ impl<'a> Resolve<'a> for LitNumber {
             .source(text.source_id, span)
             .ok_or_else(|| ResolveError::new(span, ResolveErrorKind::BadSlice))?;
 
-        if text.is_fractional {
-            let number = f64::from_str(string).map_err(err_span(span))?;
+        let string = match text.suffix {
+            Some(suffix) => &string[..string.len() - suffix.as_str().len()],
+            None => string,
+        };
+
+        if text.is_fractional || matches!(text.suffix, Some(suffix) if suffix.is_float()) {
+            let number = match text.suffix {
+                Some(ast::NumberSuffix::F32) => {
+                    f32::from_str(string).map_err(err_span(span))? as f64
+                }
+                Some(suffix) if !suffix.is_float() => {
+                    return Err(ResolveError::new(span, ResolveErrorKind::BadNumberLiteral));
+                }
+                _ => f64::from_str(string).map_err(err_span(span))?,
+            };
+
             return Ok(ast::Number::Float(number));
         }
 
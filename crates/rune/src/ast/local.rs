@@ -10,6 +10,7 @@ use crate::ast::prelude::*;
 /// testing::roundtrip::<ast::Local>("let x = 1;");
 /// testing::roundtrip::<ast::Local>("#[attr] let a = f();");
 /// testing::roundtrip::<ast::Local>("let a = b{}().foo[0].await;");
+/// testing::roundtrip::<ast::Local>("let Some(a) = b else { return; };");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Parse, Spanned)]
 #[non_exhaustive]
@@ -26,10 +27,30 @@ pub struct Local {
     /// The expression the binding is assigned to.
     #[rune(parse_with = "parse_expr")]
     pub expr: ast::Expr,
+    /// The diverging else branch of the local, as in `let Some(a) = b else {
+    /// return; };`.
+    #[rune(iter)]
+    pub else_: Option<LocalElse>,
     /// Trailing semicolon of the local.
     pub semi: T![;],
 }
 
+/// The else branch of a local declaration, which must diverge.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Parse, Spanned)]
+#[non_exhaustive]
+pub struct LocalElse {
+    /// The `else` token.
+    pub else_: T![else],
+    /// The body of the else branch.
+    pub block: Box<ast::Block>,
+}
+
+impl Peek for LocalElse {
+    fn peek(p: &mut Peeker<'_>) -> bool {
+        matches!(p.nth(0), K![else])
+    }
+}
+
 fn parse_expr(p: &mut Parser<'_>) -> Result<ast::Expr, ParseError> {
     ast::Expr::parse_with(
         p,
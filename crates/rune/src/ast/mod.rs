@@ -114,6 +114,7 @@ mod expr_loop;
 mod expr_match;
 mod expr_object;
 mod expr_range;
+mod expr_repeat;
 mod expr_return;
 mod expr_select;
 mod expr_try;
@@ -164,7 +165,7 @@ pub use self::expr_assign::ExprAssign;
 pub use self::expr_await::ExprAwait;
 pub use self::expr_binary::{BinOp, ExprBinary};
 pub use self::expr_block::ExprBlock;
-pub use self::expr_break::{ExprBreak, ExprBreakValue};
+pub use self::expr_break::ExprBreak;
 pub use self::expr_call::ExprCall;
 pub use self::expr_closure::ExprClosure;
 pub use self::expr_continue::ExprContinue;
@@ -180,12 +181,13 @@ pub use self::expr_loop::ExprLoop;
 pub use self::expr_match::{ExprMatch, ExprMatchBranch};
 pub use self::expr_object::{ExprObject, FieldAssign, ObjectIdent, ObjectKey};
 pub use self::expr_range::{ExprRange, ExprRangeLimits};
+pub use self::expr_repeat::ExprRepeat;
 pub use self::expr_return::ExprReturn;
 pub use self::expr_select::{ExprSelect, ExprSelectBranch};
 pub use self::expr_try::ExprTry;
 pub use self::expr_tuple::ExprTuple;
 pub use self::expr_unary::{ExprUnary, UnOp};
-pub use self::expr_vec::ExprVec;
+pub use self::expr_vec::{ExprVec, ExprVecItem};
 pub use self::expr_while::ExprWhile;
 pub use self::expr_yield::ExprYield;
 pub use self::file::{File, Shebang};
@@ -209,9 +211,11 @@ pub use self::lit_byte_str::LitByteStr;
 pub use self::lit_char::LitChar;
 pub use self::lit_number::LitNumber;
 pub use self::lit_str::LitStr;
-pub use self::local::Local;
+pub use self::local::{Local, LocalElse};
 pub use self::macro_call::MacroCall;
-pub use self::pat::{Pat, PatBinding, PatLit, PatObject, PatPath, PatTuple, PatVec};
+pub use self::pat::{
+    Pat, PatBinding, PatLit, PatObject, PatOr, PatPath, PatRange, PatTuple, PatVec,
+};
 pub use self::path::{Path, PathKind, PathSegment, PathSegmentExpr};
 pub use self::span::{ByteIndex, Span};
 pub use self::spanned::{OptionSpanned, Spanned};
@@ -219,8 +223,8 @@ pub use self::spanned_error::SpannedError;
 pub(crate) use self::spanned_error::WithSpan;
 pub use self::stmt::{ItemOrExpr, Stmt, StmtSortKey};
 pub use self::token::{
-    BuiltIn, CopySource, Delimiter, LitSource, Number, NumberBase, NumberSource, NumberText,
-    StrSource, StrText, Token,
+    BuiltIn, CopySource, Delimiter, LitSource, Number, NumberBase, NumberSource, NumberSuffix,
+    NumberText, StrSource, StrText, Token,
 };
 pub use self::vis::Visibility;
 
@@ -20,6 +20,10 @@ pub enum Pat {
     PatBinding(PatBinding),
     /// The rest pattern `..`.
     PatRest(PatRest),
+    /// A range pattern like `a..b`, `a..=b`, `a..` or `..b`.
+    PatRange(PatRange),
+    /// An or-pattern like `1 | 2 | 3`.
+    PatOr(PatOr),
 }
 
 /// Parsing a block expression.
@@ -41,23 +45,52 @@ pub enum Pat {
 /// testing::roundtrip::<ast::Pat>("var");
 /// testing::roundtrip::<ast::Pat>("_");
 /// testing::roundtrip::<ast::Pat>("Foo(n)");
+/// testing::roundtrip::<ast::Pat>("0..=9");
+/// testing::roundtrip::<ast::Pat>("-10..=-1");
+/// testing::roundtrip::<ast::Pat>("'a'..='z'");
+/// testing::roundtrip::<ast::Pat>("b'0'..=b'9'");
+/// testing::roundtrip::<ast::Pat>("0..10");
+/// testing::roundtrip::<ast::Pat>("5..");
+/// testing::roundtrip::<ast::Pat>("..10");
+/// testing::roundtrip::<ast::Pat>("..=-1");
+/// testing::roundtrip::<ast::Pat>("..");
+/// testing::roundtrip::<ast::Pat>("1 | 2 | 3");
+/// testing::roundtrip::<ast::Pat>("Option::Some(_) | Option::None");
+/// testing::roundtrip::<ast::Pat>("(1 | 2, _)");
 /// ```
 impl Parse for Pat {
     fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let first = Self::parse_one(p)?;
+
+        if !matches!(p.nth(0)?, K![|]) {
+            return Ok(first);
+        }
+
+        let mut rest = Vec::new();
+
+        while let Some(pipe) = p.parse::<Option<T![|]>>()? {
+            rest.push((pipe, Self::parse_one(p)?));
+        }
+
+        Ok(Self::PatOr(PatOr {
+            first: Box::new(first),
+            rest,
+        }))
+    }
+}
+
+impl Pat {
+    /// Parse a single pattern which is not an or-pattern.
+    ///
+    /// This is used in locations where a `|` can't be part of the pattern,
+    /// like in closure arguments.
+    pub(crate) fn parse_one(p: &mut Parser<'_>) -> Result<Self, ParseError> {
         let attributes = p.parse::<Vec<ast::Attribute>>()?;
 
         match p.nth(0)? {
-            K![byte] => {
-                return Ok(Self::PatLit(PatLit {
-                    attributes,
-                    expr: Box::new(ast::Expr::from_lit(ast::Lit::Byte(p.parse()?))),
-                }));
-            }
-            K![char] => {
-                return Ok(Self::PatLit(PatLit {
-                    attributes,
-                    expr: Box::new(ast::Expr::from_lit(ast::Lit::Char(p.parse()?))),
-                }));
+            K![byte] | K![char] => {
+                let expr = range_bound(p)?;
+                return Self::lit_or_range(p, attributes, expr);
             }
             K![bytestr] => {
                 return Ok(Self::PatLit(PatLit {
@@ -86,9 +119,15 @@ impl Parse for Pat {
                 });
             }
             K![number] => {
-                return Ok(Self::PatLit(PatLit {
+                let expr = range_bound(p)?;
+                return Self::lit_or_range(p, attributes, expr);
+            }
+            K![..] if is_range_bound(p, 1)? => {
+                return Ok(Self::PatRange(PatRange {
                     attributes,
-                    expr: Box::new(ast::Expr::from_lit(ast::Lit::Number(p.parse()?))),
+                    start: None,
+                    limits: ast::ExprRangeLimits::HalfOpen(p.parse()?),
+                    end: Some(Box::new(range_bound(p)?)),
                 }));
             }
             K![..] => {
@@ -97,6 +136,14 @@ impl Parse for Pat {
                     dot_dot: p.parse()?,
                 }))
             }
+            K![..=] => {
+                return Ok(Self::PatRange(PatRange {
+                    attributes,
+                    start: None,
+                    limits: ast::ExprRangeLimits::Closed(p.parse()?),
+                    end: Some(Box::new(range_bound(p)?)),
+                }));
+            }
             K!['('] => {
                 return Ok({
                     let _nth = p.nth(1)?;
@@ -121,15 +168,9 @@ impl Parse for Pat {
                     items: p.parse()?,
                 }))
             }
-            K![-] => {
-                let expr: ast::Expr = p.parse()?;
-
-                if expr.is_lit() {
-                    return Ok(Self::PatLit(PatLit {
-                        attributes,
-                        expr: Box::new(expr),
-                    }));
-                }
+            K![-] if matches!(p.nth(1)?, K![number]) => {
+                let expr = range_bound(p)?;
+                return Self::lit_or_range(p, attributes, expr);
             }
             K![_] => {
                 return Ok(Self::PatIgnore(PatIgnore {
@@ -165,6 +206,74 @@ impl Parse for Pat {
 
         Err(ParseError::expected(p.tok_at(0)?, "pattern"))
     }
+
+    /// Construct a literal pattern out of the given expression, or a range
+    /// pattern if it's followed by `..` or `..=`.
+    fn lit_or_range(
+        p: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+        expr: ast::Expr,
+    ) -> Result<Self, ParseError> {
+        let (limits, end) = match p.nth(0)? {
+            K![..=] => (
+                ast::ExprRangeLimits::Closed(p.parse()?),
+                Some(Box::new(range_bound(p)?)),
+            ),
+            K![..] => {
+                let limits = ast::ExprRangeLimits::HalfOpen(p.parse()?);
+
+                let end = if is_range_bound(p, 0)? {
+                    Some(Box::new(range_bound(p)?))
+                } else {
+                    None
+                };
+
+                (limits, end)
+            }
+            _ => {
+                return Ok(Self::PatLit(PatLit {
+                    attributes,
+                    expr: Box::new(expr),
+                }));
+            }
+        };
+
+        Ok(Self::PatRange(PatRange {
+            attributes,
+            start: Some(Box::new(expr)),
+            limits,
+            end,
+        }))
+    }
+}
+
+/// Parse a literal which can be used as a bound in a range pattern, this is
+/// any literal which can be used in a literal pattern except for strings.
+fn range_bound(p: &mut Parser<'_>) -> Result<ast::Expr, ParseError> {
+    let lit = match p.nth(0)? {
+        K![byte] => ast::Lit::Byte(p.parse()?),
+        K![char] => ast::Lit::Char(p.parse()?),
+        K![number] => ast::Lit::Number(p.parse()?),
+        K![-] if matches!(p.nth(1)?, K![number]) => {
+            return Ok(ast::Expr::Unary(ast::ExprUnary {
+                attributes: Vec::new(),
+                op: ast::UnOp::Neg(p.parse()?),
+                expr: Box::new(ast::Expr::from_lit(ast::Lit::Number(p.parse()?))),
+            }));
+        }
+        _ => return Err(ParseError::expected(p.tok_at(0)?, "range pattern bound")),
+    };
+
+    Ok(ast::Expr::from_lit(lit))
+}
+
+/// Test if the token at the given offset starts a range pattern bound.
+fn is_range_bound(p: &mut Parser<'_>, n: usize) -> Result<bool, ParseError> {
+    Ok(match p.nth(n)? {
+        K![byte] | K![char] | K![number] => true,
+        K![-] => matches!(p.nth(n + 1)?, K![number]),
+        _ => false,
+    })
 }
 
 impl Peek for Pat {
@@ -174,7 +283,7 @@ impl Peek for Pat {
             K!['['] => true,
             K![#] => matches!(p.nth(1), K!['{']),
             K![_] => true,
-            K![..] => true,
+            K![..] | K![..=] => true,
             K![byte] | K![char] | K![number] | K![str] => true,
             K![true] | K![false] => true,
             K![-] => matches!(p.nth(1), K![number]),
@@ -183,6 +292,25 @@ impl Peek for Pat {
     }
 }
 
+/// An or-pattern like `1 | 2 | 3`, which matches if any of its alternatives
+/// match.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct PatOr {
+    /// The first alternative.
+    pub first: Box<ast::Pat>,
+    /// The rest of the alternatives, each preceded by a `|`.
+    #[rune(iter)]
+    pub rest: Vec<(T![|], ast::Pat)>,
+}
+
+impl PatOr {
+    /// Iterate over all alternatives in the pattern.
+    pub(crate) fn alternatives(&self) -> impl Iterator<Item = &ast::Pat> {
+        std::iter::once(&*self.first).chain(self.rest.iter().map(|(_, pat)| pat))
+    }
+}
+
 /// A literal pattern.
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
@@ -205,6 +333,31 @@ pub struct PatRest {
     pub dot_dot: T![..],
 }
 
+/// A range pattern like `a..b`, `a..=b`, `a..` or `..b`, where the bounds
+/// are literals.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct PatRange {
+    /// Attributes associated with the range pattern.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The lower bound of the range.
+    #[rune(iter)]
+    pub start: Option<Box<ast::Expr>>,
+    /// The `..` or `..=` token.
+    pub limits: ast::ExprRangeLimits,
+    /// The upper bound of the range.
+    #[rune(iter)]
+    pub end: Option<Box<ast::Expr>>,
+}
+
+impl PatRange {
+    /// Test if the range includes its upper bound.
+    pub(crate) fn is_inclusive(&self) -> bool {
+        matches!(self.limits, ast::ExprRangeLimits::Closed(..))
+    }
+}
+
 /// An array pattern.
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
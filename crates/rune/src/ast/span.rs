@@ -94,6 +94,12 @@ impl Span {
 
     /// Join two spans creating the larger of the two spans.
     ///
+    /// A span doesn't know which source it belongs to, so the caller must make
+    /// sure that both spans come from the same source. Joining spans from
+    /// different sources produces a meaningless range, use
+    /// [Location::join][crate::compile::Location::join] if the sources might
+    /// differ.
+    ///
     /// # Examples
     ///
     /// ```
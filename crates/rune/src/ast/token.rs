@@ -287,6 +287,125 @@ impl fmt::Display for NumberBase {
     }
 }
 
+/// The type suffix of a number literal, like `u8` in `42u8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum NumberSuffix {
+    /// The `i8` suffix.
+    I8,
+    /// The `i16` suffix.
+    I16,
+    /// The `i32` suffix.
+    I32,
+    /// The `i64` suffix.
+    I64,
+    /// The `u8` suffix.
+    U8,
+    /// The `u16` suffix.
+    U16,
+    /// The `u32` suffix.
+    U32,
+    /// The `u64` suffix.
+    U64,
+    /// The `f32` suffix.
+    F32,
+    /// The `f64` suffix.
+    F64,
+}
+
+impl NumberSuffix {
+    /// All supported suffixes.
+    const ALL: [Self; 10] = [
+        Self::I8,
+        Self::I16,
+        Self::I32,
+        Self::I64,
+        Self::U8,
+        Self::U16,
+        Self::U32,
+        Self::U64,
+        Self::F32,
+        Self::F64,
+    ];
+
+    /// Find the suffix at the end of the given number literal, if any.
+    ///
+    /// Float suffixes are only recognized for decimal literals, since `f` is
+    /// a valid hex digit.
+    pub(crate) fn from_literal(literal: &str, base: NumberBase) -> Option<Self> {
+        Self::ALL.iter().copied().find(|suffix| {
+            if suffix.is_float() && base != NumberBase::Decimal {
+                return false;
+            }
+
+            let s = suffix.as_str();
+            literal.len() > s.len() && literal.ends_with(s)
+        })
+    }
+
+    /// Get the suffix as a string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+        }
+    }
+
+    /// Test if this is a floating point suffix.
+    pub fn is_float(self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
+
+    /// Check that the given number fits within the type indicated by the
+    /// suffix, after optionally being negated.
+    pub(crate) fn check(self, number: &Number, span: Span, neg: bool) -> Result<(), ParseError> {
+        use num::ToPrimitive as _;
+        use std::ops::Neg as _;
+
+        let in_bounds = match number {
+            Number::Float(n) => n.is_finite(),
+            Number::Integer(n) => {
+                let n = if neg { n.clone().neg() } else { n.clone() };
+
+                match self {
+                    Self::I8 => n.to_i8().is_some(),
+                    Self::I16 => n.to_i16().is_some(),
+                    Self::I32 => n.to_i32().is_some(),
+                    Self::I64 => n.to_i64().is_some(),
+                    Self::U8 => n.to_u8().is_some(),
+                    Self::U16 => n.to_u16().is_some(),
+                    Self::U32 => n.to_u32().is_some(),
+                    Self::U64 => n.to_u64().is_some(),
+                    Self::F32 | Self::F64 => true,
+                }
+            }
+        };
+
+        if !in_bounds {
+            return Err(ParseError::new(
+                span,
+                ParseErrorKind::BadNumberSuffixOutOfBounds { suffix: self },
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for NumberSuffix {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
 /// A built-in identifiers that do not have a source.
 ///
 /// This is necessary to synthesize identifiers in the lexer since there's not
@@ -393,6 +512,8 @@ pub struct NumberText {
     pub is_fractional: bool,
     /// The number literal kind.
     pub base: NumberBase,
+    /// The type suffix of the number literal, like `u8` in `42u8`.
+    pub suffix: Option<NumberSuffix>,
 }
 
 /// A delimiter, `{`, `{`, or `[`.
@@ -1,6 +1,9 @@
 use crate::ast::Span;
 use crate::compile;
-use crate::compile::{CompileVisitor, FileSourceLoader, NoopCompileVisitor, Options, SourceLoader};
+use crate::compile::{
+    BuildProgress, CompileVisitor, FileSourceLoader, NoopCompileVisitor, Options, Phase, Progress,
+    SourceLoader,
+};
 use crate::runtime::Unit;
 use crate::{Context, Diagnostics, SourceId, Sources};
 use thiserror::Error;
@@ -64,6 +67,12 @@ pub fn prepare(sources: &mut Sources) -> Build<'_> {
         options: None,
         visitor: None,
         source_loader: None,
+        progress: None,
+        prelude: Vec::new(),
+        globals: Vec::new(),
+        env: Vec::new(),
+        denied_modules: Vec::new(),
+        const_recursion_limit: None,
     }
 }
 
@@ -75,6 +84,12 @@ pub struct Build<'a> {
     options: Option<&'a Options>,
     visitor: Option<&'a mut dyn compile::CompileVisitor>,
     source_loader: Option<&'a mut dyn SourceLoader>,
+    progress: Option<&'a mut dyn BuildProgress>,
+    prelude: Vec<Box<str>>,
+    globals: Vec<Box<str>>,
+    env: Vec<(Box<str>, Box<str>)>,
+    denied_modules: Vec<compile::Item>,
+    const_recursion_limit: Option<usize>,
 }
 
 impl<'a> Build<'a> {
@@ -125,6 +140,195 @@ impl<'a> Build<'a> {
         self
     }
 
+    /// Modify the current [Build] to report its progress to the given
+    /// [BuildProgress] observer.
+    ///
+    /// The observer is notified as each phase of the build starts and
+    /// finishes, and receives a [BuildTimings][compile::BuildTimings] summary
+    /// once the build has completed successfully. Nothing is timed unless an
+    /// observer is configured.
+    #[inline]
+    pub fn with_progress(mut self, progress: &'a mut dyn BuildProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Modify the current [Build] to add the given items to the prelude.
+    ///
+    /// Items in the prelude can be used by their last component without an
+    /// explicit `use`, so adding `std::string::String` makes it available as
+    /// `String`. Items defined or imported in a script shadow the prelude.
+    ///
+    /// Paths which are empty or have empty components cause the build to fail
+    /// with an error.
+    ///
+    /// ```
+    /// use rune::{Context, Source, Sources};
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(Source::new("entry", r#"
+    /// pub fn main() {
+    ///     parse("42")
+    /// }
+    /// "#));
+    ///
+    /// let unit = rune::prepare(&mut sources)
+    ///     .with_context(&context)
+    ///     .with_prelude(["std::int::parse"])
+    ///     .build()?;
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn with_prelude<I>(mut self, items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.prelude
+            .extend(items.into_iter().map(|item| item.as_ref().into()));
+        self
+    }
+
+    /// Modify the current [Build] to declare the given names as globals.
+    ///
+    /// A global is resolved when the script runs, by looking up a value with
+    /// the same name which has been set through
+    /// [Vm::set_global][crate::Vm::set_global]. Locals and items in scope
+    /// shadow globals.
+    #[inline]
+    pub fn with_globals<I>(mut self, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.globals
+            .extend(names.into_iter().map(|name| name.as_ref().into()));
+        self
+    }
+
+    /// Modify the current [Build] to provide the given configuration values to
+    /// the script.
+    ///
+    /// Values are read by scripts through `std::env::var`, which only sees the
+    /// values provided here and never the environment of the host process.
+    ///
+    /// ```
+    /// use rune::{Context, FromValue, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             std::env::var("greeting")
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources)
+    ///     .with_context(&context)
+    ///     .with_env([("greeting", "Hello")])
+    ///     .build()?;
+    ///
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    /// let output = Option::<String>::from_value(vm.call(&["main"], ())?)?;
+    /// assert_eq!(output.as_deref(), Some("Hello"));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn with_env<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.env.extend(
+            vars.into_iter()
+                .map(|(name, value)| (name.as_ref().into(), value.as_ref().into())),
+        );
+        self
+    }
+
+    /// Modify the current [Build] to deny any references to the given module.
+    ///
+    /// The first component of the path is treated as the crate the module
+    /// belongs to. Referencing the module or anything inside of it, whether
+    /// directly, through a `use` or through the prelude, causes the build to
+    /// fail with an error naming the denied module. This can be called
+    /// multiple times to deny several modules.
+    ///
+    /// ```
+    /// use rune::{Context, Source, Sources};
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(Source::new("entry", r#"
+    /// pub fn main() {
+    ///     std::io::println("Hello World");
+    /// }
+    /// "#));
+    ///
+    /// let result = rune::prepare(&mut sources)
+    ///     .with_context(&context)
+    ///     .deny_module(["std", "io"])
+    ///     .build();
+    ///
+    /// assert!(result.is_err());
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn deny_module<I>(mut self, path: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut it = path.into_iter();
+
+        if let Some(krate) = it.next() {
+            let module =
+                compile::Item::with_crate_item(krate.as_ref(), it.map(|c| c.as_ref().to_owned()));
+            self.denied_modules.push(module);
+        }
+
+        self
+    }
+
+    /// Modify the current [Build] to limit how deeply const fn calls may be
+    /// nested during constant evaluation.
+    ///
+    /// Exceeding the limit fails the build with a dedicated error, which is
+    /// separate from exhausting the overall evaluation budget. By default only
+    /// the evaluation budget applies.
+    ///
+    /// ```
+    /// use rune::{Source, Sources};
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(Source::new("entry", r#"
+    /// const fn count(n) { if n == 0 { 0 } else { 1 + count(n - 1) } }
+    /// const COUNT = count(100);
+    /// pub fn main() { COUNT }
+    /// "#));
+    ///
+    /// let result = rune::prepare(&mut sources)
+    ///     .with_const_recursion_limit(10)
+    ///     .build();
+    ///
+    /// assert!(result.is_err());
+    /// ```
+    #[inline]
+    pub fn with_const_recursion_limit(mut self, limit: usize) -> Self {
+        self.const_recursion_limit = Some(limit);
+        self
+    }
+
     /// Build a [Unit] with the current configuration.
     pub fn build(mut self) -> Result<Unit, BuildError> {
         let default_context;
@@ -143,6 +347,22 @@ impl<'a> Build<'a> {
             compile::UnitBuilder::default()
         };
 
+        for name in &self.globals {
+            unit.add_global(name);
+        }
+
+        for (name, value) in self.env.drain(..) {
+            unit.add_env_var(name, value);
+        }
+
+        for module in self.denied_modules.drain(..) {
+            unit.deny_module(module);
+        }
+
+        if let Some(limit) = self.const_recursion_limit {
+            unit.set_const_recursion_limit(limit);
+        }
+
         let mut default_diagnostics;
 
         let diagnostics = match self.diagnostics.take() {
@@ -153,6 +373,16 @@ impl<'a> Build<'a> {
             }
         };
 
+        for item in &self.prelude {
+            if let Err(error) = unit.add_prelude_item(Span::empty(), item) {
+                diagnostics.error(SourceId::empty(), error);
+            }
+        }
+
+        if diagnostics.has_error() {
+            return Err(BuildError);
+        }
+
         let default_options;
 
         let options = match self.options.take() {
@@ -183,6 +413,8 @@ impl<'a> Build<'a> {
             }
         };
 
+        let mut progress = self.progress.take().map(Progress::new);
+
         let result = compile::compile(
             &mut unit,
             self.sources,
@@ -191,10 +423,16 @@ impl<'a> Build<'a> {
             options,
             visitor,
             source_loader,
+            progress.as_mut(),
         );
 
-        if let Err(()) = result {
-            return Err(BuildError);
+        let stats = match result {
+            Ok(stats) => stats,
+            Err(()) => return Err(BuildError),
+        };
+
+        if let Some(progress) = &mut progress {
+            progress.start(Phase::Link, None);
         }
 
         if options.link_checks {
@@ -206,7 +444,13 @@ impl<'a> Build<'a> {
         }
 
         match unit.build(Span::empty()) {
-            Ok(unit) => Ok(unit),
+            Ok(unit) => {
+                if let Some(progress) = progress {
+                    progress.finish(&stats);
+                }
+
+                Ok(unit)
+            }
             Err(error) => {
                 diagnostics.error(SourceId::empty(), error);
                 Err(BuildError)
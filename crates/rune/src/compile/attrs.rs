@@ -132,6 +132,52 @@ impl Attribute for BuiltIn {
     const PATH: &'static str = "builtin";
 }
 
+/// The lints which can be suppressed with `#[allow(..)]`.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct AllowLints {
+    pub(crate) unreachable: bool,
+    pub(crate) non_exhaustive_match: bool,
+    pub(crate) constant_condition: bool,
+}
+
+/// The `#[allow(..)]` attribute, used to suppress warnings.
+#[derive(Parse)]
+pub(crate) struct Allow {
+    /// The lints to allow.
+    pub args: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Allow {
+    /// Parse the allowed lints.
+    pub(crate) fn lints(&self, ctx: ResolveContext<'_>) -> Result<AllowLints, ParseError> {
+        let mut out = AllowLints::default();
+
+        for (ident, _) in &self.args {
+            match ident.resolve(ctx)? {
+                "unreachable" => {
+                    out.unreachable = true;
+                }
+                "non_exhaustive_match" => {
+                    out.non_exhaustive_match = true;
+                }
+                "constant_condition" => {
+                    out.constant_condition = true;
+                }
+                _ => {
+                    return Err(ParseError::msg(ident, "unsupported lint"));
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+impl Attribute for Allow {
+    /// Must match the specified name.
+    const PATH: &'static str = "allow";
+}
+
 /// NB: at this point we don't support attributes beyond the empty `#[test]`.
 #[derive(Parse)]
 pub(crate) struct Test {}
@@ -141,6 +187,26 @@ impl Attribute for Test {
     const PATH: &'static str = "test";
 }
 
+/// The `#[no_debug]` attribute, which opts a struct, an enum or a variant out
+/// of structural debug formatting.
+#[derive(Parse)]
+pub(crate) struct NoDebug {}
+
+impl Attribute for NoDebug {
+    /// Must match the specified name.
+    const PATH: &'static str = "no_debug";
+}
+
+/// The `#[call]` attribute, which makes an instance function the handler for
+/// calling values of its type like functions.
+#[derive(Parse)]
+pub(crate) struct Call {}
+
+impl Attribute for Call {
+    /// Must match the specified name.
+    const PATH: &'static str = "call";
+}
+
 /// NB: at this point we don't support attributes beyond the empty `#[bench]`.
 #[derive(Parse)]
 pub(crate) struct Bench {}
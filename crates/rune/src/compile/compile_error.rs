@@ -116,6 +116,10 @@ pub enum CompileErrorKind {
     MissingLocal { name: String },
     #[error("missing item `{item}`")]
     MissingItem { item: Item },
+    #[error("invalid prelude item `{path}`, expected a path like `std::int::parse`")]
+    InvalidPreludeItem { path: Box<str> },
+    #[error("cannot reference `{item}` since the module `{module}` is denied")]
+    DeniedModule { item: Item, module: Item },
     #[error("unsupported crate prefix `::`")]
     UnsupportedGlobal,
     #[error("cannot load modules using a source without an associated URL")]
@@ -162,6 +166,8 @@ pub enum CompileErrorKind {
     UnsupportedPatternExpr,
     #[error("not a valid binding")]
     UnsupportedBinding,
+    #[error("variable `{name}` cannot be bound in an or-pattern")]
+    UnsupportedOrPatternBinding { name: String },
     #[error("floating point numbers cannot be used in patterns")]
     MatchFloatInPattern,
     #[error("duplicate key in literal object")]
@@ -180,6 +186,8 @@ pub enum CompileErrorKind {
     BreakOutsideOfLoop,
     #[error("continue outside of loop")]
     ContinueOutsideOfLoop,
+    #[error("the `else` branch of a `let` must diverge, like with `return`, `break`, `continue`, or `panic`")]
+    LetElseMustDiverge,
     #[error("multiple `default` branches in select")]
     SelectMultipleDefaults,
     #[error("expected expression to be terminated by a semicolon `;`")]
@@ -198,7 +206,7 @@ pub enum CompileErrorKind {
     UnsupportedCrate,
     #[error("`Self` is only supported inside of `impl` blocks")]
     UnsupportedSelfType,
-    #[error("`self` cannot be used here")]
+    #[error("`self` can only be used inside of methods")]
     UnsupportedSelfValue,
     #[error("`super` is not supported at the root module level")]
     UnsupportedSuper,
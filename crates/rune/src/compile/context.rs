@@ -1,14 +1,15 @@
 use crate::collections::{HashMap, HashSet};
 use crate::compile::module::{
-    AssocFn, AssocKey, AssocKind, Function, InternalEnum, Macro, Module, ModuleFn, Type,
-    TypeSpecification, UnitType,
+    AssocFn, AssocKey, AssocKind, Enum, Function, Implementation, Interface, InternalEnum, Macro,
+    Module, ModuleFn, Type, TypeSpecification, UnitType, VariantKind,
 };
 use crate::compile::{
-    ComponentRef, IntoComponent, Item, Meta, Names, PrivMeta, PrivMetaKind, StructMeta, TupleMeta,
+    ComponentRef, EmptyMeta, IntoComponent, Item, LinkerError, Meta, Names, PrivMeta, PrivMetaKind,
+    StructMeta, TupleMeta,
 };
 use crate::runtime::{
-    ConstValue, FunctionHandler, MacroHandler, Protocol, RuntimeContext, StaticType, TypeCheck,
-    TypeInfo, TypeOf, VmError,
+    ConstEvalHandler, ConstValue, FunctionHandler, KeyFn, MacroHandler, NativeEnum, NativeVariant,
+    Protocol, RuntimeContext, StaticType, TypeCheck, TypeInfo, TypeOf, VmError,
 };
 use crate::{Hash, InstFnKind};
 use std::fmt;
@@ -33,6 +34,8 @@ pub enum ContextError {
     },
     #[error("function with name `{name}` already exists")]
     ConflictingFunctionName { name: Item },
+    #[error("function with name `{name}` does not exist")]
+    MissingFunction { name: Item },
     #[error("constant with name `{name}` already exists")]
     ConflictingConstantName { name: Item },
     #[error("instance function `{name}` for type `{type_info}` already exists")]
@@ -53,10 +56,37 @@ pub enum ContextError {
     ConflictingTypeHash { hash: Hash, existing: Hash },
     #[error("variant with `{item}` already exists")]
     ConflictingVariant { item: Item },
+    #[error("instance function with hash `{hash}` for type `{type_info}` does not exist")]
+    MissingInstanceFunction { type_info: TypeInfo, hash: Hash },
     #[error("instance `{instance_type}` does not exist in module")]
     MissingInstance { instance_type: TypeInfo },
     #[error("error when converting to constant value: {error}")]
     ValueError { error: VmError },
+    #[error(
+        "reexport `{item}` refers to missing item `{target}`, the module providing `{target}` has to be installed before the module reexporting it"
+    )]
+    MissingReexportTarget { item: Item, target: Item },
+    #[error("interface `{item}` already exists")]
+    ConflictingInterface { item: Item },
+    #[error("enum `{type_info}` has no variant with index `{index}`")]
+    MissingEnumVariant { type_info: TypeInfo, index: usize },
+    #[error("struct variant `{name}` of `{type_info}` can't have a constructor")]
+    UnsupportedVariantConstructor {
+        type_info: TypeInfo,
+        name: &'static str,
+    },
+    #[error("constructor for variant `{name}` of `{type_info}` takes {actual} arguments, but the variant has {expected} fields")]
+    BadVariantConstructorArgs {
+        type_info: TypeInfo,
+        name: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("variant `{name}` of `{type_info}` already has a constructor")]
+    ConflictingVariantConstructor {
+        type_info: TypeInfo,
+        name: &'static str,
+    },
 }
 
 /// Information on a specific type.
@@ -190,6 +220,17 @@ pub struct Context {
     crates: HashSet<Box<str>>,
     /// Constants visible in this context
     constants: HashMap<Hash, ConstValue>,
+    /// Native functions which can be evaluated in constant expressions.
+    const_eval: HashMap<Hash, Arc<ConstEvalHandler>>,
+    /// Interfaces and the instance functions they're made up of.
+    interfaces: HashMap<Item, Box<[(Box<str>, usize)]>>,
+    /// Types which have been declared to implement an interface, checked
+    /// when linking.
+    implementations: Vec<Implementation>,
+    /// Native types which can be used as keys.
+    keys: HashMap<Hash, Arc<KeyFn>>,
+    /// Native enums whose variants can be matched on.
+    enums: HashMap<Hash, Arc<NativeEnum>>,
 }
 
 impl Context {
@@ -219,6 +260,7 @@ impl Context {
         this.install(&crate::modules::cmp::module()?)?;
         this.install(&crate::modules::collections::module()?)?;
         this.install(&crate::modules::core::module()?)?;
+        this.install(&crate::modules::env::module()?)?;
         this.install(&crate::modules::float::module()?)?;
         this.install(&crate::modules::fmt::module()?)?;
         this.install(&crate::modules::future::module()?)?;
@@ -233,6 +275,7 @@ impl Context {
         this.install(&crate::modules::result::module()?)?;
         this.install(&crate::modules::stream::module()?)?;
         this.install(&crate::modules::string::module()?)?;
+        this.install(&crate::modules::tuple::module()?)?;
         this.install(&crate::modules::vec::module()?)?;
         this.has_default_modules = true;
         Ok(this)
@@ -262,7 +305,12 @@ impl Context {
     /// # Ok(()) }
     /// ```
     pub fn runtime(&self) -> RuntimeContext {
-        RuntimeContext::new(self.functions.clone(), self.constants.clone())
+        RuntimeContext::new(
+            self.functions.clone(),
+            self.constants.clone(),
+            self.keys.clone(),
+            self.enums.clone(),
+        )
     }
 
     /// Install the specified module.
@@ -303,6 +351,21 @@ impl Context {
             self.install_associated_function(key, inst)?;
         }
 
+        for (hash, key_fn) in &module.keys {
+            self.keys.insert(*hash, key_fn.clone());
+        }
+
+        for (name, target) in &module.reexports {
+            self.install_reexport(module, name, target)?;
+        }
+
+        for (name, interface) in &module.interfaces {
+            self.install_interface(module, name, interface)?;
+        }
+
+        self.implementations
+            .extend(module.implementations.iter().cloned());
+
         Ok(())
     }
 
@@ -316,6 +379,29 @@ impl Context {
         })
     }
 
+    /// Replace the handlers of native functions installed in the [Context].
+    ///
+    /// The `wrap` callback is called once for every installed function with
+    /// its signature and current handler. If it returns a new handler, that
+    /// handler is used in place of the old one. This makes it possible to
+    /// intercept calls to native functions, like when recording or replaying
+    /// their results in tests.
+    pub fn wrap_functions<F>(&mut self, mut wrap: F)
+    where
+        F: FnMut(&ContextSignature, &Arc<FunctionHandler>) -> Option<Arc<FunctionHandler>>,
+    {
+        for (hash, signature) in &self.functions_info {
+            let handler = match self.functions.get_mut(hash) {
+                Some(handler) => handler,
+                None => continue,
+            };
+
+            if let Some(wrapped) = wrap(signature, handler) {
+                *handler = wrapped;
+            }
+        }
+    }
+
     /// Iterate over all available types in the [Context].
     pub fn iter_types(&self) -> impl Iterator<Item = (Hash, &ContextTypeInfo)> {
         let mut it = self.types.iter();
@@ -353,6 +439,12 @@ impl Context {
         self.functions.get(&hash)
     }
 
+    /// Lookup the handler used to evaluate the given native function in
+    /// constant expressions.
+    pub(crate) fn lookup_const_eval(&self, hash: Hash) -> Option<&Arc<ConstEvalHandler>> {
+        self.const_eval.get(&hash)
+    }
+
     /// Lookup the given macro handler.
     pub(crate) fn lookup_macro(&self, hash: Hash) -> Option<&Arc<MacroHandler>> {
         self.macros.get(&hash)
@@ -369,6 +461,45 @@ impl Context {
         self.crates.contains(name)
     }
 
+    /// Check that every type which has been declared to implement an interface
+    /// provides all of its instance functions.
+    ///
+    /// This is done once the context is complete, so that interfaces, types
+    /// and their instance functions can be installed in any order.
+    pub(crate) fn check_implementations(&self) -> Result<(), LinkerError> {
+        for implementation in &self.implementations {
+            let methods = match self.interfaces.get(&implementation.interface) {
+                Some(methods) => methods,
+                None => {
+                    return Err(LinkerError::MissingInterface {
+                        item: implementation.interface.clone(),
+                    });
+                }
+            };
+
+            for (name, args) in methods.iter() {
+                let hash = Hash::instance_function(implementation.type_hash, Hash::of(name));
+
+                let found = match self.functions_info.get(&hash) {
+                    Some(ContextSignature::Instance { args: Some(n), .. }) => *n == *args + 1,
+                    Some(ContextSignature::Instance { args: None, .. }) => true,
+                    _ => false,
+                };
+
+                if !found {
+                    return Err(LinkerError::MissingInterfaceMethod {
+                        type_info: implementation.type_info.clone(),
+                        interface: implementation.interface.clone(),
+                        name: name.clone(),
+                        args: *args,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Test if the context has the default modules installed.
     ///
     /// This determines among other things whether a prelude should be used or
@@ -416,7 +547,20 @@ impl Context {
                     st: StructMeta {
                         fields: st.fields.clone(),
                     },
+                    debug: true,
                 },
+                TypeSpecification::Enum(en) => {
+                    self.install_native_enum(&item, type_hash, ty, en)?;
+
+                    PrivMetaKind::Enum {
+                        type_hash,
+                        variants: en
+                            .variants
+                            .iter()
+                            .map(|variant| Box::from(variant.name))
+                            .collect(),
+                    }
+                }
             }
         } else {
             PrivMetaKind::Unknown { type_hash }
@@ -431,6 +575,98 @@ impl Context {
         Ok(())
     }
 
+    /// Install the variants of a native enum.
+    fn install_native_enum(
+        &mut self,
+        enum_item: &Item,
+        type_hash: Hash,
+        ty: &Type,
+        en: &Enum,
+    ) -> Result<(), ContextError> {
+        let mut variants = Vec::with_capacity(en.variants.len());
+
+        for variant in &en.variants {
+            let item = enum_item.extended(variant.name);
+            let hash = Hash::type_hash(&item);
+
+            self.install_type_info(
+                hash,
+                ContextTypeInfo {
+                    type_check: TypeCheck::Variant(hash),
+                    item: item.clone(),
+                    type_hash: hash,
+                    type_info: ty.type_info.clone(),
+                },
+            )?;
+
+            let kind = match variant.kind {
+                VariantKind::Unit => PrivMetaKind::UnitVariant {
+                    type_hash: hash,
+                    enum_item: enum_item.clone(),
+                    empty: EmptyMeta { hash },
+                    debug: true,
+                },
+                VariantKind::Tuple(args) => PrivMetaKind::TupleVariant {
+                    type_hash: hash,
+                    enum_item: enum_item.clone(),
+                    tuple: TupleMeta { args, hash },
+                    debug: true,
+                },
+                VariantKind::Struct(fields) => PrivMetaKind::StructVariant {
+                    type_hash: hash,
+                    enum_item: enum_item.clone(),
+                    st: StructMeta {
+                        fields: fields.iter().copied().map(Box::<str>::from).collect(),
+                    },
+                    debug: true,
+                },
+            };
+
+            self.install_meta(PrivMeta {
+                item: Arc::new(item.clone().into()),
+                kind,
+                source: None,
+            })?;
+
+            if let Some((constructor, args)) = &variant.constructor {
+                let signature = ContextSignature::Function {
+                    type_hash,
+                    item,
+                    args: Some(*args),
+                };
+
+                if let Some(old) = self.functions_info.insert(hash, signature) {
+                    return Err(ContextError::ConflictingFunction {
+                        signature: old,
+                        hash,
+                    });
+                }
+
+                self.functions.insert(hash, constructor.clone());
+            }
+
+            variants.push(NativeVariant {
+                hash,
+                fields: match variant.kind {
+                    VariantKind::Struct(fields) => {
+                        Some(fields.iter().copied().map(Box::<str>::from).collect())
+                    }
+                    _ => None,
+                },
+            });
+        }
+
+        self.enums.insert(
+            type_hash,
+            Arc::new(NativeEnum {
+                variants: variants.into(),
+                handler: en.handler.clone(),
+            }),
+        );
+
+        Ok(())
+    }
+
     fn install_type_info(&mut self, hash: Hash, info: ContextTypeInfo) -> Result<(), ContextError> {
         self.names.insert(&info.item);
 
@@ -485,6 +721,11 @@ impl Context {
         );
 
         self.functions.insert(hash, f.handler.clone());
+
+        if let Some(const_eval) = &f.const_eval {
+            self.const_eval.insert(hash, const_eval.clone());
+        }
+
         self.meta.insert(
             item.clone(),
             PrivMeta {
@@ -637,6 +878,106 @@ impl Context {
         Ok(())
     }
 
+    /// Install an alias for an existing function, constant or macro.
+    fn install_reexport(
+        &mut self,
+        module: &Module,
+        item: &Item,
+        target: &Item,
+    ) -> Result<(), ContextError> {
+        let item = module.item.join(item);
+        let hash = Hash::type_hash(&item);
+        let target_hash = Hash::type_hash(target);
+
+        let mut found = false;
+
+        if let (Some(handler), Some(signature)) = (
+            self.functions.get(&target_hash),
+            self.functions_info.get(&target_hash),
+        ) {
+            let handler = handler.clone();
+
+            let args = match signature {
+                ContextSignature::Function { args, .. } => *args,
+                ContextSignature::Instance { args, .. } => *args,
+            };
+
+            let signature = ContextSignature::Function {
+                type_hash: hash,
+                item: item.clone(),
+                args,
+            };
+
+            if let Some(old) = self.functions_info.insert(hash, signature) {
+                return Err(ContextError::ConflictingFunction {
+                    signature: old,
+                    hash,
+                });
+            }
+
+            self.constants.insert(
+                Hash::instance_function(hash, Protocol::INTO_TYPE_NAME),
+                ConstValue::String(item.to_string()),
+            );
+
+            self.functions.insert(hash, handler);
+            self.install_meta(PrivMeta {
+                item: Arc::new(item.clone().into()),
+                kind: PrivMetaKind::Function {
+                    type_hash: hash,
+                    is_test: false,
+                    is_bench: false,
+                },
+                source: None,
+            })?;
+
+            found = true;
+        } else if let Some(const_value) = self.constants.get(&target_hash) {
+            let const_value = const_value.clone();
+            self.constants.insert(hash, const_value.clone());
+            self.install_meta(PrivMeta {
+                item: Arc::new(item.clone().into()),
+                kind: PrivMetaKind::Const { const_value },
+                source: None,
+            })?;
+
+            found = true;
+        }
+
+        if let Some(handler) = self.macros.get(&target_hash) {
+            let handler = handler.clone();
+            self.macros.insert(hash, handler);
+            found = true;
+        }
+
+        if !found {
+            return Err(ContextError::MissingReexportTarget {
+                item,
+                target: target.clone(),
+            });
+        }
+
+        self.names.insert(&item);
+        Ok(())
+    }
+
+    /// Install an interface.
+    fn install_interface(
+        &mut self,
+        module: &Module,
+        name: &Item,
+        interface: &Interface,
+    ) -> Result<(), ContextError> {
+        let item = module.item.join(name);
+
+        if self.interfaces.contains_key(&item) {
+            return Err(ContextError::ConflictingInterface { item });
+        }
+
+        self.interfaces.insert(item, interface.methods.clone());
+        Ok(())
+    }
+
     /// Install unit type.
     fn install_unit_type(
         &mut self,
@@ -679,6 +1020,11 @@ impl Context {
             item: Arc::new(enum_item.clone().into()),
             kind: PrivMetaKind::Enum {
                 type_hash: internal_enum.static_type.hash,
+                variants: internal_enum
+                    .variants
+                    .iter()
+                    .map(|variant| Box::from(variant.name))
+                    .collect(),
             },
             source: None,
         })?;
@@ -716,6 +1062,7 @@ impl Context {
                         args: variant.args,
                         hash,
                     },
+                    debug: true,
                 },
                 source: None,
             })?;
@@ -762,12 +1109,17 @@ impl Context {
                     type_hash,
                     enum_item,
                     tuple,
+                    debug: true,
                 },
                 source: None,
             },
             None => PrivMeta {
                 item: Arc::new(item.clone().into()),
-                kind: PrivMetaKind::TupleStruct { type_hash, tuple },
+                kind: PrivMetaKind::TupleStruct {
+                    type_hash,
+                    tuple,
+                    debug: true,
+                },
                 source: None,
             },
         };
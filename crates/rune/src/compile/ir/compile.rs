@@ -1,10 +1,9 @@
 use crate::ast;
-use crate::ast::Spanned;
+use crate::ast::{OptionSpanned, Spanned};
 use crate::compile::ir;
 use crate::compile::{IrError, IrValue};
 use crate::parse::Resolve;
 use crate::query::{BuiltInMacro, BuiltInTemplate, Query};
-use crate::runtime::{Bytes, Shared};
 
 /// A c that compiles AST into Rune IR.
 pub(crate) struct IrCompiler<'a> {
@@ -20,6 +19,28 @@ impl IrCompiler<'_> {
         Ok(value.resolve(resolve_context!(self.q))?)
     }
 
+    /// Resolve a path which only consists of identifiers, like
+    /// `Enum::Variant`, into its components.
+    pub(crate) fn path_components(
+        &self,
+        path: &ast::Path,
+    ) -> Result<Option<Box<[Box<str>]>>, IrError> {
+        if path.global.is_some() || path.trailing.is_some() {
+            return Ok(None);
+        }
+
+        let mut components = Vec::new();
+
+        for segment in path.as_components() {
+            match segment.try_as_ident() {
+                Some(ident) => components.push(self.resolve(ident)?.into()),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(components.into_boxed_slice()))
+    }
+
     /// Resolve an ir target from an expression.
     fn ir_target(&self, expr: &ast::Expr) -> Result<ir::IrTarget, IrError> {
         match expr {
@@ -67,23 +88,31 @@ impl IrCompiler<'_> {
 pub(crate) fn expr(ast: &ast::Expr, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
     Ok(match ast {
         ast::Expr::Vec(e) => ir::Ir::new(e.span(), expr_vec(e, c)?),
+        ast::Expr::Repeat(e) => ir::Ir::new(e.span(), expr_repeat(e, c)?),
         ast::Expr::Tuple(e) => expr_tuple(e, c)?,
         ast::Expr::Object(e) => ir::Ir::new(e.span(), expr_object(e, c)?),
         ast::Expr::Group(e) => expr(&e.expr, c)?,
         ast::Expr::Empty(e) => expr(&e.expr, c)?,
         ast::Expr::Binary(e) => expr_binary(e, c)?,
+        ast::Expr::Unary(e) => expr_unary(e, c)?,
         ast::Expr::Assign(e) => expr_assign(e, c)?,
-        ast::Expr::Call(e) => ir::Ir::new(e.span(), expr_call(e, c)?),
+        ast::Expr::Call(e) => expr_call(e, c)?,
         ast::Expr::If(e) => ir::Ir::new(e.span(), expr_if(e, c)?),
+        ast::Expr::Match(e) => expr_match(e, c)?,
         ast::Expr::Loop(e) => ir::Ir::new(e.span(), expr_loop(e, c)?),
         ast::Expr::While(e) => ir::Ir::new(e.span(), expr_while(e, c)?),
         ast::Expr::Lit(e) => expr_lit(e, c)?,
         ast::Expr::Block(e) => expr_block(e, c)?,
         ast::Expr::Path(e) => path(e, c)?,
-        ast::Expr::FieldAccess(..) => ir::Ir::new(ast.span(), c.ir_target(ast)?),
+        ast::Expr::FieldAccess(e) => ir::Ir::new(e.span(), expr_field_access(e, c)?),
+        ast::Expr::Index(e) => ir::Ir::new(e.span(), expr_index(e, c)?),
         ast::Expr::Break(expr_break) => {
             ir::Ir::new(expr_break, ir::IrBreak::compile_ast(expr_break, c)?)
         }
+        ast::Expr::Continue(expr_continue) => ir::Ir::new(
+            expr_continue,
+            ir::IrContinue::compile_ast(expr_continue, c)?,
+        ),
         ast::Expr::MacroCall(macro_call) => {
             let internal_macro = c.q.builtin_macro_for(&*macro_call)?;
 
@@ -93,8 +122,8 @@ pub(crate) fn expr(ast: &ast::Expr, c: &mut IrCompiler<'_>) -> Result<ir::Ir, Ir
                     ir::Ir::new(ast.span(), ir_template)
                 }
                 BuiltInMacro::File(file) => {
-                    let s = c.resolve(&file.value)?;
-                    ir::Ir::new(file.span, IrValue::String(Shared::new(s.into_owned())))
+                    let s = c.resolve(&file.value)?.into_owned();
+                    ir::Ir::new(file.span, IrValue::String(c.q.consts.intern_string(&s)))
                 }
                 BuiltInMacro::Line(line) => {
                     let n = c.resolve(&line.value)?;
@@ -129,7 +158,7 @@ fn expr_assign(ast: &ast::ExprAssign, c: &mut IrCompiler<'_>) -> Result<ir::Ir,
     ))
 }
 
-fn expr_call(ast: &ast::ExprCall, c: &mut IrCompiler<'_>) -> Result<ir::IrCall, IrError> {
+fn expr_call(ast: &ast::ExprCall, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
     let span = ast.span();
 
     let mut args = Vec::with_capacity(ast.args.len());
@@ -138,16 +167,52 @@ fn expr_call(ast: &ast::ExprCall, c: &mut IrCompiler<'_>) -> Result<ir::IrCall,
         args.push(expr(e, c)?);
     }
 
-    if let ast::Expr::Path(path) = &*ast.expr {
-        if let Some(ident) = path.try_as_ident() {
-            let target = c.resolve(ident)?;
+    match &*ast.expr {
+        ast::Expr::Path(path) => {
+            if let Some(ident) = path.try_as_ident() {
+                let target = c.resolve(ident)?;
+
+                return Ok(ir::Ir::new(
+                    span,
+                    ir::IrCall {
+                        span,
+                        target: target.into(),
+                        instance: None,
+                        args,
+                    },
+                ));
+            }
 
-            return Ok(ir::IrCall {
-                span,
-                target: target.into(),
-                args,
-            });
+            if let Some(path) = c.path_components(path)? {
+                return Ok(ir::Ir::new(
+                    span,
+                    ir::IrVariant {
+                        span,
+                        path,
+                        args: Some(args),
+                    },
+                ));
+            }
         }
+        ast::Expr::FieldAccess(field_access) => {
+            if let ast::ExprField::Path(path) = &field_access.expr_field {
+                if let Some(ident) = path.try_as_ident() {
+                    let instance = expr(&field_access.expr, c)?;
+                    let target = c.resolve(ident)?;
+
+                    return Ok(ir::Ir::new(
+                        span,
+                        ir::IrCall {
+                            span,
+                            target: target.into(),
+                            instance: Some(Box::new(instance)),
+                            args,
+                        },
+                    ));
+                }
+            }
+        }
+        _ => (),
     }
 
     Err(IrError::msg(span, "call not supported"))
@@ -162,9 +227,13 @@ fn expr_binary(ast: &ast::ExprBinary, c: &mut IrCompiler<'_>) -> Result<ir::Ir,
             ast::BinOp::SubAssign(..) => ir::IrAssignOp::Sub,
             ast::BinOp::MulAssign(..) => ir::IrAssignOp::Mul,
             ast::BinOp::DivAssign(..) => ir::IrAssignOp::Div,
+            ast::BinOp::RemAssign(..) => ir::IrAssignOp::Rem,
             ast::BinOp::ShlAssign(..) => ir::IrAssignOp::Shl,
             ast::BinOp::ShrAssign(..) => ir::IrAssignOp::Shr,
-            _ => return Err(IrError::msg(&ast.op, "op not supported yet")),
+            ast::BinOp::BitAndAssign(..) => ir::IrAssignOp::BitAnd,
+            ast::BinOp::BitOrAssign(..) => ir::IrAssignOp::BitOr,
+            ast::BinOp::BitXorAssign(..) => ir::IrAssignOp::BitXor,
+            _ => return Err(IrError::msg(ast.op, "op not supported yet")),
         };
 
         let target = c.ir_target(&ast.lhs)?;
@@ -188,14 +257,20 @@ fn expr_binary(ast: &ast::ExprBinary, c: &mut IrCompiler<'_>) -> Result<ir::Ir,
         ast::BinOp::Sub(..) => ir::IrBinaryOp::Sub,
         ast::BinOp::Mul(..) => ir::IrBinaryOp::Mul,
         ast::BinOp::Div(..) => ir::IrBinaryOp::Div,
+        ast::BinOp::Rem(..) => ir::IrBinaryOp::Rem,
         ast::BinOp::Shl(..) => ir::IrBinaryOp::Shl,
         ast::BinOp::Shr(..) => ir::IrBinaryOp::Shr,
+        ast::BinOp::BitAnd(..) => ir::IrBinaryOp::BitAnd,
+        ast::BinOp::BitOr(..) => ir::IrBinaryOp::BitOr,
+        ast::BinOp::BitXor(..) => ir::IrBinaryOp::BitXor,
         ast::BinOp::Lt(..) => ir::IrBinaryOp::Lt,
         ast::BinOp::Lte(..) => ir::IrBinaryOp::Lte,
         ast::BinOp::Eq(..) => ir::IrBinaryOp::Eq,
         ast::BinOp::Gt(..) => ir::IrBinaryOp::Gt,
         ast::BinOp::Gte(..) => ir::IrBinaryOp::Gte,
-        _ => return Err(IrError::msg(&ast.op, "op not supported yet")),
+        ast::BinOp::And(..) => ir::IrBinaryOp::And,
+        ast::BinOp::Or(..) => ir::IrBinaryOp::Or,
+        _ => return Err(IrError::msg(ast.op, "op not supported yet")),
     };
 
     Ok(ir::Ir::new(
@@ -209,20 +284,50 @@ fn expr_binary(ast: &ast::ExprBinary, c: &mut IrCompiler<'_>) -> Result<ir::Ir,
     ))
 }
 
+fn expr_unary(ast: &ast::ExprUnary, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
+    let span = ast.span();
+
+    let op = match &ast.op {
+        ast::UnOp::Not(..) => ir::IrUnaryOp::Not,
+        _ => return Err(IrError::msg(ast.op, "op not supported yet")),
+    };
+
+    let expr = expr(&ast.expr, c)?;
+
+    Ok(ir::Ir::new(
+        span,
+        ir::IrUnary {
+            span,
+            op,
+            expr: Box::new(expr),
+        },
+    ))
+}
+
 fn expr_lit(ast: &ast::ExprLit, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
+    use num::ToPrimitive as _;
+
     let span = ast.span();
 
     Ok(match &ast.lit {
         ast::Lit::Bool(b) => ir::Ir::new(span, IrValue::Bool(b.value)),
         ast::Lit::Str(s) => {
-            let s = c.resolve(s)?;
-            ir::Ir::new(span, IrValue::String(Shared::new(s.into_owned())))
+            let s = c.resolve(s)?.into_owned();
+            ir::Ir::new(span, IrValue::String(c.q.consts.intern_string(&s)))
         }
-        ast::Lit::Number(n) => {
-            let n = c.resolve(n)?;
+        ast::Lit::Number(lit) => {
+            let n = c.resolve(lit)?;
+            let suffix = lit.suffix();
+
+            if let Some(suffix) = suffix {
+                suffix.check(&n, span, false)?;
+            }
 
             let const_value = match n {
-                ast::Number::Integer(n) => IrValue::Integer(n),
+                ast::Number::Integer(n) => match (suffix, n.to_u8()) {
+                    (Some(ast::NumberSuffix::U8), Some(b)) => IrValue::Byte(b),
+                    _ => IrValue::Integer(n),
+                },
                 ast::Number::Float(n) => IrValue::Float(n),
             };
 
@@ -233,8 +338,8 @@ fn expr_lit(ast: &ast::ExprLit, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrErro
             ir::Ir::new(span, IrValue::Byte(b))
         }
         ast::Lit::ByteStr(lit) => {
-            let byte_str = c.resolve(lit)?;
-            let value = IrValue::Bytes(Shared::new(Bytes::from_vec(byte_str.into_owned())));
+            let byte_str = c.resolve(lit)?.into_owned();
+            let value = IrValue::Bytes(c.q.consts.intern_bytes(&byte_str));
             ir::Ir::new(span, value)
         }
         ast::Lit::Char(lit) => {
@@ -269,8 +374,11 @@ fn expr_tuple(ast: &ast::ExprTuple, c: &mut IrCompiler<'_>) -> Result<ir::Ir, Ir
 fn expr_vec(ast: &ast::ExprVec, c: &mut IrCompiler<'_>) -> Result<ir::IrVec, IrError> {
     let mut items = Vec::new();
 
-    for (e, _) in &ast.items {
-        items.push(expr(e, c)?);
+    for (item, _) in &ast.items {
+        items.push(match item {
+            ast::ExprVecItem::Expr(e) => ir::IrVecItem::Ir(expr(e, c)?),
+            ast::ExprVecItem::Spread(_, e) => ir::IrVecItem::Spread(expr(e, c)?),
+        });
     }
 
     Ok(ir::IrVec {
@@ -279,6 +387,56 @@ fn expr_vec(ast: &ast::ExprVec, c: &mut IrCompiler<'_>) -> Result<ir::IrVec, IrE
     })
 }
 
+fn expr_index(ast: &ast::ExprIndex, c: &mut IrCompiler<'_>) -> Result<ir::IrIndex, IrError> {
+    Ok(ir::IrIndex {
+        span: ast.span(),
+        target: Box::new(expr(&ast.target, c)?),
+        index: Box::new(expr(&ast.index, c)?),
+    })
+}
+
+fn expr_field_access(
+    ast: &ast::ExprFieldAccess,
+    c: &mut IrCompiler<'_>,
+) -> Result<ir::IrIndex, IrError> {
+    let index = match &ast.expr_field {
+        ast::ExprField::Path(path) => {
+            let ident = match path.try_as_ident() {
+                Some(ident) => ident,
+                None => return Err(IrError::msg(path, "not supported yet")),
+            };
+
+            let field = c.resolve(ident)?.to_owned();
+            ir::Ir::new(
+                path,
+                IrValue::String(c.q.consts.intern_string(field.as_ref())),
+            )
+        }
+        ast::ExprField::LitNumber(number) => {
+            let index = match c.resolve(number)?.as_tuple_index() {
+                Some(index) => index,
+                None => return Err(IrError::msg(number, "unsupported tuple index")),
+            };
+
+            ir::Ir::new(number, IrValue::Integer(index.into()))
+        }
+    };
+
+    Ok(ir::IrIndex {
+        span: ast.span(),
+        target: Box::new(expr(&ast.expr, c)?),
+        index: Box::new(index),
+    })
+}
+
+fn expr_repeat(ast: &ast::ExprRepeat, c: &mut IrCompiler<'_>) -> Result<ir::IrRepeat, IrError> {
+    Ok(ir::IrRepeat {
+        span: ast.span(),
+        expr: Box::new(expr(&ast.expr, c)?),
+        count: Box::new(expr(&ast.count, c)?),
+    })
+}
+
 fn expr_object(ast: &ast::ExprObject, c: &mut IrCompiler<'_>) -> Result<ir::IrObject, IrError> {
     let mut assignments = Vec::new();
 
@@ -307,6 +465,13 @@ fn expr_object(ast: &ast::ExprObject, c: &mut IrCompiler<'_>) -> Result<ir::IrOb
 }
 
 pub(crate) fn expr_block(ast: &ast::ExprBlock, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
+    if let Some(span) = ast.label.option_span() {
+        return Err(IrError::msg(
+            span,
+            "labeled blocks are not supported in constant evaluation",
+        ));
+    }
+
     Ok(ir::Ir::new(ast.span(), block(&ast.block, c)?))
 }
 
@@ -390,12 +555,27 @@ fn path(ast: &ast::Path, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
         return Ok(ir::Ir::new(span, <Box<str>>::from(name)));
     }
 
+    if let Some(path) = c.path_components(ast)? {
+        return Ok(ir::Ir::new(
+            span,
+            ir::IrVariant {
+                span,
+                path,
+                args: None,
+            },
+        ));
+    }
+
     Err(IrError::msg(span, "not supported yet"))
 }
 
 fn local(ast: &ast::Local, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
     let span = ast.span();
 
+    if ast.else_.is_some() {
+        return Err(IrError::msg(span, "not supported yet"));
+    }
+
     let name = loop {
         match &ast.pat {
             ast::Pat::PatIgnore(_) => {
@@ -460,9 +640,69 @@ fn expr_if(ast: &ast::ExprIf, c: &mut IrCompiler<'_>) -> Result<ir::IrBranches,
     Ok(ir::IrBranches {
         branches,
         default_branch,
+        unmatched: None,
     })
 }
 
+fn expr_match(ast: &ast::ExprMatch, c: &mut IrCompiler<'_>) -> Result<ir::Ir, IrError> {
+    /// The name the value being matched over is bound to. This is not a valid
+    /// identifier, so it can't conflict with any variable in scope.
+    const MATCHED: &str = "$match";
+
+    let span = ast.span();
+    let mut branches = Vec::new();
+
+    for (branch, _) in &ast.branches {
+        if let Some((_, condition)) = &branch.condition {
+            return Err(IrError::msg(condition, "not supported yet"));
+        }
+
+        let pat = ir::IrPat::compile_ast(&branch.pat, c)?;
+        let body = expr(&branch.body, c)?;
+
+        let condition = ir::IrCondition::Let(ir::IrLet {
+            span: branch.pat.span(),
+            pat,
+            ir: ir::Ir::new(ast.expr.span(), ir::IrKind::Name(MATCHED.into())),
+        });
+
+        let body = ir::IrScope {
+            span: body.span(),
+            instructions: Vec::new(),
+            last: Some(Box::new(body)),
+        };
+
+        branches.push((condition, body));
+    }
+
+    let decl = ir::Ir::new(
+        ast.expr.span(),
+        ir::IrDecl {
+            span: ast.expr.span(),
+            name: MATCHED.into(),
+            value: Box::new(expr(&ast.expr, c)?),
+        },
+    );
+
+    let branches = ir::Ir::new(
+        span,
+        ir::IrBranches {
+            branches,
+            default_branch: None,
+            unmatched: Some(span),
+        },
+    );
+
+    Ok(ir::Ir::new(
+        span,
+        ir::IrScope {
+            span,
+            instructions: vec![decl],
+            last: Some(Box::new(branches)),
+        },
+    ))
+}
+
 fn expr_while(ast: &ast::ExprWhile, c: &mut IrCompiler<'_>) -> Result<ir::IrLoop, IrError> {
     Ok(ir::IrLoop {
         span: ast.span(),
@@ -1,8 +1,8 @@
-use crate::ast::{Spanned, SpannedError};
-use crate::compile::{IrValue, Meta};
-use crate::parse::{ResolveError, ResolveErrorKind};
+use crate::ast::{Span, Spanned, SpannedError};
+use crate::compile::{IrValue, Item, Meta};
+use crate::parse::{ParseError, ParseErrorKind, ResolveError, ResolveErrorKind};
 use crate::query::{QueryError, QueryErrorKind};
-use crate::runtime::{AccessError, TypeInfo, TypeOf};
+use crate::runtime::{AccessError, TypeInfo, TypeOf, VmError};
 use crate::shared::{ScopeError, ScopeErrorKind};
 use thiserror::Error;
 
@@ -13,6 +13,7 @@ error! {
         kind: IrErrorKind,
     }
 
+    impl From<ParseError>;
     impl From<ResolveError>;
     impl From<QueryError>;
     impl From<ScopeError>;
@@ -81,6 +82,12 @@ pub enum IrErrorKind {
         error: Box<QueryErrorKind>,
     },
     #[error("{error}")]
+    ParseError {
+        #[source]
+        #[from]
+        error: ParseErrorKind,
+    },
+    #[error("{error}")]
     ResolveError {
         #[source]
         #[from]
@@ -110,6 +117,12 @@ pub enum IrErrorKind {
     /// Exceeded evaluation budget.
     #[error("evaluation budget exceeded")]
     BudgetExceeded,
+    /// Exceeded the maximum depth of nested const fn calls.
+    #[error("const recursion limit reached ({limit} nested calls)")]
+    ConstRecursionLimit {
+        /// The configured limit.
+        limit: usize,
+    },
     /// Integer underflow.
     #[error("integer underflow")]
     IntegerUnderflow,
@@ -125,6 +138,9 @@ pub enum IrErrorKind {
         /// The field that was missing.
         field: Box<str>,
     },
+    /// No arm of a constant `match` expression matched the value.
+    #[error("no match arm matched the value")]
+    NoMatchingArm,
     /// Missing local with the given name.
     #[error("missing local `{name}`")]
     MissingLocal {
@@ -137,13 +153,40 @@ pub enum IrErrorKind {
         /// Name of the missing thing.
         name: Box<str>,
     },
+    /// Missing enum variant with the given path.
+    #[error("no variant matching `{name}`")]
+    MissingVariant {
+        /// Path to the missing variant.
+        name: Box<str>,
+    },
     /// Error raised when trying to use a break outside of a loop.
     #[error("break outside of supported loop")]
     BreakOutsideOfLoop,
+    /// Error raised when trying to use a continue outside of a loop.
+    #[error("continue outside of supported loop")]
+    ContinueOutsideOfLoop,
     #[error("function not found")]
     FnNotFound,
+    /// A native const evaluator registered for a host function errored.
+    #[error("error in native const evaluation: {error}")]
+    NativeConstEval {
+        /// The error raised by the evaluator.
+        #[source]
+        error: VmError,
+    },
     #[error("argument count mismatch, got {actual} but expected {expected}")]
     ArgumentCountMismatch { actual: usize, expected: usize },
     #[error("value `{value}` is outside of the supported integer range")]
     NotInteger { value: num::BigInt },
+    /// An error raised inside of a const fn call while evaluating a constant
+    /// item.
+    #[error("{error}")]
+    ConstEval {
+        /// The constant item being evaluated.
+        item: Item,
+        /// The calls leading up to the error, outermost first.
+        calls: Vec<(Span, Item)>,
+        /// The error being raised.
+        error: Box<IrErrorKind>,
+    },
 }
@@ -1,9 +1,9 @@
 use crate::ast::{Span, Spanned};
 use crate::collections::HashMap;
 use crate::compile::ir;
-use crate::compile::ir::{IrError, IrInterpreter, IrValue};
+use crate::compile::ir::{IrCallee, IrError, IrErrorKind, IrInterpreter, IrValue};
 use crate::query::Used;
-use crate::runtime::Shared;
+use crate::runtime::{BorrowRef, Shared};
 use std::convert::TryFrom;
 use std::fmt::Write;
 
@@ -22,6 +22,8 @@ pub enum IrEvalOutcome {
     Error(IrError),
     /// Break until the next loop, or the optional label.
     Break(Span, IrEvalBreak),
+    /// Continue the next loop, or the loop with the optional label.
+    Continue(Span, Option<Box<str>>),
 }
 
 impl IrEvalOutcome {
@@ -43,14 +45,12 @@ where
     }
 }
 
-/// The value of a break.
-pub enum IrEvalBreak {
-    /// Break the next nested loop.
-    Inherent,
-    /// The break had a value.
-    Value(IrValue),
-    /// The break had a label.
-    Label(Box<str>),
+/// The target and value of a break.
+pub struct IrEvalBreak {
+    /// The label of the loop to break, or the next nested loop if `None`.
+    pub(crate) label: Option<Box<str>>,
+    /// The value to break with, if any.
+    pub(crate) value: Option<IrValue>,
 }
 
 fn eval_ir_assign(
@@ -60,37 +60,136 @@ fn eval_ir_assign(
 ) -> Result<IrValue, IrEvalOutcome> {
     interp.budget.take(ir)?;
     let value = eval_ir(&ir.value, interp, used)?;
+    let big_integers = interp.q.options.const_big_integers;
 
-    interp
-        .scopes
-        .mut_target(&ir.target, move |t| ir.op.assign(ir, t, value))?;
+    interp.scopes.mut_target(&ir.target, move |t| {
+        ir.op.assign(ir, t, value, big_integers)
+    })?;
 
     Ok(IrValue::Unit)
 }
 
+/// Check that the result of an arithmetic operation fits in the integer type
+/// used at runtime.
+pub(crate) fn check_integer<S>(spanned: S, n: &num::BigInt) -> Result<(), IrError>
+where
+    S: Spanned,
+{
+    use num::ToPrimitive as _;
+
+    if n.to_i64().is_none() {
+        return Err(IrError::msg(spanned, "integer overflow"));
+    }
+
+    Ok(())
+}
+
+/// The largest amount an integer can be shifted by when big integers are
+/// enabled, which keeps shifts from producing arbitrarily large integers.
+const MAX_BIG_INTEGER_SHIFT: u32 = 1 << 16;
+
+/// Convert the right-hand side of a shift into the amount to shift by.
+///
+/// Without big integers the amount is bounded the same way as shifts are at
+/// runtime, so `1 >> 64` is rejected rather than evaluating to `0`.
+pub(crate) fn shift_amount<S>(
+    spanned: S,
+    operand: num::BigInt,
+    big_integers: bool,
+) -> Result<u32, IrError>
+where
+    S: Spanned,
+{
+    if !big_integers {
+        return match u32::try_from(operand) {
+            Ok(n) if n < i64::BITS => Ok(n),
+            _ => Err(IrError::msg(spanned, "numerical overflow")),
+        };
+    }
+
+    match u32::try_from(operand) {
+        Ok(n) if n <= MAX_BIG_INTEGER_SHIFT => Ok(n),
+        _ => Err(IrError::msg(
+            spanned,
+            "cannot be converted to shift operand",
+        )),
+    }
+}
+
+fn eval_ir_unary(
+    ir: &ir::IrUnary,
+    interp: &mut IrInterpreter<'_>,
+    used: Used,
+) -> Result<IrValue, IrEvalOutcome> {
+    let span = ir.span();
+    interp.budget.take(span)?;
+
+    let value = eval_ir(&ir.expr, interp, used)?;
+
+    match (ir.op, value) {
+        (ir::IrUnaryOp::Not, IrValue::Bool(value)) => Ok(IrValue::Bool(!value)),
+        (ir::IrUnaryOp::Not, IrValue::Integer(value)) => Ok(IrValue::Integer(!value)),
+        _ => Err(IrEvalOutcome::not_const(span)),
+    }
+}
+
 fn eval_ir_binary(
     ir: &ir::IrBinary,
     interp: &mut IrInterpreter<'_>,
     used: Used,
 ) -> Result<IrValue, IrEvalOutcome> {
-    use std::ops::{Add, Mul, Shl, Shr, Sub};
+    use num::Zero as _;
+    use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Rem, Shl, Shr, Sub};
 
     let span = ir.span();
     interp.budget.take(span)?;
 
     let a = eval_ir(&ir.lhs, interp, used)?;
+
+    if let ir::IrBinaryOp::And | ir::IrBinaryOp::Or = ir.op {
+        let a = as_bool(ir.lhs.span(), a)?;
+
+        // NB: the right-hand side is only evaluated if it can affect the
+        // outcome, since it might not be possible to evaluate.
+        if a == matches!(ir.op, ir::IrBinaryOp::Or) {
+            return Ok(IrValue::Bool(a));
+        }
+
+        let b = eval_ir(&ir.rhs, interp, used)?;
+        return Ok(IrValue::Bool(as_bool(ir.rhs.span(), b)?));
+    }
+
     let b = eval_ir(&ir.rhs, interp, used)?;
+    let big_integers = interp.q.options.const_big_integers;
 
     match (a, b) {
         (IrValue::Integer(a), IrValue::Integer(b)) => match ir.op {
             ir::IrBinaryOp::Add => {
-                return Ok(IrValue::Integer(a.add(&b)));
+                let n = a.add(&b);
+
+                if !big_integers {
+                    check_integer(span, &n)?;
+                }
+
+                return Ok(IrValue::Integer(n));
             }
             ir::IrBinaryOp::Sub => {
-                return Ok(IrValue::Integer(a.sub(&b)));
+                let n = a.sub(&b);
+
+                if !big_integers {
+                    check_integer(span, &n)?;
+                }
+
+                return Ok(IrValue::Integer(n));
             }
             ir::IrBinaryOp::Mul => {
-                return Ok(IrValue::Integer(a.mul(&b)));
+                let n = a.mul(&b);
+
+                if !big_integers {
+                    check_integer(span, &n)?;
+                }
+
+                return Ok(IrValue::Integer(n));
             }
             ir::IrBinaryOp::Div => {
                 let number = a
@@ -98,25 +197,32 @@ fn eval_ir_binary(
                     .ok_or_else(|| IrError::msg(span, "division by zero"))?;
                 return Ok(IrValue::Integer(number));
             }
-            ir::IrBinaryOp::Shl => {
-                let b = u32::try_from(b)
-                    .map_err(|_| IrError::msg(&ir.rhs, "cannot be converted to shift operand"))?;
+            ir::IrBinaryOp::Rem => {
+                if b.is_zero() {
+                    return Err(IrEvalOutcome::from(IrError::msg(span, "division by zero")));
+                }
 
+                return Ok(IrValue::Integer(a.rem(&b)));
+            }
+            ir::IrBinaryOp::Shl => {
+                let b = shift_amount(&ir.rhs, b, big_integers)?;
                 let n = a.shl(b);
                 return Ok(IrValue::Integer(n));
             }
             ir::IrBinaryOp::Shr => {
-                let b = u32::try_from(b)
-                    .map_err(|_| IrError::msg(&ir.rhs, "cannot be converted to shift operand"))?;
-
+                let b = shift_amount(&ir.rhs, b, big_integers)?;
                 let n = a.shr(b);
                 return Ok(IrValue::Integer(n));
             }
+            ir::IrBinaryOp::BitAnd => return Ok(IrValue::Integer(a.bitand(&b))),
+            ir::IrBinaryOp::BitOr => return Ok(IrValue::Integer(a.bitor(&b))),
+            ir::IrBinaryOp::BitXor => return Ok(IrValue::Integer(a.bitxor(&b))),
             ir::IrBinaryOp::Lt => return Ok(IrValue::Bool(a < b)),
             ir::IrBinaryOp::Lte => return Ok(IrValue::Bool(a <= b)),
             ir::IrBinaryOp::Eq => return Ok(IrValue::Bool(a == b)),
             ir::IrBinaryOp::Gt => return Ok(IrValue::Bool(a > b)),
             ir::IrBinaryOp::Gte => return Ok(IrValue::Bool(a >= b)),
+            ir::IrBinaryOp::And | ir::IrBinaryOp::Or => (),
         },
         (IrValue::Float(a), IrValue::Float(b)) => {
             #[allow(clippy::float_cmp)]
@@ -125,6 +231,7 @@ fn eval_ir_binary(
                 ir::IrBinaryOp::Sub => return Ok(IrValue::Float(a - b)),
                 ir::IrBinaryOp::Mul => return Ok(IrValue::Float(a * b)),
                 ir::IrBinaryOp::Div => return Ok(IrValue::Float(a / b)),
+                ir::IrBinaryOp::Rem => return Ok(IrValue::Float(a % b)),
                 ir::IrBinaryOp::Lt => return Ok(IrValue::Bool(a < b)),
                 ir::IrBinaryOp::Lte => return Ok(IrValue::Bool(a <= b)),
                 ir::IrBinaryOp::Eq => return Ok(IrValue::Bool(a == b)),
@@ -133,9 +240,29 @@ fn eval_ir_binary(
                 _ => (),
             };
         }
-        (IrValue::String(a), IrValue::String(b)) => {
-            if let ir::IrBinaryOp::Add = ir.op {
-                return Ok(IrValue::String(add_strings(span, &a, &b)?));
+        (IrValue::Bool(a), IrValue::Bool(b)) => match ir.op {
+            ir::IrBinaryOp::BitAnd => return Ok(IrValue::Bool(a & b)),
+            ir::IrBinaryOp::BitOr => return Ok(IrValue::Bool(a | b)),
+            ir::IrBinaryOp::BitXor => return Ok(IrValue::Bool(a ^ b)),
+            _ => (),
+        },
+        (IrValue::String(a), IrValue::String(b)) => match ir.op {
+            ir::IrBinaryOp::Add => return Ok(IrValue::String(add_strings(span, &a, &b)?)),
+            ir::IrBinaryOp::Lt
+            | ir::IrBinaryOp::Lte
+            | ir::IrBinaryOp::Eq
+            | ir::IrBinaryOp::Gt
+            | ir::IrBinaryOp::Gte => {
+                return Ok(IrValue::Bool(compare_strings(span, ir.op, &a, &b)?));
+            }
+            _ => (),
+        },
+        (a @ IrValue::Tuple(..), b @ IrValue::Tuple(..))
+        | (a @ IrValue::Vec(..), b @ IrValue::Vec(..)) => {
+            if let ir::IrBinaryOp::Eq = ir.op {
+                if let Some(eq) = ir_value_eq(span, &a, &b)? {
+                    return Ok(IrValue::Bool(eq));
+                }
             }
         }
         _ => (),
@@ -157,6 +284,79 @@ fn eval_ir_binary(
     }
 }
 
+/// Compare two strings lexicographically using the given comparison operator.
+fn compare_strings(
+    span: Span,
+    op: ir::IrBinaryOp,
+    a: &Shared<String>,
+    b: &Shared<String>,
+) -> Result<bool, IrError> {
+    let a = a.borrow_ref().map_err(|e| IrError::new(span, e))?;
+    let b = b.borrow_ref().map_err(|e| IrError::new(span, e))?;
+    let (a, b) = (a.as_str(), b.as_str());
+
+    Ok(match op {
+        ir::IrBinaryOp::Lt => a < b,
+        ir::IrBinaryOp::Lte => a <= b,
+        ir::IrBinaryOp::Eq => a == b,
+        ir::IrBinaryOp::Gt => a > b,
+        ir::IrBinaryOp::Gte => a >= b,
+        _ => return Err(IrError::msg(span, "unsupported string comparison")),
+    })
+}
+
+/// Test two constant values for structural equality, returning `None` if
+/// they can't be compared during constant evaluation.
+fn ir_value_eq(span: Span, a: &IrValue, b: &IrValue) -> Result<Option<bool>, IrError> {
+    let eq = match (a, b) {
+        (IrValue::Unit, IrValue::Unit) => true,
+        (IrValue::Byte(a), IrValue::Byte(b)) => a == b,
+        (IrValue::Char(a), IrValue::Char(b)) => a == b,
+        (IrValue::Bool(a), IrValue::Bool(b)) => a == b,
+        (IrValue::Integer(a), IrValue::Integer(b)) => a == b,
+        #[allow(clippy::float_cmp)]
+        (IrValue::Float(a), IrValue::Float(b)) => a == b,
+        (IrValue::String(a), IrValue::String(b)) => {
+            let a = a.borrow_ref().map_err(|e| IrError::new(span, e))?;
+            let b = b.borrow_ref().map_err(|e| IrError::new(span, e))?;
+            *a == *b
+        }
+        (IrValue::Bytes(a), IrValue::Bytes(b)) => {
+            let a = a.borrow_ref().map_err(|e| IrError::new(span, e))?;
+            let b = b.borrow_ref().map_err(|e| IrError::new(span, e))?;
+            *a == *b
+        }
+        (IrValue::Tuple(a), IrValue::Tuple(b)) => {
+            let a = a.borrow_ref().map_err(|e| IrError::new(span, e))?;
+            let b = b.borrow_ref().map_err(|e| IrError::new(span, e))?;
+            return slice_eq(span, &a, &b);
+        }
+        (IrValue::Vec(a), IrValue::Vec(b)) => {
+            let a = a.borrow_ref().map_err(|e| IrError::new(span, e))?;
+            let b = b.borrow_ref().map_err(|e| IrError::new(span, e))?;
+            return slice_eq(span, &a, &b);
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(eq))
+}
+
+fn slice_eq(span: Span, a: &[IrValue], b: &[IrValue]) -> Result<Option<bool>, IrError> {
+    if a.len() != b.len() {
+        return Ok(Some(false));
+    }
+
+    for (a, b) in a.iter().zip(b) {
+        match ir_value_eq(span, a, b)? {
+            Some(true) => (),
+            outcome => return Ok(outcome),
+        }
+    }
+
+    Ok(Some(true))
+}
+
 fn eval_ir_branches(
     ir: &ir::IrBranches,
     interp: &mut IrInterpreter<'_>,
@@ -184,6 +384,13 @@ fn eval_ir_branches(
         return eval_ir_scope(branch, interp, used);
     }
 
+    if let Some(span) = ir.unmatched {
+        return Err(IrEvalOutcome::from(IrError::new(
+            span,
+            IrErrorKind::NoMatchingArm,
+        )));
+    }
+
     Ok(IrValue::Unit)
 }
 
@@ -192,13 +399,230 @@ fn eval_ir_call(
     interp: &mut IrInterpreter<'_>,
     used: Used,
 ) -> Result<IrValue, IrEvalOutcome> {
+    let instance = match &ir.instance {
+        Some(instance) => Some(eval_ir(instance, interp, used)?),
+        None => None,
+    };
+
     let mut args = Vec::new();
 
     for arg in &ir.args {
         args.push(eval_ir(arg, interp, used)?);
     }
 
-    Ok(interp.call_const_fn(ir, &ir.target, args, used)?)
+    if let Some(instance) = instance {
+        return eval_instance_fn(ir, interp, instance, args);
+    }
+
+    let path = std::slice::from_ref(&ir.target);
+    Ok(interp.call_const_fn(ir, path, args, used)?)
+}
+
+fn eval_ir_variant(
+    ir: &ir::IrVariant,
+    interp: &mut IrInterpreter<'_>,
+    used: Used,
+) -> Result<IrValue, IrEvalOutcome> {
+    let span = ir.span();
+
+    // NB: a call through a path, like `host::double(5)`, is a call to a
+    // constant function unless it constructs a variant.
+    if let Some(args) = &ir.args {
+        match interp.resolve_fn(span, &ir.path, used)? {
+            Some(f @ (IrCallee::Script(..) | IrCallee::Native(..))) => {
+                let mut values = Vec::with_capacity(args.len());
+
+                for arg in args {
+                    values.push(eval_ir(arg, interp, used)?);
+                }
+
+                return Ok(interp.call_fn(span, f, values, used)?);
+            }
+            _ => (),
+        }
+    }
+
+    let (variant, expected) = interp.resolve_variant(span, &ir.path, used)?;
+
+    let args = match &ir.args {
+        Some(args) => args.as_slice(),
+        // NB: a tuple variant which isn't called is a function pointer to its
+        // constructor, which isn't supported in constant expressions.
+        None if expected == 0 => &[],
+        None => return Err(IrEvalOutcome::not_const(span)),
+    };
+
+    if args.len() != expected {
+        return Err(IrEvalOutcome::from(IrError::new(
+            span,
+            IrErrorKind::ArgumentCountMismatch {
+                actual: args.len(),
+                expected,
+            },
+        )));
+    }
+
+    let mut values = Vec::with_capacity(args.len());
+
+    for arg in args {
+        values.push(eval_ir(arg, interp, used)?);
+    }
+
+    Ok(variant.construct(values))
+}
+
+/// Evaluate one of the pure instance functions which are supported in constant
+/// expressions, dispatched by the type of the receiver.
+fn eval_instance_fn(
+    ir: &ir::IrCall,
+    interp: &mut IrInterpreter<'_>,
+    instance: IrValue,
+    args: Vec<IrValue>,
+) -> Result<IrValue, IrEvalOutcome> {
+    let span = ir.span();
+    interp.budget.take(span)?;
+
+    let value = match &instance {
+        IrValue::String(string) => string_fn(ir, string, &args)?,
+        IrValue::Vec(vec) => vec_fn(ir, vec, args)?,
+        IrValue::Tuple(tuple) => {
+            let tuple = tuple.borrow_ref().map_err(IrError::access(span))?;
+
+            match (ir.target.as_ref(), &args[..]) {
+                ("len", []) => Some(IrValue::Integer(tuple.len().into())),
+                ("is_empty", []) => Some(IrValue::Bool(tuple.is_empty())),
+                _ => None,
+            }
+        }
+        IrValue::Bytes(bytes) => {
+            let bytes = bytes.borrow_ref().map_err(IrError::access(span))?;
+
+            match (ir.target.as_ref(), &args[..]) {
+                ("len", []) => Some(IrValue::Integer(bytes.len().into())),
+                ("is_empty", []) => Some(IrValue::Bool(bytes.is_empty())),
+                _ => None,
+            }
+        }
+        IrValue::Object(object) => {
+            let object = object.borrow_ref().map_err(IrError::access(span))?;
+
+            match (ir.target.as_ref(), &args[..]) {
+                ("len", []) => Some(IrValue::Integer(object.len().into())),
+                ("is_empty", []) => Some(IrValue::Bool(object.is_empty())),
+                ("contains_key", [key]) => {
+                    let key = as_string(&ir.args[0], key)?;
+                    Some(IrValue::Bool(object.contains_key(&*key)))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    return match value {
+        Some(value) => Ok(value),
+        None => Err(IrEvalOutcome::not_const(span)),
+    };
+
+    fn string_fn(
+        ir: &ir::IrCall,
+        string: &Shared<String>,
+        args: &[IrValue],
+    ) -> Result<Option<IrValue>, IrError> {
+        let span = ir.span();
+
+        if let ("push", [c]) = (ir.target.as_ref(), args) {
+            let c = match c {
+                IrValue::Char(c) => *c,
+                actual => return Err(IrError::expected::<_, char>(&ir.args[0], actual)),
+            };
+
+            string.borrow_mut().map_err(IrError::access(span))?.push(c);
+            return Ok(Some(IrValue::Unit));
+        }
+
+        let string = string.borrow_ref().map_err(IrError::access(span))?;
+
+        Ok(Some(match (ir.target.as_ref(), args) {
+            ("len", []) => IrValue::Integer(string.len().into()),
+            ("is_empty", []) => IrValue::Bool(string.is_empty()),
+            ("to_uppercase", []) => IrValue::String(Shared::new(string.to_uppercase())),
+            ("to_lowercase", []) => IrValue::String(Shared::new(string.to_lowercase())),
+            ("trim", []) => IrValue::String(Shared::new(string.trim().to_owned())),
+            ("contains", [IrValue::Char(c)]) => IrValue::Bool(string.contains(*c)),
+            ("contains", [pattern]) => {
+                let pattern = as_string(&ir.args[0], pattern)?;
+                IrValue::Bool(string.contains(&*pattern))
+            }
+            ("char_at", [index]) => {
+                let index = match index {
+                    IrValue::Integer(index) => usize::try_from(index)
+                        .map_err(|_| IrError::msg(&ir.args[0], "cannot be converted to index"))?,
+                    actual => return Err(IrError::expected::<_, i64>(&ir.args[0], actual)),
+                };
+
+                let c = if string.is_char_boundary(index) {
+                    string[index..].chars().next().map(IrValue::Char)
+                } else {
+                    None
+                };
+
+                IrValue::Option(Shared::new(c))
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    fn vec_fn(
+        ir: &ir::IrCall,
+        vec: &Shared<Vec<IrValue>>,
+        mut args: Vec<IrValue>,
+    ) -> Result<Option<IrValue>, IrError> {
+        let span = ir.span();
+
+        Ok(Some(match (ir.target.as_ref(), args.len()) {
+            ("len", 0) => {
+                let vec = vec.borrow_ref().map_err(IrError::access(span))?;
+                IrValue::Integer(vec.len().into())
+            }
+            ("is_empty", 0) => {
+                let vec = vec.borrow_ref().map_err(IrError::access(span))?;
+                IrValue::Bool(vec.is_empty())
+            }
+            ("push", 1) => {
+                let mut vec = vec.borrow_mut().map_err(IrError::access(span))?;
+                if let Some(value) = args.pop() {
+                    vec.push(value);
+                }
+
+                IrValue::Unit
+            }
+            ("contains", 1) => {
+                let vec = vec.borrow_ref().map_err(IrError::access(span))?;
+
+                for value in vec.iter() {
+                    match ir_value_eq(span, value, &args[0])? {
+                        Some(true) => return Ok(Some(IrValue::Bool(true))),
+                        Some(false) => (),
+                        None => return Ok(None),
+                    }
+                }
+
+                IrValue::Bool(false)
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    fn as_string<'a>(
+        spanned: &ir::Ir,
+        value: &'a IrValue,
+    ) -> Result<BorrowRef<'a, String>, IrError> {
+        match value {
+            IrValue::String(string) => string.borrow_ref().map_err(IrError::access(spanned)),
+            actual => Err(IrError::expected::<_, String>(spanned, actual)),
+        }
+    }
 }
 
 fn eval_ir_condition(
@@ -253,26 +677,34 @@ fn eval_ir_loop(
         match eval_ir_scope(&ir.body, interp, used) {
             Ok(..) => (),
             Err(outcome) => match outcome {
-                IrEvalOutcome::Break(span, b) => match b {
-                    IrEvalBreak::Inherent => break,
-                    IrEvalBreak::Label(l) => {
-                        if ir.label.as_ref() == Some(&l) {
-                            break;
-                        }
+                IrEvalOutcome::Continue(span, label) => {
+                    if label.is_some() && label != ir.label {
+                        return Err(IrEvalOutcome::Continue(span, label));
+                    }
 
-                        return Err(IrEvalOutcome::Break(span, IrEvalBreak::Label(l)));
+                    interp.scopes.unwind(&guard);
+                }
+                IrEvalOutcome::Break(span, b) => {
+                    if b.label.is_some() && b.label != ir.label {
+                        return Err(IrEvalOutcome::Break(span, b));
                     }
-                    IrEvalBreak::Value(value) => {
-                        if ir.condition.is_none() {
-                            return Ok(value);
+
+                    interp.scopes.unwind(&guard);
+
+                    if let Some(value) = b.value {
+                        if ir.condition.is_some() {
+                            return Err(IrEvalOutcome::from(IrError::msg(
+                                span,
+                                "break with value is not supported for conditional loops",
+                            )));
                         }
 
-                        return Err(IrEvalOutcome::from(IrError::msg(
-                            span,
-                            "break with value is not supported for unconditional loops",
-                        )));
+                        interp.scopes.pop(ir, guard)?;
+                        return Ok(value);
                     }
-                },
+
+                    break;
+                }
                 outcome => return Err(outcome),
             },
         };
@@ -357,6 +789,9 @@ fn eval_ir_template(
                     IrValue::Bool(b) => {
                         write!(buf, "{}", b).unwrap();
                     }
+                    IrValue::Char(c) => {
+                        buf.push(c);
+                    }
                     IrValue::String(s) => {
                         let s = s.borrow_ref().map_err(IrError::access(ir))?;
                         buf.push_str(&*s);
@@ -394,12 +829,149 @@ fn eval_ir_vec(
     let mut vec = Vec::with_capacity(ir.items.len());
 
     for item in ir.items.iter() {
-        vec.push(eval_ir(item, interp, used)?);
+        match item {
+            ir::IrVecItem::Ir(ir) => {
+                vec.push(eval_ir(ir, interp, used)?);
+            }
+            ir::IrVecItem::Spread(ir) => match eval_ir(ir, interp, used)? {
+                IrValue::Vec(items) => {
+                    let items = items.borrow_ref().map_err(|e| IrError::new(ir, e))?;
+                    vec.extend(items.iter().cloned());
+                }
+                actual => {
+                    return Err(IrEvalOutcome::from(IrError::expected::<
+                        _,
+                        crate::runtime::Vec,
+                    >(ir, &actual)));
+                }
+            },
+        }
     }
 
     Ok(IrValue::Vec(Shared::new(vec)))
 }
 
+fn eval_ir_index(
+    ir: &ir::IrIndex,
+    interp: &mut IrInterpreter<'_>,
+    used: Used,
+) -> Result<IrValue, IrEvalOutcome> {
+    let span = ir.span();
+    interp.budget.take(span)?;
+
+    let target = eval_ir(&ir.target, interp, used)?;
+    let index = eval_ir(&ir.index, interp, used)?;
+
+    let value = match target {
+        IrValue::Vec(vec) => {
+            let index = as_index(&ir.index, index)?;
+            let vec = vec.borrow_ref().map_err(IrError::access(span))?;
+            vec.get(index)
+                .cloned()
+                .ok_or_else(|| IrError::new(&*ir.index, IrErrorKind::MissingIndex { index }))?
+        }
+        IrValue::Tuple(tuple) => {
+            let index = as_index(&ir.index, index)?;
+            let tuple = tuple.borrow_ref().map_err(IrError::access(span))?;
+            tuple
+                .get(index)
+                .cloned()
+                .ok_or_else(|| IrError::new(&*ir.index, IrErrorKind::MissingIndex { index }))?
+        }
+        IrValue::Object(object) => {
+            let field = match index {
+                IrValue::String(field) => field,
+                actual => {
+                    return Err(IrEvalOutcome::from(IrError::expected::<_, String>(
+                        &*ir.index, &actual,
+                    )))
+                }
+            };
+
+            let field = field.borrow_ref().map_err(IrError::access(&*ir.index))?;
+            let object = object.borrow_ref().map_err(IrError::access(span))?;
+
+            match object.get(field.as_str()) {
+                Some(value) => value.clone(),
+                None => {
+                    return Err(IrEvalOutcome::from(IrError::new(
+                        &*ir.index,
+                        IrErrorKind::MissingField {
+                            field: field.as_str().into(),
+                        },
+                    )))
+                }
+            }
+        }
+        _ => return Err(IrEvalOutcome::not_const(span)),
+    };
+
+    return Ok(value);
+
+    fn as_index(ir: &ir::Ir, index: IrValue) -> Result<usize, IrError> {
+        match index {
+            IrValue::Integer(index) => match usize::try_from(&index) {
+                Ok(index) => Ok(index),
+                Err(..) => Err(IrError::new(ir, IrErrorKind::NotInteger { value: index })),
+            },
+            actual => Err(IrError::expected::<_, i64>(ir, &actual)),
+        }
+    }
+}
+
+fn eval_ir_repeat(
+    ir: &ir::IrRepeat,
+    interp: &mut IrInterpreter<'_>,
+    used: Used,
+) -> Result<IrValue, IrEvalOutcome> {
+    let count = match eval_ir(&ir.count, interp, used)? {
+        IrValue::Integer(count) => match usize::try_from(&count) {
+            Ok(count) => count,
+            Err(..) => {
+                return Err(IrEvalOutcome::from(IrError::new(
+                    &*ir.count,
+                    IrErrorKind::NotInteger { value: count },
+                )));
+            }
+        },
+        value => {
+            return Err(IrEvalOutcome::from(IrError::expected::<_, i64>(
+                &*ir.count, &value,
+            )))
+        }
+    };
+
+    // NB: every element needs to be a distinct value, since shared values
+    // can only be converted into a constant once.
+    let value = eval_ir(&ir.expr, interp, used)?.into_const(&*ir.expr)?;
+
+    let mut vec = Vec::new();
+
+    for _ in 0..count {
+        // NB: every element counts towards the budget, which also bounds the
+        // number of elements which can be allocated.
+        interp.budget.take(ir)?;
+        vec.push(IrValue::from_const(&value));
+    }
+
+    Ok(IrValue::Vec(Shared::new(vec)))
+}
+
+/// Evaluate a literal value.
+///
+/// Strings are copied, since they can be modified through methods like
+/// `String::push`. Literals share storage with the interned values in
+/// [Consts][crate::shared::Consts], which must never be modified.
+fn eval_ir_value(ir: &ir::Ir, value: &IrValue) -> Result<IrValue, IrError> {
+    Ok(match value {
+        IrValue::String(string) => {
+            let string = string.borrow_ref().map_err(IrError::access(ir))?;
+            IrValue::String(Shared::new(string.clone()))
+        }
+        value => value.clone(),
+    })
+}
+
 /// IrEval the interior expression.
 pub(crate) fn eval_ir(
     ir: &ir::Ir,
@@ -411,19 +983,55 @@ pub(crate) fn eval_ir(
     match &ir.kind {
         ir::IrKind::Scope(ir) => eval_ir_scope(ir, interp, used),
         ir::IrKind::Binary(ir) => eval_ir_binary(ir, interp, used),
+        ir::IrKind::Unary(ir) => eval_ir_unary(ir, interp, used),
         ir::IrKind::Decl(ir) => eval_ir_decl(ir, interp, used),
         ir::IrKind::Set(ir) => eval_ir_set(ir, interp, used),
         ir::IrKind::Assign(ir) => eval_ir_assign(ir, interp, used),
         ir::IrKind::Template(ir) => eval_ir_template(ir, interp, used),
         ir::IrKind::Name(name) => Ok(interp.resolve_var(ir.span(), name.as_ref(), used)?),
         ir::IrKind::Target(target) => Ok(interp.scopes.get_target(target)?),
-        ir::IrKind::Value(value) => Ok(value.clone()),
+        ir::IrKind::Value(value) => Ok(eval_ir_value(ir, value)?),
         ir::IrKind::Branches(ir) => eval_ir_branches(ir, interp, used),
         ir::IrKind::Loop(ir) => eval_ir_loop(ir, interp, used),
         ir::IrKind::Break(ir) => Err(ir.as_outcome(interp, used)),
+        ir::IrKind::Continue(ir) => Err(ir.as_outcome(interp)),
         ir::IrKind::Vec(ir) => eval_ir_vec(ir, interp, used),
+        ir::IrKind::Repeat(ir) => eval_ir_repeat(ir, interp, used),
         ir::IrKind::Tuple(ir) => eval_ir_tuple(ir, interp, used),
         ir::IrKind::Object(ir) => eval_ir_object(ir, interp, used),
+        ir::IrKind::Index(ir) => eval_ir_index(ir, interp, used),
         ir::IrKind::Call(ir) => eval_ir_call(ir, interp, used),
+        ir::IrKind::Variant(ir) => eval_ir_variant(ir, interp, used),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compare_strings;
+    use crate::ast::{Span, Spanned};
+    use crate::compile::ir::{IrBinaryOp, IrErrorKind};
+    use crate::runtime::Shared;
+
+    #[test]
+    fn test_compare_strings() {
+        let a = Shared::new(String::from("abc"));
+        let b = Shared::new(String::from("abd"));
+
+        assert!(compare_strings(Span::empty(), IrBinaryOp::Lt, &a, &b).unwrap());
+        assert!(!compare_strings(Span::empty(), IrBinaryOp::Eq, &a, &b).unwrap());
+        assert!(compare_strings(Span::empty(), IrBinaryOp::Gte, &b, &a).unwrap());
+    }
+
+    #[test]
+    fn test_compare_strings_borrowed() {
+        let a = Shared::new(String::from("abc"));
+        let b = Shared::new(String::from("abd"));
+        let span = Span::new(4, 8);
+
+        let _guard = a.borrow_mut().unwrap();
+        let error = compare_strings(span, IrBinaryOp::Lt, &a, &b).unwrap_err();
+
+        assert_eq!(error.span(), span);
+        assert!(matches!(error.kind(), IrErrorKind::AccessError { .. }));
     }
 }
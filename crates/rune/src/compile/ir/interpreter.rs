@@ -1,8 +1,16 @@
 use crate::ast::{Span, Spanned};
 use crate::compile::ir;
-use crate::compile::{IrError, IrErrorKind, IrEvalOutcome, IrValue, Item, ModMeta, PrivMetaKind};
+use crate::compile::{
+    IrError, IrErrorKind, IrEvalOutcome, IrValue, Item, ModMeta, PrivMeta, PrivMetaKind,
+};
+use crate::parse::Id;
 use crate::query::{Query, Used};
-use crate::runtime::{ConstValue, Object, Tuple};
+use crate::runtime::{
+    ConstEvalHandler, ConstValue, InstVariant, Object, Shared, Tuple, TypeCheck, VariantRtti,
+};
+use crate::Hash;
+use std::sync::Arc;
+use std::time::Instant;
 
 /// Ir Scopes.
 pub(crate) type IrScopes = crate::shared::Scopes<IrValue>;
@@ -13,13 +21,23 @@ pub struct IrInterpreter<'a> {
     /// allowed to evaluate.
     pub(crate) budget: IrBudget,
     /// The module in which the interpreter is run.
-    pub(crate) module: &'a ModMeta,
+    pub(crate) module: &'a Arc<ModMeta>,
     /// The item where the constant expression is located.
     pub(crate) item: &'a Item,
     /// Constant scopes.
     pub(crate) scopes: IrScopes,
     /// Query engine to look for constant expressions.
     pub(crate) q: Query<'a>,
+    /// The stack of const fn calls currently being evaluated.
+    pub(crate) calls: Vec<(Span, Item)>,
+    /// When the evaluation started, if it's being timed.
+    pub(crate) start: Option<Instant>,
+}
+
+impl Drop for IrInterpreter<'_> {
+    fn drop(&mut self) {
+        self.q.record_const_eval(self.budget.used(), self.start);
+    }
 }
 
 impl IrInterpreter<'_> {
@@ -38,15 +56,30 @@ impl IrInterpreter<'_> {
         let ir_value = match ir::eval_ir(ir, self, used) {
             Ok(ir_value) => ir_value,
             Err(outcome) => match outcome {
-                IrEvalOutcome::Error(error) => {
+                IrEvalOutcome::Error(error) if self.calls.is_empty() => {
                     return Err(error);
                 }
+                IrEvalOutcome::Error(error) => {
+                    let span = error.span();
+
+                    return Err(IrError::new(
+                        span,
+                        IrErrorKind::ConstEval {
+                            item: self.item.clone(),
+                            calls: std::mem::take(&mut self.calls),
+                            error: Box::new(error.into_kind()),
+                        },
+                    ));
+                }
                 IrEvalOutcome::NotConst(span) => {
                     return Err(IrError::new(span, IrErrorKind::NotConst))
                 }
                 IrEvalOutcome::Break(span, _) => {
                     return Err(IrError::new(span, IrErrorKind::BreakOutsideOfLoop))
                 }
+                IrEvalOutcome::Continue(span, _) => {
+                    return Err(IrError::new(span, IrErrorKind::ContinueOutsideOfLoop))
+                }
             },
         };
 
@@ -74,6 +107,9 @@ impl IrInterpreter<'_> {
                 IrEvalOutcome::Break(span, _) => {
                     Err(IrError::new(span, IrErrorKind::BreakOutsideOfLoop))
                 }
+                IrEvalOutcome::Continue(span, _) => {
+                    Err(IrError::new(span, IrErrorKind::ContinueOutsideOfLoop))
+                }
             },
         }
     }
@@ -122,6 +158,10 @@ impl IrInterpreter<'_> {
             base.pop();
         }
 
+        if let Some((variant, 0)) = self.resolve_builtin_variant(spanned, &[name.into()], used)? {
+            return Ok(IrVariantRef::Builtin(variant).construct(Vec::new()));
+        }
+
         if name.starts_with(char::is_lowercase) {
             Err(IrError::new(
                 spanned,
@@ -135,41 +175,216 @@ impl IrInterpreter<'_> {
         }
     }
 
+    /// Resolve the unit or tuple variant at the given path, returning the
+    /// variant and the number of arguments it expects.
+    pub(crate) fn resolve_variant(
+        &mut self,
+        spanned: Span,
+        path: &[Box<str>],
+        used: Used,
+    ) -> Result<(IrVariantRef, usize), IrError> {
+        if let Some(variant) = self.try_resolve_variant(spanned, path, used)? {
+            return Ok(variant);
+        }
+
+        Err(IrError::new(
+            spanned,
+            IrErrorKind::MissingVariant {
+                name: path.join("::").into(),
+            },
+        ))
+    }
+
+    /// Try to resolve the unit or tuple variant at the given path, returning
+    /// `None` if the path doesn't refer to a variant.
+    pub(crate) fn try_resolve_variant(
+        &mut self,
+        spanned: Span,
+        path: &[Box<str>],
+        used: Used,
+    ) -> Result<Option<(IrVariantRef, usize)>, IrError> {
+        let mut base = self.item.clone();
+
+        loop {
+            let item = base.join(path.iter().map(|c| &**c));
+
+            if let Some(meta) = self.q.query_meta(spanned, &item, used)? {
+                let (enum_item, hash, args, debug) = match &meta.kind {
+                    PrivMetaKind::UnitVariant {
+                        enum_item,
+                        empty,
+                        debug,
+                        ..
+                    } => (enum_item, empty.hash, 0, *debug),
+                    PrivMetaKind::TupleVariant {
+                        enum_item,
+                        tuple,
+                        debug,
+                        ..
+                    } => (enum_item, tuple.hash, tuple.args, *debug),
+                    _ => return Ok(None),
+                };
+
+                let rtti = Arc::new(VariantRtti {
+                    enum_hash: Hash::type_hash(enum_item),
+                    hash,
+                    item: meta.item.item.clone(),
+                    debug,
+                });
+
+                return Ok(Some((IrVariantRef::Script(rtti), args)));
+            }
+
+            if base.is_empty() {
+                break;
+            }
+
+            base.pop();
+        }
+
+        Ok(self
+            .resolve_builtin_variant(spanned, path, used)?
+            .map(|(variant, args)| (IrVariantRef::Builtin(variant), args)))
+    }
+
+    /// Resolve one of the variants of the built-in `Option` and `Result`
+    /// types, like `Some` or `Option::None`, returning the variant and the
+    /// number of arguments it expects.
+    fn resolve_builtin_variant(
+        &mut self,
+        span: Span,
+        path: &[Box<str>],
+        used: Used,
+    ) -> Result<Option<(InstVariant, usize)>, IrError> {
+        let item = match self.resolve_item(span, path, used)? {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        Ok(match self.q.context.type_check_for(&item) {
+            Some(TypeCheck::Option(0)) => Some((InstVariant::Some, 1)),
+            Some(TypeCheck::Option(1)) => Some((InstVariant::None, 0)),
+            Some(TypeCheck::Result(0)) => Some((InstVariant::Ok, 1)),
+            Some(TypeCheck::Result(1)) => Some((InstVariant::Err, 1)),
+            _ => None,
+        })
+    }
+
+    /// Resolve the item at the given path, as seen from the item where the
+    /// constant expression is located. The path is resolved like any other
+    /// path, so imports are followed.
+    fn resolve_item(
+        &mut self,
+        span: Span,
+        path: &[Box<str>],
+        used: Used,
+    ) -> Result<Option<Item>, IrError> {
+        let (first, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return Ok(None),
+        };
+
+        let item = self
+            .q
+            .convert_initial_item(self.q.context, self.module, self.item, first)
+            .join(rest.iter().map(|c| &**c));
+
+        Ok(Some(
+            match self.q.import(span, self.module, &item, used)? {
+                Some(item) => item,
+                None => item,
+            },
+        ))
+    }
+
+    /// Resolve the function at the given path, as seen from the item where the
+    /// constant expression is located.
+    pub(crate) fn resolve_fn(
+        &mut self,
+        span: Span,
+        path: &[Box<str>],
+        used: Used,
+    ) -> Result<Option<IrCallee>, IrError> {
+        let item = match self.resolve_item(span, path, used)? {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        if let Some(meta) = self.q.query_meta(span, &item, used)? {
+            return Ok(Some(match &meta.kind {
+                PrivMetaKind::ConstFn { id, .. } => IrCallee::Script(*id, item),
+                _ => IrCallee::Unsupported(meta),
+            }));
+        }
+
+        if let Some((variant, args)) = self.resolve_builtin_variant(span, path, used)? {
+            return Ok(Some(IrCallee::Variant(
+                IrVariantRef::Builtin(variant),
+                args,
+            )));
+        }
+
+        if let Some(handler) = self.q.context.lookup_const_eval(Hash::type_hash(&item)) {
+            return Ok(Some(IrCallee::Native(handler.clone())));
+        }
+
+        Ok(None)
+    }
+
+    /// Call the function at the given path.
     pub(crate) fn call_const_fn<S>(
         &mut self,
         spanned: S,
-        target: &str,
+        path: &[Box<str>],
         args: Vec<IrValue>,
         used: Used,
     ) -> Result<IrValue, IrError>
     where
         S: Copy + Spanned,
     {
-        let span = spanned.span();
-        let mut base = self.item.clone();
+        match self.resolve_fn(spanned.span(), path, used)? {
+            Some(f) => self.call_fn(spanned, f, args, used),
+            None => Err(IrError::new(spanned, IrErrorKind::FnNotFound)),
+        }
+    }
 
-        let id = loop {
-            let item = base.extended(target);
+    /// Call a resolved function.
+    pub(crate) fn call_fn<S>(
+        &mut self,
+        spanned: S,
+        f: IrCallee,
+        args: Vec<IrValue>,
+        used: Used,
+    ) -> Result<IrValue, IrError>
+    where
+        S: Copy + Spanned,
+    {
+        let span = spanned.span();
 
-            if let Some(meta) = self.q.query_meta(span, &item, used)? {
-                match &meta.kind {
-                    PrivMetaKind::ConstFn { id, .. } => {
-                        break *id;
-                    }
-                    _ => {
-                        return Err(IrError::new(
-                            span,
-                            IrErrorKind::UnsupportedMeta { meta: meta.info() },
-                        ));
-                    }
-                }
+        let (id, item) = match f {
+            IrCallee::Script(id, item) => (id, item),
+            IrCallee::Native(handler) => {
+                return call_native_const_eval(spanned, &*handler, args);
             }
+            IrCallee::Variant(variant, expected) => {
+                if args.len() != expected {
+                    return Err(IrError::new(
+                        spanned,
+                        IrErrorKind::ArgumentCountMismatch {
+                            actual: args.len(),
+                            expected,
+                        },
+                    ));
+                }
 
-            if base.is_empty() {
-                return Err(IrError::new(spanned, IrErrorKind::FnNotFound));
+                return Ok(variant.construct(args));
+            }
+            IrCallee::Unsupported(meta) => {
+                return Err(IrError::new(
+                    span,
+                    IrErrorKind::UnsupportedMeta { meta: meta.info() },
+                ));
             }
-
-            base.pop();
         };
 
         let const_fn = self.q.const_fn_for((spanned.span(), id))?;
@@ -184,18 +399,133 @@ impl IrInterpreter<'_> {
             ));
         }
 
+        if let Some(limit) = self.q.unit.const_recursion_limit() {
+            if self.calls.len() >= limit {
+                return Err(IrError::new(
+                    spanned,
+                    IrErrorKind::ConstRecursionLimit { limit },
+                ));
+            }
+        }
+
         let guard = self.scopes.isolate();
 
         for (name, value) in const_fn.ir_fn.args.iter().zip(args) {
             self.scopes.decl(&**name, value, spanned)?;
         }
 
+        // NB: the call is deliberately left on the stack if evaluation fails,
+        // so that the constant being evaluated can report the call path.
+        self.calls.push((span, item));
         let value = self.eval_value(&const_fn.ir_fn.ir, used)?;
+        self.calls.pop();
         self.scopes.pop(spanned, guard)?;
         Ok(value)
     }
 }
 
+/// A function resolved during constant evaluation.
+pub(crate) enum IrCallee {
+    /// A `const fn` declared in a script.
+    Script(Id, Item),
+    /// A native function with a registered const evaluator.
+    Native(Arc<ConstEvalHandler>),
+    /// The constructor of a tuple variant, and the number of arguments it
+    /// expects.
+    Variant(IrVariantRef, usize),
+    /// An item which can't be called in a constant expression.
+    Unsupported(PrivMeta),
+}
+
+/// A unit or tuple variant resolved during constant evaluation.
+pub(crate) enum IrVariantRef {
+    /// A variant of the built-in `Option` or `Result` types, which have values
+    /// of their own.
+    Builtin(InstVariant),
+    /// A variant of an enum declared in a script.
+    Script(Arc<VariantRtti>),
+}
+
+impl IrVariantRef {
+    /// Construct the variant out of the given arguments, which have already
+    /// been checked against the number of arguments it expects.
+    pub(crate) fn construct(self, mut args: Vec<IrValue>) -> IrValue {
+        let variant = match self {
+            Self::Builtin(variant) => variant,
+            Self::Script(rtti) => {
+                return IrValue::Variant(rtti, Shared::new(args.into_boxed_slice()));
+            }
+        };
+
+        let value = args.pop().unwrap_or(IrValue::Unit);
+
+        match variant {
+            InstVariant::Some => IrValue::Option(Shared::new(Some(value))),
+            InstVariant::None => IrValue::Option(Shared::new(None)),
+            InstVariant::Ok => IrValue::Result(Shared::new(Ok(value))),
+            InstVariant::Err => IrValue::Result(Shared::new(Err(value))),
+        }
+    }
+
+    /// Get the arguments of the given value if it's this variant.
+    pub(crate) fn args_of<S>(
+        &self,
+        spanned: S,
+        value: IrValue,
+    ) -> Result<Option<Vec<IrValue>>, IrError>
+    where
+        S: Copy + Spanned,
+    {
+        Ok(match (self, value) {
+            (Self::Builtin(variant), IrValue::Option(option)) => {
+                let option = option.borrow_ref().map_err(IrError::access(spanned))?;
+
+                match (variant, &*option) {
+                    (InstVariant::Some, Some(value)) => Some(vec![value.clone()]),
+                    (InstVariant::None, None) => Some(Vec::new()),
+                    _ => None,
+                }
+            }
+            (Self::Builtin(variant), IrValue::Result(result)) => {
+                let result = result.borrow_ref().map_err(IrError::access(spanned))?;
+
+                match (variant, &*result) {
+                    (InstVariant::Ok, Ok(value)) | (InstVariant::Err, Err(value)) => {
+                        Some(vec![value.clone()])
+                    }
+                    _ => None,
+                }
+            }
+            (Self::Script(rtti), IrValue::Variant(actual, args)) if actual.hash == rtti.hash => {
+                let args = args.borrow_ref().map_err(IrError::access(spanned))?;
+                Some(args.to_vec())
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Call a native const evaluator registered for a host function.
+fn call_native_const_eval<S>(
+    spanned: S,
+    handler: &ConstEvalHandler,
+    args: Vec<IrValue>,
+) -> Result<IrValue, IrError>
+where
+    S: Copy + Spanned,
+{
+    let mut const_args = Vec::with_capacity(args.len());
+
+    for arg in args {
+        const_args.push(arg.into_const(spanned)?);
+    }
+
+    let value = handler(&const_args)
+        .map_err(|error| IrError::new(spanned, IrErrorKind::NativeConstEval { error }))?;
+
+    Ok(IrValue::from_const(&value))
+}
+
 impl IrScopes {
     /// Get the given target as mut.
     pub(crate) fn get_target(&mut self, ir_target: &ir::IrTarget) -> Result<IrValue, IrError> {
@@ -370,13 +700,22 @@ impl IrScopes {
 
 /// A budget dictating the number of evaluations the compiler is allowed to do.
 pub(crate) struct IrBudget {
+    limit: usize,
     budget: usize,
 }
 
 impl IrBudget {
     /// Construct a new constant evaluation budget with the given constraint.
     pub(crate) fn new(budget: usize) -> Self {
-        Self { budget }
+        Self {
+            limit: budget,
+            budget,
+        }
+    }
+
+    /// The amount of budget which has been used.
+    pub(crate) fn used(&self) -> usize {
+        self.limit - self.budget
     }
 
     /// Take an item from the budget. Errors if the budget is exceeded.
@@ -13,7 +13,7 @@ mod eval;
 pub(crate) use self::eval::{eval_ir, IrEvalOutcome};
 
 mod interpreter;
-pub(crate) use self::interpreter::{IrBudget, IrInterpreter};
+pub(crate) use self::interpreter::{IrBudget, IrCallee, IrInterpreter};
 
 mod value;
 pub use self::value::IrValue;
@@ -24,6 +24,9 @@ use crate::compile::ast;
 use crate::compile::ir;
 use crate::compile::ItemMeta;
 use crate::query::Used;
+use crate::runtime::Shared;
+use std::mem;
+use std::ops::{Bound, RangeBounds as _};
 
 /// Context used for [IrEval].
 pub struct IrEvalContext<'a> {
@@ -49,7 +52,9 @@ impl IrEval for ast::Expr {
             scopes: Default::default(),
             module: &ctx.item.module,
             item: &ctx.item.item,
+            start: ctx.c.q.start_const_eval(),
             q: ctx.c.q.borrow(),
+            calls: Vec::new(),
         };
 
         ir_interpreter.eval_value(&ir, Used::Used)
@@ -129,6 +134,8 @@ decl_kind! {
         Scope(IrScope),
         /// A binary operation.
         Binary(IrBinary),
+        /// A unary operation.
+        Unary(IrUnary),
         /// Declare a local variable with the value of the operand.
         Decl(IrDecl),
         /// Set the given target.
@@ -150,14 +157,22 @@ decl_kind! {
         Loop(IrLoop),
         /// A break to the given target.
         Break(IrBreak),
+        /// A continue to the given target.
+        Continue(IrContinue),
         /// Constructing a vector.
         Vec(IrVec),
+        /// Constructing a vector by repeating a value.
+        Repeat(IrRepeat),
         /// Constructing a tuple.
         Tuple(IrTuple),
         /// Constructing an object.
         Object(IrObject),
+        /// Indexing into a collection.
+        Index(IrIndex),
         /// A call.
         Call(IrCall),
+        /// Constructing an enum variant.
+        Variant(IrVariant),
     }
 }
 
@@ -224,6 +239,18 @@ pub struct IrBinary {
     pub(crate) rhs: Box<Ir>,
 }
 
+/// A unary operation.
+#[derive(Debug, Clone, Spanned)]
+pub struct IrUnary {
+    /// The span of the unary op.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The unary operation.
+    pub(crate) op: IrUnaryOp,
+    /// The operand of the unary op.
+    pub(crate) expr: Box<Ir>,
+}
+
 /// A local variable declaration.
 #[derive(Debug, Clone, Spanned)]
 pub struct IrDecl {
@@ -288,6 +315,10 @@ pub struct IrBranches {
     pub(crate) branches: Vec<(IrCondition, IrScope)>,
     /// The default fallback branch.
     pub(crate) default_branch: Option<IrScope>,
+    /// The span to report an error at if no branch matches and there is no
+    /// default branch. Only set for `match` expressions, which unlike `if`
+    /// don't evaluate to unit when nothing matches.
+    pub(crate) unmatched: Option<Span>,
 }
 
 /// The condition for a branch.
@@ -316,8 +347,47 @@ pub struct IrLet {
 pub enum IrPat {
     /// An ignore pattern `_`.
     Ignore,
-    /// A named binding.
+    /// A named binding, unless the name resolves to a unit variant like
+    /// `None` in which case the pattern matches that variant.
     Binding(Box<str>),
+    /// A literal pattern, like `1`, `'a'` or `"hello"`.
+    Lit(IrValue),
+    /// A vector pattern, which matches vectors of exactly the same length as
+    /// the number of patterns unless it ends with a rest pattern `..`.
+    Vec {
+        /// The patterns to match the leading elements of the vector against.
+        items: Box<[IrPat]>,
+        /// If the pattern ends with `..`, and matches longer vectors.
+        is_open: bool,
+    },
+    /// A tuple pattern, which matches tuples the same way as vector patterns
+    /// match vectors.
+    Tuple {
+        /// The patterns to match the leading elements of the tuple against.
+        items: Box<[IrPat]>,
+        /// If the pattern ends with `..`, and matches longer tuples.
+        is_open: bool,
+    },
+    /// A range pattern of integers, bytes or characters, like `0..=9` or
+    /// `..10`.
+    Range {
+        /// The lower bound of the range.
+        start: Option<IrValue>,
+        /// The upper bound of the range.
+        end: Option<IrValue>,
+        /// If the upper bound is included in the range.
+        inclusive: bool,
+    },
+    /// A unit or tuple variant pattern, like `Enum::Variant(a, b)`.
+    Variant {
+        /// The path to the variant, which is resolved when matched.
+        path: Box<[Box<str>]>,
+        /// The patterns to match the items of the variant against.
+        items: Box<[IrPat]>,
+    },
+    /// An or-pattern like `0..=2 | 5..`, which matches if any of its
+    /// alternatives match.
+    Or(Box<[IrPat]>),
 }
 
 impl IrPat {
@@ -329,6 +399,80 @@ impl IrPat {
                     let name = c.resolve(ident)?;
                     return Ok(ir::IrPat::Binding(name.into()));
                 }
+
+                if let Some(path) = c.path_components(&path.path)? {
+                    return Ok(ir::IrPat::Variant {
+                        path,
+                        items: Box::default(),
+                    });
+                }
+            }
+            ast::Pat::PatTuple(ast::PatTuple {
+                path: Some(path),
+                items,
+                ..
+            }) => {
+                if let Some(path) = c.path_components(path)? {
+                    let mut pats = Vec::new();
+
+                    for (p, _) in items {
+                        pats.push(Self::compile_ast(p, c)?);
+                    }
+
+                    return Ok(ir::IrPat::Variant {
+                        path,
+                        items: pats.into_boxed_slice(),
+                    });
+                }
+            }
+            ast::Pat::PatTuple(ast::PatTuple {
+                path: None, items, ..
+            }) => {
+                let (items, is_open) = Self::compile_items(items.iter().map(|(p, _)| p), c)?;
+                return Ok(ir::IrPat::Tuple { items, is_open });
+            }
+            ast::Pat::PatVec(pat) => {
+                let (items, is_open) = Self::compile_items(pat.items.iter().map(|(p, _)| p), c)?;
+                return Ok(ir::IrPat::Vec { items, is_open });
+            }
+            ast::Pat::PatLit(pat) => match Self::lit(&pat.expr, c)? {
+                Some(lit) => return Ok(ir::IrPat::Lit(lit)),
+                None => return Err(IrError::msg(pat, "unsupported literal pattern")),
+            },
+            ast::Pat::PatRange(pat) => {
+                let start = match &pat.start {
+                    Some(start) => Some(Self::range_bound(start, c)?),
+                    None => None,
+                };
+
+                let end = match &pat.end {
+                    Some(end) => Some(Self::range_bound(end, c)?),
+                    None => None,
+                };
+
+                if let (Some(start), Some(end)) = (&start, &end) {
+                    if mem::discriminant(start) != mem::discriminant(end) {
+                        return Err(IrError::msg(
+                            pat,
+                            "bounds of range pattern must have the same type",
+                        ));
+                    }
+                }
+
+                return Ok(ir::IrPat::Range {
+                    start,
+                    end,
+                    inclusive: pat.is_inclusive(),
+                });
+            }
+            ast::Pat::PatOr(pat) => {
+                let mut alternatives = Vec::new();
+
+                for alternative in pat.alternatives() {
+                    alternatives.push(Self::compile_ast(alternative, c)?);
+                }
+
+                return Ok(ir::IrPat::Or(alternatives.into_boxed_slice()));
             }
             _ => (),
         }
@@ -336,23 +480,267 @@ impl IrPat {
         Err(IrError::msg(ast, "pattern not supported yet"))
     }
 
+    /// Compile the items of a vector or tuple pattern, returning if the
+    /// pattern ends with a rest pattern `..`.
+    fn compile_items<'a, I>(
+        items: I,
+        c: &mut IrCompiler<'_>,
+    ) -> Result<(Box<[IrPat]>, bool), IrError>
+    where
+        I: IntoIterator<Item = &'a ast::Pat>,
+    {
+        let mut compiled = Vec::new();
+        let mut it = items.into_iter().peekable();
+
+        while let Some(p) = it.next() {
+            if let ast::Pat::PatRest(rest) = p {
+                if it.peek().is_some() {
+                    return Err(IrError::msg(rest, "`..` is not supported in this location"));
+                }
+
+                return Ok((compiled.into_boxed_slice(), true));
+            }
+
+            compiled.push(Self::compile_ast(p, c)?);
+        }
+
+        Ok((compiled.into_boxed_slice(), false))
+    }
+
+    /// Resolve the literal of a literal pattern, returning `None` if the
+    /// expression isn't a supported literal.
+    fn lit(ast: &ast::Expr, c: &mut IrCompiler<'_>) -> Result<Option<IrValue>, IrError> {
+        let (lit, neg) = match ast {
+            ast::Expr::Lit(expr_lit) => (&expr_lit.lit, false),
+            ast::Expr::Unary(ast::ExprUnary {
+                op: ast::UnOp::Neg(..),
+                expr,
+                ..
+            }) => match &**expr {
+                ast::Expr::Lit(expr_lit) => (&expr_lit.lit, true),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(match lit {
+            ast::Lit::Number(lit) => match c.resolve(lit)? {
+                ast::Number::Integer(n) if neg => IrValue::Integer(-n),
+                ast::Number::Integer(n) => IrValue::Integer(n),
+                ast::Number::Float(..) => return Ok(None),
+            },
+            ast::Lit::Byte(lit) if !neg => IrValue::Byte(c.resolve(lit)?),
+            ast::Lit::Char(lit) if !neg => IrValue::Char(c.resolve(lit)?),
+            ast::Lit::Bool(lit) if !neg => IrValue::Bool(lit.value),
+            ast::Lit::Str(lit) if !neg => {
+                IrValue::String(Shared::new(c.resolve(lit)?.into_owned()))
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Resolve the literal bound of a range pattern.
+    fn range_bound(ast: &ast::Expr, c: &mut IrCompiler<'_>) -> Result<IrValue, IrError> {
+        match Self::lit(ast, c)? {
+            Some(bound @ (IrValue::Integer(..) | IrValue::Byte(..) | IrValue::Char(..))) => {
+                Ok(bound)
+            }
+            _ => Err(IrError::msg(ast, "unsupported range pattern bound")),
+        }
+    }
+
     fn matches<S>(
         &self,
         interp: &mut IrInterpreter<'_>,
         value: IrValue,
         spanned: S,
     ) -> Result<bool, IrEvalOutcome>
+    where
+        S: Spanned,
+    {
+        self.matches_with(interp, value, spanned, true)
+    }
+
+    /// Match the pattern against the given value, where `bind` indicates if
+    /// the pattern is allowed to bind variables, which isn't the case inside
+    /// of or-patterns.
+    fn matches_with<S>(
+        &self,
+        interp: &mut IrInterpreter<'_>,
+        value: IrValue,
+        spanned: S,
+        bind: bool,
+    ) -> Result<bool, IrEvalOutcome>
     where
         S: Spanned,
     {
         match self {
             IrPat::Ignore => Ok(true),
             IrPat::Binding(name) => {
+                let span = spanned.span();
+                let path = std::slice::from_ref(name);
+
+                if let Some((variant, 0)) = interp.try_resolve_variant(span, path, Used::Used)? {
+                    return Ok(variant.args_of(span, value)?.is_some());
+                }
+
+                if !bind {
+                    return Err(IrEvalOutcome::from(IrError::msg(
+                        span,
+                        "bindings are not supported in or-patterns",
+                    )));
+                }
+
                 interp.scopes.decl(name, value, spanned)?;
                 Ok(true)
             }
+            IrPat::Lit(lit) => {
+                let span = spanned.span();
+
+                Ok(match (lit, &value) {
+                    (IrValue::Integer(a), IrValue::Integer(b)) => a == b,
+                    (IrValue::Byte(a), IrValue::Byte(b)) => a == b,
+                    (IrValue::Char(a), IrValue::Char(b)) => a == b,
+                    (IrValue::Bool(a), IrValue::Bool(b)) => a == b,
+                    (IrValue::String(a), IrValue::String(b)) => {
+                        let a = a.borrow_ref().map_err(IrError::access(span))?;
+                        let b = b.borrow_ref().map_err(IrError::access(span))?;
+                        *a == *b
+                    }
+                    _ => false,
+                })
+            }
+            IrPat::Vec { items, is_open } => {
+                let span = spanned.span();
+
+                let vec = match value {
+                    IrValue::Vec(vec) => vec,
+                    _ => return Ok(false),
+                };
+
+                let vec = vec.borrow_ref().map_err(IrError::access(span))?;
+                Self::matches_items(interp, items, *is_open, &vec, span, bind)
+            }
+            IrPat::Tuple { items, is_open } => {
+                let span = spanned.span();
+
+                let tuple = match value {
+                    IrValue::Tuple(tuple) => tuple,
+                    _ => return Ok(false),
+                };
+
+                let tuple = tuple.borrow_ref().map_err(IrError::access(span))?;
+                Self::matches_items(interp, items, *is_open, &tuple, span, bind)
+            }
+            IrPat::Range {
+                start,
+                end,
+                inclusive,
+            } => Ok(match &value {
+                IrValue::Integer(n) => range_contains(n, start, end, *inclusive, |v| match v {
+                    IrValue::Integer(v) => Some(v),
+                    _ => None,
+                }),
+                IrValue::Byte(n) => range_contains(n, start, end, *inclusive, |v| match v {
+                    IrValue::Byte(v) => Some(v),
+                    _ => None,
+                }),
+                IrValue::Char(n) => range_contains(n, start, end, *inclusive, |v| match v {
+                    IrValue::Char(v) => Some(v),
+                    _ => None,
+                }),
+                _ => false,
+            }),
+            IrPat::Variant { path, items } => {
+                let span = spanned.span();
+                let (variant, _) = interp.resolve_variant(span, path, Used::Used)?;
+
+                let args = match variant.args_of(span, value)? {
+                    Some(args) => args,
+                    None => return Ok(false),
+                };
+
+                if args.len() != items.len() {
+                    return Ok(false);
+                }
+
+                for (pat, value) in items.iter().zip(args) {
+                    if !pat.matches_with(interp, value, span, bind)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+            IrPat::Or(alternatives) => {
+                let span = spanned.span();
+
+                for pat in alternatives.iter() {
+                    if pat.matches_with(interp, value.clone(), span, false)? {
+                        return Ok(true);
+                    }
+                }
+
+                Ok(false)
+            }
         }
     }
+
+    /// Match the items of a vector or tuple pattern against the given values.
+    fn matches_items(
+        interp: &mut IrInterpreter<'_>,
+        items: &[IrPat],
+        is_open: bool,
+        values: &[IrValue],
+        span: Span,
+        bind: bool,
+    ) -> Result<bool, IrEvalOutcome> {
+        let matches_len = if is_open {
+            values.len() >= items.len()
+        } else {
+            values.len() == items.len()
+        };
+
+        if !matches_len {
+            return Ok(false);
+        }
+
+        for (pat, value) in items.iter().zip(values) {
+            if !pat.matches_with(interp, value.clone(), span, bind)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// Test if `n` is inside of the given range, where `f` converts a bound into a
+/// value comparable with `n` and bounds of other types never match.
+fn range_contains<'a, T>(
+    n: &T,
+    start: &'a Option<IrValue>,
+    end: &'a Option<IrValue>,
+    inclusive: bool,
+    f: fn(&'a IrValue) -> Option<&'a T>,
+) -> bool
+where
+    T: PartialOrd,
+{
+    let start = match start.as_ref().map(f) {
+        Some(Some(start)) => Bound::Included(start),
+        Some(None) => return false,
+        None => Bound::Unbounded,
+    };
+
+    let end = match end.as_ref().map(f) {
+        Some(Some(end)) if inclusive => Bound::Included(end),
+        Some(Some(end)) => Bound::Excluded(end),
+        Some(None) => return false,
+        None => Bound::Unbounded,
+    };
+
+    (start, end).contains(&n)
 }
 
 /// A loop with an optional condition.
@@ -375,25 +763,27 @@ pub struct IrBreak {
     /// The span of the break.
     #[rune(span)]
     pub(crate) span: Span,
-    /// The kind of the break.
-    pub(crate) kind: IrBreakKind,
+    /// The label of the loop to break, or the next nested loop if `None`.
+    pub(crate) label: Option<Box<str>>,
+    /// The expression to break with, if any.
+    pub(crate) expr: Option<Box<Ir>>,
 }
 
 impl IrBreak {
     fn compile_ast(ast: &ast::ExprBreak, c: &mut IrCompiler<'_>) -> Result<Self, IrError> {
         let span = ast.span();
 
-        let kind = match ast.expr.as_deref() {
-            Some(expr) => match expr {
-                ast::ExprBreakValue::Expr(e) => ir::IrBreakKind::Ir(Box::new(compile::expr(e, c)?)),
-                ast::ExprBreakValue::Label(label) => {
-                    ir::IrBreakKind::Label(c.resolve(label)?.into())
-                }
-            },
-            None => ir::IrBreakKind::Inherent,
+        let label = match &ast.label {
+            Some(label) => Some(c.resolve(label)?.into()),
+            None => None,
+        };
+
+        let expr = match ast.expr.as_deref() {
+            Some(e) => Some(Box::new(compile::expr(e, c)?)),
+            None => None,
         };
 
-        Ok(ir::IrBreak { span, kind })
+        Ok(ir::IrBreak { span, label, expr })
     }
 
     /// Evaluate the break into an [IrEvalOutcome].
@@ -404,28 +794,57 @@ impl IrBreak {
             return e.into();
         }
 
-        match &self.kind {
-            IrBreakKind::Ir(ir) => match ir::eval_ir(ir, interp, used) {
-                Ok(value) => IrEvalOutcome::Break(span, IrEvalBreak::Value(value)),
-                Err(err) => err,
+        let value = match &self.expr {
+            Some(ir) => match ir::eval_ir(ir, interp, used) {
+                Ok(value) => Some(value),
+                Err(err) => return err,
             },
-            IrBreakKind::Label(label) => {
-                IrEvalOutcome::Break(span, IrEvalBreak::Label(label.clone()))
-            }
-            IrBreakKind::Inherent => IrEvalOutcome::Break(span, IrEvalBreak::Inherent),
-        }
+            None => None,
+        };
+
+        IrEvalOutcome::Break(
+            span,
+            IrEvalBreak {
+                label: self.label.clone(),
+                value,
+            },
+        )
     }
 }
 
-/// The kind of a break expression.
-#[derive(Debug, Clone)]
-pub enum IrBreakKind {
-    /// Break to the next loop.
-    Inherent,
-    /// Break to the given label.
-    Label(Box<str>),
-    /// Break with the value acquired from evaluating the ir.
-    Ir(Box<Ir>),
+/// A continue operation.
+#[derive(Debug, Clone, Spanned)]
+pub struct IrContinue {
+    /// The span of the continue.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The label of the loop to continue, or the innermost loop if `None`.
+    pub(crate) label: Option<Box<str>>,
+}
+
+impl IrContinue {
+    fn compile_ast(ast: &ast::ExprContinue, c: &mut IrCompiler<'_>) -> Result<Self, IrError> {
+        let label = match &ast.label {
+            Some(label) => Some(c.resolve(label)?.into()),
+            None => None,
+        };
+
+        Ok(ir::IrContinue {
+            span: ast.span(),
+            label,
+        })
+    }
+
+    /// Evaluate the continue into an [IrEvalOutcome].
+    fn as_outcome(&self, interp: &mut IrInterpreter<'_>) -> IrEvalOutcome {
+        let span = self.span();
+
+        if let Err(e) = interp.budget.take(span) {
+            return e.into();
+        }
+
+        IrEvalOutcome::Continue(span, self.label.clone())
+    }
 }
 
 /// Tuple expression.
@@ -448,6 +867,18 @@ pub struct IrObject {
     pub(crate) assignments: Box<[(Box<str>, Ir)]>,
 }
 
+/// Index expression, like `value[index]` or `value.field`.
+#[derive(Debug, Clone, Spanned)]
+pub struct IrIndex {
+    /// Span of the index expression.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The collection being indexed.
+    pub(crate) target: Box<Ir>,
+    /// The index or key to look up.
+    pub(crate) index: Box<Ir>,
+}
+
 /// Call expressions.
 #[derive(Debug, Clone, Spanned)]
 pub struct IrCall {
@@ -456,10 +887,26 @@ pub struct IrCall {
     pub(crate) span: Span,
     /// The target of the call.
     pub(crate) target: Box<str>,
+    /// The receiver of an instance function call, like `value` in
+    /// `value.len()`.
+    pub(crate) instance: Option<Box<Ir>>,
     /// Arguments to the call.
     pub(crate) args: Vec<Ir>,
 }
 
+/// Constructing a unit or tuple variant of an enum.
+#[derive(Debug, Clone, Spanned)]
+pub struct IrVariant {
+    /// Span of the variant expression.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The path to the variant, which is resolved when evaluated.
+    pub(crate) path: Box<[Box<str>]>,
+    /// Arguments to the variant, or `None` if it's referenced without being
+    /// called, like `Enum::Variant`.
+    pub(crate) args: Option<Vec<Ir>>,
+}
+
 /// Vector expression.
 #[derive(Debug, Clone, Spanned)]
 pub struct IrVec {
@@ -467,7 +914,28 @@ pub struct IrVec {
     #[rune(span)]
     pub(crate) span: Span,
     /// Arguments to construct the vector.
-    pub(crate) items: Box<[Ir]>,
+    pub(crate) items: Box<[IrVecItem]>,
+}
+
+/// An item in a vector expression.
+#[derive(Debug, Clone)]
+pub enum IrVecItem {
+    /// A single element.
+    Ir(Ir),
+    /// A spread item, whose elements are inserted into the vector.
+    Spread(Ir),
+}
+
+/// Repeat vector expression.
+#[derive(Debug, Clone, Spanned)]
+pub struct IrRepeat {
+    /// Span of the vector.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The value to repeat.
+    pub(crate) expr: Box<Ir>,
+    /// The number of times to repeat the value.
+    pub(crate) count: Box<Ir>,
 }
 
 /// A binary operation.
@@ -481,10 +949,18 @@ pub enum IrBinaryOp {
     Mul,
     /// Division `/`.
     Div,
+    /// Remainder `%`.
+    Rem,
     /// `<<`.
     Shl,
     /// `>>`.
     Shr,
+    /// `&`.
+    BitAnd,
+    /// `|`.
+    BitOr,
+    /// `^`.
+    BitXor,
     /// `<`,
     Lt,
     /// `<=`,
@@ -495,6 +971,17 @@ pub enum IrBinaryOp {
     Gt,
     /// `>=`,
     Gte,
+    /// Short-circuiting logical and `&&`.
+    And,
+    /// Short-circuiting logical or `||`.
+    Or,
+}
+
+/// A unary operation.
+#[derive(Debug, Clone, Copy)]
+pub enum IrUnaryOp {
+    /// Not `!`.
+    Not,
 }
 
 /// An assign operation.
@@ -508,10 +995,18 @@ pub enum IrAssignOp {
     Mul,
     /// `/=`.
     Div,
+    /// `%=`.
+    Rem,
     /// `<<=`.
     Shl,
     /// `>>=`.
     Shr,
+    /// `&=`.
+    BitAnd,
+    /// `|=`.
+    BitOr,
+    /// `^=`.
+    BitXor,
 }
 
 impl IrAssignOp {
@@ -521,13 +1016,14 @@ impl IrAssignOp {
         spanned: S,
         target: &mut IrValue,
         operand: IrValue,
+        big_integers: bool,
     ) -> Result<(), IrError>
     where
         S: Copy + Spanned,
     {
         if let IrValue::Integer(target) = target {
             if let IrValue::Integer(operand) = operand {
-                return self.assign_int(spanned, target, operand);
+                return self.assign_int(spanned, target, operand, big_integers);
             }
         }
 
@@ -540,39 +1036,68 @@ impl IrAssignOp {
         spanned: S,
         target: &mut num::BigInt,
         operand: num::BigInt,
+        big_integers: bool,
     ) -> Result<(), IrError>
     where
         S: Copy + Spanned,
     {
-        use std::ops::{AddAssign, MulAssign, ShlAssign, ShrAssign, SubAssign};
+        use num::Zero as _;
+        use std::ops::{
+            AddAssign, BitAndAssign, BitOrAssign, BitXorAssign, MulAssign, RemAssign, ShlAssign,
+            ShrAssign, SubAssign,
+        };
 
         match self {
             IrAssignOp::Add => {
                 target.add_assign(operand);
+
+                if !big_integers {
+                    eval::check_integer(spanned, target)?;
+                }
             }
             IrAssignOp::Sub => {
                 target.sub_assign(operand);
+
+                if !big_integers {
+                    eval::check_integer(spanned, target)?;
+                }
             }
             IrAssignOp::Mul => {
                 target.mul_assign(operand);
+
+                if !big_integers {
+                    eval::check_integer(spanned, target)?;
+                }
             }
             IrAssignOp::Div => {
                 *target = target
                     .checked_div(&operand)
                     .ok_or_else(|| IrError::msg(spanned, "division by zero"))?;
             }
-            IrAssignOp::Shl => {
-                let operand =
-                    u32::try_from(operand).map_err(|_| IrError::msg(spanned, "bad operand"))?;
+            IrAssignOp::Rem => {
+                if operand.is_zero() {
+                    return Err(IrError::msg(spanned, "division by zero"));
+                }
 
+                target.rem_assign(operand);
+            }
+            IrAssignOp::Shl => {
+                let operand = eval::shift_amount(spanned, operand, big_integers)?;
                 target.shl_assign(operand);
             }
             IrAssignOp::Shr => {
-                let operand =
-                    u32::try_from(operand).map_err(|_| IrError::msg(spanned, "bad operand"))?;
-
+                let operand = eval::shift_amount(spanned, operand, big_integers)?;
                 target.shr_assign(operand);
             }
+            IrAssignOp::BitAnd => {
+                target.bitand_assign(operand);
+            }
+            IrAssignOp::BitOr => {
+                target.bitor_assign(operand);
+            }
+            IrAssignOp::BitXor => {
+                target.bitxor_assign(operand);
+            }
         }
 
         Ok(())
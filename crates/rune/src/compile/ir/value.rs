@@ -2,8 +2,9 @@ use crate::ast::Spanned;
 use crate::collections::HashMap;
 use crate::compile::{IrError, IrErrorKind};
 use crate::runtime as rt;
-use crate::runtime::{Bytes, ConstValue, Shared, TypeInfo};
+use crate::runtime::{Bytes, ConstValue, Shared, TypeInfo, VariantRtti};
 use std::convert::TryFrom;
+use std::sync::Arc;
 
 /// A constant value.
 #[derive(Debug, Clone)]
@@ -24,6 +25,8 @@ pub enum IrValue {
     String(Shared<String>),
     /// An optional value.
     Option(Shared<Option<IrValue>>),
+    /// A result value.
+    Result(Shared<Result<IrValue, IrValue>>),
     /// A byte string.
     Bytes(Shared<Bytes>),
     /// A vector of values.
@@ -32,6 +35,8 @@ pub enum IrValue {
     Tuple(Shared<Box<[IrValue]>>),
     /// An anonymous object.
     Object(Shared<HashMap<String, IrValue>>),
+    /// A unit or tuple variant of an enum.
+    Variant(Arc<VariantRtti>, Shared<Box<[IrValue]>>),
 }
 
 impl IrValue {
@@ -69,6 +74,10 @@ impl IrValue {
             ConstValue::Option(option) => Self::Option(Shared::new(
                 option.as_ref().map(|some| Self::from_const(some)),
             )),
+            ConstValue::Result(result) => Self::Result(Shared::new(match result {
+                Ok(ok) => Ok(Self::from_const(ok)),
+                Err(err) => Err(Self::from_const(err)),
+            })),
             ConstValue::Vec(vec) => {
                 let mut ir_vec = Vec::with_capacity(vec.len());
 
@@ -96,6 +105,15 @@ impl IrValue {
 
                 Self::Object(Shared::new(ir_object))
             }
+            ConstValue::Variant(rtti, args) => {
+                let mut ir_args = Vec::with_capacity(args.len());
+
+                for value in args.iter() {
+                    ir_args.push(Self::from_const(value));
+                }
+
+                Self::Variant(rtti.clone(), Shared::new(ir_args.into_boxed_slice()))
+            }
         }
     }
 
@@ -122,13 +140,15 @@ impl IrValue {
                 ConstValue::Integer(n)
             }
             IrValue::Float(f) => ConstValue::Float(f),
+            // NB: strings and bytes are immutable and might be shared, so they
+            // are copied out rather than taken.
             IrValue::String(s) => {
-                let s = s.take().map_err(IrError::access(spanned))?;
-                ConstValue::String(s)
+                let s = s.borrow_ref().map_err(IrError::access(spanned))?;
+                ConstValue::String(s.clone())
             }
             IrValue::Bytes(b) => {
-                let b = b.take().map_err(IrError::access(spanned))?;
-                ConstValue::Bytes(b)
+                let b = b.borrow_ref().map_err(IrError::access(spanned))?;
+                ConstValue::Bytes(b.clone())
             }
             Self::Option(option) => {
                 ConstValue::Option(match option.take().map_err(IrError::access(spanned))? {
@@ -136,6 +156,12 @@ impl IrValue {
                     None => None,
                 })
             }
+            Self::Result(result) => {
+                ConstValue::Result(match result.take().map_err(IrError::access(spanned))? {
+                    Ok(value) => Ok(Box::new(value.into_const(spanned)?)),
+                    Err(value) => Err(Box::new(value.into_const(spanned)?)),
+                })
+            }
             IrValue::Vec(vec) => {
                 let vec = vec.take().map_err(IrError::access(spanned))?;
                 let mut const_vec = Vec::with_capacity(vec.len());
@@ -166,6 +192,16 @@ impl IrValue {
 
                 ConstValue::Object(const_object)
             }
+            IrValue::Variant(rtti, args) => {
+                let args = args.take().map_err(IrError::access(spanned))?;
+                let mut const_args = Vec::with_capacity(args.len());
+
+                for value in Vec::from(args) {
+                    const_args.push(value.into_const(spanned)?);
+                }
+
+                ConstValue::Variant(rtti, const_args.into_boxed_slice())
+            }
         })
     }
 
@@ -181,9 +217,11 @@ impl IrValue {
             Self::Integer(..) => TypeInfo::StaticType(rt::INTEGER_TYPE),
             Self::Float(..) => TypeInfo::StaticType(rt::FLOAT_TYPE),
             Self::Option(..) => TypeInfo::StaticType(rt::OPTION_TYPE),
+            Self::Result(..) => TypeInfo::StaticType(rt::RESULT_TYPE),
             Self::Vec(..) => TypeInfo::StaticType(rt::VEC_TYPE),
             Self::Tuple(..) => TypeInfo::StaticType(rt::TUPLE_TYPE),
             Self::Object(..) => TypeInfo::StaticType(rt::OBJECT_TYPE),
+            Self::Variant(rtti, ..) => TypeInfo::Variant(rtti.clone()),
         }
     }
 }
@@ -18,6 +18,34 @@ impl Location {
     pub fn new(source_id: SourceId, span: Span) -> Self {
         Self { source_id, span }
     }
+
+    /// Join two locations creating the larger of the two, or `None` if they
+    /// come from different sources.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::ast::Span;
+    /// use rune::compile::Location;
+    /// use rune::SourceId;
+    ///
+    /// let a = Location::new(SourceId::new(0), Span::new(10, 12));
+    /// let b = Location::new(SourceId::new(0), Span::new(20, 22));
+    /// let c = Location::new(SourceId::new(1), Span::new(20, 22));
+    ///
+    /// assert_eq!(a.join(b).map(|l| l.span), Some(Span::new(10, 22)));
+    /// assert!(a.join(c).is_none());
+    /// ```
+    pub fn join(self, other: Self) -> Option<Self> {
+        if self.source_id != other.source_id {
+            return None;
+        }
+
+        Some(Self {
+            source_id: self.source_id,
+            span: self.span.join(other.span),
+        })
+    }
 }
 
 impl fmt::Debug for Location {
@@ -237,6 +237,8 @@ pub(crate) enum PrivMetaKind {
         type_hash: Hash,
         /// The underlying object.
         empty: EmptyMeta,
+        /// Whether the type is formatted structurally when debug printed.
+        debug: bool,
     },
     /// Metadata about a tuple.
     TupleStruct {
@@ -244,6 +246,8 @@ pub(crate) enum PrivMetaKind {
         type_hash: Hash,
         /// The underlying tuple.
         tuple: TupleMeta,
+        /// Whether the type is formatted structurally when debug printed.
+        debug: bool,
     },
     /// Metadata about an object.
     Struct {
@@ -251,6 +255,8 @@ pub(crate) enum PrivMetaKind {
         type_hash: Hash,
         /// The underlying object.
         st: StructMeta,
+        /// Whether the type is formatted structurally when debug printed.
+        debug: bool,
     },
     /// Metadata about an empty variant.
     UnitVariant {
@@ -260,6 +266,8 @@ pub(crate) enum PrivMetaKind {
         enum_item: Item,
         /// The underlying empty.
         empty: EmptyMeta,
+        /// Whether the variant is formatted structurally when debug printed.
+        debug: bool,
     },
     /// Metadata about a tuple variant.
     TupleVariant {
@@ -269,6 +277,8 @@ pub(crate) enum PrivMetaKind {
         enum_item: Item,
         /// The underlying tuple.
         tuple: TupleMeta,
+        /// Whether the variant is formatted structurally when debug printed.
+        debug: bool,
     },
     /// Metadata about a variant object.
     StructVariant {
@@ -278,11 +288,15 @@ pub(crate) enum PrivMetaKind {
         enum_item: Item,
         /// The underlying object.
         st: StructMeta,
+        /// Whether the variant is formatted structurally when debug printed.
+        debug: bool,
     },
     /// An enum item.
     Enum {
         /// The type hash associated with this meta kind.
         type_hash: Hash,
+        /// The names of the variants of the enum.
+        variants: Vec<Box<str>>,
     },
     /// A function declaration.
     Function {
@@ -11,6 +11,7 @@ use crate::query::{Build, BuildEntry, Query};
 use crate::shared::{Consts, Gen};
 use crate::worker::{LoadFileKind, Task, Worker};
 use crate::{Diagnostics, Sources};
+use std::time::Instant;
 
 mod assembly;
 pub(crate) use self::assembly::{Assembly, AssemblyInst};
@@ -34,6 +35,10 @@ pub use self::ir::{IrError, IrErrorKind, IrEval, IrValue};
 pub(crate) mod item;
 pub use self::item::{Component, ComponentRef, IntoComponent, Item};
 
+mod progress;
+pub use self::progress::{BuildProgress, BuildTimings, Phase};
+pub(crate) use self::progress::{BuildStats, Progress};
+
 mod source_loader;
 pub use self::source_loader::{FileSourceLoader, SourceLoader};
 
@@ -56,7 +61,7 @@ pub(crate) use self::meta::{
 pub use self::meta::{Meta, MetaKind, MetaRef, SourceMeta};
 
 mod module;
-pub use self::module::{AssocType, InstallWith, Module};
+pub use self::module::{AssocType, InstallWith, Module, Variant};
 
 mod named;
 pub use self::named::Named;
@@ -79,7 +84,8 @@ pub(crate) fn compile(
     options: &Options,
     visitor: &mut dyn CompileVisitor,
     source_loader: &mut dyn SourceLoader,
-) -> Result<(), ()> {
+    progress: Option<&mut Progress<'_>>,
+) -> Result<BuildStats, ()> {
     // Shared id generator.
     let gen = Gen::new();
     let mut consts = Consts::default();
@@ -97,10 +103,16 @@ pub(crate) fn compile(
         diagnostics,
         visitor,
         source_loader,
+        progress,
         &gen,
         &mut inner,
     );
 
+    if let Some(progress) = worker.progress.as_deref_mut() {
+        worker.q.enable_timings();
+        progress.start(Phase::Index, Some(worker.q.sources.source_ids().count()));
+    }
+
     // Queue up the initial sources to be loaded.
     for source_id in worker.q.sources.source_ids() {
         let mod_item = match worker.q.insert_root_mod(source_id, Span::empty()) {
@@ -124,10 +136,20 @@ pub(crate) fn compile(
         return Err(());
     }
 
+    if let Some(progress) = worker.progress.as_deref_mut() {
+        progress.start(Phase::Assemble, Some(worker.q.build_queue_len()));
+    }
+
     loop {
         while let Some(entry) = worker.q.next_build_entry() {
             let source_id = entry.location.source_id;
 
+            let timed = match &entry.build {
+                Build::Unused | Build::Import(..) | Build::ReExport => None,
+                _ if worker.progress.is_some() => Some((entry.item.clone(), Instant::now())),
+                _ => None,
+            };
+
             let task = CompileBuildEntry {
                 context,
                 options,
@@ -138,6 +160,10 @@ pub(crate) fn compile(
             if let Err(error) = task.compile(entry) {
                 worker.diagnostics.error(source_id, error);
             }
+
+            if let (Some((item, start)), Some(progress)) = (timed, worker.progress.as_deref_mut()) {
+                progress.item_assembled(&item.item, start.elapsed());
+            }
         }
 
         match worker.q.queue_unused_entries() {
@@ -153,7 +179,7 @@ pub(crate) fn compile(
         return Err(());
     }
 
-    Ok(())
+    Ok(worker.q.take_stats())
 }
 
 struct CompileBuildEntry<'a> {
@@ -180,6 +206,8 @@ impl CompileBuildEntry<'_> {
             loops: self::v1::Loops::new(),
             options: self.options,
             diagnostics: self.diagnostics,
+            allow_non_exhaustive_match: false,
+            allow_constant_condition: false,
         }
     }
 
@@ -252,6 +280,10 @@ impl CompileBuildEntry<'_> {
                         f.call,
                         args,
                     )?;
+
+                    if f.is_call {
+                        self.q.unit.new_call_protocol(location, type_hash, name)?;
+                    }
                 }
             }
             Build::Closure(closure) => {
@@ -265,6 +297,8 @@ impl CompileBuildEntry<'_> {
                 )?;
 
                 let mut c = self.compiler1(location, span, &mut asm);
+                c.allow_non_exhaustive_match = closure.allow.non_exhaustive_match;
+                c.allow_constant_condition = closure.allow.constant_condition;
                 assemble::closure_from_expr_closure(&closure.ast, &mut c, &closure.captures)?;
 
                 if used.is_unused() {
@@ -288,6 +322,8 @@ impl CompileBuildEntry<'_> {
                 let span = b.ast.span();
 
                 let mut c = self.compiler1(location, span, &mut asm);
+                c.allow_non_exhaustive_match = b.allow.non_exhaustive_match;
+                c.allow_constant_condition = b.allow.constant_condition;
                 assemble::closure_from_block(&b.ast, &mut c, &b.captures)?;
 
                 if used.is_unused() {
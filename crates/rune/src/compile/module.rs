@@ -4,14 +4,17 @@
 //! native code.
 
 use crate::collections::{HashMap, HashSet};
-use crate::compile::{ContextError, IntoComponent, Item, Named};
+use crate::compile::{ComponentRef, ContextError, IntoComponent, Item, Named};
 use crate::macros::{MacroContext, TokenStream};
 use crate::runtime::{
-    ConstValue, FromValue, FunctionHandler, Future, GeneratorState, MacroHandler, Protocol, Stack,
-    StaticType, ToValue, TypeCheck, TypeInfo, TypeOf, UnsafeFromValue, Value, VmError, VmErrorKind,
+    AnyKey, ConstEvalHandler, ConstValue, FromValue, FunctionHandler, Future, GeneratorState,
+    KeyFn, MacroHandler, Protocol, Ref, Stack, StaticType, ToValue, TypeCheck, TypeInfo, TypeOf,
+    UnsafeFromValue, Value, VariantFn, VmError, VmErrorKind,
 };
-use crate::{Hash, InstFnInfo, InstFnKind, InstFnName};
+use crate::{Any, Hash, InstFnInfo, InstFnKind, InstFnName};
 use std::future;
+use std::iter;
+use std::ops;
 use std::sync::Arc;
 
 /// Trait to handle the installation of auxilliary functions for a type
@@ -118,8 +121,67 @@ pub(crate) struct Struct {
     pub(crate) fields: HashSet<Box<str>>,
 }
 
+/// The type specification for a native enum.
+pub(crate) struct Enum {
+    /// The variants of the enum, in declaration order.
+    pub(crate) variants: Vec<EnumVariant>,
+    /// Handler used to inspect which variant a value holds.
+    pub(crate) handler: Arc<VariantFn>,
+}
+
+/// A variant of a native enum.
+pub(crate) struct EnumVariant {
+    /// The name of the variant.
+    pub(crate) name: &'static str,
+    /// The kind of the variant.
+    pub(crate) kind: VariantKind,
+    /// The constructor of the variant, if one has been registered.
+    pub(crate) constructor: Option<(Arc<FunctionHandler>, usize)>,
+}
+
 pub(crate) enum TypeSpecification {
     Struct(Struct),
+    Enum(Enum),
+}
+
+/// The kind of a variant.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum VariantKind {
+    Unit,
+    Tuple(usize),
+    Struct(&'static [&'static str]),
+}
+
+/// Compile-time metadata on a variant of a native enum, as registered with
+/// [Module::enum_meta].
+#[derive(Debug, Clone, Copy)]
+pub struct Variant {
+    pub(crate) kind: VariantKind,
+}
+
+impl Variant {
+    /// A unit variant, like `External::First`.
+    pub const fn unit() -> Self {
+        Self {
+            kind: VariantKind::Unit,
+        }
+    }
+
+    /// A tuple variant with the given number of fields, like
+    /// `External::Second(a, b)`.
+    pub const fn tuple(args: usize) -> Self {
+        Self {
+            kind: VariantKind::Tuple(args),
+        }
+    }
+
+    /// A struct variant with the given named fields, like
+    /// `External::Third { a, b }`.
+    pub const fn st(fields: &'static [&'static str]) -> Self {
+        Self {
+            kind: VariantKind::Struct(fields),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -156,12 +218,49 @@ pub(crate) struct AssocKey {
 pub(crate) struct ModuleFn {
     pub(crate) handler: Arc<FunctionHandler>,
     pub(crate) args: Option<usize>,
+    pub(crate) const_eval: Option<Arc<ConstEvalHandler>>,
 }
 
 pub(crate) struct Macro {
     pub(crate) handler: Arc<MacroHandler>,
 }
 
+/// Construct an absolute item from a path, where the first component names
+/// the crate it lives in.
+fn absolute_item<I>(path: I) -> Item
+where
+    I: IntoIterator,
+    I::Item: IntoComponent,
+{
+    let mut path = path.into_iter();
+
+    match path.next() {
+        Some(first) => match first.as_component_ref() {
+            ComponentRef::Str(krate) => Item::with_crate_item(krate, path),
+            _ => Item::with_item(iter::once(first).chain(path)),
+        },
+        None => Item::new(),
+    }
+}
+
+/// A named set of instance functions which types can implement.
+pub(crate) struct Interface {
+    /// The names of the instance functions and the number of arguments they
+    /// take, not counting the receiver.
+    pub(crate) methods: Box<[(Box<str>, usize)]>,
+}
+
+/// A type which has been declared to implement an interface.
+#[derive(Clone)]
+pub(crate) struct Implementation {
+    /// The hash of the implementing type.
+    pub(crate) type_hash: Hash,
+    /// Type information for the implementing type.
+    pub(crate) type_info: TypeInfo,
+    /// The absolute path of the implemented interface.
+    pub(crate) interface: Item,
+}
+
 /// A [Module] that is a collection of native functions and types.
 ///
 /// Needs to be installed into a [Context][crate::compile::Context] using
@@ -176,6 +275,8 @@ pub struct Module {
     pub(crate) macros: HashMap<Item, Macro>,
     /// Constant values.
     pub(crate) constants: HashMap<Item, ConstValue>,
+    /// Items which are aliases for other items in the context.
+    pub(crate) reexports: HashMap<Item, Item>,
     /// Instance functions.
     pub(crate) associated_functions: HashMap<AssocKey, AssocFn>,
     /// Registered types.
@@ -184,6 +285,12 @@ pub struct Module {
     pub(crate) unit_type: Option<UnitType>,
     /// Registered generator state type.
     pub(crate) internal_enums: Vec<InternalEnum>,
+    /// Interfaces declared by the module.
+    pub(crate) interfaces: HashMap<Item, Interface>,
+    /// Interfaces which types have been declared to implement.
+    pub(crate) implementations: Vec<Implementation>,
+    /// Native types which can be used as keys.
+    pub(crate) keys: HashMap<Hash, Arc<KeyFn>>,
 }
 
 impl Module {
@@ -225,6 +332,10 @@ impl Module {
             unit_type: None,
             internal_enums: Vec::new(),
             constants: Default::default(),
+            reexports: Default::default(),
+            interfaces: Default::default(),
+            implementations: Vec::new(),
+            keys: Default::default(),
         }
     }
 
@@ -293,6 +404,36 @@ impl Module {
         Ok(())
     }
 
+    /// Register that the given type can be used as a key in maps and sets, like
+    /// `HashMap` and `HashSet`.
+    ///
+    /// A copy of the value is stored as the key, so modifying the value after
+    /// it has been inserted doesn't affect the collection. Keys are hashed and
+    /// compared using the [Hash][std::hash::Hash], [Eq] and [Ord]
+    /// implementations of the type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Any;
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Any)]
+    /// struct Id(u32);
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = rune::Module::default();
+    /// module.ty::<Id>()?;
+    /// module.key::<Id>()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn key<T>(&mut self) -> Result<(), ContextError>
+    where
+        T: Any + Clone + Eq + Ord + std::hash::Hash + std::fmt::Debug + Send + Sync,
+    {
+        self.keys.insert(T::type_hash(), AnyKey::key_fn::<T>());
+        Ok(())
+    }
+
     /// Register that the given type is a struct, and that it has the given
     /// compile-time metadata. This implies that each field has a
     /// [Protocol::GET] field function.
@@ -330,6 +471,167 @@ impl Module {
         Ok(())
     }
 
+    /// Register that the given type is an enum with the given variants, which
+    /// allows scripts to pattern match over it.
+    ///
+    /// The handler is called with a value of the type, and returns the index
+    /// of the variant it holds together with the fields of that variant, in
+    /// the order they were declared in.
+    ///
+    /// Variants can be constructed from scripts if a constructor has been
+    /// registered for them with [Module::variant_constructor].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Any, Module, ToValue};
+    /// use rune::compile::Variant;
+    ///
+    /// #[derive(Any)]
+    /// enum External {
+    ///     First(u32),
+    ///     Second { a: u32, b: String },
+    /// }
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = Module::new();
+    /// module.ty::<External>()?;
+    ///
+    /// module.enum_meta(
+    ///     &[("First", Variant::tuple(1)), ("Second", Variant::st(&["a", "b"]))],
+    ///     |value: &External| {
+    ///         Ok(match value {
+    ///             External::First(a) => (0, vec![a.to_value()?]),
+    ///             External::Second { a, b } => (1, vec![a.to_value()?, b.clone().to_value()?]),
+    ///         })
+    ///     },
+    /// )?;
+    ///
+    /// module.variant_constructor::<External, _, _>(0, External::First)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn enum_meta<T, F>(
+        &mut self,
+        variants: &[(&'static str, Variant)],
+        f: F,
+    ) -> Result<(), ContextError>
+    where
+        T: Any + TypeOf,
+        F: 'static + Fn(&T) -> Result<(usize, Vec<Value>), VmError> + Send + Sync,
+    {
+        let type_hash = <T as TypeOf>::type_hash();
+        let type_info = T::type_info();
+
+        let ty = match self.types.get_mut(&type_hash) {
+            Some(ty) => ty,
+            None => {
+                return Err(ContextError::MissingType {
+                    item: Item::with_item(&[T::full_name()]),
+                    type_info,
+                });
+            }
+        };
+
+        let handler: Arc<VariantFn> = Arc::new(move |value| {
+            let value = <Ref<T> as FromValue>::from_value(value.clone())?;
+            f(&value)
+        });
+
+        let old = ty.spec.replace(TypeSpecification::Enum(Enum {
+            variants: variants
+                .iter()
+                .map(|&(name, variant)| EnumVariant {
+                    name,
+                    kind: variant.kind,
+                    constructor: None,
+                })
+                .collect(),
+            handler,
+        }));
+
+        if old.is_some() {
+            return Err(ContextError::ConflictingTypeMeta {
+                item: Item::with_item(&[T::full_name()]),
+                type_info: ty.type_info.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Register a constructor for the variant with the given index of an enum
+    /// registered with [Module::enum_meta].
+    ///
+    /// The constructor is called with the fields of the variant. It may
+    /// return a `Result<T, VmError>` to fail construction. Only unit and
+    /// tuple variants can have constructors.
+    pub fn variant_constructor<T, Func, Args>(
+        &mut self,
+        index: usize,
+        constructor: Func,
+    ) -> Result<(), ContextError>
+    where
+        T: Named + TypeOf,
+        Func: Function<Args>,
+    {
+        let type_hash = T::type_hash();
+        let type_info = T::type_info();
+
+        let ty = match self.types.get_mut(&type_hash) {
+            Some(ty) => ty,
+            None => {
+                return Err(ContextError::MissingType {
+                    item: Item::with_item(&[T::full_name()]),
+                    type_info,
+                });
+            }
+        };
+
+        let variant = match &mut ty.spec {
+            Some(TypeSpecification::Enum(en)) => en.variants.get_mut(index),
+            _ => None,
+        };
+
+        let variant = match variant {
+            Some(variant) => variant,
+            None => {
+                return Err(ContextError::MissingEnumVariant { type_info, index });
+            }
+        };
+
+        let expected = match variant.kind {
+            VariantKind::Unit => 0,
+            VariantKind::Tuple(args) => args,
+            VariantKind::Struct(..) => {
+                return Err(ContextError::UnsupportedVariantConstructor {
+                    type_info,
+                    name: variant.name,
+                });
+            }
+        };
+
+        if Func::args() != expected {
+            return Err(ContextError::BadVariantConstructorArgs {
+                type_info,
+                name: variant.name,
+                expected,
+                actual: Func::args(),
+            });
+        }
+
+        if variant.constructor.is_some() {
+            return Err(ContextError::ConflictingVariantConstructor {
+                type_info,
+                name: variant.name,
+            });
+        }
+
+        let handler: Arc<FunctionHandler> =
+            Arc::new(move |stack, args| constructor.fn_call(stack, args));
+        variant.constructor = Some((handler, expected));
+        Ok(())
+    }
+
     /// Construct type information for the `unit` type.
     ///
     /// Registering this allows the given type to be used in Rune scripts when
@@ -503,12 +805,148 @@ impl Module {
             ModuleFn {
                 handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
                 args: Some(Func::args()),
+                const_eval: None,
             },
         );
 
         Ok(())
     }
 
+    /// Mark the function with the given name as requiring the given
+    /// capability.
+    ///
+    /// The function must already have been registered with
+    /// [Module::function], [Module::async_function] or [Module::raw_fn].
+    /// Calling it from a virtual machine which hasn't been granted the
+    /// capability through [Vm::grant][crate::Vm::grant] results in
+    /// [VmErrorKind::CapabilityDenied].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = rune::Module::default();
+    ///
+    /// module.function(&["fetch"], |url: String| url)?;
+    /// module.capability(&["fetch"], "net")?;
+    /// # Ok(()) }
+    /// ```
+    pub fn capability<N>(&mut self, name: N, capability: &str) -> Result<(), ContextError>
+    where
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        let name = Item::with_item(name);
+        let hash = Hash::type_hash(&self.item.join(&name));
+
+        let f = match self.functions.get_mut(&name) {
+            Some(f) => f,
+            None => return Err(ContextError::MissingFunction { name }),
+        };
+
+        f.handler = crate::runtime::gate(capability.into(), hash, f.handler.clone());
+        Ok(())
+    }
+
+    /// Mark the instance function with the given name on the type `T` as
+    /// requiring the given capability.
+    ///
+    /// This is the equivalent of [Module::capability] for functions which
+    /// have been registered with [Module::inst_fn] or [Module::async_inst_fn].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Any;
+    ///
+    /// #[derive(Any)]
+    /// struct Client;
+    ///
+    /// impl Client {
+    ///     fn fetch(&self, url: String) -> String {
+    ///         url
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = rune::Module::default();
+    ///
+    /// module.ty::<Client>()?;
+    /// module.inst_fn("fetch", Client::fetch)?;
+    /// module.inst_capability::<Client, _>("fetch", "net")?;
+    /// # Ok(()) }
+    /// ```
+    pub fn inst_capability<T, N>(&mut self, name: N, capability: &str) -> Result<(), ContextError>
+    where
+        T: TypeOf,
+        N: InstFnName,
+    {
+        let name = name.info();
+
+        let key = AssocKey {
+            type_hash: T::type_hash(),
+            hash: name.hash,
+            kind: AssocKind::Instance,
+            parameters: name.parameters,
+        };
+
+        let hash = Hash::instance_function(T::type_hash(), name.hash);
+
+        let f = match self.associated_functions.get_mut(&key) {
+            Some(f) => f,
+            None => {
+                return Err(ContextError::MissingInstanceFunction {
+                    type_info: T::type_info(),
+                    hash,
+                })
+            }
+        };
+
+        f.handler = crate::runtime::gate(capability.into(), hash, f.handler.clone());
+        Ok(())
+    }
+
+    /// Make the function with the given name evaluable in constant
+    /// expressions, using the given pure handler.
+    ///
+    /// The function must already have been registered with
+    /// [Module::function], [Module::async_function] or [Module::raw_fn]. The
+    /// handler is only used when folding calls during compilation, calls at
+    /// runtime still go through the registered function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{ConstValue, VmError};
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = rune::Module::default();
+    ///
+    /// module.function(&["double"], |n: i64| n * 2)?;
+    ///
+    /// module.const_eval(&["double"], |args| match args {
+    ///     [ConstValue::Integer(n)] => Ok(ConstValue::Integer(n * 2)),
+    ///     _ => Err(VmError::panic("expected an integer")),
+    /// })?;
+    /// # Ok(()) }
+    /// ```
+    pub fn const_eval<N, F>(&mut self, name: N, f: F) -> Result<(), ContextError>
+    where
+        N: IntoIterator,
+        N::Item: IntoComponent,
+        F: 'static + Fn(&[ConstValue]) -> Result<ConstValue, VmError> + Send + Sync,
+    {
+        let name = Item::with_item(name);
+
+        let function = match self.functions.get_mut(&name) {
+            Some(function) => function,
+            None => return Err(ContextError::MissingFunction { name }),
+        };
+
+        function.const_eval = Some(Arc::new(f));
+        Ok(())
+    }
+
     /// Register a constant value, at a crate, module or associated level.
     ///
     /// # Examples
@@ -550,6 +988,135 @@ impl Module {
         Ok(())
     }
 
+    /// Register an alias for an item which already exists in the context.
+    ///
+    /// The target is an absolute path to a function, constant or macro, and
+    /// must have been installed before this module is, unless it is provided
+    /// by this module itself. Installing a module with a reexport to an item
+    /// which doesn't exist results in [ContextError::MissingReexportTarget].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = rune::Module::default();
+    /// module.reexport(&["fast_max"], &["std", "int", "max"])?;
+    ///
+    /// let mut context = rune::Context::with_default_modules()?;
+    /// context.install(&module)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn reexport<N, T>(&mut self, name: N, target: T) -> Result<(), ContextError>
+    where
+        N: IntoIterator,
+        N::Item: IntoComponent,
+        T: IntoIterator,
+        T::Item: IntoComponent,
+    {
+        let name = Item::with_item(name);
+
+        if self.reexports.contains_key(&name) {
+            return Err(ContextError::ConflictingFunctionName { name });
+        }
+
+        self.reexports.insert(name, absolute_item(target));
+        Ok(())
+    }
+
+    /// Declare an interface, which is a set of instance functions that types
+    /// can implement to be used interchangeably by scripts.
+    ///
+    /// Each method is given as its name and the number of arguments it takes,
+    /// not counting the receiver. Native types declare that they implement the
+    /// interface with [Module::implements].
+    ///
+    /// Interfaces are only checked for native types. Script types which
+    /// provide the same methods can be used in their place through dynamic
+    /// method dispatch, but there's no compile time check that they do.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = rune::Module::with_crate("shapes");
+    /// module.interface(&["Shape"], &[("draw", 0), ("scale", 1)])?;
+    /// # Ok(()) }
+    /// ```
+    pub fn interface<N>(&mut self, name: N, methods: &[(&str, usize)]) -> Result<(), ContextError>
+    where
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        let name = Item::with_item(name);
+
+        if self.interfaces.contains_key(&name) {
+            return Err(ContextError::ConflictingInterface {
+                item: self.item.join(&name),
+            });
+        }
+
+        let methods = methods
+            .iter()
+            .map(|(name, args)| (Box::<str>::from(*name), *args))
+            .collect();
+
+        self.interfaces.insert(name, Interface { methods });
+        Ok(())
+    }
+
+    /// Declare that the type `T` implements the interface at the given
+    /// absolute path.
+    ///
+    /// When a unit is linked against the context, it checks that every method
+    /// of the interface has been registered as an instance function of `T`
+    /// with the expected number of arguments, or fails with
+    /// [LinkerError::MissingInterfaceMethod][crate::compile::LinkerError::MissingInterfaceMethod].
+    /// Since this happens once the context is complete, the interface, the
+    /// type and its instance functions can be installed in any order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Any;
+    ///
+    /// #[derive(Any)]
+    /// struct Square {
+    ///     side: i64,
+    /// }
+    ///
+    /// impl Square {
+    ///     fn draw(&self) -> String {
+    ///         format!("square with side {}", self.side)
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = rune::Module::with_crate("shapes");
+    /// module.interface(&["Shape"], &[("draw", 0)])?;
+    ///
+    /// module.ty::<Square>()?;
+    /// module.inst_fn("draw", Square::draw)?;
+    /// module.implements::<Square, _>(&["shapes", "Shape"])?;
+    ///
+    /// let mut context = rune::Context::with_default_modules()?;
+    /// context.install(&module)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn implements<T, I>(&mut self, interface: I) -> Result<(), ContextError>
+    where
+        T: TypeOf,
+        I: IntoIterator,
+        I::Item: IntoComponent,
+    {
+        self.implementations.push(Implementation {
+            type_hash: T::type_hash(),
+            type_info: T::type_info(),
+            interface: absolute_item(interface),
+        });
+
+        Ok(())
+    }
+
     /// Register a native macro handler.
     pub fn macro_<N, M>(&mut self, name: N, f: M) -> Result<(), ContextError>
     where
@@ -602,6 +1169,7 @@ impl Module {
             ModuleFn {
                 handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
                 args: Some(Func::args()),
+                const_eval: None,
             },
         );
 
@@ -627,6 +1195,7 @@ impl Module {
             ModuleFn {
                 handler: Arc::new(move |stack, args| f(stack, args)),
                 args: None,
+                const_eval: None,
             },
         );
 
@@ -680,6 +1249,163 @@ impl Module {
         self.assoc_fn(name, handler, ty, args, AssocKind::Instance)
     }
 
+    /// Install the arithmetic operator protocols for the given type based on
+    /// its implementations of the [std::ops] traits.
+    ///
+    /// This installs [Protocol::ADD], [Protocol::SUB], [Protocol::MUL] and
+    /// [Protocol::DIV], as well as their corresponding assign protocols like
+    /// [Protocol::ADD_ASSIGN]. Both operands are expected to be of type `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Any;
+    /// use std::ops;
+    ///
+    /// #[derive(Any, Clone, Copy)]
+    /// struct Meters(f64);
+    ///
+    /// impl ops::Add for Meters {
+    ///     type Output = Meters;
+    ///
+    ///     fn add(self, rhs: Meters) -> Meters {
+    ///         Meters(self.0 + rhs.0)
+    ///     }
+    /// }
+    ///
+    /// # impl ops::Sub for Meters {
+    /// #     type Output = Meters;
+    /// #     fn sub(self, rhs: Meters) -> Meters { Meters(self.0 - rhs.0) }
+    /// # }
+    /// # impl ops::Mul for Meters {
+    /// #     type Output = Meters;
+    /// #     fn mul(self, rhs: Meters) -> Meters { Meters(self.0 * rhs.0) }
+    /// # }
+    /// # impl ops::Div for Meters {
+    /// #     type Output = Meters;
+    /// #     fn div(self, rhs: Meters) -> Meters { Meters(self.0 / rhs.0) }
+    /// # }
+    /// # fn main() -> rune::Result<()> {
+    /// let mut module = rune::Module::default();
+    /// module.ty::<Meters>()?;
+    /// module.install_ops::<Meters>()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn install_ops<T>(&mut self) -> Result<(), ContextError>
+    where
+        T: Any
+            + TypeOf
+            + Clone
+            + ops::Add<Output = T>
+            + ops::Sub<Output = T>
+            + ops::Mul<Output = T>
+            + ops::Div<Output = T>,
+    {
+        self.install_add::<T>()?;
+        self.install_sub::<T>()?;
+        self.install_mul::<T>()?;
+        self.install_div::<T>()?;
+        Ok(())
+    }
+
+    /// Install [Protocol::ADD] and [Protocol::ADD_ASSIGN] for the given type
+    /// based on its implementation of [std::ops::Add].
+    ///
+    /// See [Module::install_ops].
+    pub fn install_add<T>(&mut self) -> Result<(), ContextError>
+    where
+        T: Any + TypeOf + Clone + ops::Add<Output = T>,
+    {
+        self.install_op::<T, _>(Protocol::ADD, Protocol::ADD_ASSIGN, |a, b| a + b)
+    }
+
+    /// Install [Protocol::SUB] and [Protocol::SUB_ASSIGN] for the given type
+    /// based on its implementation of [std::ops::Sub].
+    ///
+    /// See [Module::install_ops].
+    pub fn install_sub<T>(&mut self) -> Result<(), ContextError>
+    where
+        T: Any + TypeOf + Clone + ops::Sub<Output = T>,
+    {
+        self.install_op::<T, _>(Protocol::SUB, Protocol::SUB_ASSIGN, |a, b| a - b)
+    }
+
+    /// Install [Protocol::MUL] and [Protocol::MUL_ASSIGN] for the given type
+    /// based on its implementation of [std::ops::Mul].
+    ///
+    /// See [Module::install_ops].
+    pub fn install_mul<T>(&mut self) -> Result<(), ContextError>
+    where
+        T: Any + TypeOf + Clone + ops::Mul<Output = T>,
+    {
+        self.install_op::<T, _>(Protocol::MUL, Protocol::MUL_ASSIGN, |a, b| a * b)
+    }
+
+    /// Install [Protocol::DIV] and [Protocol::DIV_ASSIGN] for the given type
+    /// based on its implementation of [std::ops::Div].
+    ///
+    /// See [Module::install_ops].
+    pub fn install_div<T>(&mut self) -> Result<(), ContextError>
+    where
+        T: Any + TypeOf + Clone + ops::Div<Output = T>,
+    {
+        self.install_op::<T, _>(Protocol::DIV, Protocol::DIV_ASSIGN, |a, b| a / b)
+    }
+
+    /// Install a binary operator and its assign protocol for the given type.
+    fn install_op<T, F>(
+        &mut self,
+        protocol: Protocol,
+        assign_protocol: Protocol,
+        op: F,
+    ) -> Result<(), ContextError>
+    where
+        T: Any + TypeOf + Clone,
+        F: 'static + Copy + Send + Sync + Fn(T, T) -> T,
+    {
+        self.inst_fn(protocol, move |a: Ref<T>, b: Ref<T>| {
+            op(T::clone(&a), T::clone(&b))
+        })?;
+
+        // NB: both operands might be the same value, like in `a += a`, so the
+        // right hand side is cloned before the left hand side is borrowed
+        // mutably.
+        let handler: Arc<FunctionHandler> = Arc::new(move |stack, args| {
+            if args != 2 {
+                return Err(VmError::from(VmErrorKind::BadArgumentCount {
+                    actual: args,
+                    expected: 2,
+                }));
+            }
+
+            let mut it = stack.drain(2)?;
+            let a = it.next().unwrap().into_any()?;
+            let b = it.next().unwrap().into_any()?;
+            drop(it);
+
+            let b = T::clone(&*b.downcast_borrow_ref::<T>()?);
+            let mut a = a.downcast_borrow_mut::<T>()?;
+            *a = op(T::clone(&a), b);
+            drop(a);
+
+            stack.push(Value::Unit);
+            Ok(())
+        });
+
+        let ty = AssocType {
+            hash: <T as TypeOf>::type_hash(),
+            type_info: <T as TypeOf>::type_info(),
+        };
+
+        self.assoc_fn(
+            assign_protocol.info(),
+            handler,
+            ty,
+            Some(2),
+            AssocKind::Instance,
+        )
+    }
+
     /// Install a protocol function that interacts with the given field.
     pub fn field_fn<N, Func, Args>(
         &mut self,
@@ -20,6 +20,9 @@ pub struct Options {
     pub(crate) debug_info: bool,
     /// Support (experimental) macros.
     pub(crate) macros: bool,
+    /// Evaluate constant integer arithmetic with arbitrary precision, only
+    /// requiring that the final value fits in an `i64`.
+    pub(crate) const_big_integers: bool,
     /// Support (experimental) bytecode caching.
     pub bytecode: bool,
 
@@ -52,6 +55,9 @@ impl Options {
             Some("macros") => {
                 self.macros = it.next() != Some("false");
             }
+            Some("const-big-integers") => {
+                self.const_big_integers = it.next() != Some("false");
+            }
             Some("bytecode") => {
                 self.bytecode = it.next() != Some("false");
             }
@@ -93,6 +99,16 @@ impl Options {
         self.macros = enabled;
     }
 
+    /// Set if constant integer arithmetic should be performed with arbitrary
+    /// precision. Defaults to `false`.
+    ///
+    /// When enabled, intermediate values in constant expressions are allowed
+    /// to exceed the range of an `i64`. The final value of a constant must
+    /// still fit or compilation fails.
+    pub fn const_big_integers(&mut self, enabled: bool) {
+        self.const_big_integers = enabled;
+    }
+
     /// Set if bytecode caching is enabled or not. Defaults to `false`.
     pub fn bytecode(&mut self, enabled: bool) {
         self.bytecode = enabled;
@@ -111,6 +127,7 @@ impl Default for Options {
             memoize_instance_fn: true,
             debug_info: true,
             macros: true,
+            const_big_integers: false,
             bytecode: false,
             cfg_test: false,
             v2: false,
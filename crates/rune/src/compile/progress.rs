@@ -0,0 +1,292 @@
+use crate::compile::Item;
+use crate::SourceId;
+use std::cmp::Reverse;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// A phase of a build, as reported to a [BuildProgress] observer.
+///
+/// The build runs through [Phase::Index], [Phase::Assemble] and [Phase::Link]
+/// in order. Parsing, macro expansion and constant evaluation happen as needed
+/// while indexing and assembling, so they are reported once the build has
+/// finished with the time accumulated across the whole build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Phase {
+    /// Sources are parsed and their items are indexed.
+    Index,
+    /// Indexed items are assembled into instructions.
+    Assemble,
+    /// The assembled unit is linked.
+    Link,
+    /// Sources are parsed, as part of [Phase::Index].
+    Parse,
+    /// Macros are expanded, as part of [Phase::Index] and [Phase::Assemble].
+    MacroExpansion,
+    /// Constant expressions are evaluated, as part of [Phase::Index] and
+    /// [Phase::Assemble].
+    ConstEval,
+}
+
+impl Phase {
+    /// Test if the phase is run as part of another phase.
+    pub fn is_nested(self) -> bool {
+        matches!(self, Self::Parse | Self::MacroExpansion | Self::ConstEval)
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Index => write!(f, "index"),
+            Self::Assemble => write!(f, "assemble"),
+            Self::Link => write!(f, "link"),
+            Self::Parse => write!(f, "parse"),
+            Self::MacroExpansion => write!(f, "macro expansion"),
+            Self::ConstEval => write!(f, "const eval"),
+        }
+    }
+}
+
+/// An observer which is notified about the progress of a build.
+///
+/// Every method has a default implementation which does nothing, so only the
+/// events of interest need to be implemented. Timing is only measured when an
+/// observer is configured through
+/// [Build::with_progress][crate::Build::with_progress].
+pub trait BuildProgress {
+    /// Called when a phase is started, with a hint of how many items it is
+    /// expected to process if known.
+    ///
+    /// This is not called for [nested][Phase::is_nested] phases.
+    fn phase_started(&mut self, _phase: Phase, _item_count_hint: Option<usize>) {}
+
+    /// Called when a phase has finished.
+    ///
+    /// For [nested][Phase::is_nested] phases this is called once the build has
+    /// finished, with the time accumulated over the whole build.
+    fn phase_finished(&mut self, _phase: Phase, _duration: Duration) {}
+
+    /// Called when a source has been parsed.
+    fn source_parsed(&mut self, _source_id: SourceId, _duration: Duration) {}
+
+    /// Called when an item has been assembled.
+    fn item_assembled(&mut self, _item: &Item, _duration: Duration) {}
+
+    /// Called with a summary of the build once it has completed successfully.
+    fn build_finished(&mut self, _timings: &BuildTimings) {}
+}
+
+/// A summary of where time was spent during a build.
+///
+/// This is passed to [BuildProgress::build_finished]. It also implements
+/// [BuildProgress] itself, in which case it stores the summary of the build it
+/// was used with.
+///
+/// ```
+/// use rune::compile::{BuildTimings, Phase};
+/// use rune::{Source, Sources};
+///
+/// # fn main() -> rune::Result<()> {
+/// let mut sources = Sources::new();
+/// sources.insert(Source::new("entry", "pub fn main() { 42 }"));
+///
+/// let mut timings = BuildTimings::default();
+///
+/// let unit = rune::prepare(&mut sources)
+///     .with_progress(&mut timings)
+///     .build()?;
+///
+/// assert!(timings.phase(Phase::Assemble).is_some());
+/// assert_eq!(timings.items().len(), 1);
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BuildTimings {
+    phases: Vec<(Phase, Duration)>,
+    sources: Vec<(SourceId, Duration)>,
+    items: Vec<(Item, Duration)>,
+    macro_expansions: usize,
+    const_eval_budget: usize,
+}
+
+impl BuildTimings {
+    /// The duration of every phase, in the order they were run, followed by
+    /// the [nested][Phase::is_nested] phases.
+    pub fn phases(&self) -> &[(Phase, Duration)] {
+        &self.phases
+    }
+
+    /// The duration of the given phase, if it was run.
+    pub fn phase(&self, phase: Phase) -> Option<Duration> {
+        self.phases
+            .iter()
+            .find(|(p, _)| *p == phase)
+            .map(|(_, duration)| *duration)
+    }
+
+    /// The total duration of the build.
+    ///
+    /// Since they're part of other phases, [nested][Phase::is_nested] phases
+    /// are not counted.
+    pub fn total(&self) -> Duration {
+        self.phases
+            .iter()
+            .filter(|(phase, _)| !phase.is_nested())
+            .map(|(_, duration)| *duration)
+            .sum()
+    }
+
+    /// The time spent parsing each source.
+    pub fn sources(&self) -> &[(SourceId, Duration)] {
+        &self.sources
+    }
+
+    /// The time spent assembling each item, in the order they were assembled.
+    pub fn items(&self) -> &[(Item, Duration)] {
+        &self.items
+    }
+
+    /// The `n` items which took the longest to assemble, slowest first.
+    pub fn slowest(&self, n: usize) -> Vec<&(Item, Duration)> {
+        let mut items = self.items.iter().collect::<Vec<_>>();
+        items.sort_by_key(|(_, duration)| Reverse(*duration));
+        items.truncate(n);
+        items
+    }
+
+    /// The number of macros which were expanded.
+    pub fn macro_expansions(&self) -> usize {
+        self.macro_expansions
+    }
+
+    /// The budget consumed by constant evaluation, in number of evaluated
+    /// expressions.
+    pub fn const_eval_budget(&self) -> usize {
+        self.const_eval_budget
+    }
+}
+
+impl BuildProgress for BuildTimings {
+    fn build_finished(&mut self, timings: &BuildTimings) {
+        self.clone_from(timings);
+    }
+}
+
+impl fmt::Display for BuildTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16} {:>12}", "phase", "time")?;
+
+        for (phase, duration) in self.phases.iter().filter(|(p, _)| !p.is_nested()) {
+            writeln!(f, "{:<16} {:>12?}", phase.to_string(), duration)?;
+        }
+
+        writeln!(f, "{:<16} {:>12?}", "total", self.total())?;
+
+        for (phase, duration) in self.phases.iter().filter(|(p, _)| p.is_nested()) {
+            writeln!(f, "{:<16} {:>12?}", format!("  {}", phase), duration)?;
+        }
+
+        let slowest = self.slowest(10);
+
+        if !slowest.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "slowest items:")?;
+
+            for (item, duration) in slowest {
+                writeln!(f, "{:>12?} {}", duration, item)?;
+            }
+        }
+
+        writeln!(f)?;
+        writeln!(f, "macro expansions: {}", self.macro_expansions)?;
+        writeln!(f, "const eval budget: {}", self.const_eval_budget)?;
+        Ok(())
+    }
+}
+
+/// Counters collected during a build, which are cheap enough to always keep
+/// track of.
+#[derive(Debug, Default)]
+pub(crate) struct BuildStats {
+    /// Whether the time spent in nested phases is measured.
+    pub(crate) timed: bool,
+    /// The number of macros which were expanded.
+    pub(crate) macro_expansions: usize,
+    /// The time spent expanding macros.
+    pub(crate) macro_expansion_time: Duration,
+    /// The budget consumed by constant evaluation.
+    pub(crate) const_eval_budget: usize,
+    /// The time spent evaluating constant expressions.
+    pub(crate) const_eval_time: Duration,
+    /// The number of constant evaluations in progress, so that nested
+    /// evaluations are only timed once.
+    pub(crate) const_eval_depth: usize,
+}
+
+/// Forwards events to a [BuildProgress] observer while collecting them into a
+/// [BuildTimings] summary.
+pub(crate) struct Progress<'a> {
+    observer: &'a mut dyn BuildProgress,
+    timings: BuildTimings,
+    phase: Option<(Phase, Instant)>,
+}
+
+impl<'a> Progress<'a> {
+    /// Construct a new progress reporter for the given observer.
+    pub(crate) fn new(observer: &'a mut dyn BuildProgress) -> Self {
+        Self {
+            observer,
+            timings: BuildTimings::default(),
+            phase: None,
+        }
+    }
+
+    /// Start the given phase, finishing the current one if there is one.
+    pub(crate) fn start(&mut self, phase: Phase, item_count_hint: Option<usize>) {
+        self.finish_phase();
+        self.observer.phase_started(phase, item_count_hint);
+        self.phase = Some((phase, Instant::now()));
+    }
+
+    /// Finish the current phase.
+    pub(crate) fn finish_phase(&mut self) {
+        if let Some((phase, start)) = self.phase.take() {
+            let duration = start.elapsed();
+            self.timings.phases.push((phase, duration));
+            self.observer.phase_finished(phase, duration);
+        }
+    }
+
+    /// Report that a source has been parsed.
+    pub(crate) fn source_parsed(&mut self, source_id: SourceId, duration: Duration) {
+        self.timings.sources.push((source_id, duration));
+        self.observer.source_parsed(source_id, duration);
+    }
+
+    /// Report that an item has been assembled.
+    pub(crate) fn item_assembled(&mut self, item: &Item, duration: Duration) {
+        self.timings.items.push((item.clone(), duration));
+        self.observer.item_assembled(item, duration);
+    }
+
+    /// Finish the build, reporting the summary to the observer.
+    pub(crate) fn finish(mut self, stats: &BuildStats) {
+        self.finish_phase();
+
+        let parse = self.timings.sources.iter().map(|(_, d)| *d).sum();
+
+        for (phase, duration) in [
+            (Phase::Parse, parse),
+            (Phase::MacroExpansion, stats.macro_expansion_time),
+            (Phase::ConstEval, stats.const_eval_time),
+        ] {
+            self.timings.phases.push((phase, duration));
+            self.observer.phase_finished(phase, duration);
+        }
+
+        self.timings.macro_expansions = stats.macro_expansions;
+        self.timings.const_eval_budget = stats.const_eval_budget;
+        self.observer.build_finished(&self.timings);
+    }
+}
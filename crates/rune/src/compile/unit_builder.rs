@@ -4,7 +4,7 @@
 //! metadata like function locations.
 
 use crate::ast::Span;
-use crate::collections::HashMap;
+use crate::collections::{HashMap, HashSet};
 use crate::compile::{
     Assembly, AssemblyInst, CompileError, CompileErrorKind, IntoComponent, Item, Location,
     PrivMeta, PrivMetaKind,
@@ -12,10 +12,11 @@ use crate::compile::{
 use crate::query::{QueryError, QueryErrorKind};
 use crate::runtime::debug::{DebugArgs, DebugSignature};
 use crate::runtime::{
-    Call, ConstValue, DebugInfo, DebugInst, Inst, Label, Protocol, Rtti, StaticString, Unit,
-    UnitFn, VariantRtti,
+    Call, ConstValue, DebugInfo, DebugInst, Inst, Label, Protocol, Rtti, StaticString, TypeInfo,
+    Unit, UnitFn, VariantRtti,
 };
 use crate::{Context, Diagnostics, Hash, SourceId};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -29,6 +30,15 @@ pub enum LinkerError {
         hash: Hash,
         spans: Vec<(Span, SourceId)>,
     },
+    #[error("interface `{item}` does not exist")]
+    MissingInterface { item: Item },
+    #[error("type `{type_info}` is missing instance function `{name}` taking {args} argument(s), required by interface `{interface}`")]
+    MissingInterfaceMethod {
+        type_info: TypeInfo,
+        interface: Item,
+        name: Box<str>,
+        args: usize,
+    },
 }
 
 /// Instructions from a single source file.
@@ -36,6 +46,14 @@ pub enum LinkerError {
 pub(crate) struct UnitBuilder {
     /// Prelude imports.
     prelude: HashMap<Box<str>, Item>,
+    /// Modules which are not allowed to be referenced.
+    denied_modules: Vec<Item>,
+    /// Names which are resolved as globals set on the virtual machine.
+    globals: HashSet<Box<str>>,
+    /// Configuration values provided by the host.
+    env: HashMap<Box<str>, Box<str>>,
+    /// The maximum depth of nested const fn calls, if any.
+    const_recursion_limit: Option<usize>,
     /// The instructions contained in the source file.
     instructions: Vec<Inst>,
     /// Registered re-exports.
@@ -43,7 +61,7 @@ pub(crate) struct UnitBuilder {
     /// Where functions are located in the collection of instructions.
     functions: HashMap<Hash, UnitFn>,
     /// Function by address.
-    functions_rev: HashMap<usize, Hash>,
+    functions_rev: BTreeMap<usize, Hash>,
     /// A static string.
     static_strings: Vec<Arc<StaticString>>,
     /// Reverse lookup for static strings.
@@ -91,6 +109,7 @@ impl UnitBuilder {
         this.add_prelude("file", &["macros", "builtin", "file"]);
         this.add_prelude("float", &["float"]);
         this.add_prelude("format", &["fmt", "format"]);
+        this.add_prelude("format_args", &["string", "format_args"]);
         this.add_prelude("int", &["int"]);
         this.add_prelude("is_readable", &["is_readable"]);
         this.add_prelude("is_writable", &["is_writable"]);
@@ -112,6 +131,69 @@ impl UnitBuilder {
         this
     }
 
+    /// Add the given item to the prelude, making it available by its last
+    /// component without a `use`.
+    ///
+    /// The first component of the item is treated as the crate it belongs to,
+    /// so `std::string::String` is made available as `String`. Errors if the
+    /// path is empty or has empty components.
+    pub(crate) fn add_prelude_item(&mut self, span: Span, path: &str) -> Result<(), CompileError> {
+        if path.split("::").any(str::is_empty) {
+            return Err(CompileError::new(
+                span,
+                CompileErrorKind::InvalidPreludeItem { path: path.into() },
+            ));
+        }
+
+        let mut it = path.split("::");
+        let krate = it.next().unwrap_or(path);
+        let local = path.rsplit("::").next().unwrap_or(path);
+
+        self.prelude
+            .insert(local.into(), Item::with_crate_item(krate, it));
+        Ok(())
+    }
+
+    /// Deny any references to the given module and the items inside of it.
+    pub(crate) fn deny_module(&mut self, module: Item) {
+        if !self.denied_modules.contains(&module) {
+            self.denied_modules.push(module);
+        }
+    }
+
+    /// Get the denied module which contains the given item, if any.
+    pub(crate) fn denied_module(&self, item: &Item) -> Option<&Item> {
+        self.denied_modules
+            .iter()
+            .find(|module| item.starts_with(module))
+    }
+
+    /// Declare a global, which is resolved at runtime.
+    pub(crate) fn add_global(&mut self, name: &str) {
+        self.globals.insert(name.into());
+    }
+
+    /// Test if the given name has been declared as a global.
+    pub(crate) fn is_global(&self, name: &str) -> bool {
+        self.globals.contains(name)
+    }
+
+    /// Provide a configuration value, which can be read through
+    /// `std::env::var`.
+    pub(crate) fn add_env_var(&mut self, name: Box<str>, value: Box<str>) {
+        self.env.insert(name, value);
+    }
+
+    /// Limit the depth of nested const fn calls during constant evaluation.
+    pub(crate) fn set_const_recursion_limit(&mut self, limit: usize) {
+        self.const_recursion_limit = Some(limit);
+    }
+
+    /// Get the maximum depth of nested const fn calls, if any.
+    pub(crate) fn const_recursion_limit(&self) -> Option<usize> {
+        self.const_recursion_limit
+    }
+
     /// Clone the prelude.
     pub(crate) fn prelude(&self) -> &HashMap<Box<str>, Item> {
         &self.prelude
@@ -167,6 +249,8 @@ impl UnitBuilder {
             self.variant_rtti,
             self.debug,
             self.constants,
+            self.env,
+            self.globals,
         ))
     }
 
@@ -324,6 +408,7 @@ impl UnitBuilder {
                 let rtti = Arc::new(Rtti {
                     hash,
                     item: meta.item.item.clone(),
+                    debug: true,
                 });
 
                 self.constants.insert(
@@ -338,7 +423,7 @@ impl UnitBuilder {
                     ));
                 }
             }
-            PrivMetaKind::UnitStruct { empty, .. } => {
+            PrivMetaKind::UnitStruct { empty, debug, .. } => {
                 let info = UnitFn::UnitStruct { hash: empty.hash };
 
                 let signature = DebugSignature::new(meta.item.item.clone(), DebugArgs::EmptyArgs);
@@ -346,6 +431,7 @@ impl UnitBuilder {
                 let rtti = Arc::new(Rtti {
                     hash: empty.hash,
                     item: meta.item.item.clone(),
+                    debug: *debug,
                 });
 
                 if self.rtti.insert(empty.hash, rtti).is_some() {
@@ -373,7 +459,7 @@ impl UnitBuilder {
                     .functions
                     .insert(empty.hash, signature);
             }
-            PrivMetaKind::TupleStruct { tuple, .. } => {
+            PrivMetaKind::TupleStruct { tuple, debug, .. } => {
                 let info = UnitFn::TupleStruct {
                     hash: tuple.hash,
                     args: tuple.args,
@@ -385,6 +471,7 @@ impl UnitBuilder {
                 let rtti = Arc::new(Rtti {
                     hash: tuple.hash,
                     item: meta.item.item.clone(),
+                    debug: *debug,
                 });
 
                 if self.rtti.insert(tuple.hash, rtti).is_some() {
@@ -412,12 +499,13 @@ impl UnitBuilder {
                     .functions
                     .insert(tuple.hash, signature);
             }
-            PrivMetaKind::Struct { .. } => {
+            PrivMetaKind::Struct { debug, .. } => {
                 let hash = Hash::type_hash(&meta.item.item);
 
                 let rtti = Arc::new(Rtti {
                     hash,
                     item: meta.item.item.clone(),
+                    debug: *debug,
                 });
 
                 self.constants.insert(
@@ -433,7 +521,10 @@ impl UnitBuilder {
                 }
             }
             PrivMetaKind::UnitVariant {
-                enum_item, empty, ..
+                enum_item,
+                empty,
+                debug,
+                ..
             } => {
                 let enum_hash = Hash::type_hash(enum_item);
 
@@ -441,6 +532,7 @@ impl UnitBuilder {
                     enum_hash,
                     hash: empty.hash,
                     item: meta.item.item.clone(),
+                    debug: *debug,
                 });
 
                 if self.variant_rtti.insert(empty.hash, rtti).is_some() {
@@ -468,7 +560,10 @@ impl UnitBuilder {
                     .insert(empty.hash, signature);
             }
             PrivMetaKind::TupleVariant {
-                enum_item, tuple, ..
+                enum_item,
+                tuple,
+                debug,
+                ..
             } => {
                 let enum_hash = Hash::type_hash(enum_item);
 
@@ -476,6 +571,7 @@ impl UnitBuilder {
                     enum_hash,
                     hash: tuple.hash,
                     item: meta.item.item.clone(),
+                    debug: *debug,
                 });
 
                 if self.variant_rtti.insert(tuple.hash, rtti).is_some() {
@@ -506,7 +602,9 @@ impl UnitBuilder {
                     .functions
                     .insert(tuple.hash, signature);
             }
-            PrivMetaKind::StructVariant { enum_item, .. } => {
+            PrivMetaKind::StructVariant {
+                enum_item, debug, ..
+            } => {
                 let hash = Hash::type_hash(&meta.item.item);
                 let enum_hash = Hash::type_hash(enum_item);
 
@@ -514,6 +612,7 @@ impl UnitBuilder {
                     enum_hash,
                     hash,
                     item: meta.item.item.clone(),
+                    debug: *debug,
                 });
 
                 if self.variant_rtti.insert(hash, rtti).is_some() {
@@ -523,7 +622,7 @@ impl UnitBuilder {
                     ));
                 }
             }
-            PrivMetaKind::Enum { type_hash } => {
+            PrivMetaKind::Enum { type_hash, .. } => {
                 self.constants.insert(
                     Hash::instance_function(*type_hash, Protocol::INTO_TYPE_NAME),
                     ConstValue::String(meta.item.item.to_string()),
@@ -657,6 +756,37 @@ impl UnitBuilder {
         Ok(())
     }
 
+    /// Make the instance function `name` of the given type the
+    /// [Protocol::CALL] handler of that type, as requested with `#[call]`.
+    pub(crate) fn new_call_protocol(
+        &mut self,
+        location: Location,
+        type_hash: Hash,
+        name: &str,
+    ) -> Result<(), CompileError> {
+        let instance_fn = Hash::instance_function(type_hash, name);
+        let call_fn = Hash::instance_function(type_hash, Protocol::CALL);
+
+        let info = match self.functions.get(&instance_fn) {
+            Some(info) => *info,
+            None => {
+                return Err(CompileError::msg(
+                    location.span,
+                    "missing instance function",
+                ))
+            }
+        };
+
+        if self.functions.insert(call_fn, info).is_some() {
+            return Err(CompileError::msg(
+                location.span,
+                "only one function can be marked with #[call]",
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Try to link the unit with the context, checking that all necessary
     /// functions are provided.
     ///
@@ -673,6 +803,10 @@ impl UnitBuilder {
                 );
             }
         }
+
+        if let Err(error) = context.check_implementations() {
+            diagnostics.error(SourceId::empty(), error);
+        }
     }
 
     /// Define a prelude item.
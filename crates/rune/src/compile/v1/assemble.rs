@@ -3,7 +3,7 @@ use crate::ast::{Span, Spanned};
 use crate::collections::{HashMap, HashSet};
 use crate::compile::v1::{Assembler, Loop, Needs, Scope, Var};
 use crate::compile::{
-    CaptureMeta, CompileError, CompileErrorKind, CompileResult, Item, PrivMeta, PrivMetaKind,
+    attrs, CaptureMeta, CompileError, CompileErrorKind, CompileResult, Item, PrivMeta, PrivMetaKind,
 };
 use crate::hash::ParametersBuilder;
 use crate::parse::{Id, ParseErrorKind, Resolve};
@@ -15,6 +15,7 @@ use crate::runtime::{
 use crate::Hash;
 use rune_macros::__instrument_ast as instrument;
 use std::convert::TryFrom;
+use std::mem;
 
 /// `self` variable.
 const SELF: &str = "self";
@@ -295,6 +296,7 @@ fn pat(
             Ok(false)
         }
         ast::Pat::PatLit(p) => Ok(pat_lit(p, c, false_label, load)?),
+        ast::Pat::PatRange(p) => Ok(pat_range(p, c, false_label, load)?),
         ast::Pat::PatVec(p) => {
             pat_vec(p, c, false_label, &load)?;
             Ok(true)
@@ -307,6 +309,7 @@ fn pat(
             pat_object(object, c, false_label, &load)?;
             Ok(true)
         }
+        ast::Pat::PatOr(p) => Ok(pat_or(p, c, false_label, load)?),
         pat => Err(CompileError::new(
             pat,
             CompileErrorKind::UnsupportedPatternExpr,
@@ -314,6 +317,78 @@ fn pat(
     }
 }
 
+/// Assemble an or-pattern, which tries each alternative in order.
+///
+/// Every alternative is matched in its own scope against a copy of the loaded
+/// value, so that a failed alternative only cleans up its own locals.
+#[instrument]
+fn pat_or(
+    ast: &ast::PatOr,
+    c: &mut Assembler<'_>,
+    false_label: Label,
+    load: &dyn Fn(&mut Assembler<'_>, Needs) -> CompileResult<()>,
+) -> CompileResult<bool> {
+    let span = ast.span();
+
+    load(c, Needs::Value)?;
+    let offset = c.scopes.decl_anon(span)?;
+
+    let ok_label = c.asm.new_label("pat_or_ok");
+    let mut is_refutable = true;
+
+    for alternative in ast.alternatives() {
+        let alternative_span = alternative.span();
+        let next_label = c.asm.new_label("pat_or_next");
+
+        let scope = c.scopes.child(alternative_span)?;
+        let guard = c.scopes.push(scope);
+
+        let load = move |c: &mut Assembler<'_>, needs: Needs| {
+            if needs.value() {
+                c.asm.push(Inst::Copy { offset }, alternative_span);
+            }
+
+            Ok(())
+        };
+
+        is_refutable &= pat(alternative, c, next_label, &load)?;
+        let scope = c.scopes.pop(guard, alternative_span)?;
+
+        if let Some((name, span)) = scope.first_var() {
+            return Err(CompileError::new(
+                span,
+                CompileErrorKind::UnsupportedOrPatternBinding {
+                    name: name.to_owned(),
+                },
+            ));
+        }
+
+        // NB: the alternative matched, so clean up any anonymous locals it
+        // left behind before jumping to the shared exit.
+        if scope.local_var_count > 0 {
+            c.asm.push(
+                Inst::PopN {
+                    count: scope.local_var_count,
+                },
+                alternative_span,
+            );
+        }
+
+        c.asm.jump(ok_label, alternative_span);
+        c.asm.label(next_label)?;
+    }
+
+    if is_refutable {
+        // None of the alternatives matched.
+        c.asm.push(Inst::bool(false), span);
+        c.asm
+            .pop_and_jump_if_not(c.scopes.local_var_count(span)?, false_label, span);
+    }
+
+    c.asm.label(ok_label)?;
+    Ok(is_refutable)
+}
+
 /// Assemble a pattern literal.
 #[instrument]
 fn pat_lit(
@@ -397,6 +472,180 @@ fn pat_lit(
     Ok(true)
 }
 
+/// Assemble a range pattern.
+#[instrument]
+fn pat_range(
+    pat_range: &ast::PatRange,
+    c: &mut Assembler<'_>,
+    false_label: Label,
+    load: &dyn Fn(&mut Assembler<'_>, Needs) -> CompileResult<()>,
+) -> CompileResult<bool> {
+    let span = pat_range.span();
+    let (start, end) = pat_range_bounds(pat_range, c)?;
+
+    load(c, Needs::Value)?;
+
+    c.asm.push(
+        Inst::MatchRange {
+            start,
+            end,
+            inclusive: pat_range.is_inclusive(),
+        },
+        span,
+    );
+
+    c.asm
+        .pop_and_jump_if_not(c.scopes.local_var_count(span)?, false_label, span);
+    Ok(true)
+}
+
+/// Resolve the bounds of a range pattern, making sure that they are of the
+/// same type.
+fn pat_range_bounds(
+    pat_range: &ast::PatRange,
+    c: &mut Assembler<'_>,
+) -> CompileResult<(Option<InstValue>, Option<InstValue>)> {
+    let start = match &pat_range.start {
+        Some(start) => Some(pat_range_bound(start, c)?),
+        None => None,
+    };
+
+    let end = match &pat_range.end {
+        Some(end) => Some(pat_range_bound(end, c)?),
+        None => None,
+    };
+
+    if let (Some(start), Some(end)) = (&start, &end) {
+        if mem::discriminant(start) != mem::discriminant(end) {
+            return Err(CompileError::new(
+                pat_range,
+                CompileErrorKind::UnsupportedPatternExpr,
+            ));
+        }
+    }
+
+    Ok((start, end))
+}
+
+/// The values covered by an unconditional match arm, used to detect range
+/// patterns which can never be reached.
+#[derive(Debug, Clone, Copy)]
+enum PatCoverage {
+    /// The arm matches any value.
+    Any,
+    /// The arm matches the closed interval `start..=end` of values of the
+    /// given type.
+    Range {
+        kind: mem::Discriminant<InstValue>,
+        start: i128,
+        end: i128,
+    },
+}
+
+impl PatCoverage {
+    /// Calculate the values covered by a range pattern.
+    fn range(pat_range: &ast::PatRange, c: &mut Assembler<'_>) -> CompileResult<Option<Self>> {
+        let (start, end) = pat_range_bounds(pat_range, c)?;
+
+        let bound = match start.or(end) {
+            Some(bound) => bound,
+            None => return Ok(None),
+        };
+
+        let (min, max) = match bound {
+            InstValue::Integer(..) => (i64::MIN as i128, i64::MAX as i128),
+            InstValue::Byte(..) => (0, u8::MAX as i128),
+            InstValue::Char(..) => (0, char::MAX as i128),
+            _ => return Ok(None),
+        };
+
+        let start = start.and_then(Self::value).unwrap_or(min);
+
+        let end = match end.and_then(Self::value) {
+            Some(end) if pat_range.is_inclusive() => end,
+            Some(end) => end - 1,
+            None => max,
+        };
+
+        Ok(Some(Self::Range {
+            kind: mem::discriminant(&bound),
+            start,
+            end,
+        }))
+    }
+
+    /// Convert a range bound into a comparable value.
+    fn value(value: InstValue) -> Option<i128> {
+        Some(match value {
+            InstValue::Integer(n) => n as i128,
+            InstValue::Byte(b) => b as i128,
+            InstValue::Char(c) => c as i128,
+            _ => return None,
+        })
+    }
+
+    /// Test if this covers every value covered by `other`.
+    fn covers(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Any, _) => true,
+            (
+                Self::Range { kind, start, end },
+                Self::Range {
+                    kind: other_kind,
+                    start: other_start,
+                    end: other_end,
+                },
+            ) => kind == other_kind && start <= other_start && other_end <= end,
+            _ => false,
+        }
+    }
+}
+
+/// Resolve the literal bound of a range pattern.
+fn pat_range_bound(ast: &ast::Expr, c: &mut Assembler<'_>) -> CompileResult<InstValue> {
+    let (lit, neg) = match ast {
+        ast::Expr::Lit(expr_lit) => (&expr_lit.lit, false),
+        ast::Expr::Unary(ast::ExprUnary {
+            op: ast::UnOp::Neg(..),
+            expr,
+            ..
+        }) => match &**expr {
+            ast::Expr::Lit(expr_lit) => (&expr_lit.lit, true),
+            _ => {
+                return Err(CompileError::new(
+                    ast,
+                    CompileErrorKind::UnsupportedPatternExpr,
+                ))
+            }
+        },
+        _ => {
+            return Err(CompileError::new(
+                ast,
+                CompileErrorKind::UnsupportedPatternExpr,
+            ))
+        }
+    };
+
+    Ok(match lit {
+        ast::Lit::Number(lit_number) => {
+            let number = lit_number.resolve(resolve_context!(c.q))?;
+            InstValue::Integer(number.as_i64(ast.span(), neg)?)
+        }
+        ast::Lit::Byte(lit_byte) if !neg => {
+            InstValue::Byte(lit_byte.resolve(resolve_context!(c.q))?)
+        }
+        ast::Lit::Char(lit_char) if !neg => {
+            InstValue::Char(lit_char.resolve(resolve_context!(c.q))?)
+        }
+        _ => {
+            return Err(CompileError::new(
+                ast,
+                CompileErrorKind::UnsupportedPatternExpr,
+            ))
+        }
+    })
+}
+
 /// Assemble an [ast::Condition].
 #[instrument]
 fn condition(
@@ -439,6 +688,50 @@ fn condition(
     }
 }
 
+/// Warn if the given condition always evaluates to the same value.
+fn check_constant_condition(condition: &ast::Condition, body: Span, c: &mut Assembler<'_>) {
+    if c.allow_constant_condition {
+        return;
+    }
+
+    if let ast::Condition::Expr(e) = condition {
+        if let Some(value) = constant_bool(e) {
+            c.diagnostics
+                .constant_condition(c.source_id, e.span(), value, body);
+        }
+    }
+}
+
+/// Fold an expression made up of boolean literals into its value.
+///
+/// This intentionally doesn't look up constant items, since those are commonly
+/// used as configuration flags where a fixed value is expected.
+fn constant_bool(e: &ast::Expr) -> Option<bool> {
+    match e {
+        ast::Expr::Lit(ast::ExprLit {
+            lit: ast::Lit::Bool(b),
+            ..
+        }) => Some(b.value),
+        ast::Expr::Group(group) => constant_bool(&group.expr),
+        ast::Expr::Unary(ast::ExprUnary {
+            op: ast::UnOp::Not(..),
+            expr,
+            ..
+        }) => Some(!constant_bool(expr)?),
+        ast::Expr::Binary(binary) => {
+            let lhs = constant_bool(&binary.lhs)?;
+            let rhs = constant_bool(&binary.rhs)?;
+
+            match binary.op {
+                ast::BinOp::And(..) => Some(lhs && rhs),
+                ast::BinOp::Or(..) => Some(lhs || rhs),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Encode a vector pattern match.
 #[instrument]
 fn pat_vec(
@@ -836,6 +1129,38 @@ pub(crate) fn closure_from_block(
     Ok(())
 }
 
+/// Assemble a labeled block, which can be exited early with a labeled
+/// `break`.
+#[instrument]
+fn labeled_block(
+    ast: &ast::Block,
+    c: &mut Assembler<'_>,
+    needs: Needs,
+    label: ast::Label,
+) -> CompileResult<Asm> {
+    let span = ast.span();
+
+    let break_label = c.asm.new_label("block_break");
+    let var_count = c.scopes.total_var_count(span)?;
+
+    let guard = c.loops.push(Loop {
+        label: Some(label),
+        continue_label: break_label,
+        continue_var_count: var_count,
+        break_label,
+        break_var_count: var_count,
+        needs,
+        drop: None,
+        is_block: true,
+    });
+
+    block(ast, c, needs)?.apply(c)?;
+    drop(guard);
+
+    c.asm.label(break_label)?;
+    Ok(Asm::top(span))
+}
+
 /// Call a block.
 #[instrument]
 fn block(ast: &ast::Block, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<Asm> {
@@ -1083,12 +1408,27 @@ fn const_(
                 );
             }
         },
+        ConstValue::Result(result) => {
+            let (value, variant) = match result {
+                Ok(value) => (value, InstVariant::Ok),
+                Err(value) => (value, InstVariant::Err),
+            };
+
+            const_(span, c, value, Needs::Value)?;
+            c.asm.push(Inst::Variant { variant }, span);
+        }
         ConstValue::Vec(vec) => {
             for value in vec.iter() {
                 const_(span, c, value, Needs::Value)?;
             }
 
-            c.asm.push(Inst::Vec { count: vec.len() }, span);
+            c.asm.push(
+                Inst::Vec {
+                    count: vec.len(),
+                    capacity: vec.len(),
+                },
+                span,
+            );
         }
         ConstValue::Tuple(tuple) => {
             for value in tuple.iter() {
@@ -1111,6 +1451,21 @@ fn const_(
 
             c.asm.push(Inst::Object { slot }, span);
         }
+        ConstValue::Variant(rtti, args) => {
+            for value in args.iter() {
+                const_(span, c, value, Needs::Value)?;
+            }
+
+            // NB: the variant is built through its constructor.
+            c.asm.push_with_comment(
+                Inst::Call {
+                    hash: rtti.hash,
+                    args: args.len(),
+                },
+                span,
+                rtti.item.to_string(),
+            );
+        }
     }
 
     Ok(())
@@ -1150,6 +1505,7 @@ fn expr(ast: &ast::Expr, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<A
         ast::Expr::ForceSemi(e) => expr(&e.expr, c, needs)?,
         ast::Expr::Tuple(e) => expr_tuple(e, c, needs)?,
         ast::Expr::Vec(e) => expr_vec(e, c, needs)?,
+        ast::Expr::Repeat(e) => expr_repeat(e, c, needs)?,
         ast::Expr::Object(e) => expr_object(e, c, needs)?,
         ast::Expr::Range(e) => expr_range(e, c, needs)?,
         ast::Expr::MacroCall(expr_call_macro) => {
@@ -1491,6 +1847,10 @@ fn expr_block(ast: &ast::ExprBlock, c: &mut Assembler<'_>, needs: Needs) -> Comp
     let span = ast.span();
 
     if ast.async_token.is_none() && ast.const_token.is_none() {
+        if let Some((label, _)) = ast.label {
+            return labeled_block(&ast.block, c, needs, label);
+        }
+
         return block(&ast.block, c, needs);
     }
 
@@ -1555,30 +1915,24 @@ fn expr_block(ast: &ast::ExprBlock, c: &mut Assembler<'_>, needs: Needs) -> Comp
 fn expr_break(ast: &ast::ExprBreak, c: &mut Assembler<'_>, _: Needs) -> CompileResult<Asm> {
     let span = ast.span();
 
-    let current_loop = match c.loops.last() {
-        Some(current_loop) => current_loop,
-        None => {
-            return Err(CompileError::new(
-                span,
-                CompileErrorKind::BreakOutsideOfLoop,
-            ));
-        }
+    let (last_loop, to_drop) = match &ast.label {
+        Some(label) => c.loops.walk_until_label(resolve_context!(c.q), label)?,
+        None => match c.loops.last() {
+            Some(current_loop) => (current_loop, current_loop.drop.into_iter().collect()),
+            None => {
+                return Err(CompileError::new(
+                    span,
+                    CompileErrorKind::BreakOutsideOfLoop,
+                ));
+            }
+        },
     };
 
-    let (last_loop, to_drop, has_value) = if let Some(e) = ast.expr.as_deref() {
-        match e {
-            ast::ExprBreakValue::Expr(e) => {
-                expr(e, c, current_loop.needs)?.apply(c)?;
-                (current_loop, current_loop.drop.into_iter().collect(), true)
-            }
-            ast::ExprBreakValue::Label(label) => {
-                let (last_loop, to_drop) =
-                    c.loops.walk_until_label(resolve_context!(c.q), label)?;
-                (last_loop, to_drop, false)
-            }
-        }
+    let has_value = if let Some(e) = ast.expr.as_deref() {
+        expr(e, c, last_loop.needs)?.apply(c)?;
+        true
     } else {
-        (current_loop, current_loop.drop.into_iter().collect(), false)
+        false
     };
 
     // Drop loop temporary. Typically an iterator.
@@ -1994,6 +2348,16 @@ fn expr_continue(ast: &ast::ExprContinue, c: &mut Assembler<'_>, _: Needs) -> Co
 
     let last_loop = if let Some(label) = &ast.label {
         let (last_loop, _) = c.loops.walk_until_label(resolve_context!(c.q), label)?;
+
+        if last_loop.is_block {
+            return Err(CompileError::new(
+                label,
+                CompileErrorKind::MissingLoopLabel {
+                    label: label.resolve(resolve_context!(c.q))?.into(),
+                },
+            ));
+        }
+
         last_loop
     } else {
         current_loop
@@ -2193,6 +2557,7 @@ fn expr_for(ast: &ast::ExprFor, c: &mut Assembler<'_>, needs: Needs) -> CompileR
         break_var_count,
         needs,
         drop: Some(iter_offset),
+        is_block: false,
     });
 
     // Use the memoized loop variable.
@@ -2289,6 +2654,12 @@ fn expr_if(ast: &ast::ExprIf, c: &mut Assembler<'_>, needs: Needs) -> CompileRes
     let then_label = c.asm.new_label("if_then");
     let end_label = c.asm.new_label("if_end");
 
+    check_constant_condition(&ast.condition, ast.block.span(), c);
+
+    for branch in &ast.expr_else_ifs {
+        check_constant_condition(&branch.condition, branch.block.span(), c);
+    }
+
     let mut branches = Vec::new();
     let then_scope = condition(&ast.condition, c, then_label)?;
 
@@ -2418,6 +2789,7 @@ fn expr_loop(ast: &ast::ExprLoop, c: &mut Assembler<'_>, needs: Needs) -> Compil
         break_var_count: var_count,
         needs,
         drop: None,
+        is_block: false,
     });
 
     c.asm.label(continue_label)?;
@@ -2440,10 +2812,38 @@ fn expr_match(ast: &ast::ExprMatch, c: &mut Assembler<'_>, needs: Needs) -> Comp
 
     let end_label = c.asm.new_label("match_end");
     let mut branches = Vec::new();
+    let mut exhaustive = false;
+    let mut covered = Vec::<(Span, PatCoverage)>::new();
 
     for (branch, _) in &ast.branches {
         let span = branch.span();
 
+        let coverage = match &branch.pat {
+            ast::Pat::PatIgnore(..) => Some(PatCoverage::Any),
+            ast::Pat::PatRange(pat_range) => {
+                let coverage = PatCoverage::range(pat_range, c)?;
+
+                if let Some(coverage) = &coverage {
+                    let cause = covered
+                        .iter()
+                        .find(|(_, other)| other.covers(coverage))
+                        .map(|(cause, _)| *cause);
+
+                    if let Some(cause) = cause {
+                        c.diagnostics
+                            .unreachable_pattern(c.source_id, pat_range.span(), cause);
+                    }
+                }
+
+                coverage
+            }
+            _ => None,
+        };
+
+        if let (Some(coverage), None) = (coverage, &branch.condition) {
+            covered.push((branch.pat.span(), coverage));
+        }
+
         let branch_label = c.asm.new_label("match_branch");
         let match_false = c.asm.new_label("match_false");
 
@@ -2458,7 +2858,9 @@ fn expr_match(ast: &ast::ExprMatch, c: &mut Assembler<'_>, needs: Needs) -> Comp
             Ok(())
         };
 
-        pat(&branch.pat, c, match_false, &load)?;
+        if !pat(&branch.pat, c, match_false, &load)? && branch.condition.is_none() {
+            exhaustive = true;
+        }
 
         let scope = if let Some((_, condition)) = &branch.condition {
             let span = condition.span();
@@ -2485,6 +2887,10 @@ fn expr_match(ast: &ast::ExprMatch, c: &mut Assembler<'_>, needs: Needs) -> Comp
         branches.push((branch_label, scope));
     }
 
+    if !exhaustive && !c.allow_non_exhaustive_match {
+        match_missing_variants(ast, c)?;
+    }
+
     // what to do in case nothing matches and the pattern doesn't have any
     // default match branch.
     if needs.value() {
@@ -2516,6 +2922,193 @@ fn expr_match(ast: &ast::ExprMatch, c: &mut Assembler<'_>, needs: Needs) -> Comp
     Ok(Asm::top(span))
 }
 
+/// Warn if the branches of a match only cover some of the variants of an
+/// enum.
+///
+/// This is conservative: a variant is covered by a branch without a condition
+/// whose subpatterns always match, and any branch which isn't a variant
+/// pattern disables the check.
+fn match_missing_variants(ast: &ast::ExprMatch, c: &mut Assembler<'_>) -> CompileResult<()> {
+    let mut enum_item = None;
+    let mut covered = HashSet::new();
+
+    for (branch, _) in &ast.branches {
+        // NB: a guarded wildcard doesn't cover anything.
+        if branch.condition.is_some() && matches!(branch.pat, ast::Pat::PatIgnore(..)) {
+            continue;
+        }
+
+        let mut paths = Vec::new();
+
+        if !match_variant_paths(&branch.pat, &mut paths) {
+            return Ok(());
+        }
+
+        for (path, items) in paths {
+            let span = path.span();
+            let named = c.convert_path(path)?;
+
+            let meta = match c.try_lookup_meta(span, &named.item)? {
+                Some(meta) => meta,
+                // NB: a guarded binding doesn't cover anything, and an
+                // unguarded one makes the match exhaustive.
+                None if branch.condition.is_some() && path.try_as_ident().is_some() => continue,
+                None => return Ok(()),
+            };
+
+            let item = match &meta.kind {
+                PrivMetaKind::UnitVariant { enum_item, .. } => enum_item,
+                PrivMetaKind::TupleVariant { enum_item, .. } => enum_item,
+                PrivMetaKind::StructVariant { enum_item, .. } => enum_item,
+                _ => return Ok(()),
+            };
+
+            match &enum_item {
+                Some((_, existing)) if existing != item => return Ok(()),
+                Some(..) => (),
+                None => enum_item = Some((span, item.clone())),
+            }
+
+            if branch.condition.is_some() || !pats_irrefutable(items, c)? {
+                continue;
+            }
+
+            if let Some(last) = meta.item.item.last() {
+                covered.insert(last.to_string());
+            }
+        }
+    }
+
+    let (span, enum_item) = match enum_item {
+        Some(enum_item) => enum_item,
+        None => return Ok(()),
+    };
+
+    let variants = match c.lookup_meta(span, &enum_item)?.kind {
+        PrivMetaKind::Enum { variants, .. } => variants,
+        _ => return Ok(()),
+    };
+
+    let missing = variants
+        .into_iter()
+        .filter(|variant| !covered.contains(&**variant))
+        .collect::<Box<[_]>>();
+
+    if !missing.is_empty() {
+        c.diagnostics
+            .non_exhaustive_match(c.source_id, ast.span(), missing);
+    }
+
+    Ok(())
+}
+
+/// Collect the paths of the variants matched by the given pattern, together
+/// with their subpatterns. Alternatives of or-patterns are expanded.
+///
+/// Returns `false` if the pattern isn't made up of paths.
+fn match_variant_paths<'a>(
+    pat: &'a ast::Pat,
+    out: &mut Vec<(&'a ast::Path, Vec<&'a ast::Pat>)>,
+) -> bool {
+    match pat {
+        ast::Pat::PatPath(p) => {
+            out.push((&p.path, Vec::new()));
+        }
+        ast::Pat::PatTuple(ast::PatTuple {
+            path: Some(path),
+            items,
+            ..
+        }) => {
+            out.push((path, items.iter().map(|(pat, _)| pat).collect()));
+        }
+        ast::Pat::PatObject(ast::PatObject {
+            ident: ast::ObjectIdent::Named(path),
+            items,
+            ..
+        }) => {
+            out.push((path, items.iter().map(|(pat, _)| pat).collect()));
+        }
+        ast::Pat::PatOr(pat_or) => {
+            let alternatives =
+                std::iter::once(&*pat_or.first).chain(pat_or.rest.iter().map(|(_, pat)| pat));
+
+            for pat in alternatives {
+                if !match_variant_paths(pat, out) {
+                    return false;
+                }
+            }
+        }
+        _ => return false,
+    }
+
+    true
+}
+
+/// Test if all of the given patterns match any value they're applied to.
+fn pats_irrefutable<'a, I>(pats: I, c: &mut Assembler<'_>) -> CompileResult<bool>
+where
+    I: IntoIterator<Item = &'a ast::Pat>,
+{
+    for pat in pats {
+        if !pat_irrefutable(pat, c)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Test if the given pattern matches any value it's applied to.
+fn pat_irrefutable(pat: &ast::Pat, c: &mut Assembler<'_>) -> CompileResult<bool> {
+    let (path, items) = match pat {
+        ast::Pat::PatIgnore(..) | ast::Pat::PatRest(..) => return Ok(true),
+        ast::Pat::PatBinding(binding) => return pat_irrefutable(&binding.pat, c),
+        ast::Pat::PatPath(p) => (&p.path, None),
+        ast::Pat::PatTuple(ast::PatTuple { path, items, .. }) => match path {
+            Some(path) => (
+                path,
+                Some(items.iter().map(|(pat, _)| pat).collect::<Vec<_>>()),
+            ),
+            None => return pats_irrefutable(items.iter().map(|(pat, _)| pat), c),
+        },
+        ast::Pat::PatObject(ast::PatObject {
+            ident: ast::ObjectIdent::Named(path),
+            items,
+            ..
+        }) => (path, Some(items.iter().map(|(pat, _)| pat).collect())),
+        ast::Pat::PatOr(pat_or) => {
+            let alternatives =
+                std::iter::once(&*pat_or.first).chain(pat_or.rest.iter().map(|(_, pat)| pat));
+
+            for pat in alternatives {
+                if pat_irrefutable(pat, c)? {
+                    return Ok(true);
+                }
+            }
+
+            return Ok(false);
+        }
+        _ => return Ok(false),
+    };
+
+    let span = path.span();
+    let named = c.convert_path(path)?;
+
+    // NB: a path which doesn't resolve to an item is a binding, and a path
+    // which does only always matches if it's a struct.
+    match (c.try_lookup_meta(span, &named.item)?, items) {
+        (None, None) => Ok(true),
+        (None, Some(..)) => Ok(false),
+        (Some(meta), None) => Ok(matches!(meta.kind, PrivMetaKind::UnitStruct { .. })),
+        (Some(meta), Some(items)) => match &meta.kind {
+            PrivMetaKind::TupleStruct { .. } | PrivMetaKind::Struct { .. } => {
+                pats_irrefutable(items, c)
+            }
+            _ => Ok(false),
+        },
+    }
+}
+
 /// Compile a literal object.
 #[instrument]
 fn expr_object(ast: &ast::ExprObject, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<Asm> {
@@ -2580,6 +3173,16 @@ fn expr_object(ast: &ast::ExprObject, c: &mut Assembler<'_>, needs: Needs) -> Co
                     let hash = Hash::type_hash(&meta.item.item);
                     c.asm.push(Inst::Struct { hash, slot }, span);
                 }
+                PrivMetaKind::StructVariant { .. }
+                    if c.context.lookup_meta(&meta.item.item).is_some() =>
+                {
+                    // NB: struct variants of native enums can only be matched
+                    // on, not constructed.
+                    return Err(CompileError::new(
+                        span,
+                        CompileErrorKind::UnsupportedLitObject { meta: meta.info() },
+                    ));
+                }
                 PrivMetaKind::StructVariant { st, .. } => {
                     check_object_fields(&st.fields, check_keys, span, &meta.item.item)?;
 
@@ -2648,7 +3251,15 @@ fn path(ast: &ast::Path, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<A
     let span = ast.span();
 
     if let Some(ast::PathKind::SelfValue) = ast.as_kind() {
-        let var = c.scopes.get_var(c.q.visitor, SELF, c.source_id, span)?;
+        let var = match c.scopes.try_get_var(c.q.visitor, SELF, c.source_id, span)? {
+            Some(var) => var,
+            None => {
+                return Err(CompileError::new(
+                    span,
+                    CompileErrorKind::UnsupportedSelfValue,
+                ))
+            }
+        };
 
         if needs.value() {
             var.copy(c, span, SELF);
@@ -2675,6 +3286,17 @@ fn path(ast: &ast::Path, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<A
         return Ok(Asm::top(span));
     }
 
+    if let Some(local) = named.as_local() {
+        if c.q.unit.is_global(local) {
+            if needs.value() {
+                let slot = c.q.unit.new_static_string(span, local)?;
+                c.asm.push(Inst::LoadGlobal { slot }, span);
+            }
+
+            return Ok(Asm::top(span));
+        }
+    }
+
     if let (Needs::Value, Some(local)) = (needs, named.as_local()) {
         // light heuristics, treat it as a type error in case the
         // first character is uppercase.
@@ -3002,28 +3624,8 @@ fn expr_unary(ast: &ast::ExprUnary, c: &mut Assembler<'_>, needs: Needs) -> Comp
 
     if let (ast::UnOp::Neg(..), ast::Expr::Lit(expr_lit)) = (ast.op, &*ast.expr) {
         if let ast::Lit::Number(n) = &expr_lit.lit {
-            match n.resolve(resolve_context!(c.q))? {
-                ast::Number::Float(n) => {
-                    c.asm.push(Inst::float(-n), span);
-                }
-                ast::Number::Integer(int) => {
-                    use num::ToPrimitive as _;
-                    use std::ops::Neg as _;
-
-                    let n = match int.neg().to_i64() {
-                        Some(n) => n,
-                        None => {
-                            return Err(CompileError::new(
-                                span,
-                                ParseErrorKind::BadNumberOutOfBounds,
-                            ));
-                        }
-                    };
-
-                    c.asm.push(Inst::integer(n), span);
-                }
-            }
-
+            let inst = number_inst(n, c, span, true)?;
+            c.asm.push(inst, span);
             return Ok(Asm::top(span));
         }
     }
@@ -3059,15 +3661,38 @@ fn expr_unary(ast: &ast::ExprUnary, c: &mut Assembler<'_>, needs: Needs) -> Comp
 fn expr_vec(ast: &ast::ExprVec, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<Asm> {
     let span = ast.span();
 
-    let count = ast.items.len();
-
-    for (e, _) in &ast.items {
-        expr(e, c, Needs::Value)?.apply(c)?;
-        c.scopes.decl_anon(e.span())?;
+    // Whether the vector being built is on the stack, and the number of
+    // elements pushed after it which still have to be added to it.
+    let mut is_started = false;
+    let mut count = 0;
+
+    // NB: the vector is allocated up front with room for every item which
+    // isn't a spread, since their number is known at compile time.
+    let capacity = ast
+        .items
+        .iter()
+        .filter(|(item, _)| matches!(item, ast::ExprVecItem::Expr(..)))
+        .count();
+
+    for (item, _) in &ast.items {
+        match item {
+            ast::ExprVecItem::Expr(e) => {
+                expr(e, c, Needs::Value)?.apply(c)?;
+                c.scopes.decl_anon(e.span())?;
+                count += 1;
+            }
+            ast::ExprVecItem::Spread(_, e) => {
+                expr_vec_flush(c, span, &mut is_started, &mut count, capacity)?;
+                expr(e, c, Needs::Value)?.apply(c)?;
+                c.scopes.decl_anon(e.span())?;
+                c.asm.push(Inst::VecExtend, item.span());
+                c.scopes.undecl_anon(item.span(), 1)?;
+            }
+        }
     }
 
-    c.asm.push(Inst::Vec { count }, span);
-    c.scopes.undecl_anon(span, ast.items.len())?;
+    expr_vec_flush(c, span, &mut is_started, &mut count, capacity)?;
+    c.scopes.undecl_anon(span, 1)?;
 
     // Evaluate the expressions one by one, then pop them to cause any
     // side effects (without creating an object).
@@ -3079,6 +3704,62 @@ fn expr_vec(ast: &ast::ExprVec, c: &mut Assembler<'_>, needs: Needs) -> CompileR
     Ok(Asm::top(span))
 }
 
+/// Add the elements pushed since the last spread item to the vector being
+/// built, constructing it if it's not on the stack yet.
+fn expr_vec_flush(
+    c: &mut Assembler<'_>,
+    span: Span,
+    is_started: &mut bool,
+    count: &mut usize,
+    capacity: usize,
+) -> CompileResult<()> {
+    if *is_started && *count == 0 {
+        return Ok(());
+    }
+
+    let capacity = if *is_started { *count } else { capacity };
+
+    c.asm.push(
+        Inst::Vec {
+            count: *count,
+            capacity,
+        },
+        span,
+    );
+    c.scopes.undecl_anon(span, *count)?;
+    c.scopes.decl_anon(span)?;
+
+    if std::mem::replace(is_started, true) {
+        c.asm.push(Inst::VecExtend, span);
+        c.scopes.undecl_anon(span, 1)?;
+    }
+
+    *count = 0;
+    Ok(())
+}
+
+/// Assemble a repeat vector expression.
+#[instrument]
+fn expr_repeat(ast: &ast::ExprRepeat, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<Asm> {
+    let span = ast.span();
+
+    expr(&ast.expr, c, Needs::Value)?.apply(c)?;
+    c.scopes.decl_anon(ast.expr.span())?;
+
+    expr(&ast.count, c, Needs::Value)?.apply(c)?;
+    c.scopes.decl_anon(ast.count.span())?;
+
+    c.asm.push(Inst::VecRepeat, span);
+    c.scopes.undecl_anon(span, 2)?;
+
+    if !needs.value() {
+        c.diagnostics.not_used(c.source_id, span, c.context());
+        c.asm.push(Inst::Pop, span);
+    }
+
+    Ok(Asm::top(span))
+}
+
 /// Assemble a while loop.
 #[instrument]
 fn expr_while(ast: &ast::ExprWhile, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<Asm> {
@@ -3099,8 +3780,11 @@ fn expr_while(ast: &ast::ExprWhile, c: &mut Assembler<'_>, needs: Needs) -> Comp
         break_var_count: var_count,
         needs,
         drop: None,
+        is_block: false,
     });
 
+    check_constant_condition(&ast.condition, ast.body.span(), c);
+
     c.asm.label(continue_label)?;
 
     let then_scope = condition(&ast.condition, c, then_label)?;
@@ -3152,6 +3836,14 @@ pub(crate) fn fn_from_item_fn(
 ) -> CompileResult<()> {
     let span = ast.span();
 
+    let mut attributes = attrs::Attributes::new(ast.attributes.clone());
+
+    if let Some((_, allow)) = attributes.try_parse::<attrs::Allow>(resolve_context!(c.q))? {
+        let lints = allow.lints(resolve_context!(c.q))?;
+        c.allow_non_exhaustive_match = lints.non_exhaustive_match;
+        c.allow_constant_condition = lints.constant_condition;
+    }
+
     let mut patterns = Vec::new();
     let mut first = true;
 
@@ -3259,8 +3951,6 @@ fn lit_str(ast: &ast::LitStr, c: &mut Assembler<'_>, needs: Needs) -> CompileRes
 /// Assemble a literal number.
 #[instrument]
 fn lit_number(ast: &ast::LitNumber, c: &mut Assembler<'_>, needs: Needs) -> CompileResult<Asm> {
-    use num::ToPrimitive;
-
     let span = ast.span();
 
     // Elide the entire literal if it's not needed.
@@ -3270,28 +3960,46 @@ fn lit_number(ast: &ast::LitNumber, c: &mut Assembler<'_>, needs: Needs) -> Comp
     }
 
     // NB: don't encode unecessary literal.
+    let inst = number_inst(ast, c, span, false)?;
+    c.asm.push(inst, span);
+    Ok(Asm::top(span))
+}
+
+/// Construct the instruction which loads the given number literal, optionally
+/// negated, while taking its type suffix into account.
+fn number_inst(
+    ast: &ast::LitNumber,
+    c: &mut Assembler<'_>,
+    span: Span,
+    neg: bool,
+) -> CompileResult<Inst> {
+    use num::ToPrimitive as _;
+    use std::ops::Neg as _;
+
     let number = ast.resolve(resolve_context!(c.q))?;
+    let suffix = ast.suffix();
 
-    match number {
-        ast::Number::Float(number) => {
-            c.asm.push(Inst::float(number), span);
-        }
-        ast::Number::Integer(number) => {
-            let n = match number.to_i64() {
-                Some(n) => n,
-                None => {
-                    return Err(CompileError::new(
-                        span,
-                        ParseErrorKind::BadNumberOutOfBounds,
-                    ));
-                }
-            };
+    if let Some(suffix) = suffix {
+        suffix.check(&number, span, neg)?;
+    }
 
-            c.asm.push(Inst::integer(n), span);
-        }
+    let number = match number {
+        ast::Number::Float(n) => return Ok(Inst::float(if neg { -n } else { n })),
+        ast::Number::Integer(n) if neg => n.neg(),
+        ast::Number::Integer(n) => n,
+    };
+
+    if let (Some(ast::NumberSuffix::U8), Some(b)) = (suffix, number.to_u8()) {
+        return Ok(Inst::byte(b));
     }
 
-    Ok(Asm::top(span))
+    match number.to_i64() {
+        Some(n) => Ok(Inst::integer(n)),
+        None => Err(CompileError::new(
+            span,
+            ParseErrorKind::BadNumberOutOfBounds,
+        )),
+    }
 }
 
 /// Assemble a local expression.
@@ -3305,6 +4013,10 @@ fn local(ast: &ast::Local, c: &mut Assembler<'_>, needs: Needs) -> CompileResult
         Ok(())
     };
 
+    if let Some(else_) = &ast.else_ {
+        return local_else(ast, else_, c, needs, &load);
+    }
+
     let false_label = c.asm.new_label("let_panic");
 
     if pat(&ast.pat, c, false_label, &load)? {
@@ -3332,6 +4044,135 @@ fn local(ast: &ast::Local, c: &mut Assembler<'_>, needs: Needs) -> CompileResult
     Ok(Asm::top(span))
 }
 
+/// Assemble a local expression with a diverging else branch, like `let
+/// Some(a) = b else { return; };`.
+fn local_else(
+    ast: &ast::Local,
+    else_: &ast::LocalElse,
+    c: &mut Assembler<'_>,
+    needs: Needs,
+    load: &dyn Fn(&mut Assembler<'_>, Needs) -> CompileResult<()>,
+) -> CompileResult<Asm> {
+    let span = ast.span();
+
+    if !block_diverges(&else_.block, c)? {
+        return Err(CompileError::new(
+            &*else_.block,
+            CompileErrorKind::LetElseMustDiverge,
+        ));
+    }
+
+    // The else branch must not see the bindings introduced by the pattern, so
+    // we keep a copy of the scope as it was before the pattern is assembled.
+    let parent = c.scopes.snapshot(span)?;
+
+    let false_label = c.asm.new_label("let_else");
+
+    if pat(&ast.pat, c, false_label, load)? {
+        let ok_label = c.asm.new_label("let_ok");
+        c.asm.jump(ok_label, span);
+        c.asm.label(false_label)?;
+
+        let current = c.scopes.replace_last(span, parent)?;
+        block(&else_.block, c, Needs::None)?.apply(c)?;
+        c.scopes.replace_last(span, current)?;
+
+        // NB: the else block has been checked to diverge, so this is never
+        // reached unless the check is wrong.
+        c.asm.push(
+            Inst::Panic {
+                reason: PanicReason::UnmatchedPattern,
+            },
+            span,
+        );
+
+        c.asm.label(ok_label)?;
+    }
+
+    // If a value is needed for a let expression, it is evaluated as a unit.
+    if needs.value() {
+        c.asm.push(Inst::unit(), span);
+    }
+
+    Ok(Asm::top(span))
+}
+
+/// Test if the last statement of a block unconditionally diverges, through
+/// `return`, `break`, `continue`, a call to `::std::panic`, or branches which
+/// all diverge.
+fn block_diverges(ast: &ast::Block, c: &mut Assembler<'_>) -> CompileResult<bool> {
+    match ast.statements.last() {
+        Some(ast::Stmt::Expr(expr, _)) => expr_diverges(expr, c),
+        _ => Ok(false),
+    }
+}
+
+/// Test if the given expression unconditionally diverges.
+fn expr_diverges(ast: &ast::Expr, c: &mut Assembler<'_>) -> CompileResult<bool> {
+    Ok(match ast {
+        ast::Expr::Return(..) | ast::Expr::Break(..) | ast::Expr::Continue(..) => true,
+        ast::Expr::Call(call) => match &*call.expr {
+            ast::Expr::Path(path) => calls_panic(path, c)?,
+            _ => false,
+        },
+        ast::Expr::Block(b) => block_diverges(&b.block, c)?,
+        ast::Expr::If(expr_if) => {
+            let expr_else = match &expr_if.expr_else {
+                Some(expr_else) => expr_else,
+                None => return Ok(false),
+            };
+
+            if !block_diverges(&expr_if.block, c)? || !block_diverges(&expr_else.block, c)? {
+                return Ok(false);
+            }
+
+            for else_if in &expr_if.expr_else_ifs {
+                if !block_diverges(&else_if.block, c)? {
+                    return Ok(false);
+                }
+            }
+
+            true
+        }
+        ast::Expr::Match(expr_match) => {
+            if expr_match.branches.is_empty() {
+                return Ok(false);
+            }
+
+            for (branch, _) in &expr_match.branches {
+                if !expr_diverges(&branch.body, c)? {
+                    return Ok(false);
+                }
+            }
+
+            true
+        }
+        _ => false,
+    })
+}
+
+/// Test if the given path refers to `::std::panic`, as opposed to a local
+/// variable or another function which happens to be called `panic`.
+fn calls_panic(path: &ast::Path, c: &mut Assembler<'_>) -> CompileResult<bool> {
+    let named = c.convert_path(path)?;
+
+    if let Some(name) = named.as_local() {
+        if c.scopes
+            .try_get_var(c.q.visitor, name, c.source_id, path.span())?
+            .is_some()
+        {
+            return Ok(false);
+        }
+    }
+
+    let meta = match c.try_lookup_meta(path.span(), &named.item)? {
+        Some(meta) => meta,
+        None => return Ok(false),
+    };
+
+    Ok(meta.item.item == Item::with_crate_item("std", &["panic"]))
+}
+
 /// Test if the given pattern is open or not.
 fn pat_items_count<'a, I: 'a, U: 'a>(items: I) -> Result<(bool, usize), CompileError>
 where
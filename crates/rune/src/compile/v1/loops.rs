@@ -18,7 +18,7 @@ impl Drop for LoopGuard {
     }
 }
 
-/// Loops we are inside.
+/// Loops and labeled blocks we are inside.
 #[derive(Clone, Copy)]
 pub(crate) struct Loop {
     /// The optional label of the start of the loop.
@@ -35,6 +35,9 @@ pub(crate) struct Loop {
     pub(crate) needs: Needs,
     /// Locals to drop when breaking.
     pub(crate) drop: Option<usize>,
+    /// If this is a labeled block, which can only be broken out of with a
+    /// label and can't be continued.
+    pub(crate) is_block: bool,
 }
 
 pub(crate) struct Loops {
@@ -49,9 +52,14 @@ impl Loops {
         }
     }
 
-    /// Get the last loop context.
+    /// Get the last loop context, skipping over labeled blocks.
     pub(crate) fn last(&self) -> Option<Loop> {
-        self.loops.borrow().last().copied()
+        self.loops
+            .borrow()
+            .iter()
+            .rev()
+            .find(|l| !l.is_block)
+            .copied()
     }
 
     /// Push loop information.
@@ -51,6 +51,10 @@ pub(crate) struct Assembler<'a> {
     pub(crate) options: &'a Options,
     /// Compilation warnings.
     pub(crate) diagnostics: &'a mut Diagnostics,
+    /// If warnings about non-exhaustive matches are suppressed.
+    pub(crate) allow_non_exhaustive_match: bool,
+    /// If warnings about constant conditions are suppressed.
+    pub(crate) allow_constant_condition: bool,
 }
 
 impl<'a> Assembler<'a> {
@@ -188,7 +192,9 @@ impl<'a> Assembler<'a> {
             scopes: Default::default(),
             module: &from.module,
             item: &from.item,
+            start: self.q.start_const_eval(),
             q: self.q.borrow(),
+            calls: Vec::new(),
         };
 
         for (ir, name) in compiled {
@@ -122,6 +122,15 @@ impl Scope {
         offset
     }
 
+    /// Get the name and span of the first named variable declared in this
+    /// scope, if any.
+    pub(crate) fn first_var(&self) -> Option<(&str, Span)> {
+        self.locals
+            .iter()
+            .map(|(name, var)| (name.as_str(), var.span))
+            .min_by_key(|(_, span)| *span)
+    }
+
     /// Declare an anonymous variable.
     ///
     /// This is used if cleanup is required in the middle of an expression.
@@ -337,6 +346,16 @@ impl Scopes {
         Ok(scope)
     }
 
+    /// Get a copy of the last scope.
+    pub(crate) fn snapshot(&self, span: Span) -> CompileResult<Scope> {
+        Ok(self.last(span)?.clone())
+    }
+
+    /// Replace the last scope, returning the scope that was replaced.
+    pub(crate) fn replace_last(&mut self, span: Span, scope: Scope) -> CompileResult<Scope> {
+        Ok(std::mem::replace(self.last_mut(span)?, scope))
+    }
+
     /// Construct a new child scope and return its guard.
     pub(crate) fn push_child(&mut self, span: Span) -> CompileResult<ScopeGuard> {
         let scope = self.last(span)?.child();
@@ -6,7 +6,7 @@ use crate::diagnostics::{
 };
 use crate::parse::ResolveErrorKind;
 use crate::query::QueryErrorKind;
-use crate::runtime::{Unit, VmError, VmErrorKind};
+use crate::runtime::{AccessError, Unit, VmError, VmErrorKind};
 use crate::{Source, Diagnostics, SourceId, Sources};
 use crate::ast::{Span, Spanned};
 use std::convert::TryInto;
@@ -15,6 +15,7 @@ use std::fmt;
 use std::fmt::Write;
 use std::io;
 use thiserror::Error;
+use serde::Serialize;
 use codespan_reporting::diagnostic as d;
 use codespan_reporting::term;
 use codespan_reporting::term::termcolor::WriteColor;
@@ -37,6 +38,9 @@ pub enum EmitError {
     /// Codespan reporting error.
     #[error("codespan reporting error")]
     CodespanReporting(#[from] codespan_reporting::files::Error),
+    /// JSON serialization error.
+    #[error("JSON serialization error")]
+    Json(#[from] serde_json::Error),
 }
 
 impl Diagnostics {
@@ -69,8 +73,187 @@ impl Diagnostics {
             }
         }
 
+        if self.truncated_errors() > 0 {
+            let diagnostic = d::Diagnostic::note().with_message(format!(
+                "{} more errors were not reported",
+                self.truncated_errors()
+            ));
+
+            term::emit(out, &config, sources, &diagnostic)?;
+        }
+
         Ok(())
     }
+
+    /// Write diagnostics as a single machine-readable JSON document.
+    ///
+    /// The document has the following format, where `version` is currently
+    /// [JSON_DIAGNOSTICS_VERSION] and is bumped on any incompatible change:
+    ///
+    /// ```text
+    /// {
+    ///     "version": 1,
+    ///     "truncated": number,
+    ///     "diagnostics": [
+    ///         {
+    ///             "kind": "error" | "warning" | "note",
+    ///             "message": string,
+    ///             "source_id": number,
+    ///             "span_start": number,
+    ///             "span_end": number,
+    ///             "filename": string | null,
+    ///             "labels": [
+    ///                 {
+    ///                     "primary": bool,
+    ///                     "source_id": number,
+    ///                     "span": { "start": number, "end": number },
+    ///                     "message": string
+    ///                 }
+    ///             ],
+    ///             "notes": [string]
+    ///         }
+    ///     ]
+    /// }
+    /// ```
+    ///
+    /// All numbers are unsigned 32-bit integers. Spans are byte offsets into
+    /// the source identified by `source_id`, and are empty at offset `0` for
+    /// diagnostics which don't point to a specific location. `truncated` is the
+    /// number of errors which were not reported because of
+    /// [Diagnostics::with_max_errors].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Diagnostics, Source, Sources};
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(Source::new("entry", "pub fn main() { missing }"));
+    ///
+    /// let mut diagnostics = Diagnostics::new();
+    ///
+    /// let _ = rune::prepare(&mut sources)
+    ///     .with_diagnostics(&mut diagnostics)
+    ///     .build();
+    ///
+    /// let mut out = Vec::new();
+    /// diagnostics.emit_json(&mut out, &sources)?;
+    ///
+    /// let output = String::from_utf8(out)?;
+    /// assert!(output.starts_with("{\"version\":1,"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn emit_json<W>(&self, writer: &mut W, sources: &Sources) -> Result<(), EmitError>
+    where
+        W: io::Write,
+    {
+        let mut diagnostics = Vec::new();
+
+        for diagnostic in self.diagnostics() {
+            let (source_id, span, diagnostic) = match diagnostic {
+                Diagnostic::Fatal(e) => (e.source_id(), e.span(), fatal_diagnostic(e, sources)?),
+                Diagnostic::Warning(w) => (w.source_id(), Some(w.span()), warning_diagnostic(w, sources)?),
+            };
+
+            diagnostics.push(JsonDiagnostic::new(sources, source_id, span, diagnostic));
+        }
+
+        let document = JsonDocument {
+            version: JSON_DIAGNOSTICS_VERSION,
+            truncated: json_u32(self.truncated_errors()),
+            diagnostics,
+        };
+
+        serde_json::to_writer(&mut *writer, &document)?;
+        writeln!(writer)?;
+        Ok(())
+    }
+}
+
+/// The version of the format written by [Diagnostics::emit_json].
+pub const JSON_DIAGNOSTICS_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    version: u32,
+    truncated: u32,
+    diagnostics: Vec<JsonDiagnostic<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    kind: &'static str,
+    message: String,
+    source_id: u32,
+    span_start: u32,
+    span_end: u32,
+    filename: Option<&'a str>,
+    labels: Vec<JsonLabel>,
+    notes: Vec<String>,
+}
+
+impl<'a> JsonDiagnostic<'a> {
+    fn new(
+        sources: &'a Sources,
+        source_id: SourceId,
+        span: Option<Span>,
+        diagnostic: d::Diagnostic<SourceId>,
+    ) -> Self {
+        let kind = match diagnostic.severity {
+            d::Severity::Bug | d::Severity::Error => "error",
+            d::Severity::Warning => "warning",
+            d::Severity::Note | d::Severity::Help => "note",
+        };
+
+        let labels = diagnostic
+            .labels
+            .into_iter()
+            .map(|label| JsonLabel {
+                primary: label.style == d::LabelStyle::Primary,
+                source_id: json_u32(label.file_id.into_index()),
+                span: JsonSpan {
+                    start: json_u32(label.range.start),
+                    end: json_u32(label.range.end),
+                },
+                message: label.message,
+            })
+            .collect();
+
+        let span = span.unwrap_or_else(Span::empty);
+
+        Self {
+            kind,
+            message: diagnostic.message,
+            source_id: json_u32(source_id.into_index()),
+            span_start: span.start.0,
+            span_end: span.end.0,
+            filename: sources.name(source_id),
+            labels,
+            notes: diagnostic.notes,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonLabel {
+    primary: bool,
+    source_id: u32,
+    span: JsonSpan,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonSpan {
+    start: u32,
+    end: u32,
+}
+
+/// Convert a number into the 32-bit integers used by the JSON format.
+///
+/// Source ids and byte offsets are stored as 32-bit integers, so this only
+/// saturates for counts.
+fn json_u32(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
 }
 
 impl VmError {
@@ -162,6 +345,14 @@ impl VmError {
                     ],
                 )
             }
+            e if is_moved_access(e) => {
+                labels.push(
+                    d::Label::primary(source_id, span.range())
+                        .with_message("value used here after being moved"),
+                );
+
+                ("use of moved value".to_owned(), vec![e.to_string()])
+            }
             e => {
                 labels.push(
                     d::Label::primary(source_id, span.range())
@@ -172,6 +363,7 @@ impl VmError {
         };
 
         let mut backtrace = vec![StackFrame { source_id, span }];
+        let elided_frames = self.elided_frames();
 
         for ip in frames.iter().map(|v| v.ip()) {
             let debug_inst = match debug_info.instruction_at(ip) {
@@ -203,7 +395,11 @@ impl VmError {
         if !backtrace.is_empty() {
             writeln!(out, "backtrace:")?;
 
-            for frame in &backtrace {
+            for (n, frame) in backtrace.iter().enumerate() {
+                if n == 1 && elided_frames > 0 {
+                    writeln!(out, "... {} frames elided", elided_frames)?;
+                }
+
                 let source = match sources.get(frame.source_id) {
                     Some(source) => source,
                     None => continue,
@@ -226,6 +422,21 @@ impl VmError {
     }
 }
 
+/// Test if the given error is caused by accessing a value which has already
+/// been moved, either directly or through an argument to a native function.
+fn is_moved_access(kind: &VmErrorKind) -> bool {
+    match kind {
+        VmErrorKind::AccessError {
+            error: AccessError::NotAccessibleRef { error },
+        } => error.is_moved(),
+        VmErrorKind::AccessError {
+            error: AccessError::NotAccessibleMut { error },
+        } => error.is_moved(),
+        VmErrorKind::BadArgument { error, .. } => is_moved_access(error.kind()),
+        _ => false,
+    }
+}
+
 impl FatalDiagnostic {
     /// Generate formatted diagnostics capable of referencing source lines and
     /// hints.
@@ -362,15 +573,25 @@ pub fn line_for(source: &Source, span: Span) -> Option<(usize, &str, Span)> {
 }
 
 /// Helper to emit diagnostics for a warning.
-fn warning_diagnostics_emit<'a, O>(
+fn warning_diagnostics_emit<O>(
     this: &WarningDiagnostic,
     out: &mut O,
-    sources: &'a Sources,
+    sources: &Sources,
     config: &codespan_reporting::term::Config,
 ) -> Result<(), EmitError>
 where
     O: WriteColor,
 {
+    let diagnostic = warning_diagnostic(this, sources)?;
+    term::emit(out, config, sources, &diagnostic)?;
+    Ok(())
+}
+
+/// Build the diagnostic reported for a warning.
+fn warning_diagnostic(
+    this: &WarningDiagnostic,
+    sources: &Sources,
+) -> Result<d::Diagnostic<SourceId>, EmitError> {
     let mut notes = Vec::new();
     let mut labels = Vec::new();
 
@@ -433,6 +654,56 @@ where
                     .with_message("unnecessary semicolon"),
             );
 
+            None
+        }
+        WarningDiagnosticKind::Unreachable { span, cause } => {
+            labels.push(
+                d::Label::primary(this.source_id(), span.range())
+                    .with_message("unreachable statement"),
+            );
+
+            labels.push(
+                d::Label::secondary(this.source_id(), cause.range())
+                    .with_message("any code following this expression is unreachable"),
+            );
+
+            None
+        }
+        WarningDiagnosticKind::UnreachablePattern { span, cause } => {
+            labels.push(
+                d::Label::primary(this.source_id(), span.range())
+                    .with_message("unreachable pattern"),
+            );
+
+            labels.push(
+                d::Label::secondary(this.source_id(), cause.range())
+                    .with_message("all of its values are already matched here"),
+            );
+
+            None
+        }
+        WarningDiagnosticKind::NonExhaustiveMatch { span, missing } => {
+            labels.push(
+                d::Label::primary(this.source_id(), span.range())
+                    .with_message("match is not exhaustive"),
+            );
+
+            notes.push(format!("Missing variants: {}", missing.join(", ")));
+            None
+        }
+        WarningDiagnosticKind::ConstantCondition { span, value, body } => {
+            labels.push(
+                d::Label::primary(this.source_id(), span.range())
+                    .with_message(format!("condition is always `{}`", value)),
+            );
+
+            if !*value {
+                labels.push(
+                    d::Label::secondary(this.source_id(), body.range())
+                        .with_message("this code is never executed"),
+                );
+            }
+
             None
         }
     };
@@ -443,13 +714,10 @@ where
         );
     }
 
-    let diagnostic = d::Diagnostic::warning()
-        .with_message("warning")
+    Ok(d::Diagnostic::warning()
+        .with_message(this.kind().to_string())
         .with_labels(labels)
-        .with_notes(notes);
-
-    term::emit(out, config, sources, &diagnostic)?;
-    Ok(())
+        .with_notes(notes))
 }
 
 /// Custom shared helper for emitting diagnostics for a single error.
@@ -462,6 +730,21 @@ fn fatal_diagnostics_emit<O>(
 where
     O: WriteColor,
 {
+    if let FatalDiagnosticKind::Internal(message) = this.kind() {
+        writeln!(out, "internal error: {}", message)?;
+        return Ok(());
+    }
+
+    let diagnostic = fatal_diagnostic(this, sources)?;
+    term::emit(out, config, sources, &diagnostic)?;
+    Ok(())
+}
+
+/// Build the diagnostic reported for a single error.
+fn fatal_diagnostic(
+    this: &FatalDiagnostic,
+    sources: &Sources,
+) -> Result<d::Diagnostic<SourceId>, EmitError> {
     let mut labels = Vec::new();
     let mut notes = Vec::new();
 
@@ -471,8 +754,7 @@ where
 
     match this.kind() {
         FatalDiagnosticKind::Internal(message) => {
-            writeln!(out, "internal error: {}", message)?;
-            return Ok(());
+            return Ok(d::Diagnostic::error().with_message(format!("internal error: {}", message)));
         }
         FatalDiagnosticKind::LinkError(error) => {
             match error {
@@ -486,18 +768,18 @@ where
                         );
                     }
 
-                    let diagnostic = d::Diagnostic::error()
+                    return Ok(d::Diagnostic::error()
                         .with_message(format!(
                             "linker error: missing function with hash `{}`",
                             hash
                         ))
-                        .with_labels(labels);
-
-                    term::emit(out, config, sources, &diagnostic)?;
+                        .with_labels(labels));
+                }
+                error => {
+                    return Ok(d::Diagnostic::error()
+                        .with_message(format!("linker error: {}", error)));
                 }
             }
-
-            return Ok(());
         }
         FatalDiagnosticKind::CompileError(error) => {
             format_compile_error(
@@ -522,13 +804,10 @@ where
         FatalDiagnosticKind::ParseError(..) => {},
     };
 
-    let diagnostic = d::Diagnostic::error()
+    return Ok(d::Diagnostic::error()
         .with_message(this.kind().to_string())
         .with_labels(labels)
-        .with_notes(notes);
-
-    term::emit(out, config, sources, &diagnostic)?;
-    return Ok(());
+        .with_notes(notes));
 
     fn format_compile_error(
         this: &FatalDiagnostic,
@@ -715,8 +994,23 @@ where
         labels: &mut Vec<d::Label<SourceId>>,
         notes: &mut Vec<String>,
     ) -> fmt::Result {
-        if let IrErrorKind::QueryError { error } = kind {
-            format_query_error(this, sources, error_span, error, labels, notes)?;
+        match kind {
+            IrErrorKind::QueryError { error } => {
+                format_query_error(this, sources, error_span, error, labels, notes)?;
+            }
+            IrErrorKind::ConstEval { item, calls, error } => {
+                format_ir_error(this, sources, error_span, error, labels, notes)?;
+
+                for (span, target) in calls.iter().rev() {
+                    labels.push(
+                        d::Label::secondary(this.source_id(), span.range())
+                            .with_message(format!("in call to `{}`", target)),
+                    );
+                }
+
+                notes.push(format!("while evaluating const `{}`", item));
+            }
+            _ => (),
         }
 
         Ok(())
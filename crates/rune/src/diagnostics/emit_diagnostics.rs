@@ -365,6 +365,12 @@ where
 
                     term::emit(out, config, sources, &diagnostic)?;
                 }
+                error => {
+                    let diagnostic = d::Diagnostic::error()
+                        .with_message(format!("linker error: {}", error));
+
+                    term::emit(out, config, sources, &diagnostic)?;
+                }
             }
 
             return Ok(());
@@ -43,7 +43,7 @@ pub use self::warning::{WarningDiagnostic, WarningDiagnosticKind};
 cfg_emit! {
     mod emit;
     #[doc(inline)]
-    pub use self::emit::EmitError;
+    pub use self::emit::{EmitError, JSON_DIAGNOSTICS_VERSION};
 }
 
 /// A single diagnostic.
@@ -103,6 +103,13 @@ pub struct Diagnostics {
     has_error: bool,
     /// Indicates if diagnostics contains warnings.
     has_warning: bool,
+    /// The maximum number of errors to collect, if any.
+    max_errors: Option<usize>,
+    /// The number of errors which have been collected.
+    errors: usize,
+    /// The number of errors which were reported but not collected because of
+    /// `max_errors`.
+    truncated_errors: usize,
 }
 
 impl Diagnostics {
@@ -112,6 +119,9 @@ impl Diagnostics {
             mode,
             has_error: false,
             has_warning: false,
+            max_errors: None,
+            errors: 0,
+            truncated_errors: 0,
         }
     }
 
@@ -162,6 +172,41 @@ impl Diagnostics {
         Self::default()
     }
 
+    /// Modify the current collection to stop collecting errors once `max`
+    /// errors have been collected.
+    ///
+    /// Errors reported past the limit are counted but otherwise discarded, the
+    /// number discarded can be accessed through
+    /// [truncated_errors][Diagnostics::truncated_errors] and is noted when the
+    /// diagnostics are emitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Diagnostics, SourceId};
+    /// use rune::diagnostics::FatalDiagnosticKind;
+    ///
+    /// let mut diagnostics = Diagnostics::new().with_max_errors(1);
+    ///
+    /// diagnostics.error(SourceId::empty(), FatalDiagnosticKind::Internal("first"));
+    /// diagnostics.error(SourceId::empty(), FatalDiagnosticKind::Internal("second"));
+    ///
+    /// assert!(diagnostics.has_error());
+    /// assert_eq!(diagnostics.diagnostics().len(), 1);
+    /// assert_eq!(diagnostics.truncated_errors(), 1);
+    /// ```
+    pub fn with_max_errors(mut self, max: usize) -> Self {
+        self.max_errors = Some(max);
+        self
+    }
+
+    /// Get the number of errors which were not collected because the limit
+    /// configured through [with_max_errors][Diagnostics::with_max_errors] was
+    /// reached.
+    pub fn truncated_errors(&self) -> usize {
+        self.truncated_errors
+    }
+
     /// Indicate if there is any diagnostics.
     pub fn is_empty(&self) -> bool {
         self.diagnostics.is_empty()
@@ -260,6 +305,44 @@ impl Diagnostics {
         );
     }
 
+    /// Add a warning about a statement which can never be reached.
+    pub fn unreachable(&mut self, source_id: SourceId, span: Span, cause: Span) {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::Unreachable { span, cause },
+        );
+    }
+
+    /// Add a warning about a match arm which can never be reached.
+    pub fn unreachable_pattern(&mut self, source_id: SourceId, span: Span, cause: Span) {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnreachablePattern { span, cause },
+        );
+    }
+
+    /// Add a warning about a match which doesn't cover all enum variants.
+    pub fn non_exhaustive_match(
+        &mut self,
+        source_id: SourceId,
+        span: Span,
+        missing: Box<[Box<str>]>,
+    ) {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::NonExhaustiveMatch { span, missing },
+        );
+    }
+
+    /// Add a warning about an `if` or `while` condition which always evaluates
+    /// to the same value.
+    pub fn constant_condition(&mut self, source_id: SourceId, span: Span, value: bool, body: Span) {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::ConstantCondition { span, value, body },
+        );
+    }
+
     /// Push a warning to the collection of diagnostics.
     pub fn warning<T>(&mut self, source_id: SourceId, kind: T)
     where
@@ -283,12 +366,20 @@ impl Diagnostics {
     where
         FatalDiagnosticKind: From<T>,
     {
+        self.has_error = true;
+
+        if let Some(max) = self.max_errors {
+            if self.errors >= max {
+                self.truncated_errors += 1;
+                return;
+            }
+        }
+
+        self.errors += 1;
         self.diagnostics.push(Diagnostic::Fatal(FatalDiagnostic {
             source_id,
             kind: Box::new(kind.into()),
         }));
-
-        self.has_error = true;
     }
 }
 
@@ -6,7 +6,7 @@ use thiserror::Error;
 
 /// Warning diagnostic emitted during compilation. Warning diagnostics indicates
 /// an recoverable issues.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct WarningDiagnostic {
     /// The id of the source where the warning happened.
     pub(crate) source_id: SourceId,
@@ -38,6 +38,10 @@ impl WarningDiagnostic {
             WarningDiagnosticKind::TemplateWithoutExpansions { span, .. } => *span,
             WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
             WarningDiagnosticKind::UnecessarySemiColon { span, .. } => *span,
+            WarningDiagnosticKind::Unreachable { span, .. } => *span,
+            WarningDiagnosticKind::UnreachablePattern { span, .. } => *span,
+            WarningDiagnosticKind::NonExhaustiveMatch { span, .. } => *span,
+            WarningDiagnosticKind::ConstantCondition { span, .. } => *span,
         }
     }
 }
@@ -55,7 +59,7 @@ impl error::Error for WarningDiagnostic {
 }
 
 /// The kind of a [WarningDiagnostic].
-#[derive(Debug, Clone, Copy, Error)]
+#[derive(Debug, Clone, Error)]
 #[allow(missing_docs)]
 #[non_exhaustive]
 pub enum WarningDiagnosticKind {
@@ -100,4 +104,41 @@ pub enum WarningDiagnosticKind {
         /// Span where the semi-colon is.
         span: Span,
     },
+    /// A statement which can never be reached.
+    #[error("unreachable statement")]
+    Unreachable {
+        /// The span of the unreachable statement.
+        span: Span,
+        /// The span of the expression which causes the statement to be
+        /// unreachable, like a `return`.
+        cause: Span,
+    },
+    /// A match arm whose pattern only matches values already matched by an
+    /// earlier arm.
+    #[error("unreachable pattern")]
+    UnreachablePattern {
+        /// The span of the unreachable pattern.
+        span: Span,
+        /// The span of the earlier pattern which matches all of its values.
+        cause: Span,
+    },
+    /// A match over an enum which doesn't cover all of its variants.
+    #[error("non-exhaustive match, missing variants: {}", missing.join(", "))]
+    NonExhaustiveMatch {
+        /// The span of the match expression.
+        span: Span,
+        /// The names of the variants which are not covered.
+        missing: Box<[Box<str>]>,
+    },
+    /// A condition in an `if` or `while` which always evaluates to the same
+    /// value.
+    #[error("condition is always `{value}`")]
+    ConstantCondition {
+        /// The span of the condition.
+        span: Span,
+        /// The value the condition always evaluates to.
+        value: bool,
+        /// The span of the body guarded by the condition.
+        body: Span,
+    },
 }
@@ -22,6 +22,7 @@ use crate::worker::{Import, ImportKind, LoadFileKind, Task};
 use crate::{Context, Diagnostics, Hash, SourceId};
 use rune_macros::__instrument_ast as instrument;
 use std::collections::VecDeque;
+use std::mem;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -75,6 +76,22 @@ pub(crate) struct Indexer<'a> {
     ///
     /// Then, `nested_item` would point to the span of `pub fn public`.
     pub(crate) nested_item: Option<Span>,
+    /// The loops and labeled blocks surrounding the expression being indexed,
+    /// innermost last. Used to check that `break` and `continue` are only
+    /// used inside of loops.
+    pub(crate) loops: Vec<IndexLoop>,
+    /// The lints which have been suppressed with `#[allow(..)]` on the
+    /// function being indexed.
+    pub(crate) allow: attrs::AllowLints,
+}
+
+/// A loop or labeled block which can be broken out of.
+pub(crate) struct IndexLoop {
+    /// The label of the loop or block.
+    label: Option<Box<str>>,
+    /// If this is a labeled block, which can only be broken out of with a
+    /// label and can't be continued.
+    is_block: bool,
 }
 
 impl<'a> Indexer<'a> {
@@ -105,6 +122,7 @@ impl<'a> Indexer<'a> {
         };
 
         let ident = ident.resolve(resolve_context!(self.q))?;
+        let start = self.q.start_macro_expansion();
 
         let mut internal_macro = match ident {
             "template" => self.expand_template_macro(ast, &args)?,
@@ -121,6 +139,8 @@ impl<'a> Indexer<'a> {
             }
         };
 
+        self.q.record_macro_expansion(start);
+
         match &mut internal_macro {
             BuiltInMacro::Template(template) => {
                 for e in &mut template.exprs {
@@ -623,6 +643,13 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
         ));
     }
 
+    let mut attributes = attrs::Attributes::new(ast.attributes.clone());
+
+    let allow = match attributes.try_parse::<attrs::Allow>(resolve_context!(idx.q))? {
+        Some((_, allow)) => allow.lints(resolve_context!(idx.q))?,
+        None => attrs::AllowLints::default(),
+    };
+
     let guard = idx.scopes.push_function(kind);
 
     for (arg, _) in &mut ast.args {
@@ -639,7 +666,11 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
 
     // Take and restore item nesting.
     let last = idx.nested_item.replace(ast.descriptive_span());
+    let loops = mem::take(&mut idx.loops);
+    let allow = mem::replace(&mut idx.allow, allow);
     block(&mut ast.body, idx)?;
+    idx.allow = allow;
+    idx.loops = loops;
     idx.nested_item = last;
 
     let f = guard.into_function(span)?;
@@ -671,8 +702,6 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
     // inside of a nested item.
     let is_public = item.is_public() && idx.nested_item.is_none();
 
-    let mut attributes = attrs::Attributes::new(ast.attributes.clone());
-
     let is_test = match attributes.try_parse::<attrs::Test>(resolve_context!(idx.q))? {
         Some((span, _)) => {
             if let Some(nested_span) = idx.nested_item {
@@ -705,6 +734,20 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
         _ => false,
     };
 
+    let is_call = match attributes.try_parse::<attrs::Call>(resolve_context!(idx.q))? {
+        Some((span, _)) => {
+            if !ast.is_instance() {
+                return Err(CompileError::msg(
+                    span,
+                    "#[call] is only supported on member functions",
+                ));
+            }
+
+            true
+        }
+        _ => false,
+    };
+
     if let Some(attrs) = attributes.remaining() {
         return Err(CompileError::msg(attrs, "unrecognized function attribute"));
     }
@@ -733,6 +776,7 @@ fn item_fn(ast: &mut ast::ItemFn, idx: &mut Indexer<'_>) -> CompileResult<()> {
             impl_item: impl_item.clone(),
             instance_span: span,
             call: fun.call,
+            is_call,
         };
 
         // NB: all instance functions must be pre-emptively built,
@@ -815,9 +859,29 @@ fn expr_block(ast: &mut ast::ExprBlock, idx: &mut Indexer<'_>) -> CompileResult<
             ));
         }
 
+        if let Some((label, _)) = &ast.label {
+            let label = label.resolve(resolve_context!(idx.q))?;
+
+            idx.loops.push(IndexLoop {
+                label: Some(label.into()),
+                is_block: true,
+            });
+
+            block(&mut ast.block, idx)?;
+            idx.loops.pop();
+            return Ok(());
+        }
+
         return block(&mut ast.block, idx);
     }
 
+    if let Some(span) = ast.label.option_span() {
+        return Err(CompileError::msg(
+            span,
+            "labels are not supported on async or const blocks",
+        ));
+    }
+
     let _guard = idx.items.push_id();
 
     let item = idx.q.insert_new_item(
@@ -838,7 +902,9 @@ fn expr_block(ast: &mut ast::ExprBlock, idx: &mut Indexer<'_>) -> CompileResult<
             ));
         }
 
+        let loops = mem::take(&mut idx.loops);
         block(&mut ast.block, idx)?;
+        idx.loops = loops;
         idx.q.index_const(&item, ast, ir::compile::expr_block)?;
         return Ok(());
     }
@@ -847,7 +913,9 @@ fn expr_block(ast: &mut ast::ExprBlock, idx: &mut Indexer<'_>) -> CompileResult<
         .scopes
         .push_closure(IndexFnKind::Async, ast.move_token.is_some());
 
+    let loops = mem::take(&mut idx.loops);
     block(&mut ast.block, idx)?;
+    idx.loops = loops;
 
     let c = guard.into_closure(span)?;
 
@@ -860,8 +928,14 @@ fn expr_block(ast: &mut ast::ExprBlock, idx: &mut Indexer<'_>) -> CompileResult<
         }
     };
 
-    idx.q
-        .index_async_block(&item, ast.block.clone(), captures, call, c.do_move)?;
+    idx.q.index_async_block(
+        &item,
+        ast.block.clone(),
+        captures,
+        call,
+        c.do_move,
+        idx.allow,
+    )?;
 
     Ok(())
 }
@@ -883,6 +957,10 @@ fn block(ast: &mut ast::Block, idx: &mut Indexer<'_>) -> CompileResult<()> {
 
     idx.preprocess_stmts(&mut ast.statements)?;
     let mut must_be_last = None;
+    // The span of an unconditional `return`, `break` or `continue` which any
+    // statement following it can never be reached through.
+    let mut diverges = None;
+    let mut reported_unreachable = false;
 
     for stmt in &mut ast.statements {
         if let Some(span) = must_be_last {
@@ -894,6 +972,31 @@ fn block(ast: &mut ast::Block, idx: &mut Indexer<'_>) -> CompileResult<()> {
             ));
         }
 
+        match (diverges, &*stmt) {
+            // NB: items are not evaluated in order, so they are never
+            // unreachable.
+            (_, ast::Stmt::Item(..)) => (),
+            (Some(cause), _) => {
+                // NB: only the first unreachable statement is reported.
+                if !reported_unreachable && !idx.allow.unreachable {
+                    idx.diagnostics
+                        .unreachable(idx.source_id, stmt.span(), cause);
+                }
+
+                reported_unreachable = true;
+            }
+            (
+                None,
+                ast::Stmt::Expr(
+                    e @ (ast::Expr::Return(..) | ast::Expr::Break(..) | ast::Expr::Continue(..)),
+                    _,
+                ),
+            ) => {
+                diverges = Some(e.span());
+            }
+            _ => (),
+        }
+
         match stmt {
             ast::Stmt::Local(l) => {
                 local(l, idx)?;
@@ -936,6 +1039,12 @@ fn local(ast: &mut ast::Local, idx: &mut Indexer<'_>) -> CompileResult<()> {
     // We index the rhs expression first so that it doesn't see it's own
     // declaration and use that instead of capturing from the outside.
     expr(&mut ast.expr, idx, IS_USED)?;
+
+    // The else branch can't see the bindings introduced by the pattern.
+    if let Some(else_) = &mut ast.else_ {
+        block(&mut else_.block, idx)?;
+    }
+
     pat(&mut ast.pat, idx, NOT_USED)?;
     Ok(())
 }
@@ -979,9 +1088,13 @@ fn pat(ast: &mut ast::Pat, idx: &mut Indexer<'_>, is_used: IsUsed) -> CompileRes
         ast::Pat::PatBinding(pat) => {
             pat_binding(pat, idx)?;
         }
+        ast::Pat::PatOr(pat) => {
+            pat_or(pat, idx, is_used)?;
+        }
         ast::Pat::PatIgnore(..) => (),
         ast::Pat::PatLit(..) => (),
         ast::Pat::PatRest(..) => (),
+        ast::Pat::PatRange(..) => (),
     }
 
     Ok(())
@@ -1007,6 +1120,17 @@ fn pat_binding(ast: &mut ast::PatBinding, idx: &mut Indexer<'_>) -> CompileResul
     Ok(())
 }
 
+#[instrument]
+fn pat_or(ast: &mut ast::PatOr, idx: &mut Indexer<'_>, is_used: IsUsed) -> CompileResult<()> {
+    pat(&mut ast.first, idx, is_used)?;
+
+    for (_, p) in &mut ast.rest {
+        pat(p, idx, is_used)?;
+    }
+
+    Ok(())
+}
+
 #[instrument]
 fn pat_object(ast: &mut ast::PatObject, idx: &mut Indexer<'_>) -> CompileResult<()> {
     match &mut ast.ident {
@@ -1123,6 +1247,10 @@ fn expr(ast: &mut ast::Expr, idx: &mut Indexer<'_>, is_used: IsUsed) -> CompileR
         ast::Expr::Vec(e) => {
             expr_vec(e, idx)?;
         }
+        ast::Expr::Repeat(e) => {
+            expr(&mut e.expr, idx, IS_USED)?;
+            expr(&mut e.count, idx, IS_USED)?;
+        }
         ast::Expr::Object(e) => {
             expr_object(e, idx)?;
         }
@@ -1225,11 +1353,14 @@ fn condition(ast: &mut ast::Condition, idx: &mut Indexer<'_>) -> CompileResult<(
 fn item_enum(ast: &mut ast::ItemEnum, idx: &mut Indexer<'_>) -> CompileResult<()> {
     let span = ast.span();
 
-    if let Some(first) = ast.attributes.first() {
-        return Err(CompileError::msg(
-            first,
-            "enum attributes are not supported",
-        ));
+    let mut attributes = attrs::Attributes::new(ast.attributes.clone());
+
+    let debug = attributes
+        .try_parse::<attrs::NoDebug>(resolve_context!(idx.q))?
+        .is_none();
+
+    if let Some(span) = attributes.remaining() {
+        return Err(CompileError::msg(span, "unrecognized enum attribute"));
     }
 
     let name = ast.name.resolve(resolve_context!(idx.q))?;
@@ -1240,14 +1371,24 @@ fn item_enum(ast: &mut ast::ItemEnum, idx: &mut Indexer<'_>) -> CompileResult<()
         idx.q
             .insert_new_item(&idx.items, idx.source_id, span, &idx.mod_item, visibility)?;
 
-    idx.q.index_enum(&enum_item)?;
+    let mut variants = Vec::new();
+
+    for (variant, _) in &ast.variants {
+        let name = variant.name.resolve(resolve_context!(idx.q))?;
+        variants.push(Box::from(name));
+    }
+
+    idx.q.index_enum(&enum_item, variants)?;
 
     for (variant, _) in &mut ast.variants {
-        if let Some(first) = variant.attributes.first() {
-            return Err(CompileError::msg(
-                first,
-                "variant attributes are not supported yet",
-            ));
+        let mut attributes = attrs::Attributes::new(variant.attributes.clone());
+
+        let no_debug = attributes
+            .try_parse::<attrs::NoDebug>(resolve_context!(idx.q))?
+            .is_some();
+
+        if let Some(span) = attributes.remaining() {
+            return Err(CompileError::msg(span, "unrecognized variant attribute"));
         }
 
         for (field, _) in variant.body.fields() {
@@ -1272,7 +1413,8 @@ fn item_enum(ast: &mut ast::ItemEnum, idx: &mut Indexer<'_>) -> CompileResult<()
         )?;
         variant.id = item.id;
 
-        idx.q.index_variant(&item, enum_item.id, variant.clone())?;
+        idx.q
+            .index_variant(&item, enum_item.id, variant.clone(), debug && !no_debug)?;
     }
 
     Ok(())
@@ -1282,11 +1424,14 @@ fn item_enum(ast: &mut ast::ItemEnum, idx: &mut Indexer<'_>) -> CompileResult<()
 fn item_struct(ast: &mut ast::ItemStruct, idx: &mut Indexer<'_>) -> CompileResult<()> {
     let span = ast.span();
 
-    if let Some(first) = ast.attributes.first() {
-        return Err(CompileError::msg(
-            first,
-            "struct attributes are not supported",
-        ));
+    let mut attributes = attrs::Attributes::new(ast.attributes.clone());
+
+    let debug = attributes
+        .try_parse::<attrs::NoDebug>(resolve_context!(idx.q))?
+        .is_none();
+
+    if let Some(span) = attributes.remaining() {
+        return Err(CompileError::msg(span, "unrecognized struct attribute"));
     }
 
     for (field, _) in ast.body.fields() {
@@ -1312,7 +1457,7 @@ fn item_struct(ast: &mut ast::ItemStruct, idx: &mut Indexer<'_>) -> CompileResul
         .insert_new_item(&idx.items, idx.source_id, span, &idx.mod_item, visibility)?;
     ast.id = item.id;
 
-    idx.q.index_struct(&item, Box::new(ast.clone()))?;
+    idx.q.index_struct(&item, Box::new(ast.clone()), debug)?;
     Ok(())
 }
 
@@ -1416,7 +1561,9 @@ fn item_const(ast: &mut ast::ItemConst, idx: &mut Indexer<'_>) -> CompileResult<
     ast.id = item.id;
 
     let last = idx.nested_item.replace(ast.descriptive_span());
+    let loops = mem::take(&mut idx.loops);
     expr(&mut ast.expr, idx, IS_USED)?;
+    idx.loops = loops;
     idx.nested_item = last;
 
     idx.q.index_const(&item, &ast.expr, ir::compile::expr)?;
@@ -1430,9 +1577,11 @@ fn item(ast: &mut ast::Item, idx: &mut Indexer<'_>) -> CompileResult<()> {
     match ast {
         ast::Item::Enum(item) => {
             item_enum(item, idx)?;
+            attributes.drain();
         }
         ast::Item::Struct(item) => {
             item_struct(item, idx)?;
+            attributes.drain();
         }
         ast::Item::Fn(item) => {
             item_fn(item, idx)?;
@@ -1518,15 +1667,13 @@ fn path_segment(ast: &mut ast::PathSegment, idx: &mut Indexer<'_>) -> CompileRes
 fn expr_while(ast: &mut ast::ExprWhile, idx: &mut Indexer<'_>) -> CompileResult<()> {
     let _guard = idx.scopes.push_scope();
     condition(&mut ast.condition, idx)?;
-    block(&mut ast.body, idx)?;
-    Ok(())
+    loop_body(&ast.label, &mut ast.body, idx)
 }
 
 #[instrument]
 fn expr_loop(ast: &mut ast::ExprLoop, idx: &mut Indexer<'_>) -> CompileResult<()> {
     let _guard = idx.scopes.push_scope();
-    block(&mut ast.body, idx)?;
-    Ok(())
+    loop_body(&ast.label, &mut ast.body, idx)
 }
 
 #[instrument]
@@ -1536,7 +1683,72 @@ fn expr_for(ast: &mut ast::ExprFor, idx: &mut Indexer<'_>) -> CompileResult<()>
 
     let _guard = idx.scopes.push_scope();
     pat(&mut ast.binding, idx, NOT_USED)?;
-    block(&mut ast.body, idx)?;
+    loop_body(&ast.label, &mut ast.body, idx)
+}
+
+/// Index the body of a loop with the given label.
+fn loop_body(
+    label: &Option<(ast::Label, T![:])>,
+    body: &mut ast::Block,
+    idx: &mut Indexer<'_>,
+) -> CompileResult<()> {
+    let label = match label {
+        Some((label, _)) => Some(label.resolve(resolve_context!(idx.q))?.into()),
+        None => None,
+    };
+
+    idx.loops.push(IndexLoop {
+        label,
+        is_block: false,
+    });
+
+    block(body, idx)?;
+    idx.loops.pop();
+    Ok(())
+}
+
+/// Check that a `break` or `continue` is used inside of a loop, and that the
+/// loop with the given label exists if one is specified.
+///
+/// Labeled blocks can only be targeted by a labeled `break`.
+fn loop_target(
+    keyword: Span,
+    label: Option<&ast::Label>,
+    outside: CompileErrorKind,
+    is_break: bool,
+    idx: &mut Indexer<'_>,
+) -> CompileResult<()> {
+    let label = match label {
+        Some(label) => label,
+        None => {
+            if !idx.loops.iter().any(|l| !l.is_block) {
+                return Err(CompileError::new(keyword, outside));
+            }
+
+            return Ok(());
+        }
+    };
+
+    let expected = label.resolve(resolve_context!(idx.q))?;
+
+    let found = idx
+        .loops
+        .iter()
+        .any(|l| (is_break || !l.is_block) && l.label.as_deref() == Some(expected));
+
+    if !found {
+        if idx.loops.is_empty() {
+            return Err(CompileError::new(keyword, outside));
+        }
+
+        return Err(CompileError::new(
+            label,
+            CompileErrorKind::MissingLoopLabel {
+                label: expected.into(),
+            },
+        ));
+    }
+
     Ok(())
 }
 
@@ -1573,7 +1785,9 @@ fn expr_closure(ast: &mut ast::ExprClosure, idx: &mut Indexer<'_>) -> CompileRes
         }
     }
 
+    let loops = mem::take(&mut idx.loops);
     expr(&mut ast.body, idx, IS_USED)?;
+    idx.loops = loops;
 
     let c = guard.into_closure(span)?;
 
@@ -1586,8 +1800,14 @@ fn expr_closure(ast: &mut ast::ExprClosure, idx: &mut Indexer<'_>) -> CompileRes
         }
     };
 
-    idx.q
-        .index_closure(&item, Box::new(ast.clone()), captures, call, c.do_move)?;
+    idx.q.index_closure(
+        &item,
+        Box::new(ast.clone()),
+        captures,
+        call,
+        c.do_move,
+        idx.allow,
+    )?;
 
     Ok(())
 }
@@ -1621,13 +1841,16 @@ fn expr_index(ast: &mut ast::ExprIndex, idx: &mut Indexer<'_>) -> CompileResult<
 
 #[instrument]
 fn expr_break(ast: &mut ast::ExprBreak, idx: &mut Indexer<'_>) -> CompileResult<()> {
+    loop_target(
+        ast.break_token.span(),
+        ast.label.as_ref(),
+        CompileErrorKind::BreakOutsideOfLoop,
+        true,
+        idx,
+    )?;
+
     if let Some(e) = ast.expr.as_deref_mut() {
-        match e {
-            ast::ExprBreakValue::Expr(e) => {
-                expr(e, idx, IS_USED)?;
-            }
-            ast::ExprBreakValue::Label(..) => (),
-        }
+        expr(e, idx, IS_USED)?;
     }
 
     Ok(())
@@ -1635,7 +1858,13 @@ fn expr_break(ast: &mut ast::ExprBreak, idx: &mut Indexer<'_>) -> CompileResult<
 
 #[instrument]
 fn expr_continue(ast: &mut ast::ExprContinue, idx: &mut Indexer<'_>) -> CompileResult<()> {
-    Ok(())
+    loop_target(
+        ast.break_token.span(),
+        ast.label.as_ref(),
+        CompileErrorKind::ContinueOutsideOfLoop,
+        false,
+        idx,
+    )
 }
 
 #[instrument]
@@ -1749,8 +1978,12 @@ fn expr_tuple(ast: &mut ast::ExprTuple, idx: &mut Indexer<'_>) -> CompileResult<
 
 #[instrument]
 fn expr_vec(ast: &mut ast::ExprVec, idx: &mut Indexer<'_>) -> CompileResult<()> {
-    for (e, _) in &mut ast.items {
-        expr(e, idx, IS_USED)?;
+    for (item, _) in &mut ast.items {
+        match item {
+            ast::ExprVecItem::Expr(e) | ast::ExprVecItem::Spread(_, e) => {
+                expr(e, idx, IS_USED)?;
+            }
+        }
     }
 
     Ok(())
@@ -25,6 +25,28 @@ pub struct FormatArgs {
 impl FormatArgs {
     /// Expand the format specification.
     pub fn expand(&self, ctx: &mut MacroContext<'_>) -> Result<Quote<'_>, SpannedError> {
+        let args = self.expand_args(ctx)?;
+
+        if args.is_empty() {
+            return Ok(quote!(""));
+        }
+
+        Ok(quote! {
+            #[builtin] template!(#(args),*)
+        })
+    }
+
+    /// Expand the format specification into a lazily formatted
+    /// `std::string::FormatArgs` value, which only formats its arguments once
+    /// it's displayed.
+    pub fn expand_lazy(&self, ctx: &mut MacroContext<'_>) -> Result<Quote<'_>, SpannedError> {
+        let args = self.expand_args(ctx)?;
+        Ok(quote!(::std::string::FormatArgs::new([#(args),*])))
+    }
+
+    /// Expand the format specification into the sequence of literals and
+    /// formatted arguments it consists of.
+    fn expand_args(&self, ctx: &mut MacroContext<'_>) -> Result<Vec<Quote<'_>>, SpannedError> {
         let format = ctx.eval(&self.format)?;
 
         let mut pos = Vec::new();
@@ -50,7 +72,7 @@ impl FormatArgs {
         }
 
         let format = match format {
-            IrValue::String(string) => string.take().with_span(self.format.span())?,
+            IrValue::String(string) => string.borrow_ref().with_span(self.format.span())?.clone(),
             _ => {
                 return Err(SpannedError::msg(
                     self.format.span(),
@@ -125,16 +147,23 @@ impl Peek for FormatArgs {
 }
 
 /// A named format argument.
-#[derive(Debug, Clone, Parse, Spanned)]
+#[derive(Debug, Clone, Spanned)]
 pub struct NamedFormatArg {
     /// The key of the named argument.
     pub key: ast::Ident,
-    /// The `=` token.
-    pub eq_token: T![=],
     /// The value expression.
     pub expr: ast::Expr,
 }
 
+impl Parse for NamedFormatArg {
+    fn parse(p: &mut Parser) -> Result<Self, ParseError> {
+        let key = p.parse()?;
+        let _ = p.parse::<T![=]>()?;
+        let expr = p.parse()?;
+        Ok(Self { key, expr })
+    }
+}
+
 /// A single format argument.
 #[derive(Debug, Clone)]
 pub enum FormatArg {
@@ -162,7 +191,7 @@ fn expand_format_spec<'a>(
     unused_pos: &mut BTreeSet<usize>,
     named: &HashMap<Box<str>, &'a NamedFormatArg>,
     unused_named: &mut BTreeMap<Box<str>, Span>,
-) -> Result<Quote<'a>, SpannedError> {
+) -> Result<Vec<Quote<'a>>, SpannedError> {
     let mut iter = Iter::new(input);
 
     let mut name = String::new();
@@ -220,10 +249,6 @@ fn expand_format_spec<'a>(
         buf.clear();
     }
 
-    if components.is_empty() {
-        return Ok(quote!(""));
-    }
-
     let mut args = Vec::<Quote<'static>>::new();
 
     for c in components {
@@ -285,9 +310,7 @@ fn expand_format_spec<'a>(
         }
     }
 
-    return Ok(quote! {
-        #[builtin] template!(#(args),*)
-    });
+    return Ok(args);
 
     enum C<'a> {
         Literal(Box<str>),
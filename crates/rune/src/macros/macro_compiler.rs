@@ -47,6 +47,7 @@ impl MacroCompiler<'_> {
             }
         };
 
+        let start = self.query.start_macro_expansion();
         let input_stream = &macro_call.stream;
 
         // SAFETY: Macro context only needs to live for the duration of the
@@ -62,6 +63,8 @@ impl MacroCompiler<'_> {
             handler(&mut macro_context, input_stream)
         };
 
+        self.query.record_macro_expansion(start);
+
         let token_stream = match result {
             Ok(output) => output,
             Err(error) => {
@@ -3,13 +3,14 @@
 use crate::ast;
 use crate::ast::Span;
 use crate::compile::{
-    IrCompiler, IrError, IrEval, IrEvalContext, IrValue, ItemMeta, NoopCompileVisitor, UnitBuilder,
+    IrCompiler, IrError, IrEval, IrEvalContext, IrValue, ItemMeta, NoopCompileVisitor, Options,
+    UnitBuilder,
 };
 use crate::macros::{IntoLit, Storage, ToTokens, TokenStream};
 use crate::parse::{Parse, ParseError, ParseErrorKind, Resolve, ResolveError};
 use crate::query::Query;
 use crate::shared::{Consts, Gen};
-use crate::{Source, SourceId, Sources};
+use crate::{Context, Source, SourceId, Sources};
 use std::fmt;
 use std::sync::Arc;
 
@@ -40,19 +41,23 @@ impl<'a> MacroContext<'a> {
     where
         F: FnOnce(&mut MacroContext<'_>) -> O,
     {
+        let context = Context::default();
         let mut unit = UnitBuilder::default();
         let gen = Gen::default();
         let mut consts = Consts::default();
         let mut storage = Storage::default();
         let mut sources = Sources::default();
+        let options = Options::default();
         let mut visitor = NoopCompileVisitor::new();
         let mut inner = Default::default();
 
         let mut query = Query::new(
+            &context,
             &mut unit,
             &mut consts,
             &mut storage,
             &mut sources,
+            &options,
             &mut visitor,
             &gen,
             &mut inner,
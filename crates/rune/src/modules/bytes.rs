@@ -1,6 +1,6 @@
 //! `std::bytes` module.
 
-use crate::runtime::Bytes;
+use crate::runtime::{Bytes, Protocol, TypeOf, Value, VmError, VmErrorKind};
 use crate::{ContextError, Module};
 
 /// Construct the `std::bytes` module.
@@ -13,6 +13,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.function(&["Bytes", "from_vec"], Bytes::from_vec)?;
 
     module.inst_fn("into_vec", Bytes::into_vec)?;
+    module.inst_fn("to_vec", Bytes::to_vec)?;
     module.inst_fn("extend", Bytes::extend)?;
     module.inst_fn("extend_str", Bytes::extend_str)?;
     module.inst_fn("pop", Bytes::pop)?;
@@ -25,5 +26,20 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("reserve_exact", Bytes::reserve_exact)?;
     module.inst_fn("clone", Bytes::clone)?;
     module.inst_fn("shrink_to_fit", Bytes::shrink_to_fit)?;
+    module.inst_fn(Protocol::INDEX_GET, bytes_index_get)?;
     Ok(module)
 }
+
+/// Get a sub-slice of bytes by indexing with a range.
+fn bytes_index_get(bytes: &Bytes, index: Value) -> Result<Bytes, VmError> {
+    match index {
+        Value::Range(range) => {
+            let range = range.borrow_ref()?.slice_range(bytes.len())?;
+            Ok(Bytes::from_vec(bytes[range].to_vec()))
+        }
+        index => Err(VmError::from(VmErrorKind::UnsupportedIndexGet {
+            target: Bytes::type_info(),
+            index: index.type_info()?,
+        })),
+    }
+}
@@ -337,7 +337,7 @@ impl VecDeque {
     }
 
     fn rotate_right(&mut self, mid: usize) {
-        self.inner.rotate_left(mid);
+        self.inner.rotate_right(mid);
     }
 
     fn push_front(&mut self, v: Value) {
@@ -364,12 +364,20 @@ impl VecDeque {
         self.inner.reserve(index);
     }
 
-    fn len(&mut self) -> usize {
+    fn len(&self) -> usize {
         self.inner.len()
     }
 
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
     fn get(&self, index: usize) -> Result<Value, VmError> {
-        if index > self.inner.len() {
+        if index >= self.inner.len() {
             return Err(VmError::from(VmErrorKind::OutOfRange {
                 index: index.into(),
                 len: self.inner.len().into(),
@@ -379,7 +387,7 @@ impl VecDeque {
     }
 
     fn set(&mut self, index: usize, value: Value) -> Result<(), VmError> {
-        if index > self.inner.len() {
+        if index >= self.inner.len() {
             return Err(VmError::from(VmErrorKind::OutOfRange {
                 index: index.into(),
                 len: self.inner.len().into(),
@@ -453,8 +461,10 @@ pub fn module() -> Result<Module, ContextError> {
     module.function(&["VecDeque", "with_capacity"], VecDeque::with_capacity)?;
     module.function(&["VecDeque", "from"], vecdeque_from)?;
 
+    module.inst_fn("clear", VecDeque::clear)?;
     module.inst_fn("extend", VecDeque::extend)?;
     module.inst_fn("insert", VecDeque::insert)?;
+    module.inst_fn("is_empty", VecDeque::is_empty)?;
     module.inst_fn("iter", VecDeque::iter)?;
     module.inst_fn("len", VecDeque::len)?;
     module.inst_fn("pop_back", VecDeque::pop_back)?;
@@ -1,6 +1,6 @@
 //! The core `std` module.
 
-use crate::runtime::{Panic, Value};
+use crate::runtime::{Function, Panic, Value, VmError};
 use crate::{ContextError, Module};
 
 /// Construct the `std` module.
@@ -13,6 +13,9 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<u8>()?;
     module.ty::<f64>()?;
     module.ty::<i64>()?;
+    module.ty::<Function>()?;
+
+    module.inst_fn("bind", function_bind)?;
 
     module.function(&["panic"], panic_impl)?;
     module.function(&["is_readable"], is_readable)?;
@@ -20,6 +23,11 @@ pub fn module() -> Result<Module, ContextError> {
     Ok(module)
 }
 
+/// Bind a single argument to the front of the arguments of a function.
+fn function_bind(function: &Function, value: Value) -> Result<Function, VmError> {
+    function.bind((value,))
+}
+
 fn panic_impl(m: &str) -> Result<(), Panic> {
     Err(Panic::custom(m.to_owned()))
 }
@@ -0,0 +1,19 @@
+//! The `std::env` module.
+
+use crate::runtime::{env, VmError};
+use crate::{ContextError, Module};
+
+/// Construct the `std::env` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", &["env"]);
+    module.function(&["var"], var)?;
+    Ok(module)
+}
+
+/// Read a configuration value provided through
+/// [Build::with_env][crate::Build::with_env].
+///
+/// The environment of the host process is never consulted.
+fn var(name: &str) -> Result<Option<String>, VmError> {
+    env::with(|_, unit| Ok(unit.env_var(name).map(String::from)))
+}
@@ -13,6 +13,12 @@ fn to_integer(value: f64) -> i64 {
     value as i64
 }
 
+/// Convert a float to a string.
+fn to_string(value: f64) -> String {
+    let mut buffer = ryu::Buffer::new();
+    buffer.format(value).to_owned()
+}
+
 crate::__internal_impl_any!(ParseFloatError);
 
 /// Install the core package into the given functions namespace.
@@ -28,6 +34,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("powi", f64::powi)?;
 
     module.inst_fn("to_integer", to_integer)?;
+    module.inst_fn("to_string", to_string)?;
 
     Ok(module)
 }
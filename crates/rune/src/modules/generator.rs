@@ -12,6 +12,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("next", Generator::<Vm>::next)?;
     module.inst_fn("resume", Generator::<Vm>::resume)?;
     module.inst_fn("iter", Generator::<Vm>::into_iterator)?;
+    module.inst_fn(Protocol::NEXT, Generator::<Vm>::next)?;
     module.inst_fn(Protocol::INTO_ITER, Generator::<Vm>::into_iterator)?;
 
     Ok(module)
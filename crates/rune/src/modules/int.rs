@@ -11,6 +11,7 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.function(&["parse"], parse)?;
     module.inst_fn("to_float", to_float)?;
+    module.inst_fn("to_string", to_string)?;
 
     module.inst_fn("max", i64::max)?;
     module.inst_fn("min", i64::min)?;
@@ -48,4 +49,10 @@ fn to_float(value: i64) -> f64 {
     value as f64
 }
 
+/// Convert a whole number to a string.
+fn to_string(value: i64) -> String {
+    let mut buffer = itoa::Buffer::new();
+    buffer.format(value).to_owned()
+}
+
 crate::__internal_impl_any!(ParseIntError);
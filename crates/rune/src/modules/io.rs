@@ -1,11 +1,11 @@
 //! The `std::io` module.
 
+use crate::runtime::env;
 use crate::runtime::{Panic, Protocol, Stack, Value, VmError};
 use crate::{ContextError, Module};
 use std::fmt;
 use std::fmt::Write as _;
 use std::io;
-use std::io::Write as _;
 
 /// Construct the `std::io` module.
 pub fn module(stdio: bool) -> Result<Module, ContextError> {
@@ -28,25 +28,25 @@ fn format_io_error(error: &std::io::Error, buf: &mut String) -> fmt::Result {
 }
 
 fn dbg_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
+    let values = stack.drain(args)?.collect::<Vec<_>>();
 
-    for value in stack.drain(args)? {
-        writeln!(stdout, "{:?}", value).map_err(VmError::panic)?;
-    }
+    env::with_output(|out| {
+        for value in &values {
+            writeln!(out, "{:?}", value)?;
+        }
+
+        Ok(())
+    })
+    .map_err(VmError::panic)?;
 
     stack.push(Value::Unit);
     Ok(())
 }
 
 fn print_impl(m: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    write!(stdout, "{}", m).map_err(Panic::custom)
+    env::with_output(|out| write!(out, "{}", m)).map_err(Panic::custom)
 }
 
 fn println_impl(m: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    writeln!(stdout, "{}", m).map_err(Panic::custom)
+    env::with_output(|out| writeln!(out, "{}", m)).map_err(Panic::custom)
 }
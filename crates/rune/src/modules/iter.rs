@@ -32,6 +32,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("count", Iterator::count)?;
     module.inst_fn("all", Iterator::all)?;
     module.inst_fn(Protocol::NEXT, Iterator::next)?;
+    module.inst_fn(Protocol::NEXT_BACK, Iterator::next_back)?;
     module.inst_fn(Protocol::INTO_ITER, <Iterator as From<Iterator>>::from)?;
 
     module.function(&["range"], new_range)?;
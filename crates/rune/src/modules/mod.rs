@@ -7,6 +7,7 @@ pub mod char;
 pub mod cmp;
 pub mod collections;
 pub mod core;
+pub mod env;
 pub mod float;
 pub mod fmt;
 pub mod future;
@@ -21,4 +22,5 @@ pub mod option;
 pub mod result;
 pub mod stream;
 pub mod string;
+pub mod tuple;
 pub mod vec;
@@ -10,6 +10,7 @@ pub fn module() -> Result<Module, ContextError> {
     // Sorted for ease of finding
     module.inst_fn("and_then", and_then_impl)?;
     module.inst_fn("expect", expect_impl)?;
+    module.inst_fn("filter", filter_impl)?;
     module.inst_fn("is_none", Option::<Value>::is_none)?;
     module.inst_fn("is_some", Option::<Value>::is_some)?;
     module.inst_fn("iter", option_iter)?;
@@ -19,6 +20,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("unwrap", unwrap_impl)?;
     module.inst_fn("unwrap_or", Option::<Value>::unwrap_or)?;
     module.inst_fn("unwrap_or_else", unwrap_or_else_impl)?;
+    module.inst_fn("zip", zip_impl)?;
     module.inst_fn(Protocol::INTO_ITER, option_iter)?;
     Ok(module)
 }
@@ -70,6 +72,17 @@ fn and_then_impl(option: &Option<Value>, then: Function) -> Result<Option<Value>
     }
 }
 
+fn filter_impl(option: &Option<Value>, predicate: Function) -> Result<Option<Value>, VmError> {
+    match option {
+        Some(v) if predicate.call::<_, bool>((v,))? => Ok(Some(v.clone())),
+        _ => Ok(None),
+    }
+}
+
+fn zip_impl(option: &Option<Value>, other: Option<Value>) -> Option<(Value, Value)> {
+    option.clone().zip(other)
+}
+
 fn take_impl(option: &mut Option<Value>) -> Option<Value> {
     option.take()
 }
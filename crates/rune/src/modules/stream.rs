@@ -1,7 +1,7 @@
 //! The `std::stream` module.
 
-use crate::runtime::{Stream, Vm};
-use crate::{ContextError, Module};
+use crate::runtime::{Function, Stream, Value, Vm, VmError};
+use crate::{Any, ContextError, Module};
 
 /// Construct the `std::stream` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -9,5 +9,75 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<Stream<Vm>>()?;
     module.async_inst_fn("next", Stream::<Vm>::next)?;
     module.async_inst_fn("resume", Stream::<Vm>::resume)?;
+    module.inst_fn("map", map)?;
+    module.inst_fn("filter", filter)?;
+
+    module.ty::<StreamAdapter>()?;
+    module.async_inst_fn("next", StreamAdapter::next)?;
+    module.inst_fn("map", StreamAdapter::map)?;
+    module.inst_fn("filter", StreamAdapter::filter)?;
     Ok(module)
 }
+
+/// A stream which lazily applies a chain of adaptors to the values produced
+/// by an underlying stream.
+#[derive(Any)]
+#[rune(module = "crate")]
+struct StreamAdapter {
+    stream: Stream<Vm>,
+    adaptors: Vec<Adaptor>,
+}
+
+enum Adaptor {
+    Map(Function),
+    Filter(Function),
+}
+
+impl StreamAdapter {
+    /// Get the next value produced by the stream, after all adaptors have
+    /// been applied.
+    async fn next(&mut self) -> Result<Option<Value>, VmError> {
+        'outer: while let Some(mut value) = self.stream.next().await? {
+            for adaptor in &self.adaptors {
+                match adaptor {
+                    Adaptor::Map(map) => {
+                        value = map.call((value,))?;
+                    }
+                    Adaptor::Filter(filter) => {
+                        if !filter.call::<_, bool>((value.clone(),))? {
+                            continue 'outer;
+                        }
+                    }
+                }
+            }
+
+            return Ok(Some(value));
+        }
+
+        Ok(None)
+    }
+
+    fn map(mut self, map: Function) -> Self {
+        self.adaptors.push(Adaptor::Map(map));
+        self
+    }
+
+    fn filter(mut self, filter: Function) -> Self {
+        self.adaptors.push(Adaptor::Filter(filter));
+        self
+    }
+}
+
+fn map(stream: Stream<Vm>, map: Function) -> StreamAdapter {
+    StreamAdapter {
+        stream,
+        adaptors: vec![Adaptor::Map(map)],
+    }
+}
+
+fn filter(stream: Stream<Vm>, filter: Function) -> StreamAdapter {
+    StreamAdapter {
+        stream,
+        adaptors: vec![Adaptor::Filter(filter)],
+    }
+}
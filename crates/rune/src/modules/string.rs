@@ -1,7 +1,11 @@
 //! The `std::string` module.
 
-use crate::runtime::{Bytes, Iterator, Protocol, Value, VmError, VmErrorKind};
-use crate::{Any, ContextError, Module};
+use crate::compile::Named;
+use crate::macros::{self, MacroContext, TokenStream};
+use crate::parse::Parser;
+use crate::runtime::{Bytes, FormatArgs, Iterator, Protocol, TypeOf, Value, VmError, VmErrorKind};
+use crate::{Any, ContextError, Module, Params};
+use std::fmt;
 
 /// Construct the `std::string` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -21,6 +25,9 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("clear", String::clear)?;
     module.inst_fn("push", String::push)?;
     module.inst_fn("push_str", String::push_str)?;
+    module.inst_fn("insert", string_insert)?;
+    module.inst_fn("insert_str", string_insert_str)?;
+    module.inst_fn("remove", string_remove)?;
     module.inst_fn("reserve", String::reserve)?;
     module.inst_fn("reserve_exact", String::reserve_exact)?;
     module.inst_fn("into_bytes", into_bytes)?;
@@ -35,15 +42,26 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("split_str", string_split)?;
     module.inst_fn("is_empty", str::is_empty)?;
     module.inst_fn("chars", string_chars)?;
+    module.inst_fn("char_indices", string_char_indices)?;
+    module.inst_fn("bytes", string_bytes)?;
     module.inst_fn(Protocol::ADD, add)?;
     module.inst_fn(Protocol::ADD_ASSIGN, String::push_str)?;
     module.inst_fn(Protocol::INDEX_GET, string_index_get)?;
     module.inst_fn("get", string_get)?;
 
+    module.ty::<ParseError>()?;
+    module.inst_fn(Params("parse", [i64::type_hash()]), parse_as::<i64>)?;
+    module.inst_fn(Params("parse", [f64::type_hash()]), parse_as::<f64>)?;
+
     // TODO: parameterize once generics are available.
     module.function(&["parse_int"], parse_int)?;
     module.function(&["parse_char"], parse_char)?;
 
+    module.ty::<FormatArgs>()?;
+    module.function(&["FormatArgs", "new"], FormatArgs::new)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, FormatArgs::string_display)?;
+    module.macro_(&["format_args"], format_args_macro)?;
+
     Ok(module)
 }
 
@@ -54,7 +72,37 @@ struct NotCharBoundary(());
 impl NotCharBoundary {
     fn string_display(&self, s: &mut String) -> std::fmt::Result {
         use std::fmt::Write as _;
-        write!(s, "index outside of character boundary")
+        write!(s, "{}", self)
+    }
+
+    fn install(m: &mut Module) -> Result<(), ContextError> {
+        m.inst_fn(Protocol::STRING_DISPLAY, Self::string_display)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for NotCharBoundary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "index outside of character boundary")
+    }
+}
+
+/// Error raised by `parse` when a string can't be parsed as the expected type.
+#[derive(Any, Debug, Clone)]
+#[rune(module = "crate", install_with = "ParseError::install")]
+struct ParseError {
+    /// The string which failed to parse.
+    #[rune(get)]
+    input: String,
+    /// The name of the type the string was expected to be.
+    #[rune(get)]
+    expected: String,
+}
+
+impl ParseError {
+    fn string_display(&self, s: &mut String) -> std::fmt::Result {
+        use std::fmt::Write as _;
+        write!(s, "{}", self)
     }
 
     fn install(m: &mut Module) -> Result<(), ContextError> {
@@ -63,6 +111,56 @@ impl NotCharBoundary {
     }
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to parse `{}` as {}", self.input, self.expected)
+    }
+}
+
+/// Parse the string as the given type, like `"42".parse::<int>()`.
+fn parse_as<T>(s: &str) -> Result<T, ParseError>
+where
+    T: std::str::FromStr + Named,
+{
+    s.parse::<T>().map_err(|_| ParseError {
+        input: s.to_owned(),
+        expected: T::full_name().into(),
+    })
+}
+
+/// Check that `index` can be used to insert into or remove from `s`.
+fn check_char_boundary(s: &str, index: usize, len: usize) -> Result<(), VmError> {
+    if index >= len {
+        return Err(VmError::from(VmErrorKind::OutOfRange {
+            index: index.into(),
+            len: len.into(),
+        }));
+    }
+
+    if !s.is_char_boundary(index) {
+        return Err(VmError::panic(NotCharBoundary(())));
+    }
+
+    Ok(())
+}
+
+fn string_insert(s: &mut String, index: usize, c: char) -> Result<(), VmError> {
+    check_char_boundary(s, index, s.len() + 1)?;
+    s.insert(index, c);
+    Ok(())
+}
+
+fn string_insert_str(s: &mut String, index: usize, string: &str) -> Result<(), VmError> {
+    check_char_boundary(s, index, s.len() + 1)?;
+    s.insert_str(index, string);
+    Ok(())
+}
+
+fn string_remove(s: &mut String, index: usize) -> Result<char, VmError> {
+    check_char_boundary(s, index, s.len())?;
+    Ok(s.remove(index))
+}
+
 /// into_bytes shim for strings.
 fn into_bytes(s: String) -> Bytes {
     Bytes::from_vec(s.into_bytes())
@@ -125,6 +223,16 @@ fn string_chars(s: &str) -> Iterator {
     Iterator::from_double_ended("std::str::Chars", iter)
 }
 
+fn string_char_indices(s: &str) -> Iterator {
+    let iter = s.char_indices().collect::<Vec<_>>().into_iter();
+    Iterator::from_double_ended("std::str::CharIndices", iter)
+}
+
+fn string_bytes(s: &str) -> Iterator {
+    let iter = s.bytes().collect::<Vec<_>>().into_iter();
+    Iterator::from_double_ended("std::str::Bytes", iter)
+}
+
 /// Get a specific string index.
 fn string_get(s: &str, key: Value) -> Result<Option<String>, VmError> {
     use crate::runtime::{FromValue, RangeLimits, TypeOf};
@@ -168,5 +276,35 @@ fn string_get(s: &str, key: Value) -> Result<Option<String>, VmError> {
 
 /// Get a specific string index.
 fn string_index_get(s: &str, key: Value) -> Result<String, VmError> {
-    string_get(s, key)?.ok_or_else(|| VmError::panic("missing string slice"))
+    use crate::runtime::TypeOf;
+
+    let range = match key {
+        Value::Range(range) => range.borrow_ref()?.slice_range(s.len())?,
+        index => {
+            return Err(VmError::from(VmErrorKind::UnsupportedIndexGet {
+                target: String::type_info(),
+                index: index.type_info()?,
+            }))
+        }
+    };
+
+    match s.get(range.clone()) {
+        Some(out) => Ok(out.to_owned()),
+        None => Err(VmError::from(VmErrorKind::NotCharBoundary {
+            start: range.start,
+            end: range.end,
+        })),
+    }
+}
+
+/// Implementation for the `format_args!` macro.
+fn format_args_macro(
+    ctx: &mut MacroContext<'_>,
+    stream: &TokenStream,
+) -> crate::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, ctx.stream_span());
+    let args = p.parse::<macros::FormatArgs>()?;
+    p.eof()?;
+    let expanded = args.expand_lazy(ctx)?;
+    Ok(expanded.into_token_stream(ctx))
 }
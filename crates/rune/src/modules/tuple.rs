@@ -0,0 +1,18 @@
+//! The `std::tuple` module.
+
+use crate::runtime::{Tuple, Value};
+use crate::{ContextError, Module};
+
+/// Construct the `std::tuple` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", &["tuple"]);
+    module.ty::<Tuple>()?;
+    module.inst_fn("len", Tuple::len)?;
+    module.inst_fn("is_empty", Tuple::is_empty)?;
+    module.inst_fn("get", get)?;
+    Ok(module)
+}
+
+fn get(tuple: &Tuple, index: usize) -> Option<Value> {
+    tuple.get(index).cloned()
+}
@@ -1,7 +1,8 @@
 //! The `std::vec` module.
 
-use crate::runtime::{Function, Protocol, TypeOf, Value, Vec};
+use crate::runtime::{FromValue, Function, Protocol, TypeOf, Value, Vec, VmError, VmErrorKind};
 use crate::{ContextError, Module, Params};
+use std::cmp::Ordering;
 
 /// Construct the `std::vec` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -10,6 +11,8 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<Vec>()?;
 
     module.function(&["Vec", "new"], Vec::new)?;
+    module.function(&["Vec", "with_capacity"], vec_with_capacity)?;
+    module.inst_fn("capacity", Vec::capacity)?;
     module.inst_fn("clear", Vec::clear)?;
     module.inst_fn("clone", Vec::clone)?;
     module.inst_fn("extend", Vec::extend)?;
@@ -19,9 +22,13 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("pop", Vec::pop)?;
     module.inst_fn("push", Vec::push)?;
     module.inst_fn("remove", Vec::remove)?;
+    module.inst_fn("reserve", Vec::reserve)?;
+    module.inst_fn("shrink_to_fit", Vec::shrink_to_fit)?;
+    module.inst_fn("sort", sort)?;
     module.inst_fn("sort_by", sort_by)?;
     module.inst_fn("insert", Vec::insert)?;
     module.inst_fn(Protocol::INTO_ITER, Vec::into_iterator)?;
+    module.inst_fn(Protocol::INDEX_GET, vec_index_get)?;
     module.inst_fn(Protocol::INDEX_SET, Vec::set)?;
 
     // TODO: parameterize with generics.
@@ -38,14 +45,104 @@ fn sort_int(vec: &mut Vec) {
     });
 }
 
+/// Construct a vector with at least the given capacity.
+fn vec_with_capacity(capacity: usize) -> Result<Vec, VmError> {
+    let mut vec = Vec::new();
+    vec.reserve(capacity)?;
+    Ok(vec)
+}
+
 fn vec_get(vec: &Vec, index: usize) -> Option<Value> {
     vec.get(index).cloned()
 }
 
-fn sort_by(vec: &mut Vec, comparator: &Function) {
-    vec.sort_by(|a, b| {
-        comparator
-            .call::<_, std::cmp::Ordering>((a, b))
-            .unwrap_or(std::cmp::Ordering::Equal)
+/// Get a sub-vector by indexing with a range.
+fn vec_index_get(vec: &Vec, index: Value) -> Result<Vec, VmError> {
+    match index {
+        Value::Range(range) => {
+            let range = range.borrow_ref()?.slice_range(vec.len())?;
+            Ok(Vec::from(vec[range].to_vec()))
+        }
+        index => Err(VmError::from(VmErrorKind::UnsupportedIndexGet {
+            target: Vec::type_info(),
+            index: index.type_info()?,
+        })),
+    }
+}
+
+/// Sort a vector of integers, floats or strings in ascending order.
+///
+/// Values which can't be compared with each other, like mixed types or a
+/// float which is NaN, cause an error.
+fn sort(vec: &mut Vec) -> Result<(), VmError> {
+    sort_with(vec, compare)
+}
+
+/// Sort a vector using a comparator function, which returns either an
+/// `Ordering` or an integer whose sign determines the ordering.
+fn sort_by(vec: &mut Vec, comparator: &Function) -> Result<(), VmError> {
+    sort_with(vec, |a, b| {
+        match comparator.call::<_, Value>((a.clone(), b.clone()))? {
+            Value::Integer(n) => Ok(n.cmp(&0)),
+            value => Ordering::from_value(value),
+        }
     })
 }
+
+/// Sort a vector with a fallible comparison.
+///
+/// The first error raised stops further comparisons from being performed and
+/// is returned once the sort has completed.
+fn sort_with<F>(vec: &mut Vec, mut compare: F) -> Result<(), VmError>
+where
+    F: FnMut(&Value, &Value) -> Result<Ordering, VmError>,
+{
+    let mut error = None;
+
+    vec.sort_by(|a, b| {
+        if error.is_some() {
+            return Ordering::Equal;
+        }
+
+        match compare(a, b) {
+            Ok(ordering) => ordering,
+            Err(e) => {
+                error = Some(e);
+                Ordering::Equal
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Compare two values of the same primitive type.
+fn compare(a: &Value, b: &Value) -> Result<Ordering, VmError> {
+    let ordering = match (a, b) {
+        (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+        (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+        (Value::String(a), Value::String(b)) => {
+            Some(a.borrow_ref()?.as_str().cmp(b.borrow_ref()?.as_str()))
+        }
+        (Value::String(a), Value::StaticString(b)) => {
+            Some(a.borrow_ref()?.as_str().cmp(b.as_str()))
+        }
+        (Value::StaticString(a), Value::String(b)) => {
+            Some(a.as_str().cmp(b.borrow_ref()?.as_str()))
+        }
+        (Value::StaticString(a), Value::StaticString(b)) => Some(a.as_str().cmp(b.as_str())),
+        _ => None,
+    };
+
+    match ordering {
+        Some(ordering) => Ok(ordering),
+        None => Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+            op: "cmp",
+            lhs: a.type_info()?,
+            rhs: b.type_info()?,
+        })),
+    }
+}
@@ -146,13 +146,21 @@ impl<'a> Lexer<'a> {
             }
         }
 
+        let span = self.iter.span_from(start);
+
+        let suffix = match self.iter.source.get(span.range()) {
+            Some(literal) => ast::NumberSuffix::from_literal(literal, base),
+            None => None,
+        };
+
         Ok(Some(ast::Token {
             kind: ast::Kind::Number(ast::NumberSource::Text(ast::NumberText {
                 source_id: self.source_id,
                 is_fractional,
                 base,
+                suffix,
             })),
-            span: self.iter.span_from(start),
+            span,
         }))
     }
 
@@ -967,6 +975,7 @@ mod tests {
                     source_id: SourceId::EMPTY,
                     is_fractional: false,
                     base: ast::NumberBase::Decimal,
+                    suffix: None,
                 })),
             },
             ast::Token {
@@ -984,6 +993,37 @@ mod tests {
                     source_id: SourceId::EMPTY,
                     is_fractional: true,
                     base: ast::NumberBase::Decimal,
+                    suffix: None,
+                })),
+            },
+            _,
+        };
+
+        test_lexer! {
+            "(42u8)",
+            _,
+            ast::Token {
+                span: span!(1, 5),
+                kind: ast::Kind::Number(ast::NumberSource::Text(ast::NumberText {
+                    source_id: SourceId::EMPTY,
+                    is_fractional: false,
+                    base: ast::NumberBase::Decimal,
+                    suffix: Some(ast::NumberSuffix::U8),
+                })),
+            },
+            _,
+        };
+
+        test_lexer! {
+            "(0xfff32)",
+            _,
+            ast::Token {
+                span: span!(1, 8),
+                kind: ast::Kind::Number(ast::NumberSource::Text(ast::NumberText {
+                    source_id: SourceId::EMPTY,
+                    is_fractional: false,
+                    base: ast::NumberBase::Hex,
+                    suffix: None,
                 })),
             },
             _,
@@ -1129,6 +1169,7 @@ mod tests {
                     source_id: SourceId::EMPTY,
                     is_fractional: false,
                     base: ast::NumberBase::Decimal,
+                    suffix: None,
                 })),
             },
             ast::Token {
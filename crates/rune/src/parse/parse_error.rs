@@ -107,6 +107,8 @@ pub enum ParseErrorKind {
     },
     #[error("bad number literal")]
     BadNumber,
+    #[error("number literal out of bounds for `{suffix}`")]
+    BadNumberSuffixOutOfBounds { suffix: ast::NumberSuffix },
     #[error("can only specify one attribute named `{name}`")]
     MultipleMatchingAttributes { name: &'static str },
     #[error("missing source id `{source_id}`")]
@@ -4,12 +4,13 @@
 use crate::ast;
 use crate::ast::{Span, Spanned};
 use crate::collections::{HashMap, HashSet};
+use crate::compile::attrs::AllowLints;
 use crate::compile::ir;
 use crate::compile::{
-    CaptureMeta, CompileError, CompileErrorKind, CompileVisitor, ComponentRef, EmptyMeta,
-    ImportStep, IntoComponent, IrBudget, IrCompiler, IrInterpreter, Item, ItemMeta, Location,
-    ModMeta, Names, PrivMeta, PrivMetaKind, SourceMeta, StructMeta, TupleMeta, UnitBuilder,
-    Visibility,
+    BuildStats, CaptureMeta, CompileError, CompileErrorKind, CompileVisitor, ComponentRef,
+    EmptyMeta, ImportStep, IntoComponent, IrBudget, IrCompiler, IrInterpreter, Item, ItemMeta,
+    Location, ModMeta, Names, Options, PrivMeta, PrivMetaKind, SourceMeta, StructMeta, TupleMeta,
+    UnitBuilder, Visibility,
 };
 use crate::macros::Storage;
 use crate::parse::{Id, NonZeroId, Opaque, Resolve, ResolveContext};
@@ -21,6 +22,7 @@ use std::collections::VecDeque;
 use std::fmt;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
+use std::time::Instant;
 
 pub use self::query_error::{QueryError, QueryErrorKind};
 
@@ -128,9 +130,13 @@ pub(crate) struct QueryInner {
     names: Names,
     /// Modules and associated metadata.
     modules: HashMap<Item, Arc<ModMeta>>,
+    /// Counters collected during the build.
+    stats: BuildStats,
 }
 
 pub(crate) struct Query<'a> {
+    /// The context used for the build.
+    pub(crate) context: &'a Context,
     /// The current unit being built.
     pub(crate) unit: &'a mut UnitBuilder,
     /// Cache of constants that have been expanded.
@@ -139,6 +145,8 @@ pub(crate) struct Query<'a> {
     pub(crate) storage: &'a mut Storage,
     /// Sources available.
     pub(crate) sources: &'a mut Sources,
+    /// Options used for the build.
+    pub(crate) options: &'a Options,
     /// Visitor for the compiler meta.
     pub(crate) visitor: &'a mut dyn CompileVisitor,
     /// Shared id generator.
@@ -150,19 +158,23 @@ pub(crate) struct Query<'a> {
 impl<'a> Query<'a> {
     /// Construct a new compilation context.
     pub(crate) fn new(
+        context: &'a Context,
         unit: &'a mut UnitBuilder,
         consts: &'a mut Consts,
         storage: &'a mut Storage,
         sources: &'a mut Sources,
+        options: &'a Options,
         visitor: &'a mut dyn CompileVisitor,
         gen: &'a Gen,
         inner: &'a mut QueryInner,
     ) -> Self {
         Self {
+            context,
             unit,
             consts,
             storage,
             sources,
+            options,
             visitor,
             gen,
             inner,
@@ -172,16 +184,66 @@ impl<'a> Query<'a> {
     /// Reborrow the query engine from a reference to `self`.
     pub(crate) fn borrow(&mut self) -> Query<'_> {
         Query {
+            context: self.context,
             unit: self.unit,
             consts: self.consts,
             storage: self.storage,
             sources: self.sources,
+            options: self.options,
             visitor: self.visitor,
             gen: self.gen,
             inner: self.inner,
         }
     }
 
+    /// Measure the time spent in nested phases of the build.
+    pub(crate) fn enable_timings(&mut self) {
+        self.inner.stats.timed = true;
+    }
+
+    /// Start timing a macro expansion, if timings are enabled.
+    pub(crate) fn start_macro_expansion(&self) -> Option<Instant> {
+        self.inner.stats.timed.then(Instant::now)
+    }
+
+    /// Record that a macro has been expanded.
+    pub(crate) fn record_macro_expansion(&mut self, start: Option<Instant>) {
+        self.inner.stats.macro_expansions += 1;
+
+        if let Some(start) = start {
+            self.inner.stats.macro_expansion_time += start.elapsed();
+        }
+    }
+
+    /// Start timing a constant evaluation, if timings are enabled and no other
+    /// evaluation is already being timed.
+    pub(crate) fn start_const_eval(&mut self) -> Option<Instant> {
+        let stats = &mut self.inner.stats;
+        stats.const_eval_depth += 1;
+        (stats.timed && stats.const_eval_depth == 1).then(Instant::now)
+    }
+
+    /// Record budget consumed by constant evaluation.
+    pub(crate) fn record_const_eval(&mut self, used: usize, start: Option<Instant>) {
+        let stats = &mut self.inner.stats;
+        stats.const_eval_budget += used;
+        stats.const_eval_depth -= 1;
+
+        if let Some(start) = start {
+            stats.const_eval_time += start.elapsed();
+        }
+    }
+
+    /// Take the counters collected during the build.
+    pub(crate) fn take_stats(&mut self) -> BuildStats {
+        std::mem::take(&mut self.inner.stats)
+    }
+
+    /// The number of entries queued up to be built.
+    pub(crate) fn build_queue_len(&self) -> usize {
+        self.inner.queue.len()
+    }
+
     /// Insert the given compile meta.
     pub(crate) fn insert_meta(&mut self, span: Span, meta: PrivMeta) -> Result<(), QueryError> {
         let item = meta.item.item.clone();
@@ -454,12 +516,16 @@ impl<'a> Query<'a> {
     }
 
     /// Add a new enum item.
-    pub(crate) fn index_enum(&mut self, item: &Arc<ItemMeta>) -> Result<(), QueryError> {
+    pub(crate) fn index_enum(
+        &mut self,
+        item: &Arc<ItemMeta>,
+        variants: Vec<Box<str>>,
+    ) -> Result<(), QueryError> {
         tracing::trace!("new enum: {:?}", item.item);
 
         self.index(IndexedEntry {
             item: item.clone(),
-            indexed: Indexed::Enum,
+            indexed: Indexed::Enum(Enum { variants }),
         });
 
         Ok(())
@@ -470,12 +536,13 @@ impl<'a> Query<'a> {
         &mut self,
         item: &Arc<ItemMeta>,
         ast: Box<ast::ItemStruct>,
+        debug: bool,
     ) -> Result<(), QueryError> {
         tracing::trace!("new struct: {:?}", item.item);
 
         self.index(IndexedEntry {
             item: item.clone(),
-            indexed: Indexed::Struct(Struct::new(ast)),
+            indexed: Indexed::Struct(Struct::new(ast, debug)),
         });
 
         Ok(())
@@ -487,12 +554,13 @@ impl<'a> Query<'a> {
         item: &Arc<ItemMeta>,
         enum_id: Id,
         ast: ast::ItemVariant,
+        debug: bool,
     ) -> Result<(), QueryError> {
         tracing::trace!("new variant: {:?}", item.item);
 
         self.index(IndexedEntry {
             item: item.clone(),
-            indexed: Indexed::Variant(Variant::new(enum_id, ast)),
+            indexed: Indexed::Variant(Variant::new(enum_id, ast, debug)),
         });
 
         Ok(())
@@ -506,6 +574,7 @@ impl<'a> Query<'a> {
         captures: Arc<[CaptureMeta]>,
         call: Call,
         do_move: bool,
+        allow: AllowLints,
     ) -> Result<(), QueryError> {
         tracing::trace!("new closure: {:?}", item.item);
 
@@ -516,6 +585,7 @@ impl<'a> Query<'a> {
                 captures,
                 call,
                 do_move,
+                allow,
             }),
         });
 
@@ -530,6 +600,7 @@ impl<'a> Query<'a> {
         captures: Arc<[CaptureMeta]>,
         call: Call,
         do_move: bool,
+        allow: AllowLints,
     ) -> Result<(), QueryError> {
         tracing::trace!("new closure: {:?}", item.item);
 
@@ -540,6 +611,7 @@ impl<'a> Query<'a> {
                 captures,
                 call,
                 do_move,
+                allow,
             }),
         });
 
@@ -719,12 +791,19 @@ impl<'a> Query<'a> {
             None => None,
         };
 
-        if let Some(new) = self.import(span, &qp.module, &item, Used::Used)? {
-            return Ok(Named {
-                local,
-                item: new,
-                generics,
-            });
+        let item = match self.import(span, &qp.module, &item, Used::Used)? {
+            Some(new) => new,
+            None => item,
+        };
+
+        if let Some(module) = self.unit.denied_module(&item) {
+            return Err(CompileError::new(
+                span,
+                CompileErrorKind::DeniedModule {
+                    item,
+                    module: module.clone(),
+                },
+            ));
         }
 
         Ok(Named {
@@ -940,8 +1019,9 @@ impl<'a> Query<'a> {
         } = entry;
 
         let kind = match indexed {
-            Indexed::Enum => PrivMetaKind::Enum {
+            Indexed::Enum(en) => PrivMetaKind::Enum {
                 type_hash: Hash::type_hash(&query_item.item),
+                variants: en.variants,
             },
             Indexed::Variant(variant) => {
                 let enum_item = self.item_for((query_item.location.span, variant.enum_id))?;
@@ -953,12 +1033,16 @@ impl<'a> Query<'a> {
                     &query_item.item,
                     variant.ast.body,
                     Some(&enum_item.item),
+                    variant.debug,
                     resolve_context!(self),
                 )?
             }
-            Indexed::Struct(st) => {
-                struct_into_item_decl(&query_item.item, st.ast.body, None, resolve_context!(self))?
-            }
+            Indexed::Struct(st) => struct_into_item_decl(
+                &query_item.item,
+                st.ast.body,
+                st.debug,
+                resolve_context!(self),
+            )?,
             Indexed::Function(f) => {
                 self.inner.queue.push_back(BuildEntry {
                     location: query_item.location,
@@ -1008,16 +1092,20 @@ impl<'a> Query<'a> {
                 }
             }
             Indexed::Const(c) => {
-                let mut const_compiler = IrInterpreter {
-                    budget: IrBudget::new(1_000_000),
-                    scopes: Default::default(),
-                    module: &c.module,
-                    item: &query_item.item,
-                    q: self.borrow(),
+                let const_value = {
+                    let mut const_compiler = IrInterpreter {
+                        budget: IrBudget::new(1_000_000),
+                        scopes: Default::default(),
+                        module: &c.module,
+                        item: &query_item.item,
+                        start: self.start_const_eval(),
+                        q: self.borrow(),
+                        calls: Vec::new(),
+                    };
+
+                    const_compiler.eval_const(&c.ir, used)?
                 };
 
-                let const_value = const_compiler.eval_const(&c.ir, used)?;
-
                 if used.is_unused() {
                     self.inner.queue.push_back(BuildEntry {
                         location: query_item.location,
@@ -1201,16 +1289,27 @@ impl<'a> Query<'a> {
         base: &Item,
         local: &ast::Ident,
     ) -> Result<Item, CompileError> {
+        let local = local.resolve(resolve_context!(self))?;
+        Ok(self.convert_initial_item(context, module, base, local))
+    }
+
+    /// Convert the initial component of a path into the item it refers to, as
+    /// seen from the item `base` in the given module.
+    pub(crate) fn convert_initial_item(
+        &self,
+        context: &Context,
+        module: &ModMeta,
+        base: &Item,
+        local: &str,
+    ) -> Item {
         debug_assert!(base.starts_with(&module.item));
         let mut base = base.clone();
 
-        let local = local.resolve(resolve_context!(self))?;
-
         while base.starts_with(&module.item) {
             base.push(local);
 
             if self.inner.names.contains(&base) {
-                return Ok(base);
+                return base;
             }
 
             let c = base.pop();
@@ -1222,14 +1321,14 @@ impl<'a> Query<'a> {
         }
 
         if let Some(item) = self.unit.prelude().get(local) {
-            return Ok(item.clone());
+            return item.clone();
         }
 
         if context.contains_crate(local) {
-            return Ok(Item::with_crate(local));
+            return Item::with_crate(local);
         }
 
-        Ok(module.item.extended(local))
+        module.item.extended(local)
     }
 
     /// Check that the given item is accessible from the given module.
@@ -1314,7 +1413,7 @@ impl Default for Used {
 
 #[derive(Debug, Clone)]
 pub(crate) enum Indexed {
-    Enum,
+    Enum(Enum),
     Struct(Struct),
     Variant(Variant),
     Function(Function),
@@ -1325,6 +1424,12 @@ pub(crate) enum Indexed {
     Import(Import),
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct Enum {
+    /// The names of the variants of the enum, in declaration order.
+    pub(crate) variants: Vec<Box<str>>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct Import {
     /// The import entry.
@@ -1339,12 +1444,14 @@ pub(crate) struct Import {
 pub(crate) struct Struct {
     /// The ast of the struct.
     ast: Box<ast::ItemStruct>,
+    /// Whether the struct is formatted structurally when debug printed.
+    debug: bool,
 }
 
 impl Struct {
     /// Construct a new struct entry.
-    pub(crate) fn new(ast: Box<ast::ItemStruct>) -> Self {
-        Self { ast }
+    pub(crate) fn new(ast: Box<ast::ItemStruct>, debug: bool) -> Self {
+        Self { ast, debug }
     }
 }
 
@@ -1354,12 +1461,18 @@ pub(crate) struct Variant {
     enum_id: Id,
     /// Ast for declaration.
     ast: ast::ItemVariant,
+    /// Whether the variant is formatted structurally when debug printed.
+    debug: bool,
 }
 
 impl Variant {
     /// Construct a new variant.
-    pub(crate) fn new(enum_id: Id, ast: ast::ItemVariant) -> Self {
-        Self { enum_id, ast }
+    pub(crate) fn new(enum_id: Id, ast: ast::ItemVariant, debug: bool) -> Self {
+        Self {
+            enum_id,
+            ast,
+            debug,
+        }
     }
 }
 
@@ -1380,6 +1493,9 @@ pub(crate) struct InstanceFunction {
     pub(crate) instance_span: Span,
     /// Calling convention of the instance function.
     pub(crate) call: Call,
+    /// Whether the function has a `#[call]` annotation, making it the
+    /// [Protocol::CALL][crate::runtime::Protocol::CALL] handler of its type.
+    pub(crate) is_call: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -1392,6 +1508,8 @@ pub(crate) struct Closure {
     pub(crate) call: Call,
     /// If the closure moves its captures.
     pub(crate) do_move: bool,
+    /// The lints suppressed on the function the closure is declared in.
+    pub(crate) allow: AllowLints,
 }
 
 #[derive(Debug, Clone)]
@@ -1404,6 +1522,8 @@ pub(crate) struct AsyncBlock {
     pub(crate) call: Call,
     /// If the block moves its captures.
     pub(crate) do_move: bool,
+    /// The lints suppressed on the function the block is declared in.
+    pub(crate) allow: AllowLints,
 }
 
 #[derive(Debug, Clone)]
@@ -1522,7 +1642,7 @@ impl fmt::Display for Named<'_> {
 }
 
 /// Construct metadata for an empty body.
-fn unit_body_meta(item: &Item, enum_item: Option<&Item>) -> PrivMetaKind {
+fn unit_body_meta(item: &Item, enum_item: Option<&Item>, debug: bool) -> PrivMetaKind {
     let type_hash = Hash::type_hash(item);
 
     let empty = EmptyMeta {
@@ -1534,8 +1654,13 @@ fn unit_body_meta(item: &Item, enum_item: Option<&Item>) -> PrivMetaKind {
             type_hash,
             enum_item: enum_item.clone(),
             empty,
+            debug,
+        },
+        None => PrivMetaKind::UnitStruct {
+            type_hash,
+            empty,
+            debug,
         },
-        None => PrivMetaKind::UnitStruct { type_hash, empty },
     }
 }
 
@@ -1544,6 +1669,7 @@ fn tuple_body_meta(
     item: &Item,
     enum_item: Option<&Item>,
     tuple: ast::Parenthesized<ast::Field, T![,]>,
+    debug: bool,
 ) -> PrivMetaKind {
     let type_hash = Hash::type_hash(item);
 
@@ -1557,8 +1683,13 @@ fn tuple_body_meta(
             type_hash,
             enum_item: enum_item.clone(),
             tuple,
+            debug,
+        },
+        None => PrivMetaKind::TupleStruct {
+            type_hash,
+            tuple,
+            debug,
         },
-        None => PrivMetaKind::TupleStruct { type_hash, tuple },
     }
 }
 
@@ -1568,6 +1699,7 @@ fn struct_body_meta(
     enum_item: Option<&Item>,
     ctx: ResolveContext<'_>,
     st: ast::Braced<ast::Field, T![,]>,
+    debug: bool,
 ) -> Result<PrivMetaKind, QueryError> {
     let type_hash = Hash::type_hash(item);
 
@@ -1585,8 +1717,13 @@ fn struct_body_meta(
             type_hash,
             enum_item: enum_item.clone(),
             st,
+            debug,
+        },
+        None => PrivMetaKind::Struct {
+            type_hash,
+            st,
+            debug,
         },
-        None => PrivMetaKind::Struct { type_hash, st },
     })
 }
 
@@ -1595,12 +1732,13 @@ fn variant_into_item_decl(
     item: &Item,
     body: ast::ItemVariantBody,
     enum_item: Option<&Item>,
+    debug: bool,
     ctx: ResolveContext<'_>,
 ) -> Result<PrivMetaKind, QueryError> {
     Ok(match body {
-        ast::ItemVariantBody::UnitBody => unit_body_meta(item, enum_item),
-        ast::ItemVariantBody::TupleBody(tuple) => tuple_body_meta(item, enum_item, tuple),
-        ast::ItemVariantBody::StructBody(st) => struct_body_meta(item, enum_item, ctx, st)?,
+        ast::ItemVariantBody::UnitBody => unit_body_meta(item, enum_item, debug),
+        ast::ItemVariantBody::TupleBody(tuple) => tuple_body_meta(item, enum_item, tuple, debug),
+        ast::ItemVariantBody::StructBody(st) => struct_body_meta(item, enum_item, ctx, st, debug)?,
     })
 }
 
@@ -1608,13 +1746,13 @@ fn variant_into_item_decl(
 fn struct_into_item_decl(
     item: &Item,
     body: ast::ItemStructBody,
-    enum_item: Option<&Item>,
+    debug: bool,
     ctx: ResolveContext<'_>,
 ) -> Result<PrivMetaKind, QueryError> {
     Ok(match body {
-        ast::ItemStructBody::UnitBody => unit_body_meta(item, enum_item),
-        ast::ItemStructBody::TupleBody(tuple) => tuple_body_meta(item, enum_item, tuple),
-        ast::ItemStructBody::StructBody(st) => struct_body_meta(item, enum_item, ctx, st)?,
+        ast::ItemStructBody::UnitBody => unit_body_meta(item, None, debug),
+        ast::ItemStructBody::TupleBody(tuple) => tuple_body_meta(item, None, tuple, debug),
+        ast::ItemStructBody::StructBody(st) => struct_body_meta(item, None, ctx, st, debug)?,
     })
 }
 
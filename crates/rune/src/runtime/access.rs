@@ -1,4 +1,5 @@
 use crate::runtime::{AnyObjError, RawStr};
+use serde::ser;
 use std::cell::Cell;
 use std::fmt;
 use std::future::Future;
@@ -65,12 +66,39 @@ pub(crate) enum AccessKind {
 #[error("cannot read, value is {0}")]
 pub struct NotAccessibleRef(Snapshot);
 
+impl NotAccessibleRef {
+    /// Get a snapshot of how the value was being accessed when the error was
+    /// raised.
+    pub fn snapshot(&self) -> Snapshot {
+        self.0
+    }
+
+    /// Test if the access failed because the value has been moved out, such
+    /// as when it was previously passed by value to a native function.
+    pub fn is_moved(&self) -> bool {
+        self.0.is_taken()
+    }
+}
+
 /// Error raised when tried to access for exclusive access but it was not
 /// accessible.
 #[derive(Debug, Error)]
 #[error("cannot write, value is {0}")]
 pub struct NotAccessibleMut(Snapshot);
 
+impl NotAccessibleMut {
+    /// Get a snapshot of how the value was being accessed when the error was
+    /// raised.
+    pub fn snapshot(&self) -> Snapshot {
+        self.0
+    }
+
+    /// Test if the access failed because the value has been moved out.
+    pub fn is_moved(&self) -> bool {
+        self.0.is_taken()
+    }
+}
+
 /// Error raised when tried to access the guarded data for taking.
 ///
 /// This requires exclusive access, but it's a scenario we structure separately
@@ -79,11 +107,57 @@ pub struct NotAccessibleMut(Snapshot);
 #[error("cannot take, value is {0}")]
 pub struct NotAccessibleTake(Snapshot);
 
-/// Snapshot that can be used to indicate how the value was being accessed at
-/// the time of an error.
-#[derive(Debug)]
+impl NotAccessibleTake {
+    /// Get a snapshot of how the value was being accessed when the error was
+    /// raised.
+    pub fn snapshot(&self) -> Snapshot {
+        self.0
+    }
+}
+
+/// Snapshot of how a value was being accessed at a given point in time, such
+/// as at the time of an error.
+///
+/// See [Shared::snapshot][crate::runtime::Shared::snapshot].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(transparent)]
-struct Snapshot(isize);
+pub struct Snapshot(isize);
+
+impl Snapshot {
+    /// Test if the value could be borrowed for shared access.
+    pub fn is_readable(&self) -> bool {
+        self.0 >> 1 <= 0
+    }
+
+    /// Test if the value could be borrowed for exclusive access.
+    pub fn is_writable(&self) -> bool {
+        self.0 >> 1 == 0
+    }
+
+    /// Test if the value is exclusively accessed.
+    pub fn is_exclusive(&self) -> bool {
+        self.0 >> 1 == 1
+    }
+
+    /// Test if the value was taken, after which it can no longer be accessed.
+    pub fn is_taken(&self) -> bool {
+        self.0 >> 1 == TAKEN
+    }
+
+    /// Test if the value is an external reference.
+    pub fn is_ref(&self) -> bool {
+        self.0 & IS_REF_MASK != 0
+    }
+
+    /// Get the number of live shared accesses to the value, or `None` if it
+    /// isn't being shared.
+    pub fn shared_count(&self) -> Option<usize> {
+        match self.0 >> 1 {
+            n if n < 0 => Some(n.unsigned_abs()),
+            _ => None,
+        }
+    }
+}
 
 impl fmt::Display for Snapshot {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -163,6 +237,12 @@ impl Access {
         self.get() == TAKEN
     }
 
+    /// Get a snapshot of the current access state.
+    #[inline]
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        Snapshot(self.0.get())
+    }
+
     /// Mark that we want shared access to the given access token.
     ///
     /// # Safety
@@ -285,7 +365,7 @@ impl Access {
 
 impl fmt::Debug for Access {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", Snapshot(self.get()))
+        write!(f, "{}", self.snapshot())
     }
 }
 
@@ -329,6 +409,17 @@ impl<'a, T: ?Sized> BorrowRef<'a, T> {
     /// assert_eq!(&*value, &[1u32, 2u32][..]);
     /// # Ok(()) }
     /// ```
+    ///
+    /// The mapped reference can't outlive the value it was borrowed from:
+    ///
+    /// ```compile_fail
+    /// use rune::runtime::{BorrowRef, Shared};
+    ///
+    /// let value: BorrowRef<u32> = {
+    ///     let vec = Shared::<Vec<u32>>::new(vec![1, 2, 3, 4]);
+    ///     BorrowRef::map(vec.borrow_ref().unwrap(), |vec| &vec[0])
+    /// };
+    /// ```
     pub fn map<M, U: ?Sized>(this: Self, m: M) -> BorrowRef<'a, U>
     where
         M: FnOnce(&T) -> &U,
@@ -382,6 +473,18 @@ where
     }
 }
 
+impl<T: ?Sized> ser::Serialize for BorrowRef<'_, T>
+where
+    T: ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        ser::Serialize::serialize(&**self, serializer)
+    }
+}
+
 /// A guard around some specific access access.
 #[repr(transparent)]
 pub struct AccessGuard<'a>(&'a Access);
@@ -543,7 +646,8 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{Access, AccessKind};
+    use super::{Access, AccessKind, BorrowMut, BorrowRef};
+    use crate::runtime::{AccessError, Shared};
 
     #[test]
     fn test_non_ref() {
@@ -614,4 +718,149 @@ mod tests {
             assert!(access.is_exclusive());
         }
     }
+
+    #[test]
+    fn test_snapshot() {
+        let shared = Shared::new(42u32);
+        let other = shared.clone();
+
+        let snapshot = shared.snapshot();
+        assert!(snapshot.is_readable());
+        assert!(snapshot.is_writable());
+        assert!(!snapshot.is_exclusive());
+        assert!(!snapshot.is_taken());
+        assert!(!snapshot.is_ref());
+        assert_eq!(snapshot.shared_count(), None);
+        assert_eq!(snapshot.to_string(), "fully accessible");
+
+        let first = shared.borrow_ref().unwrap();
+        let second = shared.borrow_ref().unwrap();
+
+        let snapshot = shared.snapshot();
+        assert!(snapshot.is_readable());
+        assert!(!snapshot.is_writable());
+        assert_eq!(snapshot.shared_count(), Some(2));
+        assert_eq!(snapshot.to_string(), "shared by 2");
+
+        match shared.borrow_mut() {
+            Err(AccessError::NotAccessibleMut { error }) => {
+                assert_eq!(error.snapshot(), snapshot);
+            }
+            _ => panic!("expected write to fail"),
+        }
+
+        drop(first);
+        assert_eq!(shared.snapshot().shared_count(), Some(1));
+        drop(second);
+        assert!(shared.snapshot().is_writable());
+
+        let guard = shared.borrow_mut().unwrap();
+
+        let snapshot = shared.snapshot();
+        assert!(!snapshot.is_readable());
+        assert!(!snapshot.is_writable());
+        assert!(snapshot.is_exclusive());
+        assert_eq!(snapshot.shared_count(), None);
+        assert!(!shared.is_readable());
+
+        match shared.borrow_ref() {
+            Err(AccessError::NotAccessibleRef { error }) => {
+                assert!(error.snapshot().is_exclusive());
+                assert!(!error.is_moved());
+            }
+            _ => panic!("expected read to fail"),
+        }
+
+        drop(guard);
+        assert_eq!(shared.snapshot(), other.snapshot());
+        assert!(other.snapshot().is_writable());
+
+        assert_eq!(shared.take().unwrap(), 42);
+
+        let snapshot = other.snapshot();
+        assert!(snapshot.is_taken());
+        assert!(!snapshot.is_readable());
+        assert!(!snapshot.is_writable());
+        assert!(!snapshot.is_exclusive());
+        assert_eq!(snapshot.shared_count(), None);
+        assert_eq!(snapshot.to_string(), "moved");
+
+        match other.borrow_ref() {
+            Err(AccessError::NotAccessibleRef { error }) => {
+                assert!(error.snapshot().is_taken());
+                assert!(error.is_moved());
+            }
+            _ => panic!("expected read to fail"),
+        }
+
+        match other.take() {
+            Err(AccessError::NotAccessibleTake { error }) => {
+                assert!(error.snapshot().is_taken());
+            }
+            _ => panic!("expected take to fail"),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_ref() {
+        unsafe {
+            let access = Access::new(true);
+            let snapshot = access.snapshot();
+            assert!(snapshot.is_ref());
+            assert!(snapshot.is_writable());
+            assert_eq!(snapshot.to_string(), "fully accessible (ref)");
+
+            match access.exclusive(AccessKind::Owned) {
+                Err(error) => {
+                    assert!(error.snapshot().is_ref());
+                    assert!(error.snapshot().is_writable());
+                }
+                Ok(..) => panic!("expected owned access to a reference to fail"),
+            }
+
+            let guard = access.exclusive(AccessKind::Any).unwrap();
+            assert!(access.snapshot().is_exclusive());
+            assert_eq!(access.snapshot().to_string(), "exclusively accessed (ref)");
+            drop(guard);
+
+            assert_eq!(access.snapshot(), snapshot);
+        }
+    }
+
+    #[test]
+    fn test_map_ref() {
+        let shared = Shared::new((1u32, String::from("hello")));
+
+        let first = BorrowRef::map(shared.borrow_ref().unwrap(), |(_, s)| s.as_str());
+        let second = BorrowRef::map(shared.borrow_ref().unwrap(), |(n, _)| n);
+
+        assert_eq!(&*first, "hello");
+        assert_eq!(*second, 1);
+        assert!(shared.is_readable());
+        assert!(!shared.is_writable());
+
+        drop(first);
+        assert!(!shared.is_writable());
+
+        drop(second);
+        assert!(shared.is_readable());
+        assert!(shared.is_writable());
+    }
+
+    #[test]
+    fn test_map_mut() {
+        let shared = Shared::new(vec![1u32, 2, 3, 4]);
+
+        let mut slice = BorrowMut::map(shared.borrow_mut().unwrap(), |vec| &mut vec[1..3]);
+        assert!(!shared.is_readable());
+        assert!(!shared.is_writable());
+
+        slice.copy_from_slice(&[20, 30]);
+        slice[0] += 1;
+        drop(slice);
+
+        assert!(shared.is_readable());
+        assert!(shared.is_writable());
+        assert_eq!(&*shared.borrow_ref().unwrap(), &[1, 21, 30, 4]);
+    }
 }
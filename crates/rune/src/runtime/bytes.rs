@@ -42,6 +42,11 @@ impl Bytes {
         Self { bytes }
     }
 
+    /// Copy the bytes into a new vector.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
     /// Do something with the bytes.
     pub fn extend(&mut self, other: &Self) {
         self.bytes.extend(other.bytes.iter().copied());
@@ -106,6 +111,12 @@ impl From<Vec<u8>> for Bytes {
     }
 }
 
+impl From<Bytes> for Vec<u8> {
+    fn from(bytes: Bytes) -> Self {
+        bytes.bytes
+    }
+}
+
 impl fmt::Debug for Bytes {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_list().entries(&self.bytes).finish()
@@ -0,0 +1,75 @@
+use crate::runtime::{FunctionHandler, VmError, VmErrorKind};
+use crate::Hash;
+use std::sync::Arc;
+
+/// A set of capabilities granted to a virtual machine.
+///
+/// Native functions can be marked as requiring a capability with
+/// [Module::capability][crate::Module::capability]. Calling such a function
+/// from a virtual machine which hasn't been granted the capability results in
+/// [VmErrorKind::CapabilityDenied].
+///
+/// Virtual machines which are called into while another virtual machine is
+/// running, such as when a native function calls a closure, and which don't
+/// have a set of capabilities of their own, use the capabilities of the
+/// virtual machine which called them. See
+/// [Vm::set_capabilities][crate::Vm::set_capabilities].
+#[derive(Debug, Default, Clone)]
+pub struct Capabilities {
+    granted: Vec<Box<str>>,
+}
+
+impl Capabilities {
+    /// Construct an empty set of capabilities.
+    pub const fn new() -> Self {
+        Self {
+            granted: Vec::new(),
+        }
+    }
+
+    /// Grant the given capability.
+    pub fn grant(&mut self, capability: &str) {
+        if !self.is_granted(capability) {
+            self.granted.push(capability.into());
+        }
+    }
+
+    /// Revoke the given capability.
+    pub fn revoke(&mut self, capability: &str) {
+        self.granted.retain(|c| c.as_ref() != capability);
+    }
+
+    /// Test if the given capability has been granted.
+    pub fn is_granted(&self, capability: &str) -> bool {
+        self.granted.iter().any(|c| c.as_ref() == capability)
+    }
+
+    /// Test if no capabilities have been granted.
+    pub fn is_empty(&self) -> bool {
+        self.granted.is_empty()
+    }
+
+    /// Iterate over all granted capabilities.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.granted.iter().map(|c| c.as_ref())
+    }
+}
+
+/// Wrap the given handler so that it can only be called if the given
+/// capability has been granted to the running virtual machine.
+pub(crate) fn gate(
+    capability: Box<str>,
+    hash: Hash,
+    handler: Arc<FunctionHandler>,
+) -> Arc<FunctionHandler> {
+    Arc::new(move |stack, args| {
+        if !crate::runtime::env::is_granted(&capability) {
+            return Err(VmError::from(VmErrorKind::CapabilityDenied {
+                capability: capability.clone(),
+                hash,
+            }));
+        }
+
+        handler(stack, args)
+    })
+}
@@ -1,7 +1,7 @@
 use crate::collections::HashMap;
 use crate::runtime::{
-    Bytes, FromValue, Object, Shared, StaticString, ToValue, Tuple, TypeInfo, Value, Vec, VmError,
-    VmErrorKind,
+    Bytes, FromValue, Object, Shared, StaticString, ToValue, Tuple, TypeInfo, Value, VariantData,
+    VariantRtti, Vec, VmError, VmErrorKind,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -36,6 +36,10 @@ pub enum ConstValue {
     Object(HashMap<String, ConstValue>),
     /// An option.
     Option(Option<Box<ConstValue>>),
+    /// A result.
+    Result(Result<Box<ConstValue>, Box<ConstValue>>),
+    /// A unit or tuple variant of an enum.
+    Variant(Arc<VariantRtti>, Box<[ConstValue]>),
 }
 
 impl ConstValue {
@@ -58,6 +62,10 @@ impl ConstValue {
             Self::Option(option) => {
                 Value::Option(Shared::new(option.map(|some| some.into_value())))
             }
+            Self::Result(result) => Value::Result(Shared::new(match result {
+                Ok(ok) => Ok(ok.into_value()),
+                Err(err) => Err(err.into_value()),
+            })),
             Self::Vec(vec) => {
                 let mut v = Vec::with_capacity(vec.len());
 
@@ -85,6 +93,19 @@ impl ConstValue {
 
                 Value::Object(Shared::new(o))
             }
+            Self::Variant(rtti, args) => {
+                if args.is_empty() {
+                    return Value::unit_variant(rtti);
+                }
+
+                let mut t = vec::Vec::with_capacity(args.len());
+
+                for value in vec::Vec::from(args) {
+                    t.push(value.into_value());
+                }
+
+                Value::tuple_variant(rtti, t)
+            }
         }
     }
 
@@ -112,6 +133,8 @@ impl ConstValue {
             Self::Tuple(..) => TypeInfo::StaticType(crate::runtime::TUPLE_TYPE),
             Self::Object(..) => TypeInfo::StaticType(crate::runtime::OBJECT_TYPE),
             Self::Option(..) => TypeInfo::StaticType(crate::runtime::OPTION_TYPE),
+            Self::Result(..) => TypeInfo::StaticType(crate::runtime::RESULT_TYPE),
+            Self::Variant(rtti, ..) => TypeInfo::Variant(rtti.clone()),
         }
     }
 }
@@ -134,6 +157,10 @@ impl FromValue for ConstValue {
                 Some(some) => Some(Box::new(Self::from_value(some)?)),
                 None => None,
             }),
+            Value::Result(result) => Self::Result(match result.take()? {
+                Ok(ok) => Ok(Box::new(Self::from_value(ok)?)),
+                Err(err) => Err(Box::new(Self::from_value(err)?)),
+            }),
             Value::Bytes(b) => {
                 let b = b.take()?;
                 Self::Bytes(b)
@@ -168,6 +195,27 @@ impl FromValue for ConstValue {
 
                 Self::Object(const_object)
             }
+            Value::Variant(variant) => {
+                let variant = variant.take()?;
+
+                let tuple = match variant.data {
+                    VariantData::Unit => Tuple::from(vec::Vec::new()),
+                    VariantData::Tuple(tuple) => tuple,
+                    VariantData::Struct(..) => {
+                        return Err(VmError::from(VmErrorKind::ConstNotSupported {
+                            actual: TypeInfo::Variant(variant.rtti),
+                        }))
+                    }
+                };
+
+                let mut const_tuple = vec::Vec::with_capacity(tuple.len());
+
+                for value in vec::Vec::from(tuple.into_inner()) {
+                    const_tuple.push(Self::from_value(value)?);
+                }
+
+                Self::Variant(variant.rtti, const_tuple.into_boxed_slice())
+            }
             value => {
                 return Err(VmError::from(VmErrorKind::ConstNotSupported {
                     actual: value.type_info()?,
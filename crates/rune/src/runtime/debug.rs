@@ -6,6 +6,7 @@ use crate::compile::Item;
 use crate::runtime::DebugLabel;
 use crate::{Hash, SourceId};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 /// Debug information about a unit.
@@ -16,8 +17,8 @@ pub struct DebugInfo {
     pub instructions: Vec<DebugInst>,
     /// Function signatures.
     pub functions: HashMap<Hash, DebugSignature>,
-    /// Reverse lookup of a function.
-    pub functions_rev: HashMap<usize, Hash>,
+    /// Reverse lookup of a function, ordered by the offset it starts at.
+    pub functions_rev: BTreeMap<usize, Hash>,
 }
 
 impl DebugInfo {
@@ -32,6 +33,14 @@ impl DebugInfo {
         let signature = self.functions.get(&hash)?;
         Some((hash, signature))
     }
+
+    /// Get the function whose body contains the given instruction pointer.
+    pub fn function_containing(&self, ip: usize) -> Option<(Hash, &DebugSignature)> {
+        let (_, hash) = self.functions_rev.range(..=ip).next_back()?;
+
+        let signature = self.functions.get(hash)?;
+        Some((*hash, signature))
+    }
 }
 
 /// Debug information for every instruction.
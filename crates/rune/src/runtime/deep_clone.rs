@@ -0,0 +1,247 @@
+use crate::collections::HashMap;
+use crate::runtime::env;
+use crate::runtime::{
+    EnvProtocolCaller, Object, Protocol, ProtocolCaller, Range, Shared, Struct, Tuple, TupleStruct,
+    UnitStruct, Value, Variant, VariantData, Vec, VmError, VmErrorKind,
+};
+use crate::Hash;
+
+/// State used when deep cloning a value.
+///
+/// Keeps track of the shared values which have already been cloned, so that
+/// values which are referenced more than once, including through cycles, are
+/// only cloned once and remain shared in the copy.
+#[derive(Default)]
+pub(crate) struct DeepClone {
+    seen: HashMap<*const (), Value>,
+    share_native: bool,
+}
+
+impl DeepClone {
+    /// Construct a deep clone which shares native values that don't implement
+    /// [Protocol::CLONE] with the copy, instead of raising an error.
+    pub(crate) fn sharing_native() -> Self {
+        Self {
+            seen: HashMap::new(),
+            share_native: true,
+        }
+    }
+
+    /// Deep clone the given value.
+    pub(crate) fn clone_value(&mut self, value: &Value) -> Result<Value, VmError> {
+        Ok(match value {
+            Value::String(string) => self.shared(
+                string,
+                Value::String,
+                |_| String::new(),
+                |_, s| Ok(s.clone()),
+            )?,
+            Value::Bytes(bytes) => {
+                self.shared(bytes, Value::Bytes, Clone::clone, |_, b| Ok(b.clone()))?
+            }
+            Value::Vec(vec) => self.shared(
+                vec,
+                Value::Vec,
+                |_| Vec::new(),
+                |this, vec| Ok(Vec::from(this.clone_values(vec.iter())?)),
+            )?,
+            Value::Tuple(tuple) => self.shared(
+                tuple,
+                Value::Tuple,
+                |_| Tuple::from(std::vec::Vec::new()),
+                |this, tuple| this.clone_tuple(tuple),
+            )?,
+            Value::Object(object) => self.shared(
+                object,
+                Value::Object,
+                |_| Object::new(),
+                |this, object| this.clone_object(object),
+            )?,
+            Value::Range(range) => self.shared(
+                range,
+                Value::Range,
+                |range| Range {
+                    start: None,
+                    end: None,
+                    limits: range.limits,
+                },
+                |this, range| {
+                    Ok(Range {
+                        start: this.clone_option(range.start.as_ref())?,
+                        end: this.clone_option(range.end.as_ref())?,
+                        limits: range.limits,
+                    })
+                },
+            )?,
+            Value::Option(option) => self.shared(
+                option,
+                Value::Option,
+                |_| None,
+                |this, option| this.clone_option(option.as_ref()),
+            )?,
+            Value::Result(result) => self.shared(
+                result,
+                Value::Result,
+                |_| Ok(Value::Unit),
+                |this, result| {
+                    Ok(match result {
+                        Ok(value) => Ok(this.clone_value(value)?),
+                        Err(value) => Err(this.clone_value(value)?),
+                    })
+                },
+            )?,
+            Value::UnitStruct(unit) => self.shared(
+                unit,
+                Value::UnitStruct,
+                |unit| UnitStruct {
+                    rtti: unit.rtti.clone(),
+                },
+                |_, unit| {
+                    Ok(UnitStruct {
+                        rtti: unit.rtti.clone(),
+                    })
+                },
+            )?,
+            Value::TupleStruct(tuple) => self.shared(
+                tuple,
+                Value::TupleStruct,
+                |tuple| TupleStruct {
+                    rtti: tuple.rtti.clone(),
+                    data: Tuple::from(std::vec::Vec::new()),
+                },
+                |this, tuple| {
+                    Ok(TupleStruct {
+                        rtti: tuple.rtti.clone(),
+                        data: this.clone_tuple(&tuple.data)?,
+                    })
+                },
+            )?,
+            Value::Struct(object) => self.shared(
+                object,
+                Value::Struct,
+                |object| Struct {
+                    rtti: object.rtti.clone(),
+                    data: Object::new(),
+                },
+                |this, object| {
+                    Ok(Struct {
+                        rtti: object.rtti.clone(),
+                        data: this.clone_object(&object.data)?,
+                    })
+                },
+            )?,
+            Value::Variant(variant) => self.shared(
+                variant,
+                Value::Variant,
+                |variant| Variant {
+                    rtti: variant.rtti.clone(),
+                    data: VariantData::Unit,
+                },
+                |this, variant| {
+                    let data = match &variant.data {
+                        VariantData::Unit => VariantData::Unit,
+                        VariantData::Struct(object) => {
+                            VariantData::Struct(this.clone_object(object)?)
+                        }
+                        VariantData::Tuple(tuple) => VariantData::Tuple(this.clone_tuple(tuple)?),
+                    };
+
+                    Ok(Variant {
+                        rtti: variant.rtti.clone(),
+                        data,
+                    })
+                },
+            )?,
+            Value::Any(any) => {
+                let key = any.as_ptr();
+
+                if let Some(value) = self.seen.get(&key) {
+                    return Ok(value.clone());
+                }
+
+                let hash = Hash::instance_function(value.type_hash()?, Protocol::CLONE);
+                let implemented = env::with(|context, _| Ok(context.function(hash).is_some()))?;
+
+                if !implemented {
+                    if self.share_native {
+                        return Ok(value.clone());
+                    }
+
+                    return Err(VmError::from(VmErrorKind::MissingProtocol {
+                        protocol: Protocol::CLONE,
+                        actual: value.type_info()?,
+                    }));
+                }
+
+                let value =
+                    EnvProtocolCaller.call_protocol_fn(Protocol::CLONE, value.clone(), ())?;
+
+                self.seen.insert(key, value.clone());
+                value
+            }
+            // NB: functions, futures, streams, generators and iterators are
+            // not data, so they are shared with the copy.
+            value => value.clone(),
+        })
+    }
+
+    /// Deep clone a shared value by registering a placeholder for it first,
+    /// so that references back to the value being cloned resolve to the copy.
+    fn shared<T, P, F>(
+        &mut self,
+        shared: &Shared<T>,
+        wrap: fn(Shared<T>) -> Value,
+        placeholder: P,
+        clone: F,
+    ) -> Result<Value, VmError>
+    where
+        P: FnOnce(&T) -> T,
+        F: FnOnce(&mut Self, &T) -> Result<T, VmError>,
+    {
+        let key = shared.as_ptr();
+
+        if let Some(value) = self.seen.get(&key) {
+            return Ok(value.clone());
+        }
+
+        let value = shared.borrow_ref()?;
+        let out = Shared::new(placeholder(&*value));
+        self.seen.insert(key, wrap(out.clone()));
+        let data = clone(self, &*value)?;
+        drop(value);
+
+        *out.borrow_mut()? = data;
+        Ok(wrap(out))
+    }
+
+    fn clone_values<'a, I>(&mut self, values: I) -> Result<std::vec::Vec<Value>, VmError>
+    where
+        I: IntoIterator<Item = &'a Value>,
+    {
+        values
+            .into_iter()
+            .map(|value| self.clone_value(value))
+            .collect()
+    }
+
+    fn clone_tuple(&mut self, tuple: &Tuple) -> Result<Tuple, VmError> {
+        Ok(Tuple::from(self.clone_values(tuple.iter())?))
+    }
+
+    fn clone_object(&mut self, object: &Object) -> Result<Object, VmError> {
+        let mut out = Object::new();
+
+        for (key, value) in object.iter() {
+            out.insert(key.clone(), self.clone_value(value)?);
+        }
+
+        Ok(out)
+    }
+
+    fn clone_option(&mut self, value: Option<&Value>) -> Result<Option<Value>, VmError> {
+        Ok(match value {
+            Some(value) => Some(self.clone_value(value)?),
+            None => None,
+        })
+    }
+}
@@ -6,66 +6,215 @@
 //! * [super::Value::into_type_name]
 //!
 //! See the corresponding function for documentation.
+//!
+//! It also keeps track of the [Capabilities][super::Capabilities] granted to the virtual machine
+//! which is currently running, and where it writes its output. Virtual
+//! machines constructed by native functions to call back into a script
+//! inherit the settings of the running one.
+//!
+//! Native functions can inspect the virtual machine which called them through
+//! [with_backtrace].
 
-use crate::runtime::{RuntimeContext, Unit, VmError, VmErrorKind};
+use crate::runtime::{
+    BacktraceFrame, CallFrame, RuntimeContext, Unit, Vm, VmError, VmErrorKind, VmSettings,
+};
 use std::cell::Cell;
-use std::ptr;
+use std::io;
+use std::rc::Rc;
 use std::sync::Arc;
 
-thread_local! { static ENV: Cell<Env> = Cell::new(Env::null()) }
+thread_local! { static ENV: Cell<Option<Rc<Env>>> = const { Cell::new(None) } }
+
+/// Get the current environment, if any.
+fn current() -> Option<Rc<Env>> {
+    ENV.with(|env| {
+        let current = env.take();
+        env.set(current.clone());
+        current
+    })
+}
 
 /// Call the given closure with access to the checked environment.
 pub(crate) fn with<F, T>(c: F) -> Result<T, VmError>
 where
     F: FnOnce(&Arc<RuntimeContext>, &Arc<Unit>) -> Result<T, VmError>,
 {
-    let env = ENV.with(|env| env.get());
-    let Env { context, unit } = env;
+    let env = match current() {
+        Some(env) => env,
+        None => return Err(VmError::from(VmErrorKind::MissingInterfaceEnvironment)),
+    };
+
+    c(&env.context, &env.unit)
+}
+
+/// Test if the given capability has been granted to the virtual machine which
+/// is currently running.
+pub(crate) fn is_granted(capability: &str) -> bool {
+    match current() {
+        Some(env) => match &env.settings.capabilities {
+            Some(capabilities) => capabilities.is_granted(capability),
+            None => false,
+        },
+        None => false,
+    }
+}
+
+/// Let the given virtual machine inherit the settings of the virtual machine
+/// which is currently executing on this thread, if any.
+///
+/// This is used when native code calls back into a virtual machine, so that
+/// limits like the instruction budget also apply to closures called from
+/// native functions.
+pub(crate) fn inherit(vm: &mut Vm) {
+    if let Some(env) = current() {
+        vm.inherit_settings(env.settings.clone());
+    }
+}
+
+/// Call the given closure with the backtrace of the virtual machine which is
+/// currently executing on this thread, as produced by [Vm::backtrace].
+///
+/// This is intended to be used from native functions, to find out which
+/// functions in the script called them.
+///
+/// # Errors
+///
+/// Errors with [VmErrorKind::MissingInterfaceEnvironment] if no virtual machine
+/// is executing.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{env, VmError};
+/// use rune::Module;
+///
+/// # fn main() -> rune::Result<()> {
+/// let mut module = Module::new();
+///
+/// module.function(&["depth"], || -> Result<usize, VmError> {
+///     env::with_backtrace(|backtrace| backtrace.len())
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn with_backtrace<F, T>(f: F) -> Result<T, VmError>
+where
+    F: FnOnce(&[BacktraceFrame<'_>]) -> T,
+{
+    let env = match current() {
+        Some(env) => env,
+        None => return Err(VmError::from(VmErrorKind::MissingInterfaceEnvironment)),
+    };
+
+    let backtrace = match env.frames.get() {
+        // Safety: frames can only be registered through [with_frames], which
+        // makes sure that they are live for the duration of the registration.
+        Some((ip, frames)) => crate::runtime::vm::backtrace(&env.unit, ip, unsafe { &*frames }),
+        None => Vec::new(),
+    };
+
+    Ok(f(&backtrace))
+}
+
+/// Call the given closure, which calls a native function, with the instruction
+/// pointer and call frames of the virtual machine calling it registered so
+/// that the native function can access them through [with_backtrace].
+pub(crate) fn with_frames<F, T>(ip: usize, frames: &[CallFrame], f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let env = match current() {
+        Some(env) => env,
+        None => return f(),
+    };
 
-    if context.is_null() || unit.is_null() {
-        return Err(VmError::from(VmErrorKind::MissingInterfaceEnvironment));
+    let old = env.frames.replace(Some((ip, frames)));
+    let _guard = FramesGuard { env, old };
+    f()
+}
+
+/// Restores the previously registered frames on drop.
+struct FramesGuard {
+    env: Rc<Env>,
+    old: Option<(usize, *const [CallFrame])>,
+}
+
+impl Drop for FramesGuard {
+    fn drop(&mut self) {
+        self.env.frames.set(self.old);
     }
+}
 
-    // Safety: context and unit can only be registered publicly through
-    // [Guard], which makes sure that they are live for the duration of
-    // the registration.
-    c(unsafe { &*context }, unsafe { &*unit })
+/// Call the given closure with the output of the virtual machine which is
+/// currently running, or stdout if it doesn't have one.
+pub(crate) fn with_output<F>(f: F) -> io::Result<()>
+where
+    F: FnOnce(&mut dyn io::Write) -> io::Result<()>,
+{
+    let env = current();
+
+    match env.as_ref().and_then(|env| env.settings.output.as_ref()) {
+        Some(output) => {
+            let mut output = output.lock()?;
+            f(&mut *output)
+        }
+        None => {
+            let stdout = io::stdout();
+            let mut stdout = stdout.lock();
+            f(&mut stdout)
+        }
+    }
 }
 
 pub(crate) struct Guard {
-    old: Env,
+    old: Option<Rc<Env>>,
 }
 
 impl Guard {
-    /// Construct a new environment guard with the given context and unit.
-    ///
-    /// # Safety
+    /// Construct a new environment guard for the given virtual machine, which
+    /// holds on to a snapshot of its settings.
     ///
-    /// The returned guard must be dropped before the pointed to elements are.
-    pub(crate) fn new(context: *const Arc<RuntimeContext>, unit: *const Arc<Unit>) -> Guard {
-        let old = ENV.with(|e| e.replace(Env { context, unit }));
+    /// If the virtual machine doesn't have its own set of capabilities or
+    /// doesn't have an output configured, the ones of the current environment
+    /// are kept.
+    pub(crate) fn new(vm: &Vm) -> Guard {
+        let old = current();
+        let mut settings = vm.settings();
+
+        if let Some(old) = &old {
+            if settings.capabilities.is_none() {
+                settings.capabilities = old.settings.capabilities.clone();
+            }
+
+            if settings.output.is_none() {
+                settings.output = old.settings.output.clone();
+            }
+        }
 
+        let env = Env {
+            context: vm.context().clone(),
+            unit: vm.unit().clone(),
+            settings,
+            frames: Cell::new(None),
+        };
+
+        ENV.with(|e| e.set(Some(Rc::new(env))));
         Guard { old }
     }
 }
 
 impl Drop for Guard {
     fn drop(&mut self) {
-        ENV.with(|e| e.set(self.old));
+        let current = ENV.with(|e| e.replace(self.old.take()));
+        drop(current);
     }
 }
 
-#[derive(Debug, Clone, Copy)]
 struct Env {
-    context: *const Arc<RuntimeContext>,
-    unit: *const Arc<Unit>,
-}
-
-impl Env {
-    const fn null() -> Self {
-        Self {
-            context: ptr::null(),
-            unit: ptr::null(),
-        }
-    }
+    context: Arc<RuntimeContext>,
+    unit: Arc<Unit>,
+    settings: VmSettings,
+    /// The instruction pointer and call frames of the virtual machine while
+    /// it's calling a native function.
+    frames: Cell<Option<(usize, *const [CallFrame])>>,
 }
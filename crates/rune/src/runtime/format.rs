@@ -1,8 +1,8 @@
 //! Types for dealing with formatting specifications.
 
 use crate::compile::Named;
-use crate::runtime::{FromValue, ProtocolCaller, RawStr, Value, VmError, VmErrorKind};
-use crate::InstallWith;
+use crate::runtime::{FromValue, ProtocolCaller, RawStr, Value, Vec, VmError, VmErrorKind};
+use crate::{Any, InstallWith};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::fmt;
@@ -48,8 +48,59 @@ impl FromValue for Format {
     }
 }
 
+/// Lazily formatted arguments, as produced by the `format_args!` macro.
+///
+/// The arguments are captured when the value is constructed, but they are only
+/// formatted once the value itself is displayed or [rendered][Self::render].
+/// This allows for example logging functions to skip formatting entirely when
+/// a message isn't going to be emitted.
+#[derive(Any, Debug, Clone)]
+#[rune(module = "crate")]
+pub struct FormatArgs {
+    /// The literal parts and arguments to format, in order.
+    parts: Vec,
+}
+
+impl FormatArgs {
+    /// Construct lazily formatted arguments out of the given parts, which are
+    /// displayed one after another.
+    pub fn new(parts: Vec) -> Self {
+        Self { parts }
+    }
+
+    /// Render the arguments into a string.
+    ///
+    /// Like [Value::string_display], this must be called inside of a virtual
+    /// machine if any of the arguments are formatted through the
+    /// [STRING_DISPLAY][crate::runtime::Protocol::STRING_DISPLAY] protocol.
+    pub fn render(&self) -> Result<String, VmError> {
+        let mut s = String::new();
+
+        if let Err(fmt::Error) = self.string_display(&mut s)? {
+            return Err(VmError::from(VmErrorKind::FormatError));
+        }
+
+        Ok(s)
+    }
+
+    /// Display the arguments into the given string.
+    pub(crate) fn string_display(&self, s: &mut String) -> Result<fmt::Result, VmError> {
+        let mut buf = String::new();
+
+        for part in self.parts.iter() {
+            if let Err(error) = part.string_display(s, &mut buf)? {
+                return Ok(Err(error));
+            }
+
+            buf.clear();
+        }
+
+        Ok(Ok(()))
+    }
+}
+
 /// A format specification.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct FormatSpec {
     /// Formatting flags.
@@ -357,7 +408,7 @@ impl FormatSpec {
 }
 
 /// The type of formatting requested.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum Type {
     /// Display type (default).
@@ -424,7 +475,7 @@ impl fmt::Display for Type {
 }
 
 /// The alignment requested.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum Alignment {
     /// Left alignment.
@@ -488,7 +539,7 @@ pub enum Flag {
 }
 
 /// Format specification flags.
-#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 #[repr(transparent)]
 pub struct Flags(u32);
 
@@ -141,7 +141,9 @@ where
     T: FromValue,
 {
     fn from_value(value: Value) -> Result<Self, VmError> {
-        Ok(match value.into_option()?.take()? {
+        let option = value.into_option()?.borrow_ref()?.clone();
+
+        Ok(match option {
             Some(some) => Some(T::from_value(some)?),
             None => None,
         })
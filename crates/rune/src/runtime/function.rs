@@ -1,7 +1,9 @@
+use crate::compile::{InstallWith, Named};
+use crate::runtime::RawStr;
 use crate::runtime::{
-    Args, Call, ConstValue, FromValue, FunctionHandler, RawRef, Ref, Rtti, RuntimeContext, Shared,
-    Stack, Tuple, Unit, UnsafeFromValue, Value, VariantRtti, Vm, VmCall, VmError, VmErrorKind,
-    VmHalt,
+    env, Args, Call, ConstValue, FromValue, FunctionHandler, Protocol, RawRef, Ref, Rtti,
+    RuntimeContext, Shared, Stack, ToValue, Tuple, TypeOf, Unit, UnitFn, UnsafeFromValue, Value,
+    VariantRtti, Vm, VmCall, VmError, VmErrorKind, VmHalt,
 };
 use crate::shared::AssertSend;
 use crate::Hash;
@@ -130,6 +132,29 @@ impl Function {
         Self(FunctionImpl::from_tuple_variant(rtti, args))
     }
 
+    /// Create a function pointer from a value which is callable through
+    /// [Protocol::CALL] in the current environment.
+    pub(crate) fn from_callable(value: Value) -> Result<Self, VmError> {
+        let hash = value.type_hash()?;
+
+        let resolved = crate::runtime::env::with(|context, unit| {
+            Ok(Callable::resolve(context, unit, hash).map(|c| (context.clone(), unit.clone(), c)))
+        });
+
+        match resolved {
+            Ok(Some((context, unit, callable))) => Ok(Self(FunctionImpl {
+                inner: Inner::FnCallable(FnCallable {
+                    context,
+                    unit,
+                    value,
+                    callable,
+                    hash,
+                }),
+            })),
+            _ => Err(VmError::expected::<Function>(value.type_info()?)),
+        }
+    }
+
     /// Type [Hash][struct@Hash] of the underlying function.
     ///
     /// # Examples
@@ -234,8 +259,58 @@ impl Function {
     pub fn into_sync(self) -> Result<SyncFunction, VmError> {
         Ok(SyncFunction(self.0.into_sync()?))
     }
+
+    /// Bind the given arguments to the front of the arguments of this
+    /// function, returning a new function which takes the remaining
+    /// arguments.
+    ///
+    /// Binding more arguments than the function accepts errors with
+    /// [VmErrorKind::BadArgumentCount] if the number of arguments the function
+    /// takes is known up front, otherwise it errors once the function is
+    /// called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Vm, FromValue};
+    /// use rune::runtime::Function;
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         fn add(a, b) {
+    ///             a + b
+    ///         }
+    ///
+    ///         pub fn main() { add }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let mut vm = Vm::without_runtime(Arc::new(unit));
+    /// let add = vm.call(&["main"], ())?;
+    /// let add = Function::from_value(add)?;
+    ///
+    /// let add5 = add.bind((5,))?;
+    /// assert_eq!(add5.call::<_, u32>((1,))?, 6);
+    /// assert!(add5.bind((1, 2)).is_err());
+    /// # Ok(()) }
+    /// ```
+    pub fn bind<A>(&self, args: A) -> Result<Function, VmError>
+    where
+        A: Args,
+    {
+        Ok(Self(self.0.bind(args.into_vec()?)?))
+    }
+}
+
+impl Named for Function {
+    const BASE_NAME: RawStr = RawStr::from_str("Function");
 }
 
+impl InstallWith for Function {}
+
 /// A callable sync function. This currently only supports a subset of values
 /// that are supported by the Vm.
 #[derive(Clone)]
@@ -354,17 +429,13 @@ impl SyncFunction {
 
 /// A stored function, of some specific kind.
 #[derive(Clone)]
-struct FunctionImpl<V>
-where
-    V: Clone,
-    Tuple: From<Box<[V]>>,
-{
+struct FunctionImpl<V> {
     inner: Inner<V>,
 }
 
 impl<V> FunctionImpl<V>
 where
-    V: Clone,
+    V: Clone + ToValue,
     Tuple: From<Box<[V]>>,
 {
     fn call<A, T>(&self, args: A) -> Result<T, VmError>
@@ -400,6 +471,43 @@ where
                 check_args(args.count(), tuple.args)?;
                 Value::tuple_variant(tuple.rtti.clone(), args.into_vec()?)
             }
+            Inner::FnCallable(callable) => {
+                let value = callable.value.clone().to_value()?;
+                let mut stack = Stack::with_capacity(args.count() + 1);
+                let count = args.count();
+                args.into_stack(&mut stack)?;
+                let count = callable.callable.push_target(&mut stack, value, count)?;
+
+                match &callable.callable {
+                    Callable::Handler { handler, .. } => {
+                        handler(&mut stack, count)?;
+                        stack.pop()?
+                    }
+                    Callable::Offset {
+                        offset,
+                        call,
+                        args: expected,
+                        ..
+                    } => {
+                        check_args(count, *expected)?;
+                        let mut vm =
+                            Vm::with_stack(callable.context.clone(), callable.unit.clone(), stack);
+                        vm.set_ip(*offset);
+                        env::inherit(&mut vm);
+                        call.call_with_vm(vm)?
+                    }
+                }
+            }
+            Inner::FnBound(bound) => {
+                let mut values = Vec::with_capacity(bound.args.len() + args.count());
+
+                for value in bound.args.iter() {
+                    values.push(value.clone().to_value()?);
+                }
+
+                values.extend(args.into_vec()?);
+                bound.function.call(values)?
+            }
         };
 
         T::from_value(value)
@@ -443,12 +551,12 @@ where
     pub(crate) fn call_with_vm(&self, vm: &mut Vm, args: usize) -> Result<Option<VmHalt>, VmError> {
         let reason = match &self.inner {
             Inner::FnHandler(handler) => {
-                (handler.handler)(vm.stack_mut(), args)?;
+                vm.call_handler(&*handler.handler, args)?;
                 None
             }
             Inner::FnOffset(fn_offset) => {
                 if let Some(vm_call) = fn_offset.call_with_vm(vm, args, ())? {
-                    return Ok(Some(VmHalt::VmCall(vm_call)));
+                    return Ok(Some(VmHalt::VmCall(Box::new(vm_call))));
                 }
 
                 None
@@ -459,7 +567,7 @@ where
                     args,
                     (Tuple::from(closure.environment.clone()),),
                 )? {
-                    return Ok(Some(VmHalt::VmCall(vm_call)));
+                    return Ok(Some(VmHalt::VmCall(Box::new(vm_call))));
                 }
 
                 None
@@ -492,6 +600,50 @@ where
                 vm.stack_mut().push(value);
                 None
             }
+            Inner::FnCallable(callable) => {
+                let value = callable.value.clone().to_value()?;
+
+                if vm.is_same(&callable.context, &callable.unit) {
+                    return callable.callable.call_with_vm(vm, value, args);
+                }
+
+                let mut stack = vm.stack_mut().drain(args)?.collect::<Stack>();
+                let count = callable.callable.push_target(&mut stack, value, args)?;
+
+                match &callable.callable {
+                    Callable::Handler { handler, .. } => {
+                        env::with_frames(vm.ip(), vm.call_frames(), || handler(&mut stack, count))?;
+                        vm.stack_mut().push(stack.pop()?);
+                        None
+                    }
+                    Callable::Offset {
+                        offset,
+                        call,
+                        args: expected,
+                        ..
+                    } => {
+                        check_args(count, *expected)?;
+                        let mut new_vm =
+                            Vm::with_stack(callable.context.clone(), callable.unit.clone(), stack);
+                        new_vm.set_ip(*offset);
+                        new_vm.inherit(vm);
+                        return Ok(Some(VmHalt::VmCall(Box::new(VmCall::new(*call, new_vm)))));
+                    }
+                }
+            }
+            Inner::FnBound(bound) => {
+                // NB: the bound arguments are inserted beneath the arguments
+                // which were passed in.
+                let stack = vm.stack_mut();
+                let rest = stack.pop_sequence(args)?;
+
+                for value in bound.args.iter() {
+                    stack.push(value.clone().to_value()?);
+                }
+
+                stack.extend(rest);
+                return bound.function.call_with_vm(vm, bound.args.len() + args);
+            }
         };
 
         Ok(reason)
@@ -589,11 +741,56 @@ where
             Inner::FnTupleStruct(func) => func.rtti.hash,
             Inner::FnUnitVariant(func) => func.rtti.hash,
             Inner::FnTupleVariant(func) => func.rtti.hash,
+            Inner::FnCallable(func) => func.hash,
+            Inner::FnBound(bound) => bound.function.type_hash(),
+        }
+    }
+
+    /// The number of arguments the function takes, if it's known up front.
+    fn args(&self) -> Option<usize> {
+        match &self.inner {
+            Inner::FnOffset(fn_offset) => Some(fn_offset.args),
+            Inner::FnClosureOffset(closure) => Some(closure.fn_offset.args),
+            Inner::FnUnitStruct(..) | Inner::FnUnitVariant(..) => Some(0),
+            Inner::FnTupleStruct(tuple) => Some(tuple.args),
+            Inner::FnTupleVariant(tuple) => Some(tuple.args),
+            Inner::FnBound(bound) => Some(bound.function.args()? - bound.args.len()),
+            Inner::FnHandler(..) | Inner::FnCallable(..) => None,
         }
     }
 }
 
 impl FunctionImpl<Value> {
+    /// Bind the given arguments to the front of the arguments of this
+    /// function.
+    fn bind(&self, args: Vec<Value>) -> Result<Self, VmError> {
+        if let Some(expected) = self.args() {
+            if args.len() > expected {
+                return Err(VmError::from(VmErrorKind::BadArgumentCount {
+                    expected,
+                    actual: args.len(),
+                }));
+            }
+        }
+
+        // NB: binding to an already bound function appends to its bound
+        // arguments instead of nesting.
+        let bound = match &self.inner {
+            Inner::FnBound(bound) => FnBound {
+                function: bound.function.clone(),
+                args: bound.args.iter().cloned().chain(args).collect(),
+            },
+            _ => FnBound {
+                function: Box::new(self.clone()),
+                args: args.into_boxed_slice(),
+            },
+        };
+
+        Ok(Self {
+            inner: Inner::FnBound(bound),
+        })
+    }
+
     /// Try to convert into a [SyncFunction].
     fn into_sync(self) -> Result<FunctionImpl<ConstValue>, VmError> {
         let inner = match self.inner {
@@ -615,6 +812,23 @@ impl FunctionImpl<Value> {
             Inner::FnTupleStruct(inner) => Inner::FnTupleStruct(inner),
             Inner::FnUnitVariant(inner) => Inner::FnUnitVariant(inner),
             Inner::FnTupleVariant(inner) => Inner::FnTupleVariant(inner),
+            Inner::FnCallable(callable) => {
+                return Err(VmError::from(VmErrorKind::CallableNotSync {
+                    actual: callable.value.type_info()?,
+                }));
+            }
+            Inner::FnBound(bound) => {
+                let mut args = Vec::with_capacity(bound.args.len());
+
+                for value in bound.args.into_vec() {
+                    args.push(FromValue::from_value(value)?);
+                }
+
+                Inner::FnBound(FnBound {
+                    function: Box::new(bound.function.into_sync()?),
+                    args: args.into_boxed_slice(),
+                })
+            }
         };
 
         Ok(FunctionImpl { inner })
@@ -649,6 +863,12 @@ impl fmt::Debug for Function {
             Inner::FnTupleVariant(tuple) => {
                 write!(f, "variant tuple {}", tuple.rtti.item)?;
             }
+            Inner::FnCallable(callable) => {
+                write!(f, "callable {:?}", callable.value)?;
+            }
+            Inner::FnBound(bound) => {
+                write!(f, "bound function (args: {:?})", bound.args)?;
+            }
         }
 
         Ok(())
@@ -678,6 +898,10 @@ enum Inner<V> {
     FnUnitVariant(FnUnitVariant),
     /// Constructor for a tuple variant.
     FnTupleVariant(FnTupleVariant),
+    /// A value which is callable through [Protocol::CALL].
+    FnCallable(FnCallable<V>),
+    /// A function with some of its leading arguments bound.
+    FnBound(FnBound<V>),
 }
 
 #[derive(Clone)]
@@ -721,6 +945,7 @@ impl FnOffset {
         let mut vm = Vm::new(self.context.clone(), self.unit.clone());
 
         vm.set_ip(self.offset);
+        env::inherit(&mut vm);
         args.into_stack(vm.stack_mut())?;
         extra.into_stack(vm.stack_mut())?;
 
@@ -748,9 +973,10 @@ impl FnOffset {
 
         let mut new_stack = vm.stack_mut().drain(args)?.collect::<Stack>();
         extra.into_stack(&mut new_stack)?;
-        let mut vm = Vm::with_stack(self.context.clone(), self.unit.clone(), new_stack);
-        vm.set_ip(self.offset);
-        Ok(Some(VmCall::new(self.call, vm)))
+        let mut new_vm = Vm::with_stack(self.context.clone(), self.unit.clone(), new_stack);
+        new_vm.set_ip(self.offset);
+        new_vm.inherit(vm);
+        Ok(Some(VmCall::new(self.call, new_vm)))
     }
 }
 
@@ -802,6 +1028,175 @@ struct FnTupleVariant {
     args: usize,
 }
 
+#[derive(Clone)]
+struct FnCallable<V> {
+    context: Arc<RuntimeContext>,
+    /// The unit the value is callable in.
+    unit: Arc<Unit>,
+    /// The value being called.
+    value: V,
+    /// How the value is called.
+    callable: Callable,
+    /// The type hash of the value.
+    hash: Hash,
+}
+
+impl<V> fmt::Debug for FnCallable<V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnCallable")
+            .field("value", &self.value)
+            .field("callable", &self.callable)
+            .field("hash", &self.hash)
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+struct FnBound<V> {
+    /// The function being called.
+    function: Box<FunctionImpl<V>>,
+    /// Arguments passed in front of the arguments of each call.
+    args: Box<[V]>,
+}
+
+impl<V> fmt::Debug for FnBound<V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnBound")
+            .field("args", &self.args)
+            .finish_non_exhaustive()
+    }
+}
+
+/// How a value which isn't a function is called through [Protocol::CALL].
+#[derive(Clone)]
+pub(crate) enum Callable {
+    /// Call a native handler.
+    Handler {
+        handler: Arc<FunctionHandler>,
+        /// Pack the arguments into a tuple.
+        tuple: bool,
+    },
+    /// Call a function in the unit.
+    Offset {
+        offset: usize,
+        call: Call,
+        args: usize,
+        /// Pack the arguments into a tuple.
+        tuple: bool,
+    },
+}
+
+impl Callable {
+    /// Resolve how values of the given type are called, if they are callable
+    /// at all.
+    ///
+    /// Types are callable if they have a [Protocol::CALL] handler, either
+    /// taking the arguments as they were passed or packed into a tuple. Types
+    /// declared in the unit register one by marking a method with `#[call]`.
+    pub(crate) fn resolve(context: &RuntimeContext, unit: &Unit, type_hash: Hash) -> Option<Self> {
+        let hash = Hash::instance_function(type_hash, Protocol::CALL);
+        let tuple_hash = hash.with_parameters(Hash::parameters([Tuple::type_hash()]));
+
+        for (hash, tuple) in [(hash, false), (tuple_hash, true)] {
+            if let Some(handler) = context.function(hash) {
+                return Some(Self::Handler {
+                    handler: handler.clone(),
+                    tuple,
+                });
+            }
+
+            if let Some(UnitFn::Offset { offset, call, args }) = unit.function(hash) {
+                return Some(Self::Offset {
+                    offset,
+                    call,
+                    args,
+                    tuple,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Insert the called value beneath the `args` arguments at the top of the
+    /// stack, packing the arguments into a tuple if needed.
+    ///
+    /// Returns the number of arguments the call should be performed with.
+    fn push_target(&self, stack: &mut Stack, target: Value, args: usize) -> Result<usize, VmError> {
+        let tuple = match self {
+            Self::Handler { tuple, .. } | Self::Offset { tuple, .. } => *tuple,
+        };
+
+        let rest = stack.pop_sequence(args)?;
+        stack.push(target);
+
+        if tuple {
+            stack.push(Tuple::from(rest));
+            Ok(2)
+        } else {
+            stack.extend(rest);
+            Ok(args + 1)
+        }
+    }
+
+    /// Call the value `target` with `args` arguments at the top of the stack
+    /// of the given virtual machine.
+    pub(crate) fn call_with_vm(
+        &self,
+        vm: &mut Vm,
+        target: Value,
+        args: usize,
+    ) -> Result<Option<VmHalt>, VmError> {
+        let count = self.push_target(vm.stack_mut(), target, args)?;
+
+        match self {
+            Self::Handler { handler, .. } => {
+                vm.call_handler(&**handler, count)?;
+            }
+            Self::Offset {
+                offset,
+                call,
+                args: expected,
+                ..
+            } => {
+                check_args(count, *expected)?;
+                vm.call_offset_fn(*offset, *call, count)?;
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl fmt::Debug for Callable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Handler { tuple, .. } => f
+                .debug_struct("Handler")
+                .field("tuple", tuple)
+                .finish_non_exhaustive(),
+            Self::Offset {
+                offset,
+                call,
+                args,
+                tuple,
+            } => f
+                .debug_struct("Offset")
+                .field("offset", offset)
+                .field("call", call)
+                .field("args", args)
+                .field("tuple", tuple)
+                .finish(),
+        }
+    }
+}
+
 impl FromValue for SyncFunction {
     fn from_value(value: Value) -> Result<Self, VmError> {
         value.into_function()?.take()?.into_sync()
@@ -810,7 +1205,10 @@ impl FromValue for SyncFunction {
 
 impl FromValue for Function {
     fn from_value(value: Value) -> Result<Self, VmError> {
-        Ok(value.into_function()?.take()?)
+        match value {
+            Value::Function(function) => Ok(function.take()?),
+            value => Function::from_callable(value),
+        }
     }
 }
 
@@ -6,7 +6,7 @@ use std::fmt;
 /// Pre-canned panic reasons.
 ///
 /// To formulate a custom reason, use [crate::runtime::Panic::custom].
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PanicReason {
     /// Not implemented.
     NotImplemented,
@@ -42,7 +42,7 @@ impl fmt::Display for PanicReason {
 }
 
 /// An encoded type check.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TypeCheck {
     /// Matches a unit type.
     Unit,
@@ -81,7 +81,7 @@ impl fmt::Display for TypeCheck {
 }
 
 /// An operation in the stack-based virtual machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Inst {
     /// Not operator. Takes a boolean from the top of the stack  and inverts its
     /// logical value.
@@ -519,7 +519,41 @@ pub enum Inst {
     Vec {
         /// The size of the vector.
         count: usize,
+        /// The capacity to allocate the vector with, which is the number of
+        /// items known at compile time to be added to it.
+        capacity: usize,
     },
+    /// Construct a vector by repeating a value. The number of times to repeat
+    /// the value is popped from the stack first, followed by the value to
+    /// repeat.
+    ///
+    /// The count must be a non-negative integer. Each element is a deep copy of
+    /// the value, except for native values which can't be cloned, which are
+    /// shared.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// <count>
+    /// => <vec>
+    /// ```
+    VecRepeat,
+    /// Extend a vector with every value produced by iterating over a value,
+    /// which is popped from the stack first, followed by the vector to extend.
+    /// The extended vector is pushed back onto the stack.
+    ///
+    /// The value is converted to an iterator using the
+    /// [INTO_ITER][crate::runtime::Protocol::INTO_ITER] protocol.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <vec>
+    /// <value>
+    /// => <vec>
+    /// ```
+    VecExtend,
     /// Construct a push a one-tuple value onto the stack.
     ///
     /// # Operation
@@ -691,6 +725,18 @@ pub enum Inst {
         /// The static string slot to load the string from.
         slot: usize,
     },
+    /// Load a global which has been set on the virtual machine, using the
+    /// name stored in the given static string slot.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    LoadGlobal {
+        /// The static string slot containing the name of the global.
+        slot: usize,
+    },
     /// Load a literal byte string from a static byte string slot.
     ///
     /// # Operation
@@ -813,6 +859,24 @@ pub enum Inst {
         /// The slot to test against.
         slot: usize,
     },
+    /// Test if the top of the stack is an integer, byte or character which is
+    /// inside of the range `start..end`, or `start..=end` if it's inclusive.
+    /// A missing bound leaves that side of the range open.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    MatchRange {
+        /// The lower bound of the range.
+        start: Option<InstValue>,
+        /// The upper bound of the range.
+        end: Option<InstValue>,
+        /// If the upper bound is included in the range.
+        inclusive: bool,
+    },
     /// Test that the top of the stack has the given type.
     ///
     /// # Operation
@@ -1102,8 +1166,14 @@ impl fmt::Display for Inst {
             Self::JumpIfBranch { branch, offset } => {
                 write!(fmt, "jump-if-branch branch={}, offset={}", branch, offset)?;
             }
-            Self::Vec { count } => {
-                write!(fmt, "vec count={}", count)?;
+            Self::Vec { count, capacity } => {
+                write!(fmt, "vec count={}, capacity={}", count, capacity)?;
+            }
+            Self::VecRepeat => {
+                write!(fmt, "vec-repeat")?;
+            }
+            Self::VecExtend => {
+                write!(fmt, "vec-extend")?;
             }
             Self::Tuple1 { args: [a] } => {
                 write!(fmt, "tuple-1 {}", a)?;
@@ -1144,6 +1214,9 @@ impl fmt::Display for Inst {
             Self::String { slot } => {
                 write!(fmt, "string slot={}", slot)?;
             }
+            Self::LoadGlobal { slot } => {
+                write!(fmt, "load-global slot={}", slot)?;
+            }
             Self::Bytes { slot } => {
                 write!(fmt, "bytes slot={}", slot)?;
             }
@@ -1191,6 +1264,23 @@ impl fmt::Display for Inst {
             Self::EqStaticString { slot } => {
                 write!(fmt, "eq-static-string slot={}", slot)?;
             }
+            Self::MatchRange {
+                start,
+                end,
+                inclusive,
+            } => {
+                write!(fmt, "match-range")?;
+
+                if let Some(start) = start {
+                    write!(fmt, " start={}", start)?;
+                }
+
+                if let Some(end) = end {
+                    write!(fmt, " end={}", end)?;
+                }
+
+                write!(fmt, " inclusive={}", inclusive)?;
+            }
             Self::MatchType { hash } => {
                 write!(fmt, "match-type hash={}", hash,)?;
             }
@@ -1254,7 +1344,7 @@ impl fmt::Display for Inst {
 }
 
 /// How an instruction addresses a value.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstAddress {
     /// Addressed from the top of the stack.
     Top,
@@ -1272,7 +1362,7 @@ impl fmt::Display for InstAddress {
 }
 
 /// Range limits of a range expression.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstRangeLimits {
     /// A half-open range `a .. b`.
     HalfOpen,
@@ -1290,7 +1380,7 @@ impl fmt::Display for InstRangeLimits {
 }
 
 /// The target of an operation.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstTarget {
     /// Target is an offset to the current call frame.
     Offset(usize),
@@ -1311,7 +1401,7 @@ impl fmt::Display for InstTarget {
 }
 
 /// An operation between two values on the machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstAssignOp {
     /// The add operation. `a + b`.
     Add,
@@ -1375,7 +1465,7 @@ impl fmt::Display for InstAssignOp {
 }
 
 /// An operation between two values on the machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstOp {
     /// The add operation. `a + b`.
     Add,
@@ -1564,6 +1654,23 @@ pub enum InstValue {
     Type(Hash),
 }
 
+/// Floats are compared by their bits, so that an unchanged `NaN` literal is
+/// equal to itself.
+impl PartialEq for InstValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Unit, Self::Unit) => true,
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Byte(a), Self::Byte(b)) => a == b,
+            (Self::Char(a), Self::Char(b)) => a == b,
+            (Self::Integer(a), Self::Integer(b)) => a == b,
+            (Self::Float(a), Self::Float(b)) => a.to_bits() == b.to_bits(),
+            (Self::Type(a), Self::Type(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 impl InstValue {
     /// Convert into a value that can be pushed onto the stack.
     pub fn into_value(self) -> Value {
@@ -1602,7 +1709,7 @@ impl fmt::Display for InstValue {
 }
 
 /// A variant that can be constructed.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InstVariant {
     /// `Option::Some`, which uses one value.
     Some,
@@ -1,11 +1,12 @@
 use crate::compile::Named;
 use crate::runtime::{
-    FromValue, Function, Mut, RawMut, RawRef, RawStr, Ref, ToValue, UnsafeFromValue, Value,
-    VmError, VmErrorKind,
+    FromValue, Function, FunctionHandler, Mut, RawMut, RawRef, RawStr, Ref, Stack, ToValue,
+    UnsafeFromValue, Value, VmError, VmErrorKind,
 };
 use crate::InstallWith;
 use std::fmt;
 use std::iter;
+use std::sync::Arc;
 use std::vec;
 
 // Note: A fair amount of code in this module is duplicated from the Rust
@@ -104,6 +105,25 @@ impl Iterator {
         }
     }
 
+    /// Construct an iterator over a value which implements the
+    /// [Protocol::NEXT][crate::runtime::Protocol::NEXT] protocol, with the
+    /// given handlers for it and for the
+    /// [Protocol::NEXT_BACK][crate::runtime::Protocol::NEXT_BACK] protocol if
+    /// the value implements it.
+    pub(crate) fn from_protocol(
+        value: Value,
+        next: Arc<FunctionHandler>,
+        next_back: Option<Arc<FunctionHandler>>,
+    ) -> Self {
+        Self {
+            iter: IterRepr::Protocol(Box::new(ProtocolIter {
+                value,
+                next,
+                next_back,
+            })),
+        }
+    }
+
     /// Creates an iterator that yields nothing.
     pub fn empty() -> Self {
         Self {
@@ -234,17 +254,23 @@ impl Iterator {
         })
     }
 
-    /// Map the iterator using the given function.
+    /// Reverse the iterator.
+    ///
+    /// Double-ended iterators are reversed lazily by pulling values from the
+    /// back. Any other iterator is buffered in full before being reversed.
     pub fn rev(self) -> Result<Self, VmError> {
-        if !self.iter.is_double_ended() {
-            return Err(VmError::panic(format!(
-                "`{:?}` is not a double-ended iterator",
-                self
-            )));
-        }
+        let iter = if self.iter.is_double_ended() {
+            self.iter
+        } else {
+            let values = self.collect::<Value>()?;
+            IterRepr::DoubleEndedIterator(Box::new(IteratorObj {
+                name: "std::iter::Rev",
+                iter: values.into_iter(),
+            }))
+        };
 
         Ok(Self {
-            iter: match self.iter {
+            iter: match iter {
                 // NB: reversing a reversed iterator restores the original
                 // iterator.
                 IterRepr::Rev(rev) => rev.iter,
@@ -397,6 +423,7 @@ enum IterRepr {
     Skip(Box<Skip<Self>>),
     Take(Box<Take<Self>>),
     Peekable(Box<Peekable<Self>>),
+    Protocol(Box<ProtocolIter>),
     Empty,
     Once(Option<Value>),
 }
@@ -416,6 +443,7 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.is_double_ended(),
             Self::Take(iter) => iter.is_double_ended(),
             Self::Peekable(iter) => iter.is_double_ended(),
+            Self::Protocol(iter) => iter.is_double_ended(),
             Self::Empty => true,
             Self::Once(..) => true,
         }
@@ -435,6 +463,7 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.size_hint(),
             Self::Take(iter) => iter.size_hint(),
             Self::Peekable(iter) => iter.size_hint(),
+            Self::Protocol(iter) => iter.size_hint(),
             Self::Empty => (0, Some(0)),
             Self::Once(..) => (1, Some(1)),
         }
@@ -453,6 +482,7 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.next(),
             Self::Take(iter) => iter.next(),
             Self::Peekable(iter) => iter.next(),
+            Self::Protocol(iter) => iter.next(),
             Self::Empty => Ok(None),
             Self::Once(v) => Ok(v.take()),
         }
@@ -476,6 +506,7 @@ impl RuneIterator for IterRepr {
             Self::Skip(iter) => iter.next_back(),
             Self::Take(iter) => iter.next_back(),
             Self::Peekable(iter) => iter.next_back(),
+            Self::Protocol(iter) => iter.next_back(),
             Self::Empty => Ok(None),
             Self::Once(v) => Ok(v.take()),
         }
@@ -496,12 +527,62 @@ impl fmt::Debug for IterRepr {
             Self::Skip(iter) => write!(f, "{:?}", iter),
             Self::Take(iter) => write!(f, "{:?}", iter),
             Self::Peekable(iter) => write!(f, "{:?}", iter),
+            Self::Protocol(iter) => write!(f, "{:?}", iter),
             Self::Empty => write!(f, "std::iter::Empty"),
             Self::Once(..) => write!(f, "std::iter::Once"),
         }
     }
 }
 
+/// An iterator which calls the handlers of the iterator protocols of a value.
+struct ProtocolIter {
+    value: Value,
+    next: Arc<FunctionHandler>,
+    next_back: Option<Arc<FunctionHandler>>,
+}
+
+impl ProtocolIter {
+    fn call(&self, handler: &FunctionHandler) -> Result<Option<Value>, VmError> {
+        let mut stack = Stack::with_capacity(1);
+        stack.push(self.value.clone());
+        handler(&mut stack, 1)?;
+        <Option<Value> as FromValue>::from_value(stack.pop()?)
+    }
+}
+
+impl RuneIterator for ProtocolIter {
+    fn is_double_ended(&self) -> bool {
+        self.next_back.is_some()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        self.call(&*self.next)
+    }
+
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        match &self.next_back {
+            Some(next_back) => self.call(&**next_back),
+            None => Err(VmError::panic(format!(
+                "`{:?}` is not a double-ended iterator",
+                self
+            ))),
+        }
+    }
+}
+
+impl fmt::Debug for ProtocolIter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value.type_info() {
+            Ok(type_info) => write!(f, "{}", type_info),
+            Err(..) => write!(f, "std::iter::Iterator"),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Map<I> {
     iter: I,
@@ -1,8 +1,10 @@
 use crate::runtime::{
-    Bytes, FromValue, Object, Shared, StaticString, ToValue, Tuple, TypeInfo, Value, Variant,
-    VariantData, VariantRtti, Vec, VmError, VmErrorKind,
+    env, Bytes, FromValue, Object, Ref, Shared, StaticString, ToValue, Tuple, TypeInfo, Value,
+    Variant, VariantData, VariantRtti, Vec, VmError, VmErrorKind,
 };
+use crate::{Any, Hash};
 use serde::{de, ser};
+use std::any;
 use std::cmp;
 use std::fmt;
 use std::hash;
@@ -34,6 +36,9 @@ pub enum Key {
     Option(Option<Box<Key>>),
     /// A variant.
     Variant(VariantKey),
+    /// A copy of a native value which has been registered as a key through
+    /// [Module::key][crate::Module::key].
+    Any(AnyKey),
 }
 
 impl Key {
@@ -88,6 +93,7 @@ impl Key {
                     data,
                 })
             }
+            Value::Any(..) => Key::Any(AnyKey::from_value(value)?),
             value => {
                 return Err(VmError::from(VmErrorKind::KeyNotSupported {
                     actual: value.type_info()?,
@@ -158,6 +164,7 @@ impl Key {
                     data,
                 }))
             }
+            Self::Any(any) => any.key.to_value(),
         };
 
         fn tuple_into_value(data: Box<[Key]>) -> Tuple {
@@ -203,6 +210,7 @@ impl Key {
             Self::Tuple(..) => TypeInfo::StaticType(crate::runtime::TUPLE_TYPE),
             Self::Option(..) => TypeInfo::StaticType(crate::runtime::OPTION_TYPE),
             Self::Variant(variant) => TypeInfo::Variant(variant.rtti.clone()),
+            Self::Any(any) => any.type_info.clone(),
         }
     }
 }
@@ -221,6 +229,7 @@ impl fmt::Debug for Key {
             Key::Tuple(tuple) => write!(f, "{:?}", tuple),
             Key::Option(opt) => write!(f, "{:?}", opt),
             Key::Variant(variant) => write!(f, "{:?}", variant),
+            Key::Any(any) => write!(f, "{:?}", any),
         }
     }
 }
@@ -283,6 +292,7 @@ impl ser::Serialize for Key {
             }
             Self::Option(option) => <Option<Box<Key>>>::serialize(option, serializer),
             Self::Variant(..) => Err(ser::Error::custom("cannot serialize variants")),
+            Self::Any(..) => Err(ser::Error::custom("cannot serialize native values")),
         }
     }
 }
@@ -546,6 +556,138 @@ impl fmt::Debug for VariantKey {
     }
 }
 
+/// A function which copies a native value into a key.
+pub(crate) type KeyFn = dyn Fn(&Value) -> Result<AnyKey, VmError> + Send + Sync;
+
+/// A copy of a native value which is used as a key.
+///
+/// The copy is taken when the key is constructed, so modifying the original
+/// value afterwards doesn't affect the key. It is hashed and compared through
+/// the implementations of the native type.
+#[derive(Clone)]
+pub struct AnyKey {
+    type_hash: Hash,
+    type_info: TypeInfo,
+    key: Arc<dyn DynKey>,
+}
+
+impl AnyKey {
+    /// Construct a key function for the given type.
+    pub(crate) fn key_fn<T>() -> Arc<KeyFn>
+    where
+        T: Any + Clone + Eq + Ord + hash::Hash + fmt::Debug + Send + Sync,
+    {
+        Arc::new(|value| {
+            let type_info = value.type_info()?;
+            let value = Ref::<T>::from_value(value.clone())?;
+
+            Ok(Self {
+                type_hash: T::type_hash(),
+                type_info,
+                key: Arc::new(T::clone(&value)),
+            })
+        })
+    }
+
+    fn from_value(value: &Value) -> Result<Self, VmError> {
+        let type_hash = value.type_hash()?;
+
+        let key_fn = env::with(|context, _| Ok(context.key(type_hash).cloned()))?;
+
+        match key_fn {
+            Some(key_fn) => key_fn(value),
+            None => Err(VmError::from(VmErrorKind::KeyNotSupported {
+                actual: value.type_info()?,
+            })),
+        }
+    }
+}
+
+impl fmt::Debug for AnyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.key.debug(f)
+    }
+}
+
+impl cmp::PartialEq for AnyKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_hash == other.type_hash && self.key.eq(&*other.key)
+    }
+}
+
+impl cmp::Eq for AnyKey {}
+
+impl hash::Hash for AnyKey {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.type_hash.hash(state);
+        self.key.hash(state);
+    }
+}
+
+impl cmp::PartialOrd for AnyKey {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for AnyKey {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        match self.type_hash.cmp(&other.type_hash) {
+            cmp::Ordering::Equal => self.key.cmp(&*other.key),
+            ordering => ordering,
+        }
+    }
+}
+
+/// The type erased operations of a native key.
+trait DynKey: Send + Sync {
+    fn as_any(&self) -> &dyn any::Any;
+
+    fn eq(&self, other: &dyn DynKey) -> bool;
+
+    fn cmp(&self, other: &dyn DynKey) -> cmp::Ordering;
+
+    fn hash(&self, state: &mut dyn hash::Hasher);
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    fn to_value(&self) -> Value;
+}
+
+impl<T> DynKey for T
+where
+    T: Any + Clone + Eq + Ord + hash::Hash + fmt::Debug + Send + Sync,
+{
+    fn as_any(&self) -> &dyn any::Any {
+        self
+    }
+
+    fn eq(&self, other: &dyn DynKey) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+
+    fn cmp(&self, other: &dyn DynKey) -> cmp::Ordering {
+        // NB: keys are only compared once their type hashes are known to be
+        // equal, so the downcast can't fail.
+        match other.as_any().downcast_ref::<T>() {
+            Some(other) => Ord::cmp(self, other),
+            None => cmp::Ordering::Less,
+        }
+    }
+
+    fn hash(&self, mut state: &mut dyn hash::Hasher) {
+        hash::Hash::hash(self, &mut state);
+    }
+
+    fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::from(self.clone())
+    }
+}
+
 /// Variant data that has been serialized to a key.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum VariantKeyData {
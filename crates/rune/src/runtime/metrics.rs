@@ -0,0 +1,39 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Metrics collected while performing a metered call through
+/// [Vm::call_metered][crate::Vm::call_metered].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Metrics {
+    /// The number of instructions executed.
+    pub instructions: usize,
+    /// The largest number of values which were on the stack at once, including
+    /// the stacks of any virtual machines called into.
+    pub peak_stack_depth: usize,
+}
+
+/// Metrics which are being collected, shared between the virtual machine which
+/// performs a metered call and any virtual machines it calls into.
+#[derive(Debug, Default)]
+pub(crate) struct Meter {
+    instructions: AtomicUsize,
+    peak_stack_depth: AtomicUsize,
+}
+
+impl Meter {
+    /// Record that an instruction is about to be executed with the given
+    /// number of values on the stack.
+    pub(crate) fn record(&self, stack_depth: usize) {
+        self.instructions.fetch_add(1, Ordering::Relaxed);
+        self.peak_stack_depth
+            .fetch_max(stack_depth, Ordering::Relaxed);
+    }
+
+    /// Get the metrics collected so far.
+    pub(crate) fn metrics(&self) -> Metrics {
+        Metrics {
+            instructions: self.instructions.load(Ordering::Relaxed),
+            peak_stack_depth: self.peak_stack_depth.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -7,9 +7,11 @@ mod awaited;
 pub mod budget;
 mod bytes;
 mod call;
+mod capabilities;
 mod const_value;
 pub mod debug;
-mod env;
+mod deep_clone;
+pub mod env;
 pub mod format;
 mod from_value;
 mod function;
@@ -21,6 +23,8 @@ mod inst;
 mod iterator;
 mod key;
 mod label;
+mod metrics;
+mod native_enum;
 mod object;
 mod panic;
 mod protocol;
@@ -48,19 +52,25 @@ mod vm_call;
 mod vm_error;
 mod vm_execution;
 mod vm_halt;
+mod vm_observer;
+mod vm_overflow_mode;
 
 pub(crate) use self::access::{Access, AccessKind};
 pub use self::access::{
-    AccessError, BorrowMut, BorrowRef, NotAccessibleMut, NotAccessibleRef, RawAccessGuard,
+    AccessError, BorrowMut, BorrowRef, NotAccessibleMut, NotAccessibleRef, NotAccessibleTake,
+    RawAccessGuard, Snapshot,
 };
 pub use self::any_obj::{AnyObj, AnyObjError, AnyObjVtable};
 pub use self::args::Args;
 pub(crate) use self::awaited::Awaited;
 pub use self::bytes::Bytes;
 pub use self::call::Call;
+pub(crate) use self::capabilities::gate;
+pub use self::capabilities::Capabilities;
 pub use self::const_value::ConstValue;
 pub use self::debug::{DebugInfo, DebugInst};
-pub use self::format::{Format, FormatSpec};
+pub(crate) use self::deep_clone::DeepClone;
+pub use self::format::{Format, FormatArgs, FormatSpec};
 pub use self::from_value::{FromValue, UnsafeFromValue};
 pub use self::function::{Function, SyncFunction};
 pub use self::future::Future;
@@ -73,15 +83,19 @@ pub use self::inst::{
 };
 pub use self::iterator::{Iterator, IteratorTrait};
 pub use self::key::Key;
+pub(crate) use self::key::{AnyKey, KeyFn};
 pub use self::label::{DebugLabel, Label};
+pub(crate) use self::metrics::Meter;
+pub use self::metrics::Metrics;
+pub(crate) use self::native_enum::{NativeEnum, NativeVariant, VariantFn};
 pub use self::object::Object;
 pub use self::panic::Panic;
 pub use self::protocol::Protocol;
 pub(crate) use self::protocol_caller::{EnvProtocolCaller, ProtocolCaller};
 pub use self::range::{Range, RangeLimits};
 pub use self::raw_str::RawStr;
-pub use self::runtime_context::RuntimeContext;
-pub(crate) use self::runtime_context::{FunctionHandler, MacroHandler};
+pub(crate) use self::runtime_context::{ConstEvalHandler, MacroHandler};
+pub use self::runtime_context::{FunctionHandler, RuntimeContext};
 pub use self::select::Select;
 pub use self::shared::{Mut, RawMut, RawRef, Ref, Shared, SharedPointerGuard};
 pub use self::stack::{Stack, StackError};
@@ -97,14 +111,18 @@ pub use self::to_value::{ToValue, UnsafeToValue};
 pub use self::tuple::Tuple;
 pub use self::type_info::TypeInfo;
 pub use self::type_of::TypeOf;
-pub use self::unit::{Unit, UnitFn};
+pub use self::unit::{Unit, UnitFn, UnitPatch};
 pub use self::value::{Rtti, Struct, TupleStruct, UnitStruct, Value, VariantRtti};
 pub use self::variant::{Variant, VariantData};
 pub use self::vec::Vec;
 pub use self::vec_tuple::VecTuple;
-pub use self::vm::{CallFrame, Vm};
+pub(crate) use self::vm::VmSettings;
+pub use self::vm::{BacktraceFrame, CallFrame, Vm};
 pub(crate) use self::vm_call::VmCall;
 pub use self::vm_error::{VmError, VmErrorKind, VmIntegerRepr};
 pub use self::vm_execution::{ExecutionState, VmExecution, VmSendExecution};
 pub(crate) use self::vm_halt::VmHalt;
 pub use self::vm_halt::VmHaltInfo;
+pub(crate) use self::vm_observer::SharedObserver;
+pub use self::vm_observer::VmObserver;
+pub use self::vm_overflow_mode::VmOverflowMode;
@@ -0,0 +1,56 @@
+use crate::runtime::{Value, VmError, VmErrorKind};
+use crate::Hash;
+use std::sync::Arc;
+use std::vec;
+
+/// Get the index of the variant a native enum value holds, and its fields.
+pub(crate) type VariantFn =
+    dyn Fn(&Value) -> Result<(usize, vec::Vec<Value>), VmError> + Send + Sync;
+
+/// Runtime information on a native enum, as registered through
+/// [Module::enum_meta][crate::Module::enum_meta].
+pub(crate) struct NativeEnum {
+    /// The variants of the enum, in declaration order.
+    pub(crate) variants: Box<[NativeVariant]>,
+    /// Handler used to inspect values of the enum.
+    pub(crate) handler: Arc<VariantFn>,
+}
+
+/// A variant of a native enum.
+pub(crate) struct NativeVariant {
+    /// The type hash of the variant.
+    pub(crate) hash: Hash,
+    /// The names of the fields of the variant, if it's a struct variant.
+    pub(crate) fields: Option<Box<[Box<str>]>>,
+}
+
+impl NativeEnum {
+    /// Get the variant the given value holds and its fields.
+    pub(crate) fn variant(
+        &self,
+        value: &Value,
+    ) -> Result<(&NativeVariant, vec::Vec<Value>), VmError> {
+        let (index, fields) = (self.handler)(value)?;
+
+        match self.variants.get(index) {
+            Some(variant) => Ok((variant, fields)),
+            None => Err(VmError::from(VmErrorKind::MissingVariantIndex {
+                target: value.type_info()?,
+                index,
+            })),
+        }
+    }
+}
+
+impl NativeVariant {
+    /// Get the field with the given name out of the fields of a value holding
+    /// this variant.
+    pub(crate) fn field(&self, fields: vec::Vec<Value>, name: &str) -> Option<Value> {
+        let index = self
+            .fields
+            .as_ref()?
+            .iter()
+            .position(|f| f.as_ref() == name)?;
+        fields.into_iter().nth(index)
+    }
+}
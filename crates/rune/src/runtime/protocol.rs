@@ -81,6 +81,14 @@ impl Protocol {
         hash: Hash::new(0x418f5becbf885806),
     };
 
+    /// Compare two values, returning an [Ordering][std::cmp::Ordering].
+    ///
+    /// This is used as a fallback by the `<`, `<=`, `>` and `>=` operators.
+    pub const CMP: Protocol = Protocol {
+        name: "cmp",
+        hash: Hash::new(0x0bd4f74bb49ed7ec),
+    };
+
     /// The function to access a field.
     pub const GET: Protocol = Protocol {
         name: "get",
@@ -93,6 +101,28 @@ impl Protocol {
         hash: Hash::new(0x7d13d47fd8efef5a),
     };
 
+    /// The function to access a field which isn't known up front.
+    ///
+    /// This is used as a fallback by field accesses like `value.field` on
+    /// native types which don't have a [Protocol::GET] handler for the field.
+    /// The handler receives the name of the field and returns an
+    /// `Option<Value>`, where `None` raises a missing field error.
+    pub const DYNAMIC_FIELD_GET: Protocol = Protocol {
+        name: "dynamic_field_get",
+        hash: Hash::new(0x6c3f0b9e41d2a857),
+    };
+
+    /// The function to set a field which isn't known up front.
+    ///
+    /// This is used as a fallback by field assignments like
+    /// `value.field = 42` on native types which don't have a [Protocol::SET]
+    /// handler for the field. The handler receives the name of the field and
+    /// the value to assign.
+    pub const DYNAMIC_FIELD_SET: Protocol = Protocol {
+        name: "dynamic_field_set",
+        hash: Hash::new(0x1e85d7a06fb3c942),
+    };
+
     /// The function to access an index.
     pub const INDEX_GET: Protocol = Protocol {
         name: "index_get",
@@ -249,15 +279,51 @@ impl Protocol {
         hash: Hash::new(0xc3cde069de2ba320),
     };
 
+    /// The function to call to continue iteration from the back of a
+    /// double-ended iterator.
+    pub const NEXT_BACK: Protocol = Protocol {
+        name: "next_back",
+        hash: Hash::new(0x4bdf3a7c91e2d058),
+    };
+
     /// Function used to convert an argument into a future.
     pub const INTO_FUTURE: Protocol = Protocol {
         name: "into_future",
         hash: Hash::new(0x596e6428deabfda2),
     };
 
-    /// Function used to convert an argument into a future.
+    /// Function used to get the type name of a value.
+    ///
+    /// Native types can register an instance function for this protocol which
+    /// returns a `String` to give values a name which depends on the value
+    /// itself. It is used by [Value::into_type_name][crate::runtime::Value::into_type_name]
+    /// and in the type information of errors, falling back to the registered
+    /// name of the type if the handler is missing or fails.
     pub const INTO_TYPE_NAME: Protocol = Protocol {
         name: "into_type_name",
         hash: Hash::new(0xbffd08b816c24682),
     };
+
+    /// Call a value as if it was a function.
+    ///
+    /// This is used when a value which is not a function is called, like
+    /// `value(a, b)`. The handler either receives the arguments as they were
+    /// passed, or packed into a tuple if it's registered with
+    /// `Params(Protocol::CALL, [Tuple::type_hash()])`.
+    ///
+    /// Script types opt in by marking an instance function with `#[call]`.
+    pub const CALL: Protocol = Protocol {
+        name: "call",
+        hash: Hash::new(0x2e1b1a3c0b6e9d47),
+    };
+
+    /// Function used to clone a native value when it is deep cloned through
+    /// [Value::deep_clone][crate::runtime::Value::deep_clone].
+    ///
+    /// The handler receives a reference to the value and returns an
+    /// independent copy of it.
+    pub const CLONE: Protocol = Protocol {
+        name: "clone",
+        hash: Hash::new(0x8a4e7b3d2f91c605),
+    };
 }
@@ -5,7 +5,7 @@ use crate::Hash;
 pub(crate) trait ProtocolCaller {
     /// Call the given protocol function.
     fn call_protocol_fn<A>(
-        self,
+        &mut self,
         protocol: Protocol,
         target: Value,
         args: A,
@@ -21,7 +21,7 @@ pub(crate) struct EnvProtocolCaller;
 
 impl ProtocolCaller for EnvProtocolCaller {
     fn call_protocol_fn<A>(
-        self,
+        &mut self,
         protocol: Protocol,
         target: Value,
         args: A,
@@ -49,6 +49,7 @@ impl ProtocolCaller for EnvProtocolCaller {
 
                 let mut vm = Vm::with_stack(context.clone(), unit.clone(), stack);
                 vm.set_ip(offset);
+                crate::runtime::env::inherit(&mut vm);
                 return call.call_with_vm(vm);
             }
 
@@ -81,9 +82,26 @@ impl ProtocolCaller for EnvProtocolCaller {
     }
 }
 
-impl ProtocolCaller for &mut Vm {
+impl<T> ProtocolCaller for &mut T
+where
+    T: ProtocolCaller,
+{
     fn call_protocol_fn<A>(
-        self,
+        &mut self,
+        protocol: Protocol,
+        target: Value,
+        args: A,
+    ) -> Result<Value, VmError>
+    where
+        A: GuardedArgs,
+    {
+        (**self).call_protocol_fn(protocol, target, args)
+    }
+}
+
+impl ProtocolCaller for Vm {
+    fn call_protocol_fn<A>(
+        &mut self,
         protocol: Protocol,
         target: Value,
         args: A,
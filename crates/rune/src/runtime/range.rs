@@ -78,6 +78,36 @@ impl Range {
         Ok(true)
     }
 
+    /// Resolve the range into bounds usable for slicing a collection of the
+    /// given length, erroring if the range is inverted or out of bounds.
+    pub(crate) fn slice_range(&self, len: usize) -> Result<ops::Range<usize>, VmError> {
+        let start = match self.start.clone() {
+            Some(value) => <usize as FromValue>::from_value(value)?,
+            None => 0,
+        };
+
+        let end = match (self.limits, self.end.clone()) {
+            (RangeLimits::HalfOpen, Some(value)) => <usize as FromValue>::from_value(value)?,
+            (RangeLimits::HalfOpen, None) => len,
+            (RangeLimits::Closed, Some(value)) => {
+                <usize as FromValue>::from_value(value)?.saturating_add(1)
+            }
+            (RangeLimits::Closed, None) => {
+                return Err(VmError::from(VmErrorKind::UnsupportedRange));
+            }
+        };
+
+        if start > end || end > len {
+            return Err(VmError::from(VmErrorKind::SliceOutOfRange {
+                start,
+                end,
+                len,
+            }));
+        }
+
+        Ok(start..end)
+    }
+
     /// Test if the current range contains the given integer.
     pub(crate) fn contains_int(&self, n: i64) -> Result<bool, VmError> {
         let start: Option<i64> = match self.start.clone() {
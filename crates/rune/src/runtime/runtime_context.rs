@@ -1,12 +1,21 @@
 use crate::collections::HashMap;
 use crate::macros::{MacroContext, TokenStream};
-use crate::runtime::{ConstValue, Stack, VmError};
+use crate::runtime::{ConstValue, KeyFn, NativeEnum, Stack, VmError};
 use crate::Hash;
 use std::fmt;
 use std::sync::Arc;
 
 /// A type-reduced function handler.
-pub(crate) type FunctionHandler = dyn Fn(&mut Stack, usize) -> Result<(), VmError> + Send + Sync;
+///
+/// The handler is called with the stack and the number of arguments which have
+/// been pushed onto it. It is expected to pop its arguments and push its
+/// return value.
+pub type FunctionHandler = dyn Fn(&mut Stack, usize) -> Result<(), VmError> + Send + Sync;
+
+/// A pure handler used to evaluate a native function during constant
+/// evaluation.
+pub(crate) type ConstEvalHandler =
+    dyn Fn(&[ConstValue]) -> Result<ConstValue, VmError> + Send + Sync;
 
 /// A (type erased) macro handler.
 pub(crate) type MacroHandler =
@@ -24,16 +33,24 @@ pub struct RuntimeContext {
     functions: HashMap<Hash, Arc<FunctionHandler>>,
     /// Named constant values
     constants: HashMap<Hash, ConstValue>,
+    /// Native types which can be used as keys.
+    keys: HashMap<Hash, Arc<KeyFn>>,
+    /// Native enums whose variants can be matched on.
+    enums: HashMap<Hash, Arc<NativeEnum>>,
 }
 
 impl RuntimeContext {
     pub(crate) fn new(
         functions: HashMap<Hash, Arc<FunctionHandler>>,
         constants: HashMap<Hash, ConstValue>,
+        keys: HashMap<Hash, Arc<KeyFn>>,
+        enums: HashMap<Hash, Arc<NativeEnum>>,
     ) -> Self {
         Self {
             functions,
             constants,
+            keys,
+            enums,
         }
     }
 
@@ -46,6 +63,16 @@ impl RuntimeContext {
     pub fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
     }
+
+    /// Lookup how to copy a native value of the given type into a key.
+    pub(crate) fn key(&self, hash: Hash) -> Option<&Arc<KeyFn>> {
+        self.keys.get(&hash)
+    }
+
+    /// Lookup the native enum with the given type hash.
+    pub(crate) fn native_enum(&self, hash: Hash) -> Option<&Arc<NativeEnum>> {
+        self.enums.get(&hash)
+    }
 }
 
 impl fmt::Debug for RuntimeContext {
@@ -1,9 +1,10 @@
 use crate::runtime::{
     Access, AccessError, AccessKind, AnyObj, AnyObjError, BorrowMut, BorrowRef, RawAccessGuard,
+    Snapshot,
 };
 use crate::{Any, Hash};
 use std::any;
-use std::cell::{Cell, UnsafeCell};
+use std::cell::{Cell, RefCell, UnsafeCell};
 use std::fmt;
 use std::future::Future;
 use std::mem;
@@ -33,6 +34,13 @@ impl<T> Shared<T> {
         }
     }
 
+    /// Mark the value as being debug formatted until the returned guard is
+    /// dropped, or return `None` if it already is because the value refers to
+    /// itself.
+    pub(crate) fn debug_guard(&self) -> Option<DebugGuard> {
+        DebugGuard::new(self.inner.as_ptr() as *const ())
+    }
+
     /// Return a debug formatter, that when printed will display detailed
     /// diagnostics of this shared type.
     pub fn debug(&self) -> SharedDebug<'_, T> {
@@ -103,6 +111,35 @@ impl<T> Shared<T> {
         unsafe { self.inner.as_ref().access.is_exclusive() }
     }
 
+    /// Get a snapshot of how the value is currently being accessed.
+    ///
+    /// This doesn't acquire any access, so it's suitable for inspecting values
+    /// for diagnostics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::Shared;
+    ///
+    /// let shared = Shared::new(1u32);
+    /// assert!(shared.snapshot().is_writable());
+    ///
+    /// {
+    ///     let a = shared.borrow_ref().unwrap();
+    ///     let b = shared.borrow_ref().unwrap();
+    ///     assert_eq!(shared.snapshot().shared_count(), Some(2));
+    /// }
+    ///
+    /// let shared2 = shared.clone();
+    /// shared.take().unwrap();
+    /// assert!(shared2.snapshot().is_taken());
+    /// ```
+    pub fn snapshot(&self) -> Snapshot {
+        // Safety: Since we have a reference to this shared, we know that the
+        // inner is available.
+        unsafe { self.inner.as_ref().access.snapshot() }
+    }
+
     /// Take the interior value, if we have exlusive access to it and there
     /// are no other live exlusive or shared references.
     ///
@@ -273,6 +310,12 @@ impl<T> Shared<T> {
 }
 
 impl<T: ?Sized> Shared<T> {
+    /// Get a pointer identifying the shared allocation, which is the same for
+    /// all clones of a shared value.
+    pub(crate) fn as_ptr(&self) -> *const () {
+        self.inner.as_ptr() as *const ()
+    }
+
     /// Get a reference to the interior value while checking for shared access.
     ///
     /// This prevents other exclusive accesses from being performed while the
@@ -701,14 +744,58 @@ where
             let inner = self.inner.as_ref();
 
             if !inner.access.is_shared() {
-                write!(fmt, "*not accessible*")
-            } else {
-                write!(fmt, "{:?}", &&*inner.data.get())
+                return write!(fmt, "*not accessible*");
             }
+
+            // Values can refer to themselves, so keep track of the ones
+            // which are being formatted to avoid recursing forever.
+            let _guard = match DebugGuard::new(self.inner.as_ptr() as *const ()) {
+                Some(guard) => guard,
+                None => return write!(fmt, "*recursive*"),
+            };
+
+            fmt::Debug::fmt(&*inner.data.get(), fmt)
         }
     }
 }
 
+thread_local! {
+    /// Shared values which are currently being debug formatted.
+    static DEBUGGING: RefCell<Vec<*const ()>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Guard which marks a shared value as being debug formatted until dropped.
+///
+/// This is shared between [fmt::Debug] and the structural debug formatting
+/// of [Value::string_debug][crate::runtime::Value::string_debug], so that
+/// cycles are detected across both.
+pub(crate) struct DebugGuard(*const ());
+
+impl DebugGuard {
+    /// Mark the given value as being formatted, or return `None` if it already
+    /// is.
+    fn new(ptr: *const ()) -> Option<Self> {
+        DEBUGGING.with(|debugging| {
+            let mut debugging = debugging.borrow_mut();
+
+            if debugging.contains(&ptr) {
+                return None;
+            }
+
+            debugging.push(ptr);
+            Some(Self(ptr))
+        })
+    }
+}
+
+impl Drop for DebugGuard {
+    fn drop(&mut self) {
+        DEBUGGING.with(|debugging| {
+            debugging.borrow_mut().retain(|ptr| *ptr != self.0);
+        });
+    }
+}
+
 /// A debug helper that prints detailed diagnostics on the type being debugged.
 ///
 /// Constructed using [debug][Shared::debug].
@@ -264,6 +264,24 @@ impl Stack {
         }
     }
 
+    /// Access the value at the given offset from the top mutably.
+    pub(crate) fn at_offset_from_top_mut(
+        &mut self,
+        offset: usize,
+    ) -> Result<&mut Value, StackError> {
+        let stack_bottom = self.stack_bottom;
+
+        match self
+            .stack
+            .len()
+            .checked_sub(offset)
+            .filter(|n| *n >= stack_bottom)
+        {
+            Some(n) => Ok(&mut self.stack[n]),
+            None => Err(StackError(())),
+        }
+    }
+
     /// Get the offset at the given location.
     pub(crate) fn at_offset_mut(&mut self, offset: usize) -> Result<&mut Value, StackError> {
         let n = match self.stack_bottom.checked_add(offset) {
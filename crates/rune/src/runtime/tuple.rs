@@ -1,5 +1,7 @@
+use crate::compile::{InstallWith, Named};
 use crate::runtime::{
-    ConstValue, FromValue, Mut, Ref, ToValue, Value, Vm, VmError, VmErrorKind, TUPLE_TYPE,
+    ConstValue, FromValue, Mut, RawMut, RawRef, RawStr, Ref, ToValue, UnsafeFromValue, Value, Vm,
+    VmError, VmErrorKind, TUPLE_TYPE,
 };
 use std::fmt;
 use std::ops;
@@ -76,21 +78,17 @@ impl Tuple {
 
 impl fmt::Debug for Tuple {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "(")?;
-
-        let mut it = self.inner.iter();
-        let last = it.next_back();
-
-        for el in it {
-            write!(f, "{:?}, ", el)?;
+        if self.inner.is_empty() {
+            return write!(f, "()");
         }
 
-        if let Some(last) = last {
-            write!(f, "{:?}", last)?;
+        let mut d = f.debug_tuple("");
+
+        for el in self.inner.iter() {
+            d.field(el);
         }
 
-        write!(f, ")")?;
-        Ok(())
+        d.finish()
     }
 }
 
@@ -154,6 +152,12 @@ impl From<Box<[ConstValue]>> for Tuple {
     }
 }
 
+impl Named for Tuple {
+    const BASE_NAME: RawStr = RawStr::from_str("Tuple");
+}
+
+impl InstallWith for Tuple {}
+
 impl FromValue for Mut<Tuple> {
     fn from_value(value: Value) -> Result<Self, VmError> {
         Ok(value.into_tuple()?.into_mut()?)
@@ -176,6 +180,34 @@ impl FromValue for Tuple {
     }
 }
 
+impl UnsafeFromValue for &Tuple {
+    type Output = *const Tuple;
+    type Guard = RawRef;
+
+    fn from_value(value: Value) -> Result<(Self::Output, Self::Guard), VmError> {
+        let tuple = value.into_tuple()?;
+        Ok(Ref::into_raw(tuple.into_ref()?))
+    }
+
+    unsafe fn unsafe_coerce(output: Self::Output) -> Self {
+        &*output
+    }
+}
+
+impl UnsafeFromValue for &mut Tuple {
+    type Output = *mut Tuple;
+    type Guard = RawMut;
+
+    fn from_value(value: Value) -> Result<(Self::Output, Self::Guard), VmError> {
+        let tuple = value.into_tuple()?;
+        Ok(Mut::into_raw(tuple.into_mut()?))
+    }
+
+    unsafe fn unsafe_coerce(output: Self::Output) -> Self {
+        &mut *output
+    }
+}
+
 macro_rules! impl_tuple {
     () => ();
 
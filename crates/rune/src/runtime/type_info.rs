@@ -10,6 +10,10 @@ pub enum TypeInfo {
     StaticType(&'static StaticType),
     /// Reference to an external type.
     Any(RawStr),
+    /// An external type with a name which depends on its value, as produced by
+    /// a [Protocol::INTO_TYPE_NAME][crate::runtime::Protocol::INTO_TYPE_NAME]
+    /// handler.
+    Dynamic(Arc<str>),
     /// A named type.
     Typed(Arc<Rtti>),
     /// A variant.
@@ -25,6 +29,9 @@ impl fmt::Display for TypeInfo {
             Self::Any(type_name) => {
                 write!(fmt, "{}", *type_name)?;
             }
+            Self::Dynamic(type_name) => {
+                write!(fmt, "{}", type_name)?;
+            }
             Self::Typed(rtti) => {
                 write!(fmt, "{}", rtti.item)?;
             }
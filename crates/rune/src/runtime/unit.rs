@@ -3,12 +3,15 @@
 //! A unit consists of a sequence of instructions, and lookaside tables for
 //! metadata like function locations.
 
-use crate::collections::HashMap;
+use crate::ast::Span;
+use crate::collections::{HashMap, HashSet};
 use crate::runtime::{
-    Call, ConstValue, DebugInfo, Inst, Rtti, StaticString, VariantRtti, VmError, VmErrorKind,
+    Call, ConstValue, DebugInfo, DebugInst, Inst, InstTarget, Rtti, StaticString, VariantRtti,
+    VmError, VmErrorKind,
 };
-use crate::Hash;
+use crate::{Hash, SourceId};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 
@@ -38,6 +41,19 @@ pub struct Unit {
     debug: Option<Box<DebugInfo>>,
     /// Named constants
     constants: HashMap<Hash, ConstValue>,
+    /// Configuration values provided by the host.
+    #[serde(default)]
+    env: HashMap<Box<str>, Box<str>>,
+    /// Names declared as globals, which are set on the virtual machine.
+    #[serde(default)]
+    globals: HashSet<Box<str>>,
+    /// The instructions of each function body by the offset it starts at,
+    /// mapping to where it ends and the functions it implements.
+    ///
+    /// Bodies which have been replaced by a patch are kept, so that
+    /// instruction pointers into them can still be attributed to a function.
+    #[serde(default)]
+    bodies: BTreeMap<usize, (usize, Vec<Hash>)>,
 }
 
 impl Unit {
@@ -53,7 +69,11 @@ impl Unit {
         variant_rtti: HashMap<Hash, Arc<VariantRtti>>,
         debug: Option<Box<DebugInfo>>,
         constants: HashMap<Hash, ConstValue>,
+        env: HashMap<Box<str>, Box<str>>,
+        globals: HashSet<Box<str>>,
     ) -> Self {
+        let bodies = function_bodies(&functions, instructions.len());
+
         Self {
             instructions,
             functions,
@@ -64,6 +84,9 @@ impl Unit {
             variant_rtti,
             debug,
             constants,
+            env,
+            globals,
+            bodies,
         }
     }
 
@@ -88,6 +111,18 @@ impl Unit {
         self.constants.iter()
     }
 
+    /// Lookup a configuration value provided by the host when the unit was
+    /// built.
+    pub fn env_var(&self, name: &str) -> Option<&str> {
+        Some(self.env.get(name)?.as_ref())
+    }
+
+    /// Test if the given name has been declared as a global when the unit was
+    /// built.
+    pub fn is_global(&self, name: &str) -> bool {
+        self.globals.contains(name)
+    }
+
     /// Iterate over all static object keys in the unit.
     pub fn iter_static_object_keys(&self) -> impl Iterator<Item = (usize, &[String])> + '_ {
         let mut it = self.static_object_keys.iter().enumerate();
@@ -149,10 +184,322 @@ impl Unit {
     pub fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
     }
+
+    /// Get the hashes of the functions whose instructions contain the given
+    /// instruction pointer, including bodies which have since been replaced
+    /// by a patch.
+    pub(crate) fn functions_at(&self, ip: usize) -> &[Hash] {
+        match self.bodies.range(..=ip).next_back() {
+            Some((_, (end, hashes))) if ip < *end => hashes,
+            _ => &[],
+        }
+    }
+
+    /// Patch the unit with the functions of another unit, which is typically a
+    /// recompiled version of the same sources.
+    ///
+    /// Functions which are identical in both units are left untouched, so
+    /// their location in the unit stays the same. Functions which have changed
+    /// or been added are appended to this unit, while functions which are
+    /// missing from `patch` are removed. Since functions are called by hash,
+    /// callers of a changed function call the new implementation.
+    ///
+    /// Only the instructions of changed and added functions are appended, so
+    /// repeatedly applying patches only grows the unit by the code which
+    /// changed. Instructions are never removed from the unit, so a function
+    /// which is being executed while the unit is patched runs to completion. Use
+    /// [Vm::apply_patch][crate::Vm::apply_patch] to also keep the functions
+    /// which are on the call stack of a virtual machine.
+    pub fn apply_patch(&mut self, patch: &Unit) -> UnitPatch {
+        self.apply_patch_retaining(patch, &[])
+    }
+
+    /// Patch the unit, but don't remove the functions in `retain`.
+    pub(crate) fn apply_patch_retaining(&mut self, patch: &Unit, retain: &[Hash]) -> UnitPatch {
+        let strings = merge_slots(&mut self.static_strings, &patch.static_strings);
+        let bytes = merge_slots(&mut self.static_bytes, &patch.static_bytes);
+        let object_keys = merge_slots(&mut self.static_object_keys, &patch.static_object_keys);
+
+        let mut result = UnitPatch::default();
+        let mut functions = HashMap::new();
+
+        for (start, (end, hashes)) in &patch.bodies {
+            let body = patch.instructions[*start..*end]
+                .iter()
+                .map(|inst| relocate(*inst, &strings, &bytes, &object_keys))
+                .collect::<Vec<_>>();
+
+            let mut appended = None;
+
+            for hash in hashes {
+                let f = match patch.functions.get(hash) {
+                    Some(f) => *f,
+                    None => continue,
+                };
+
+                let f = match self.functions.get(hash) {
+                    Some(existing) if self.is_same_function(*existing, f, &body) => {
+                        functions.insert(*hash, *existing);
+                        continue;
+                    }
+                    Some(..) => {
+                        result.changed.push(*hash);
+                        f
+                    }
+                    None => {
+                        result.added.push(*hash);
+                        f
+                    }
+                };
+
+                let offset =
+                    *appended.get_or_insert_with(|| self.append_body(patch, *start, &body));
+
+                if let UnitFn::Offset { call, args, .. } = f {
+                    functions.insert(*hash, UnitFn::Offset { offset, call, args });
+                }
+            }
+        }
+
+        for (hash, f) in &patch.functions {
+            if !matches!(f, UnitFn::Offset { .. }) {
+                let f = match self.functions.get(hash) {
+                    Some(existing) if existing == f => *existing,
+                    Some(..) => {
+                        result.changed.push(*hash);
+                        *f
+                    }
+                    None => {
+                        result.added.push(*hash);
+                        *f
+                    }
+                };
+
+                functions.insert(*hash, f);
+            }
+        }
+
+        for (hash, f) in &self.functions {
+            if functions.contains_key(hash) {
+                continue;
+            }
+
+            if retain.contains(hash) {
+                result.retained.push(*hash);
+                functions.insert(*hash, *f);
+            } else {
+                result.removed.push(*hash);
+            }
+        }
+
+        if let (Some(debug), Some(patch)) = (&mut self.debug, &patch.debug) {
+            debug.functions.extend(
+                patch
+                    .functions
+                    .iter()
+                    .map(|(hash, signature)| (*hash, signature.clone())),
+            );
+        }
+
+        self.functions = functions;
+        self.rtti
+            .extend(patch.rtti.iter().map(|(k, v)| (*k, v.clone())));
+        self.variant_rtti
+            .extend(patch.variant_rtti.iter().map(|(k, v)| (*k, v.clone())));
+        self.constants
+            .extend(patch.constants.iter().map(|(k, v)| (*k, v.clone())));
+        self.globals.extend(patch.globals.iter().cloned());
+        result
+    }
+
+    /// Test if the `existing` function is the same as the function `f` from a
+    /// patch, whose relocated instructions are `body`.
+    fn is_same_function(&self, existing: UnitFn, f: UnitFn, body: &[Inst]) -> bool {
+        match (existing, f) {
+            (
+                UnitFn::Offset {
+                    offset,
+                    call: a_call,
+                    args: a_args,
+                },
+                UnitFn::Offset {
+                    call: b_call,
+                    args: b_args,
+                    ..
+                },
+            ) => {
+                // NB: jumps are relative and don't leave the function, so the
+                // same instructions behave the same at any offset.
+                let existing = match self.bodies.get(&offset) {
+                    Some((end, _)) => &self.instructions[offset..*end],
+                    None => return false,
+                };
+
+                a_call == b_call && a_args == b_args && existing == body
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Append the function body which starts at `start` in `patch`, returning
+    /// the offset it was appended at.
+    fn append_body(&mut self, patch: &Unit, start: usize, body: &[Inst]) -> usize {
+        let offset = self.instructions.len();
+        let end = offset + body.len();
+        self.instructions.extend_from_slice(body);
+
+        if let Some((_, hashes)) = patch.bodies.get(&start) {
+            self.bodies.insert(offset, (end, hashes.clone()));
+        }
+
+        if let Some(debug) = &mut self.debug {
+            match &patch.debug {
+                Some(patch) => {
+                    let debug_body = &patch.instructions[start..start + body.len()];
+                    debug.instructions.extend(debug_body.iter().cloned());
+
+                    if let Some(hash) = patch.functions_rev.get(&start) {
+                        debug.functions_rev.insert(offset, *hash);
+                    }
+                }
+                // NB: debug information has to be aligned with instructions,
+                // so the body is padded with entries which don't point into
+                // any source.
+                None => {
+                    debug.instructions.extend(
+                        body.iter()
+                            .map(|_| DebugInst::new(SourceId::empty(), Span::empty(), None, None)),
+                    );
+
+                    if let Some((_, hashes)) = patch.bodies.get(&start) {
+                        if let Some(hash) = hashes.first() {
+                            debug.functions_rev.insert(offset, *hash);
+                        }
+                    }
+                }
+            }
+        }
+
+        offset
+    }
+}
+
+/// Find the instructions of each function body, which extend up until the
+/// function that follows it.
+fn function_bodies(
+    functions: &HashMap<Hash, UnitFn>,
+    len: usize,
+) -> BTreeMap<usize, (usize, Vec<Hash>)> {
+    let mut bodies = BTreeMap::<usize, (usize, Vec<Hash>)>::new();
+
+    for (hash, f) in functions {
+        if let UnitFn::Offset { offset, .. } = f {
+            bodies.entry(*offset).or_default().1.push(*hash);
+        }
+    }
+
+    let mut end = len;
+
+    for (offset, (body_end, hashes)) in bodies.iter_mut().rev() {
+        *body_end = end;
+        end = *offset;
+        hashes.sort();
+    }
+
+    bodies
+}
+
+/// The result of [Unit::apply_patch].
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct UnitPatch {
+    /// Functions which were added by the patch.
+    pub added: Vec<Hash>,
+    /// Functions which were changed by the patch.
+    pub changed: Vec<Hash>,
+    /// Functions which were removed by the patch.
+    pub removed: Vec<Hash>,
+    /// Functions which are missing from the patch, but which were kept since
+    /// they are in use.
+    pub retained: Vec<Hash>,
+}
+
+/// Merge the static slots of a patch into `slots`, returning the new slot of
+/// each slot in `patch`.
+fn merge_slots<T>(slots: &mut Vec<T>, patch: &[T]) -> Vec<usize>
+where
+    T: Clone + Eq + std::hash::Hash,
+{
+    let mut index = HashMap::with_capacity(slots.len());
+
+    for (slot, value) in slots.iter().enumerate() {
+        index.entry(value.clone()).or_insert(slot);
+    }
+
+    patch
+        .iter()
+        .map(|value| match index.get(value) {
+            Some(slot) => *slot,
+            None => {
+                slots.push(value.clone());
+                index.insert(value.clone(), slots.len() - 1);
+                slots.len() - 1
+            }
+        })
+        .collect()
+}
+
+/// Relocate the static slots referenced by an instruction.
+fn relocate(inst: Inst, strings: &[usize], bytes: &[usize], object_keys: &[usize]) -> Inst {
+    match inst {
+        Inst::ObjectIndexGet { slot } => Inst::ObjectIndexGet {
+            slot: strings[slot],
+        },
+        Inst::ObjectIndexSet { slot } => Inst::ObjectIndexSet {
+            slot: strings[slot],
+        },
+        Inst::ObjectIndexGetAt { offset, slot } => Inst::ObjectIndexGetAt {
+            offset,
+            slot: strings[slot],
+        },
+        Inst::String { slot } => Inst::String {
+            slot: strings[slot],
+        },
+        Inst::LoadGlobal { slot } => Inst::LoadGlobal {
+            slot: strings[slot],
+        },
+        Inst::EqStaticString { slot } => Inst::EqStaticString {
+            slot: strings[slot],
+        },
+        Inst::Bytes { slot } => Inst::Bytes { slot: bytes[slot] },
+        Inst::Object { slot } => Inst::Object {
+            slot: object_keys[slot],
+        },
+        Inst::Struct { hash, slot } => Inst::Struct {
+            hash,
+            slot: object_keys[slot],
+        },
+        Inst::StructVariant { hash, slot } => Inst::StructVariant {
+            hash,
+            slot: object_keys[slot],
+        },
+        Inst::MatchObject { slot, exact } => Inst::MatchObject {
+            slot: object_keys[slot],
+            exact,
+        },
+        Inst::Assign {
+            target: InstTarget::Field(slot),
+            op,
+        } => Inst::Assign {
+            target: InstTarget::Field(strings[slot]),
+            op,
+        },
+        inst => inst,
+    }
 }
 
 /// The kind and necessary information on registered functions.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum UnitFn {
     /// Instruction offset of a function inside of the unit.
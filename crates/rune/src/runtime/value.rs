@@ -1,9 +1,9 @@
 use crate::compile::Item;
 use crate::runtime::{
-    AccessKind, AnyObj, Bytes, ConstValue, EnvProtocolCaller, Format, FromValue, Function, Future,
-    Generator, GeneratorState, Iterator, Mut, Object, Protocol, ProtocolCaller, Range, RawMut,
-    RawRef, Ref, Shared, StaticString, Stream, ToValue, Tuple, TypeInfo, Variant, Vec, Vm, VmError,
-    VmErrorKind,
+    AccessKind, AnyObj, Bytes, ConstValue, DeepClone, EnvProtocolCaller, Format, FromValue,
+    Function, Future, Generator, GeneratorState, Iterator, Mut, Object, Protocol, ProtocolCaller,
+    Range, RawMut, RawRef, Ref, Shared, Stack, StaticString, Stream, ToValue, Tuple, TypeInfo,
+    Variant, VariantData, Vec, Vm, VmError, VmErrorKind,
 };
 use crate::{Any, Hash};
 use serde::{de, ser, Deserialize, Serialize};
@@ -80,7 +80,8 @@ impl TupleStruct {
 
 impl fmt::Debug for TupleStruct {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{:?}", self.rtti.item, self.data)
+        write!(f, "{}", self.rtti.item)?;
+        fmt::Debug::fmt(&self.data, f)
     }
 }
 
@@ -140,7 +141,7 @@ impl Struct {
 
 impl fmt::Debug for Struct {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.data.debug_struct(&self.rtti.item))
+        fmt::Display::fmt(&self.data.debug_struct(&self.rtti.item), f)
     }
 }
 
@@ -154,6 +155,9 @@ pub struct VariantRtti {
     pub hash: Hash,
     /// The name of the variant.
     pub item: Item,
+    /// Whether values of the variant are formatted structurally when debug
+    /// printed, as opposed to only by the name of the variant.
+    pub debug: bool,
 }
 
 impl cmp::PartialEq for VariantRtti {
@@ -190,6 +194,9 @@ pub struct Rtti {
     pub hash: Hash,
     /// The item of the type.
     pub item: Item,
+    /// Whether values of the type are formatted structurally when debug
+    /// printed, as opposed to only by the name of the type.
+    pub debug: bool,
 }
 
 impl cmp::PartialEq for Rtti {
@@ -308,7 +315,7 @@ impl Value {
         &self,
         s: &mut String,
         buf: &mut String,
-        caller: impl ProtocolCaller,
+        mut caller: impl ProtocolCaller,
     ) -> Result<fmt::Result, VmError> {
         match self {
             Value::Format(format) => {
@@ -371,8 +378,20 @@ impl Value {
     pub(crate) fn string_debug_with(
         &self,
         s: &mut String,
-        caller: impl ProtocolCaller,
+        mut caller: impl ProtocolCaller,
     ) -> Result<fmt::Result, VmError> {
+        self.string_debug_with_caller(s, &mut caller)
+    }
+
+    /// Debug format the value, reusing the same caller for any nested values.
+    fn string_debug_with_caller<C>(
+        &self,
+        s: &mut String,
+        caller: &mut C,
+    ) -> Result<fmt::Result, VmError>
+    where
+        C: ProtocolCaller,
+    {
         use std::fmt::Write as _;
 
         let result = match self {
@@ -437,16 +456,55 @@ impl Value {
                 write!(s, "{:?}", value)
             }
             Value::UnitStruct(value) => {
-                write!(s, "{:?}", value)
+                let value = value.borrow_ref()?;
+                write!(s, "{}", value.rtti.item)
             }
             Value::TupleStruct(value) => {
-                write!(s, "{:?}", value)
+                let _guard = match value.debug_guard() {
+                    Some(guard) => guard,
+                    None => return Ok(write!(s, "*recursive*")),
+                };
+
+                let value = value.borrow_ref()?;
+
+                if value.rtti.debug {
+                    debug_tuple(s, &value.rtti.item, &value.data, caller)?
+                } else {
+                    write!(s, "{}(..)", value.rtti.item)
+                }
             }
             Value::Struct(value) => {
-                write!(s, "{:?}", value)
+                let _guard = match value.debug_guard() {
+                    Some(guard) => guard,
+                    None => return Ok(write!(s, "*recursive*")),
+                };
+
+                let value = value.borrow_ref()?;
+
+                if value.rtti.debug {
+                    debug_struct(s, &value.rtti.item, &value.data, caller)?
+                } else {
+                    write!(s, "{} {{ .. }}", value.rtti.item)
+                }
             }
             Value::Variant(value) => {
-                write!(s, "{:?}", value)
+                let _guard = match value.debug_guard() {
+                    Some(guard) => guard,
+                    None => return Ok(write!(s, "*recursive*")),
+                };
+
+                let value = value.borrow_ref()?;
+                let item = &value.rtti.item;
+
+                match &value.data {
+                    VariantData::Unit => write!(s, "{}", item),
+                    VariantData::Tuple(..) if !value.rtti.debug => write!(s, "{}(..)", item),
+                    VariantData::Tuple(tuple) => debug_tuple(s, item, tuple, caller)?,
+                    VariantData::Struct(..) if !value.rtti.debug => {
+                        write!(s, "{} {{ .. }}", item)
+                    }
+                    VariantData::Struct(object) => debug_struct(s, item, object, caller)?,
+                }
             }
             Value::Function(value) => {
                 write!(s, "{:?}", value)
@@ -472,7 +530,57 @@ impl Value {
             }
         };
 
-        Ok(result)
+        return Ok(result);
+
+        /// Debug format a tuple-like value, like `Foo(1, 2)`.
+        fn debug_tuple(
+            s: &mut String,
+            item: &Item,
+            tuple: &[Value],
+            caller: &mut impl ProtocolCaller,
+        ) -> Result<fmt::Result, VmError> {
+            if write!(s, "{}(", item).is_err() {
+                return Ok(Err(fmt::Error));
+            }
+
+            for (index, value) in tuple.iter().enumerate() {
+                if index > 0 {
+                    s.push_str(", ");
+                }
+
+                if let Err(error) = value.string_debug_with_caller(s, caller)? {
+                    return Ok(Err(error));
+                }
+            }
+
+            s.push(')');
+            Ok(Ok(()))
+        }
+
+        /// Debug format a struct-like value, like `Foo { a: 1, b: 2 }`.
+        fn debug_struct(
+            s: &mut String,
+            item: &Item,
+            object: &Object,
+            caller: &mut impl ProtocolCaller,
+        ) -> Result<fmt::Result, VmError> {
+            if write!(s, "{} {{", item).is_err() {
+                return Ok(Err(fmt::Error));
+            }
+
+            for (index, (key, value)) in object.iter().enumerate() {
+                s.push_str(if index > 0 { ", " } else { " " });
+                s.push_str(key);
+                s.push_str(": ");
+
+                if let Err(error) = value.string_debug_with_caller(s, caller)? {
+                    return Ok(Err(error));
+                }
+            }
+
+            s.push_str(if object.is_empty() { "}" } else { " }" });
+            Ok(Ok(()))
+        }
     }
 
     /// Convert value into an iterator using the [Protocol::INTO_ITER] protocol.
@@ -536,6 +644,9 @@ impl Value {
 
     /// Retrieves a human readable type name for the current value.
     ///
+    /// This calls the [Protocol::INTO_TYPE_NAME] handler of the value if it
+    /// has one, and returns any error it raises.
+    ///
     /// You must use [Vm::with] to specify which virtual machine this function
     /// is called inside.
     ///
@@ -546,6 +657,13 @@ impl Value {
         let hash = Hash::instance_function(self.type_hash()?, Protocol::INTO_TYPE_NAME);
 
         crate::runtime::env::with(|context, unit| {
+            if let Some(handler) = context.function(hash) {
+                let mut stack = Stack::with_capacity(1);
+                stack.push(self);
+                handler(&mut stack, 1)?;
+                return String::from_value(stack.pop()?);
+            }
+
             if let Some(name) = context.constant(hash) {
                 match name {
                     ConstValue::String(s) => return Ok(s.clone()),
@@ -633,6 +751,45 @@ impl Value {
         })
     }
 
+    /// Construct an independent copy of the value.
+    ///
+    /// Cloning a [Value] only clones the reference to the data it contains,
+    /// while this recursively copies vectors, tuples, objects and other script
+    /// data. Native types are copied through their [Protocol::CLONE] handler.
+    ///
+    /// Values which are referenced more than once, including through cycles,
+    /// are copied once and remain shared in the copy. Functions, futures,
+    /// streams, generators and iterators are not copied.
+    ///
+    /// You must use [Vm::with] to specify which virtual machine this function
+    /// is called inside if the value contains native types.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any part of the value is exclusively borrowed, or if it
+    /// contains a native type which doesn't implement [Protocol::CLONE].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{Object, Shared, Value};
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut object = Object::new();
+    /// object.insert(String::from("a"), Value::from(1i64));
+    /// let a = Value::from(Shared::new(object));
+    ///
+    /// let b = a.deep_clone()?;
+    /// b.clone().into_object()?.borrow_mut()?.insert(String::from("a"), Value::from(2i64));
+    ///
+    /// assert_eq!(a.into_object()?.borrow_ref()?.get_value::<_, i64>("a")?, Some(1));
+    /// assert_eq!(b.into_object()?.borrow_ref()?.get_value::<_, i64>("a")?, Some(2));
+    /// # Ok(()) }
+    /// ```
+    pub fn deep_clone(&self) -> Result<Value, VmError> {
+        DeepClone::default().clone_value(self)
+    }
+
     /// Try to coerce value into a unit.
     #[inline]
     pub fn into_unit(self) -> Result<(), VmError> {
@@ -885,6 +1042,37 @@ impl Value {
     ///
     /// One notable feature is that the type of a variant is its container
     /// *enum*, and not the type hash of the variant itself.
+    ///
+    /// This is a cheap way to dispatch on the type of a value, since the hash
+    /// can be compared against precomputed type hashes instead of comparing
+    /// type names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{TypeOf, Value, FLOAT_TYPE, INTEGER_TYPE};
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// fn describe(value: &Value) -> rune::Result<&'static str> {
+    ///     let hash = value.type_hash()?;
+    ///
+    ///     Ok(if hash == INTEGER_TYPE.hash {
+    ///         "integer"
+    ///     } else if hash == FLOAT_TYPE.hash {
+    ///         "float"
+    ///     } else if hash == String::type_hash() {
+    ///         "string"
+    ///     } else {
+    ///         "other"
+    ///     })
+    /// }
+    ///
+    /// assert_eq!(describe(&Value::Integer(42))?, "integer");
+    /// assert_eq!(describe(&Value::Float(4.2))?, "float");
+    /// assert_eq!(describe(&Value::from(String::from("hello")))?, "string");
+    /// assert_eq!(describe(&Value::Bool(true))?, "other");
+    /// # Ok(()) }
+    /// ```
     pub fn type_hash(&self) -> Result<Hash, VmError> {
         Ok(match self {
             Self::Unit => crate::runtime::UNIT_TYPE.hash,
@@ -948,10 +1136,33 @@ impl Value {
             Self::TupleStruct(tuple) => tuple.borrow_ref()?.type_info(),
             Self::Struct(object) => object.borrow_ref()?.type_info(),
             Self::Variant(empty) => empty.borrow_ref()?.type_info(),
-            Self::Any(any) => TypeInfo::Any(any.borrow_ref()?.type_name()),
+            Self::Any(any) => match self.dynamic_type_name() {
+                Some(name) => TypeInfo::Dynamic(name.into()),
+                None => TypeInfo::Any(any.borrow_ref()?.type_name()),
+            },
         })
     }
 
+    /// Get the name of the current value from its
+    /// [Protocol::INTO_TYPE_NAME] handler, if it has one and it can be called.
+    fn dynamic_type_name(&self) -> Option<String> {
+        let hash = Hash::instance_function(self.type_hash().ok()?, Protocol::INTO_TYPE_NAME);
+
+        let name = crate::runtime::env::with(|context, _| {
+            let handler = match context.function(hash) {
+                Some(handler) => handler,
+                None => return Ok(None),
+            };
+
+            let mut stack = Stack::with_capacity(1);
+            stack.push(self.clone());
+            handler(&mut stack, 1)?;
+            Ok(Some(String::from_value(stack.pop()?)?))
+        });
+
+        name.ok().flatten()
+    }
+
     /// Optimized function to test if two value pointers are deeply equal to
     /// each other.
     ///
@@ -1065,82 +1276,82 @@ impl fmt::Debug for Value {
                 write!(f, "()")?;
             }
             Value::Bool(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Byte(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Char(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Integer(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Float(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Type(value) => {
                 write!(f, "Type({})", value)?;
             }
             Value::StaticString(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::String(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Bytes(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Vec(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Tuple(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Object(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Range(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Future(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Stream(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Generator(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::GeneratorState(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Option(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Result(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::UnitStruct(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::TupleStruct(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Struct(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Variant(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Function(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Format(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             Value::Iterator(value) => {
-                write!(f, "{:?}", value)?;
+                fmt::Debug::fmt(value, f)?;
             }
             value => {
                 let mut s = String::new();
@@ -1287,6 +1498,7 @@ impl ser::Serialize for Value {
     {
         use serde::ser::SerializeMap as _;
         use serde::ser::SerializeSeq as _;
+        use serde::ser::SerializeTuple as _;
 
         match self {
             Value::Unit => serializer.serialize_unit(),
@@ -1316,7 +1528,7 @@ impl ser::Serialize for Value {
             }
             Value::Tuple(tuple) => {
                 let tuple = tuple.borrow_ref().map_err(ser::Error::custom)?;
-                let mut serializer = serializer.serialize_seq(Some(tuple.len()))?;
+                let mut serializer = serializer.serialize_tuple(tuple.len())?;
 
                 for value in tuple.iter() {
                     serializer.serialize_element(value)?;
@@ -1480,6 +1692,30 @@ impl<'de> de::Visitor<'de> for VmVisitor {
         Ok(Value::Integer(v as i64))
     }
 
+    #[inline]
+    fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Float(v as f64))
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Float(v))
+    }
+
+    #[inline]
+    fn visit_char<E>(self, v: char) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Char(v))
+    }
+
     #[inline]
     fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
     where
@@ -1496,6 +1732,14 @@ impl<'de> de::Visitor<'de> for VmVisitor {
         Ok(Value::Unit)
     }
 
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
     #[inline]
     fn visit_unit<E>(self) -> Result<Self::Value, E>
     where
@@ -1540,6 +1784,14 @@ impl<'de> de::Visitor<'de> for VmVisitor {
 #[cfg(test)]
 mod tests {
     use super::Value;
+    use crate::runtime::{TypeOf, INTEGER_TYPE};
+
+    #[test]
+    fn test_type_hash() {
+        let value = Value::from(42i64);
+        assert_eq!(value.type_hash().unwrap(), INTEGER_TYPE.hash);
+        assert_eq!(value.type_hash().unwrap(), i64::type_hash());
+    }
 
     #[test]
     fn test_size() {
@@ -57,6 +57,27 @@ impl Vec {
         }
     }
 
+    /// Returns the number of elements the dynamic vector can hold without
+    /// reallocating.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Reserve capacity for at least `additional` more elements to be
+    /// inserted. Errors if the new capacity can't be allocated.
+    pub fn reserve(&mut self, additional: usize) -> Result<(), VmError> {
+        self.inner.try_reserve(additional).map_err(|_| {
+            VmError::from(VmErrorKind::CapacityOverflow {
+                capacity: self.inner.len().saturating_add(additional),
+            })
+        })
+    }
+
+    /// Shrink the capacity of the dynamic vector as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit();
+    }
+
     /// Convert into inner std vector.
     pub fn into_inner(self) -> vec::Vec<Value> {
         self.inner
@@ -272,6 +293,19 @@ where
     T: FromValue,
 {
     fn from_value(value: Value) -> Result<Self, VmError> {
+        // NB: bytes convert element-wise, which among other things allows
+        // them to be read out as a `Vec<u8>`.
+        if let Value::Bytes(bytes) = value {
+            let bytes = bytes.borrow_ref()?;
+            let mut output = vec::Vec::with_capacity(bytes.len());
+
+            for b in bytes.iter() {
+                output.push(T::from_value(Value::Byte(*b))?);
+            }
+
+            return Ok(output);
+        }
+
         let vec = value.into_vec()?;
         let vec = vec.take()?;
 
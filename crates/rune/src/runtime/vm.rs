@@ -1,17 +1,28 @@
+use crate::ast::Span;
+use crate::collections::HashMap;
+use crate::compile::Item;
 use crate::runtime::budget;
+use crate::runtime::env;
+use crate::runtime::function::Callable;
 use crate::runtime::future::SelectFuture;
 use crate::runtime::unit::UnitFn;
 use crate::runtime::{
-    Args, Awaited, BorrowMut, Bytes, Call, Format, FormatSpec, FromValue, Function, Future,
-    Generator, GuardedArgs, Inst, InstAddress, InstAssignOp, InstOp, InstRangeLimits, InstTarget,
-    InstValue, InstVariant, Object, Panic, Protocol, Range, RangeLimits, RuntimeContext, Select,
-    Shared, Stack, Stream, Struct, Tuple, TypeCheck, Unit, UnitStruct, Value, Variant, VariantData,
-    Vec, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmSendExecution,
+    Args, Awaited, BorrowMut, Bytes, Call, Capabilities, DeepClone, Format, FormatSpec, FromValue,
+    Function, FunctionHandler, Future, Generator, GuardedArgs, Inst, InstAddress, InstAssignOp,
+    InstOp, InstRangeLimits, InstTarget, InstValue, InstVariant, Meter, Metrics, NativeVariant,
+    Object, Panic, Protocol, Range, RangeLimits, RuntimeContext, Select, Shared, SharedObserver,
+    Stack, StaticString, Stream, Struct, ToValue, Tuple, TypeCheck, Unit, UnitPatch, UnitStruct,
+    Value, Variant, VariantData, Vec, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr,
+    VmObserver, VmOverflowMode, VmSendExecution,
 };
-use crate::{Hash, IntoTypeHash};
+use crate::{Hash, IntoTypeHash, SourceId, Sources};
 use std::fmt;
+use std::io;
 use std::mem;
-use std::sync::Arc;
+use std::ops::{Bound, RangeBounds as _};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
 use std::vec;
 
 enum TargetFallback<'a> {
@@ -58,6 +69,42 @@ macro_rules! target_value {
     }};
 }
 
+/// The settings of a virtual machine which are inherited by the virtual
+/// machines it calls into, see [Vm::settings].
+#[derive(Clone)]
+pub(crate) struct VmSettings {
+    max_error_frames: usize,
+    max_call_frames: Option<usize>,
+    deadline: Option<Instant>,
+    deadline_interval: usize,
+    budget: Option<Arc<AtomicUsize>>,
+    pub(crate) capabilities: Option<Arc<Capabilities>>,
+    pub(crate) output: Option<Output>,
+    observer: Option<SharedObserver>,
+    globals: Option<Arc<HashMap<Box<str>, Value>>>,
+    overflow_mode: VmOverflowMode,
+    meter: Option<(Arc<Meter>, usize)>,
+}
+
+/// The output of a virtual machine, see [Vm::set_output].
+#[derive(Clone)]
+pub(crate) struct Output(Arc<Mutex<dyn io::Write + Send>>);
+
+impl Output {
+    /// Lock the output for writing.
+    pub(crate) fn lock(&self) -> io::Result<MutexGuard<'_, dyn io::Write + Send + 'static>> {
+        self.0
+            .lock()
+            .map_err(|_| io::Error::other("output writer is poisoned"))
+    }
+}
+
+impl fmt::Debug for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Output")
+    }
+}
+
 /// A stack which references variables indirectly from a slab.
 #[derive(Debug, Clone)]
 pub struct Vm {
@@ -71,9 +118,47 @@ pub struct Vm {
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: vec::Vec<CallFrame>,
+    /// The maximum number of call frames recorded in errors.
+    max_error_frames: usize,
+    /// The maximum number of simultaneous call frames, if limited.
+    max_call_frames: Option<usize>,
+    /// The wall-clock deadline of the current call, if any.
+    deadline: Option<Instant>,
+    /// The number of instructions executed between checks of the deadline.
+    deadline_interval: usize,
+    /// Instructions executed since the deadline was last checked.
+    deadline_ticks: usize,
+    /// The remaining instruction budget, shared with child virtual machines.
+    budget: Option<Arc<AtomicUsize>>,
+    /// Capabilities granted to the virtual machine, or `None` if it uses the
+    /// capabilities of the virtual machine which called into it.
+    capabilities: Option<Arc<Capabilities>>,
+    /// Where `print` and `println` write to, defaults to stdout.
+    output: Option<Output>,
+    /// Observer notified about execution.
+    observer: Option<SharedObserver>,
+    /// Globals which have been set on the virtual machine.
+    globals: Option<Arc<HashMap<Box<str>, Value>>>,
+    /// How integer arithmetic which overflows is handled.
+    overflow_mode: VmOverflowMode,
+    /// The meter of a metered call, and the number of values on the stacks of
+    /// the virtual machines which called into this one.
+    meter: Option<(Arc<Meter>, usize)>,
+    /// Sorted instruction pointers which execution pauses at.
+    breakpoints: vec::Vec<usize>,
+    /// Set when execution paused at a breakpoint, so that resuming executes
+    /// the instruction at it instead of pausing again.
+    at_breakpoint: bool,
 }
 
 impl Vm {
+    /// The default maximum number of call frames recorded in errors.
+    pub const DEFAULT_MAX_ERROR_FRAMES: usize = 64;
+
+    /// The default number of instructions executed between checks of the
+    /// deadline set through [Vm::call_with_deadline].
+    pub const DEFAULT_DEADLINE_INTERVAL: usize = 1024;
+
     /// Construct a new virtual machine.
     pub const fn new(context: Arc<RuntimeContext>, unit: Arc<Unit>) -> Self {
         Self::with_stack(context, unit, Stack::new())
@@ -87,6 +172,20 @@ impl Vm {
             ip: 0,
             stack,
             call_frames: vec::Vec::new(),
+            max_error_frames: Self::DEFAULT_MAX_ERROR_FRAMES,
+            max_call_frames: None,
+            deadline: None,
+            deadline_interval: Self::DEFAULT_DEADLINE_INTERVAL,
+            deadline_ticks: 0,
+            budget: None,
+            capabilities: None,
+            output: None,
+            observer: None,
+            globals: None,
+            overflow_mode: VmOverflowMode::Checked,
+            meter: None,
+            breakpoints: vec::Vec::new(),
+            at_breakpoint: false,
         }
     }
 
@@ -108,12 +207,414 @@ impl Vm {
         self.ip = ip;
     }
 
+    /// Set the maximum number of call frames which are recorded in the
+    /// backtrace of an error raised by this virtual machine.
+    ///
+    /// Only the most recent frames are recorded, the number of frames left out
+    /// is available through [VmError::elided_frames].
+    ///
+    /// Defaults to [Vm::DEFAULT_MAX_ERROR_FRAMES].
+    #[inline]
+    pub fn set_max_error_frames(&mut self, max_error_frames: usize) {
+        self.max_error_frames = max_error_frames;
+    }
+
+    /// Get the maximum number of call frames which are recorded in the
+    /// backtrace of an error raised by this virtual machine.
+    #[inline]
+    pub fn max_error_frames(&self) -> usize {
+        self.max_error_frames
+    }
+
+    /// Set the maximum number of call frames which can be active at the same
+    /// time in this virtual machine.
+    ///
+    /// Calls which would exceed the limit raise
+    /// [VmErrorKind::CallStackLimit]. This is independent of any instruction
+    /// budget, and is unlimited by default.
+    #[inline]
+    pub fn set_max_call_frames(&mut self, max_call_frames: usize) {
+        self.max_call_frames = Some(max_call_frames);
+    }
+
+    /// Get the maximum number of call frames which can be active at the same
+    /// time, if one has been set.
+    #[inline]
+    pub fn max_call_frames(&self) -> Option<usize> {
+        self.max_call_frames
+    }
+
+    /// Use the given mode for integer arithmetic which overflows.
+    ///
+    /// See [VmOverflowMode].
+    pub fn with_overflow_mode(mut self, overflow_mode: VmOverflowMode) -> Self {
+        self.overflow_mode = overflow_mode;
+        self
+    }
+
+    /// Set the mode used for integer arithmetic which overflows.
+    #[inline]
+    pub fn set_overflow_mode(&mut self, overflow_mode: VmOverflowMode) {
+        self.overflow_mode = overflow_mode;
+    }
+
+    /// Get the mode used for integer arithmetic which overflows.
+    #[inline]
+    pub fn overflow_mode(&self) -> VmOverflowMode {
+        self.overflow_mode
+    }
+
+    /// Limit the number of instructions this virtual machine can execute.
+    ///
+    /// Once the budget is used up, execution errors with
+    /// [VmErrorKind::BudgetExceeded]. The budget covers every call made
+    /// through the virtual machine until it's changed or cleared, and is shared
+    /// with the generators, streams and async functions it starts.
+    ///
+    /// Unlike [budget::with], which suspends execution when it runs out, this
+    /// is a hard limit which is suitable for running untrusted scripts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use rune::runtime::VmErrorKind;
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() { loop {} }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    /// vm.set_budget(10_000);
+    ///
+    /// let error = vm.call(&["main"], ()).unwrap_err();
+    /// assert!(matches!(error.into_unwound().0.into_kind(), VmErrorKind::BudgetExceeded));
+    /// assert_eq!(vm.budget(), Some(0));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = Some(Arc::new(AtomicUsize::new(budget)));
+    }
+
+    /// Get the remaining instruction budget, if one has been set.
+    #[inline]
+    pub fn budget(&self) -> Option<usize> {
+        Some(self.budget.as_ref()?.load(Ordering::Relaxed))
+    }
+
+    /// Remove the instruction budget, allowing an unlimited number of
+    /// instructions to be executed.
+    #[inline]
+    pub fn clear_budget(&mut self) {
+        self.budget = None;
+    }
+
+    /// Set the number of instructions executed between each check of the
+    /// deadline set through [Vm::call_with_deadline].
+    ///
+    /// Reading the clock isn't free, so a larger interval lowers the overhead
+    /// of a deadline at the cost of overshooting it by more. An interval of
+    /// `0` is treated as `1`, checking the clock before every instruction.
+    ///
+    /// Defaults to [Vm::DEFAULT_DEADLINE_INTERVAL].
+    #[inline]
+    pub fn set_deadline_interval(&mut self, deadline_interval: usize) {
+        self.deadline_interval = deadline_interval.max(1);
+    }
+
+    /// Get the number of instructions executed between each check of the
+    /// deadline.
+    #[inline]
+    pub fn deadline_interval(&self) -> usize {
+        self.deadline_interval
+    }
+
+    /// Set a global on the virtual machine, making it available to scripts
+    /// under the given name.
+    ///
+    /// The name has to be declared when the script is built through
+    /// [Build::with_globals][crate::Build::with_globals], since globals are
+    /// resolved when the script is compiled. Setting a global which hasn't been
+    /// declared by the unit raises [VmErrorKind::UndeclaredGlobal], and loading
+    /// a declared global which hasn't been set raises
+    /// [VmErrorKind::MissingGlobal].
+    ///
+    /// The global is shared with the script, so changes the script makes to
+    /// it are visible to the caller. Since the global might reference values
+    /// which can't be sent across threads, a virtual machine which has globals
+    /// can't be used with [Vm::send_execute].
+    ///
+    /// ```
+    /// use rune::{FromValue, ToValue, Vm};
+    /// use rune::runtime::Object;
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             env.answer
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_globals(["env"]).build()?;
+    ///
+    /// let mut env = Object::new();
+    /// env.insert(String::from("answer"), 42i64.to_value()?);
+    ///
+    /// let mut vm = Vm::without_runtime(Arc::new(unit));
+    /// vm.set_global("env", env)?;
+    ///
+    /// let output = i64::from_value(vm.call(&["main"], ())?)?;
+    /// assert_eq!(output, 42);
+    /// # Ok(()) }
+    /// ```
+    pub fn set_global<N, V>(&mut self, name: N, value: V) -> Result<(), VmError>
+    where
+        N: AsRef<str>,
+        V: ToValue,
+    {
+        let name = name.as_ref();
+
+        if !self.unit.is_global(name) {
+            return Err(VmError::from(VmErrorKind::UndeclaredGlobal {
+                name: name.to_owned(),
+            }));
+        }
+
+        let value = value.to_value()?;
+        let globals = self.globals.get_or_insert_with(Default::default);
+        Arc::make_mut(globals).insert(name.into(), value);
+        Ok(())
+    }
+
+    /// Get the global with the given name, if it has been set.
+    pub fn global(&self, name: &str) -> Option<&Value> {
+        self.globals.as_ref()?.get(name)
+    }
+
     /// Get the stack.
     #[inline]
     pub fn call_frames(&self) -> &[CallFrame] {
         &self.call_frames
     }
 
+    /// Get a backtrace of the functions currently being executed, resolved
+    /// through the debug information of the unit.
+    ///
+    /// The innermost frame, corresponding to the current instruction pointer,
+    /// comes first and the entry point of the execution comes last. Frames
+    /// which don't have debug information are left out, so the backtrace is
+    /// empty if the unit was compiled without it.
+    pub fn backtrace(&self) -> vec::Vec<BacktraceFrame<'_>> {
+        backtrace(&self.unit, self.ip, &self.call_frames)
+    }
+
+    /// Grant the given capability to the virtual machine, allowing it to call
+    /// native functions which require it.
+    ///
+    /// See [Module::capability][crate::Module::capability].
+    ///
+    /// This gives the virtual machine its own set of capabilities if it
+    /// doesn't have one already, see [Vm::set_capabilities].
+    #[inline]
+    pub fn grant(&mut self, capability: &str) {
+        self.capabilities_mut().grant(capability);
+    }
+
+    /// Get the capabilities granted to the virtual machine, or `None` if it
+    /// uses the capabilities of the virtual machine which called into it.
+    #[inline]
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_deref()
+    }
+
+    /// Get the capabilities granted to the virtual machine mutably.
+    ///
+    /// This gives the virtual machine its own, initially empty, set of
+    /// capabilities if it doesn't have one already.
+    #[inline]
+    pub fn capabilities_mut(&mut self) -> &mut Capabilities {
+        Arc::make_mut(self.capabilities.get_or_insert_with(Default::default))
+    }
+
+    /// Set the capabilities granted to the virtual machine.
+    ///
+    /// With `Some`, the virtual machine is granted exactly the given
+    /// capabilities, so an empty set means that nothing is granted. With
+    /// `None`, which is the default, it uses the capabilities of the virtual
+    /// machine which called into it, or none if it's not called from another
+    /// virtual machine.
+    #[inline]
+    pub fn set_capabilities(&mut self, capabilities: Option<Capabilities>) {
+        self.capabilities = capabilities.map(Arc::new);
+    }
+
+    /// Set the writer which script output, such as `print` and `println`, is
+    /// written to instead of stdout.
+    ///
+    /// ```
+    /// use rune::{Context, FromValue, Vm};
+    /// use std::io::{self, Write};
+    /// use std::sync::{Arc, Mutex, MutexGuard};
+    ///
+    /// #[derive(Clone, Default)]
+    /// struct Buffer(Arc<Mutex<Vec<u8>>>);
+    ///
+    /// impl Write for Buffer {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         self.0.lock().unwrap().write(buf)
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             println("Hello World");
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    ///
+    /// let buffer = Buffer::default();
+    ///
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    /// vm.set_output(buffer.clone());
+    /// vm.call(&["main"], ())?;
+    ///
+    /// assert_eq!(&*buffer.0.lock().unwrap(), b"Hello World\n");
+    /// # Ok(()) }
+    /// ```
+    pub fn set_output<W>(&mut self, output: W)
+    where
+        W: 'static + io::Write + Send,
+    {
+        self.output = Some(Output(Arc::new(Mutex::new(output))));
+    }
+
+    /// Set the observer which is notified about the execution of the virtual
+    /// machine.
+    ///
+    /// Virtual machines which are created to run async functions, generators
+    /// and streams called from this one share its observer.
+    pub fn set_observer<O>(&mut self, observer: O)
+    where
+        O: 'static + VmObserver + Send,
+    {
+        self.observer = Some(SharedObserver::new(observer));
+    }
+
+    /// Remove the observer of the virtual machine.
+    pub fn clear_observer(&mut self) {
+        self.observer = None;
+    }
+
+    /// Set a breakpoint at the instruction `ip`, which causes execution to
+    /// pause before the instruction is executed.
+    ///
+    /// Execution which pauses at a breakpoint notifies the observer through
+    /// [VmObserver::breakpoint] and can be continued through
+    /// [VmExecution::resume_until_breakpoint]. Other ways of running the
+    /// virtual machine error with [VmErrorKind::Halted] when a breakpoint is
+    /// hit.
+    ///
+    /// The instruction for a given span in the source can be looked up
+    /// through the [debug info][Unit::debug_info] of the unit. Breakpoints
+    /// are not inherited by the virtual machines this one calls into.
+    pub fn set_breakpoint(&mut self, ip: usize) {
+        if let Err(n) = self.breakpoints.binary_search(&ip) {
+            self.breakpoints.insert(n, ip);
+        }
+    }
+
+    /// Remove the breakpoint at the instruction `ip`, returning `true` if one
+    /// was set.
+    pub fn clear_breakpoint(&mut self, ip: usize) -> bool {
+        match self.breakpoints.binary_search(&ip) {
+            Ok(n) => {
+                self.breakpoints.remove(n);
+                true
+            }
+            Err(..) => false,
+        }
+    }
+
+    /// Remove all breakpoints.
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Inherit the settings of the given virtual machine which called into
+    /// this one.
+    pub(crate) fn inherit(&mut self, parent: &Vm) {
+        self.inherit_settings(parent.settings());
+    }
+
+    /// Call the given native function handler with `args` arguments on the
+    /// stack of the virtual machine.
+    pub(crate) fn call_handler(
+        &mut self,
+        handler: &FunctionHandler,
+        args: usize,
+    ) -> Result<(), VmError> {
+        env::with_frames(self.ip, &self.call_frames, || {
+            handler(&mut self.stack, args)
+        })
+    }
+
+    /// Take a snapshot of the settings of the virtual machine, which are
+    /// inherited by the virtual machines it calls into.
+    pub(crate) fn settings(&self) -> VmSettings {
+        VmSettings {
+            max_error_frames: self.max_error_frames,
+            max_call_frames: self.max_call_frames,
+            deadline: self.deadline,
+            deadline_interval: self.deadline_interval,
+            budget: self.budget.clone(),
+            capabilities: self.capabilities.clone(),
+            output: self.output.clone(),
+            observer: self.observer.clone(),
+            globals: self.globals.clone(),
+            overflow_mode: self.overflow_mode,
+            meter: self
+                .meter
+                .as_ref()
+                .map(|(meter, base)| (meter.clone(), base + self.stack.len())),
+        }
+    }
+
+    /// Inherit the given settings, as taken by [Vm::settings].
+    pub(crate) fn inherit_settings(&mut self, settings: VmSettings) {
+        self.max_error_frames = settings.max_error_frames;
+        self.max_call_frames = settings.max_call_frames;
+        self.deadline = settings.deadline;
+        self.deadline_interval = settings.deadline_interval;
+        self.budget = settings.budget;
+        self.capabilities = settings.capabilities;
+        self.output = settings.output;
+        self.observer = settings.observer;
+        self.globals = settings.globals;
+        self.overflow_mode = settings.overflow_mode;
+        self.meter = settings.meter;
+    }
+
     /// Get the stack.
     #[inline]
     pub fn stack(&self) -> &Stack {
@@ -138,6 +639,23 @@ impl Vm {
         &self.unit
     }
 
+    /// Patch the unit of the virtual machine with the functions of another
+    /// unit, as with [Unit::apply_patch].
+    ///
+    /// Functions which are on the call stack of the virtual machine are kept,
+    /// even if they are missing from `patch`. If the unit is shared with other
+    /// virtual machines, the virtual machine gets its own patched copy of it.
+    pub fn apply_patch(&mut self, patch: &Unit) -> UnitPatch {
+        let mut retain = vec::Vec::new();
+
+        if !self.stack.is_empty() || !self.call_frames.is_empty() {
+            let ips = std::iter::once(self.ip).chain(self.call_frames.iter().map(|f| f.ip));
+            retain.extend(ips.flat_map(|ip| self.unit.functions_at(ip)).copied());
+        }
+
+        Arc::make_mut(&mut self.unit).apply_patch_retaining(patch, &retain)
+    }
+
     /// Access the current instruction pointer.
     #[inline]
     pub fn ip(&self) -> usize {
@@ -155,6 +673,7 @@ impl Vm {
         self.ip = 0;
         self.stack.clear();
         self.call_frames.clear();
+        self.at_breakpoint = false;
     }
 
     /// Modify the current instruction pointer.
@@ -259,13 +778,69 @@ impl Vm {
     /// This is accomplished by preventing values escaping from being
     /// non-exclusively sent with the execution or escaping the execution. We
     /// only support encoding arguments which themselves are `Send`.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [VmErrorKind::SendGlobals] if globals have been set on the
+    /// virtual machine through [Vm::set_global], since they might reference
+    /// values which can't be sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, FromValue, Vm};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn add(a, b) { a + b }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    /// let execution = vm.send_execute(&["add"], (1i64, 2i64))?;
+    ///
+    /// let output = thread::spawn(move || -> rune::Result<i64> {
+    ///     Ok(i64::from_value(execution.complete()?)?)
+    /// })
+    /// .join()
+    /// .expect("thread panicked")?;
+    ///
+    /// assert_eq!(output, 3);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// Arguments which reference shared values can't be sent:
+    ///
+    /// ```compile_fail
+    /// use rune::runtime::Shared;
+    /// use rune::{Context, Unit, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let vm = Vm::new(Arc::new(context.runtime()), Arc::new(Unit::default()));
+    ///
+    /// let shared = Shared::new(String::from("hello"));
+    /// let execution = vm.send_execute(&["main"], (shared,))?;
+    /// # Ok(()) }
+    /// ```
     pub fn send_execute<A, N>(mut self, name: N, args: A) -> Result<VmSendExecution, VmError>
     where
         N: IntoTypeHash,
         A: Send + Args,
     {
-        // Safety: make sure the stack is clear, preventing any values from
-        // being sent along with the virtual machine.
+        // Safety: make sure that there are no globals and that the stack is
+        // clear, preventing any values from being sent along with the virtual
+        // machine.
+        if matches!(&self.globals, Some(globals) if !globals.is_empty()) {
+            return Err(VmError::from(VmErrorKind::SendGlobals));
+        }
+
         self.stack.clear();
 
         self.set_entrypoint(name, args.count())?;
@@ -313,6 +888,247 @@ impl Vm {
         Ok(value)
     }
 
+    /// Call the given function immediately with arguments which have already
+    /// been pushed onto a [Stack], returning the produced value.
+    ///
+    /// This avoids converting arguments through [Args] or [GuardedArgs], which
+    /// is useful when arguments are produced as [Value]s already, like when
+    /// resuming a coroutine.
+    ///
+    /// The virtual machine takes ownership of the stack and uses it in place
+    /// of its current one. Every value on the stack is passed as an argument,
+    /// in the order in which they were pushed, so the length of the stack must
+    /// match the number of arguments the function expects. Once the call has
+    /// completed, successfully or not, the stack is cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Hash, Vm};
+    /// use rune::runtime::Stack;
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let mut sources = rune::sources!(entry => {
+    ///     pub fn add(a, b) { a + b }
+    /// });
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    ///
+    /// let mut stack = Stack::new();
+    /// stack.push(1i64);
+    /// stack.push(2i64);
+    ///
+    /// let value = vm.call_with_stack(Hash::type_hash(&["add"]), stack)?;
+    /// assert_eq!(value.into_integer()?, 3);
+    /// # Ok(()) }
+    /// ```
+    pub fn call_with_stack<N>(&mut self, name: N, stack: Stack) -> Result<Value, VmError>
+    where
+        N: IntoTypeHash,
+    {
+        self.set_entrypoint(name, stack.len())?;
+        self.stack = stack;
+
+        // NB: the stack is cleared when the call completes, erroneously or
+        // not.
+        let vm = ClearStack(self);
+        VmExecution::new(&mut *vm.0).complete()
+    }
+
+    /// Call the given function immediately like [Vm::call], but error with
+    /// [VmErrorKind::DeadlineExceeded] if it's still running once the given
+    /// wall-clock deadline has passed.
+    ///
+    /// The deadline is checked periodically, see [Vm::set_deadline_interval].
+    /// It's independent of any instruction budget, and doesn't interrupt
+    /// native functions which are running when it passes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use rune::runtime::VmErrorKind;
+    /// use std::sync::Arc;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() { loop {} }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    ///
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// let error = vm.call_with_deadline(&["main"], (), deadline).unwrap_err();
+    /// assert!(matches!(error.into_unwound().0.into_kind(), VmErrorKind::DeadlineExceeded));
+    /// # Ok(()) }
+    /// ```
+    pub fn call_with_deadline<A, N>(
+        &mut self,
+        name: N,
+        args: A,
+        deadline: Instant,
+    ) -> Result<Value, VmError>
+    where
+        N: IntoTypeHash,
+        A: GuardedArgs,
+    {
+        let previous = self.deadline.replace(deadline);
+        self.deadline_ticks = 0;
+        let result = self.call(name, args);
+        self.deadline = previous;
+        result
+    }
+
+    /// Call a function value, like a closure returned from a script, with the
+    /// given arguments and return the produced value.
+    ///
+    /// The function runs with the unit and context it was created in, and a
+    /// closure is called with the environment it captured. Settings like the
+    /// capabilities, output and limits of this virtual machine apply to the
+    /// call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(n) { |a| a + n }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    ///
+    /// let closure = vm.call(&["main"], (1i64,))?;
+    /// let output = vm.call_value(closure, (41i64,))?;
+    /// assert_eq!(output.into_integer()?, 42);
+    /// # Ok(()) }
+    /// ```
+    pub fn call_value<A>(&self, value: Value, args: A) -> Result<Value, VmError>
+    where
+        A: Args,
+    {
+        let function = value.into_function()?;
+        let function = function.borrow_ref()?;
+        // NB: the virtual machine running the function inherits the settings
+        // of this one through the environment.
+        let _guard = crate::runtime::env::Guard::new(self);
+        function.call(args)
+    }
+
+    /// Call the given generator function, returning a [Generator] which
+    /// lazily produces each value it yields.
+    ///
+    /// Nothing in the body of the generator runs until values are pulled out
+    /// of it with [Generator::next] or [Generator::resume]. Calling a function
+    /// which isn't a generator errors with [VmErrorKind::NotGenerator].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(n) {
+    ///             yield n;
+    ///             yield n + 1;
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    ///
+    /// let mut generator = vm.call_generator(&["main"], (1i64,))?;
+    /// assert_eq!(generator.next()?.map(|v| v.into_integer()).transpose()?, Some(1));
+    /// assert_eq!(generator.next()?.map(|v| v.into_integer()).transpose()?, Some(2));
+    /// assert!(generator.next()?.is_none());
+    /// # Ok(()) }
+    /// ```
+    pub fn call_generator<A, N>(&mut self, name: N, args: A) -> Result<Generator<Vm>, VmError>
+    where
+        N: IntoTypeHash,
+        A: Args,
+    {
+        let hash = name.into_type_hash();
+
+        let mut vm = Self::new(self.context.clone(), self.unit.clone());
+        vm.inherit(self);
+        vm.set_entrypoint(name, args.count())?;
+
+        if !matches!(
+            self.unit.function(hash),
+            Some(UnitFn::Offset {
+                call: Call::Generator,
+                ..
+            })
+        ) {
+            return Err(VmError::from(VmErrorKind::NotGenerator { hash }));
+        }
+
+        args.into_stack(&mut vm.stack)?;
+        Ok(Generator::new(vm))
+    }
+
+    /// Call the given function immediately like [Vm::call], while collecting
+    /// [Metrics] on the work performed by the call.
+    ///
+    /// The metrics include the work performed by any virtual machines called
+    /// into, like closures called from native functions. Recording them makes
+    /// every instruction a bit slower, so this should only be used when the
+    /// metrics are needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> rune::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(n) { n + 1 }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    ///
+    /// let (output, metrics) = vm.call_metered(&["main"], (41i64,));
+    /// assert_eq!(output?.into_integer()?, 42);
+    /// assert!(metrics.instructions > 0);
+    /// # Ok(()) }
+    /// ```
+    pub fn call_metered<A, N>(&mut self, name: N, args: A) -> (Result<Value, VmError>, Metrics)
+    where
+        N: IntoTypeHash,
+        A: GuardedArgs,
+    {
+        let meter = Arc::<Meter>::default();
+        let outer = self.meter.replace((meter.clone(), 0));
+        let result = self.call(name, args);
+        self.meter = outer;
+        (result, meter.metrics())
+    }
+
     /// Call the given function immediately asynchronously, returning the
     /// produced value.
     ///
@@ -425,7 +1241,9 @@ impl Vm {
         }
 
         if let Some(handler) = self.context.function(hash) {
-            handler(&mut self.stack, count)?;
+            env::with_frames(self.ip, &self.call_frames, || {
+                handler(&mut self.stack, count)
+            })?;
             return Ok(true);
         }
 
@@ -434,6 +1252,22 @@ impl Vm {
         Ok(false)
     }
 
+    /// Inspect the variant held by a value, if it's a native enum.
+    fn native_variant(
+        &self,
+        value: &Value,
+    ) -> Result<Option<(&NativeVariant, vec::Vec<Value>)>, VmError> {
+        let native_enum = match value {
+            Value::Any(..) => match self.context.native_enum(value.type_hash()?) {
+                Some(native_enum) => native_enum,
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(native_enum.variant(value)?))
+    }
+
     /// Helper to call a field function.
     #[inline(always)]
     fn call_field_fn<H, A>(
@@ -462,7 +1296,38 @@ impl Vm {
             }
         };
 
-        handler(&mut self.stack, count)?;
+        env::with_frames(self.ip, &self.call_frames, || {
+            handler(&mut self.stack, count)
+        })?;
+        Ok(true)
+    }
+
+    /// Helper to call a dynamic field function, which receives the name of
+    /// the field being accessed.
+    fn call_dynamic_field_fn<A>(
+        &mut self,
+        protocol: Protocol,
+        target: &Value,
+        field: &Arc<StaticString>,
+        args: A,
+    ) -> Result<bool, VmError>
+    where
+        A: Args,
+    {
+        let hash = Hash::instance_function(target.type_hash()?, protocol);
+
+        let handler = match self.context.function(hash) {
+            Some(handler) => handler.clone(),
+            None => return Ok(false),
+        };
+
+        let count = args.count() + 2;
+        self.stack.push(target.clone());
+        self.stack.push(field.clone());
+        args.into_stack(&mut self.stack)?;
+        env::with_frames(self.ip, &self.call_frames, || {
+            handler(&mut self.stack, count)
+        })?;
         Ok(true)
     }
 
@@ -470,6 +1335,7 @@ impl Vm {
         &mut self,
         int_op: fn(i64, i64) -> bool,
         float_op: fn(f64, f64) -> bool,
+        ordering_op: fn(std::cmp::Ordering) -> bool,
         op: &'static str,
         lhs: InstAddress,
         rhs: InstAddress,
@@ -481,11 +1347,16 @@ impl Vm {
             (Value::Integer(lhs), Value::Integer(rhs)) => int_op(lhs, rhs),
             (Value::Float(lhs), Value::Float(rhs)) => float_op(lhs, rhs),
             (lhs, rhs) => {
-                return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                    op,
-                    lhs: lhs.type_info()?,
-                    rhs: rhs.type_info()?,
-                }))
+                if !self.call_instance_fn(lhs.clone(), Protocol::CMP, (&rhs,))? {
+                    return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                        op,
+                        lhs: lhs.type_info()?,
+                        rhs: rhs.type_info()?,
+                    }));
+                }
+
+                let ordering = std::cmp::Ordering::from_value(self.stack.pop()?)?;
+                ordering_op(ordering)
             }
         };
 
@@ -498,6 +1369,12 @@ impl Vm {
     /// This will cause the `args` number of elements on the stack to be
     /// associated and accessible to the new call frame.
     pub(crate) fn push_call_frame(&mut self, ip: usize, args: usize) -> Result<(), VmError> {
+        if let Some(limit) = self.max_call_frames {
+            if self.call_frames.len() >= limit {
+                return Err(VmError::from(VmErrorKind::CallStackLimit { limit }));
+            }
+        }
+
         let stack_top = self.stack.swap_stack_bottom(args)?;
 
         self.call_frames.push(CallFrame {
@@ -506,6 +1383,12 @@ impl Vm {
         });
 
         self.ip = ip.wrapping_sub(1);
+
+        if let Some(observer) = &self.observer {
+            let depth = self.call_frames.len();
+            observer.with(|o| o.enter_call(ip, depth));
+        }
+
         Ok(())
     }
 
@@ -521,6 +1404,12 @@ impl Vm {
 
         self.stack.pop_stack_top(frame.stack_bottom)?;
         self.ip = frame.ip;
+
+        if let Some(observer) = &self.observer {
+            let depth = self.call_frames.len();
+            observer.with(|o| o.exit_call(frame.ip, depth));
+        }
+
         Ok(false)
     }
 
@@ -845,12 +1734,35 @@ impl Vm {
                 }
             }
             target => {
-                let hash = index.hash();
+                let index = index.clone();
 
-                if self.call_field_fn(Protocol::GET, target, hash, ())? {
-                    Some(self.stack.pop()?)
-                } else {
-                    None
+                if let Some((variant, fields)) = self.native_variant(target)? {
+                    if variant.fields.is_some() {
+                        return match variant.field(fields, index.as_str()) {
+                            Some(value) => Ok(Some(value)),
+                            None => Err(VmError::from(VmErrorKind::ObjectIndexMissing {
+                                slot: string_slot,
+                            })),
+                        };
+                    }
+                }
+
+                if self.call_field_fn(Protocol::GET, target, index.hash(), ())? {
+                    return Ok(Some(self.stack.pop()?));
+                }
+
+                if !self.call_dynamic_field_fn(Protocol::DYNAMIC_FIELD_GET, target, &index, ())? {
+                    return Ok(None);
+                }
+
+                match Option::<Value>::from_value(self.stack.pop()?)? {
+                    Some(value) => Some(value),
+                    None => {
+                        return Err(VmError::from(VmErrorKind::MissingField {
+                            target: target.type_info()?,
+                            field: index.as_str().to_owned(),
+                        }));
+                    }
                 }
             }
         })
@@ -899,9 +1811,19 @@ impl Vm {
                 }));
             }
             target => {
-                let hash = field.hash();
+                let field = field.clone();
+
+                if self.call_field_fn(Protocol::SET, target, field.hash(), (value.clone(),))? {
+                    self.stack.pop()?;
+                    return Ok(Some(()));
+                }
 
-                if self.call_field_fn(Protocol::SET, target, hash, (value,))? {
+                if self.call_dynamic_field_fn(
+                    Protocol::DYNAMIC_FIELD_SET,
+                    target,
+                    &field,
+                    (value,),
+                )? {
                     self.stack.pop()?;
                     Some(())
                 } else {
@@ -980,6 +1902,18 @@ impl Vm {
                     _ => None,
                 }
             }
+            (TypeCheck::Variant(hash), value @ Value::Any(..)) => {
+                let (variant, fields) = match self.native_variant(value)? {
+                    Some(native) => native,
+                    None => return Ok(None),
+                };
+
+                if variant.hash != hash || variant.fields.is_some() {
+                    return Ok(None);
+                }
+
+                Some(f(&fields))
+            }
             (TypeCheck::Unit, Value::Unit) => Some(f(&[])),
             _ => None,
         })
@@ -1033,6 +1967,7 @@ impl Vm {
         let stack = self.stack.drain(args)?.collect::<Stack>();
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.ip = offset;
+        vm.inherit(self);
         self.stack.push(Generator::new(vm));
         Ok(())
     }
@@ -1042,6 +1977,7 @@ impl Vm {
         let stack = self.stack.drain(args)?.collect::<Stack>();
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.ip = offset;
+        vm.inherit(self);
         self.stack.push(Stream::new(vm));
         Ok(())
     }
@@ -1051,6 +1987,7 @@ impl Vm {
         let stack = self.stack.drain(args)?.collect::<Stack>();
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.ip = offset;
+        vm.inherit(self);
         self.stack.push(Future::new(vm.async_complete()));
         Ok(())
     }
@@ -1186,36 +2123,6 @@ impl Vm {
         Ok(())
     }
 
-    /// Internal impl of a numeric operation.
-    fn internal_infallible_bitwise(
-        &mut self,
-        protocol: Protocol,
-        integer_op: fn(i64, i64) -> i64,
-        lhs: InstAddress,
-        rhs: InstAddress,
-    ) -> Result<(), VmError> {
-        let rhs = self.stack.address(rhs)?;
-        let lhs = self.stack.address(lhs)?;
-
-        let (lhs, rhs) = match (lhs, rhs) {
-            (Value::Integer(lhs), Value::Integer(rhs)) => {
-                self.stack.push(integer_op(lhs, rhs));
-                return Ok(());
-            }
-            (lhs, rhs) => (lhs, rhs),
-        };
-
-        if !self.call_instance_fn(lhs.clone(), protocol, (&rhs,))? {
-            return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                op: protocol.name,
-                lhs: lhs.type_info()?,
-                rhs: rhs.type_info()?,
-            }));
-        }
-
-        Ok(())
-    }
-
     /// Internal impl of a numeric operation.
     fn internal_infallible_bitwise_bool(
         &mut self,
@@ -1509,9 +2416,81 @@ impl Vm {
 
     /// Construct a new vec.
     #[cfg_attr(feature = "bench", inline(never))]
-    fn op_vec(&mut self, count: usize) -> Result<(), VmError> {
-        let vec = Vec::from(self.stack.pop_sequence(count)?);
-        self.stack.push(Shared::new(vec));
+    fn op_vec(&mut self, count: usize, capacity: usize) -> Result<(), VmError> {
+        let mut vec = vec::Vec::with_capacity(usize::max(count, capacity));
+        vec.extend(self.stack.drain(count)?);
+        self.stack.push(Shared::new(Vec::from(vec)));
+        Ok(())
+    }
+
+    /// Charge the given amount to the instruction budget, if one has been
+    /// set.
+    #[inline]
+    fn charge_budget(&self, amount: usize) -> Result<(), VmError> {
+        if let Some(budget) = &self.budget {
+            if budget
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                    n.checked_sub(amount)
+                })
+                .is_err()
+            {
+                return Err(VmError::from(VmErrorKind::BudgetExceeded));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Construct a new vec by repeating a value.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_vec_repeat(&mut self) -> Result<(), VmError> {
+        let count = match self.stack.pop()? {
+            Value::Integer(count) => count,
+            actual => {
+                return Err(VmError::expected::<i64>(actual.type_info()?));
+            }
+        };
+
+        let count = usize::try_from(count).map_err(|_| {
+            VmError::from(VmErrorKind::ValueToIntegerCoercionError {
+                from: VmIntegerRepr::from(count),
+                to: "usize",
+            })
+        })?;
+
+        let value = self.stack.pop()?;
+
+        // NB: every element costs as much as an instruction, so that repeating
+        // a value can't be used to get around the instruction budget.
+        self.charge_budget(count)?;
+
+        let mut vec = vec::Vec::new();
+
+        vec.try_reserve(count)
+            .map_err(|_| VmError::from(VmErrorKind::CapacityOverflow { capacity: count }))?;
+
+        // NB: every element is a distinct copy of the value, just like when the
+        // repeat expression is evaluated as a constant. Native values which
+        // can't be cloned are shared between the elements instead.
+        if count > 0 {
+            for _ in 1..count {
+                vec.push(DeepClone::sharing_native().clone_value(&value)?);
+            }
+
+            vec.push(value);
+        }
+
+        self.stack.push(Shared::new(Vec::from(vec)));
+        Ok(())
+    }
+
+    /// Extend a vec with the values produced by an iterable.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_vec_extend(&mut self) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+        let vec = self.stack.pop()?.into_vec()?;
+        vec.borrow_mut()?.extend(value)?;
+        self.stack.push(vec);
         Ok(())
     }
 
@@ -1570,7 +2549,10 @@ impl Vm {
 
         let value = match value {
             Value::Float(value) => Value::from(-value),
-            Value::Integer(value) => Value::from(-value),
+            Value::Integer(value) => match self.overflow_mode.neg(value) {
+                Some(value) => Value::from(value),
+                None => return Err(VmError::from(VmErrorKind::Overflow)),
+            },
             other => {
                 let operand = other.type_info()?;
                 return Err(VmError::from(VmErrorKind::UnsupportedUnaryOperation {
@@ -1593,7 +2575,7 @@ impl Vm {
                 self.internal_num(
                     Protocol::ADD,
                     || VmErrorKind::Overflow,
-                    i64::checked_add,
+                    self.overflow_mode.add(),
                     std::ops::Add::add,
                     lhs,
                     rhs,
@@ -1603,7 +2585,7 @@ impl Vm {
                 self.internal_num(
                     Protocol::SUB,
                     || VmErrorKind::Underflow,
-                    i64::checked_sub,
+                    self.overflow_mode.sub(),
                     std::ops::Sub::sub,
                     lhs,
                     rhs,
@@ -1613,7 +2595,7 @@ impl Vm {
                 self.internal_num(
                     Protocol::MUL,
                     || VmErrorKind::Overflow,
-                    i64::checked_mul,
+                    self.overflow_mode.mul(),
                     std::ops::Mul::mul,
                     lhs,
                     rhs,
@@ -1679,19 +2661,53 @@ impl Vm {
                 )?;
             }
             InstOp::Shr => {
-                self.internal_infallible_bitwise(Protocol::SHR, std::ops::Shr::shr, lhs, rhs)?;
+                self.internal_bitwise(
+                    Protocol::SHR,
+                    || VmErrorKind::Overflow,
+                    |a, b| a.checked_shr(u32::try_from(b).ok()?),
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Gt => {
-                self.internal_boolean_ops(|a, b| a > b, |a, b| a > b, ">", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a > b,
+                    |a, b| a > b,
+                    std::cmp::Ordering::is_gt,
+                    ">",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Gte => {
-                self.internal_boolean_ops(|a, b| a >= b, |a, b| a >= b, ">=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a >= b,
+                    |a, b| a >= b,
+                    std::cmp::Ordering::is_ge,
+                    ">=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lt => {
-                self.internal_boolean_ops(|a, b| a < b, |a, b| a < b, "<", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a < b,
+                    |a, b| a < b,
+                    std::cmp::Ordering::is_lt,
+                    "<",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lte => {
-                self.internal_boolean_ops(|a, b| a <= b, |a, b| a <= b, "<=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a <= b,
+                    |a, b| a <= b,
+                    std::cmp::Ordering::is_le,
+                    "<=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Eq => {
                 let rhs = self.stack.address(rhs)?;
@@ -1734,7 +2750,7 @@ impl Vm {
                     target,
                     Protocol::ADD_ASSIGN,
                     || VmErrorKind::Overflow,
-                    i64::checked_add,
+                    self.overflow_mode.add(),
                     std::ops::Add::add,
                 )?;
             }
@@ -1743,7 +2759,7 @@ impl Vm {
                     target,
                     Protocol::SUB_ASSIGN,
                     || VmErrorKind::Underflow,
-                    i64::checked_sub,
+                    self.overflow_mode.sub(),
                     std::ops::Sub::sub,
                 )?;
             }
@@ -1752,7 +2768,7 @@ impl Vm {
                     target,
                     Protocol::MUL_ASSIGN,
                     || VmErrorKind::Overflow,
-                    i64::checked_mul,
+                    self.overflow_mode.mul(),
                     std::ops::Mul::mul,
                 )?;
             }
@@ -1804,10 +2820,11 @@ impl Vm {
                 )?;
             }
             InstAssignOp::Shr => {
-                self.internal_infallible_bitwise_assign(
+                self.internal_bitwise_assign(
                     target,
                     Protocol::SHR_ASSIGN,
-                    std::ops::ShrAssign::shr_assign,
+                    || VmErrorKind::Overflow,
+                    |a, b| a.checked_shr(u32::try_from(b).ok()?),
                 )?;
             }
         }
@@ -2019,6 +3036,17 @@ impl Vm {
             return Ok(());
         }
 
+        if let Some((variant, fields)) = self.native_variant(value)? {
+            if variant.fields.is_none() {
+                if let Some(value) = fields.into_iter().nth(index) {
+                    self.stack.push(value);
+                    return Ok(());
+                }
+            }
+        }
+
+        let value = self.stack.at_offset(offset)?;
+
         Err(VmError::from(VmErrorKind::UnsupportedTupleIndexGet {
             target: value.type_info()?,
         }))
@@ -2202,6 +3230,23 @@ impl Vm {
         Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_load_global(&mut self, slot: usize) -> Result<(), VmError> {
+        let name = self.unit.lookup_string(slot)?;
+
+        let value = match self.globals.as_ref().and_then(|g| g.get(name.as_str())) {
+            Some(value) => value.clone(),
+            None => {
+                return Err(VmError::from(VmErrorKind::MissingGlobal {
+                    name: name.as_str().to_owned(),
+                }));
+            }
+        };
+
+        self.stack.push(value);
+        Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_bytes(&mut self, slot: usize) -> Result<(), VmError> {
         let bytes = self.unit.lookup_bytes(slot)?.to_owned();
@@ -2352,13 +3397,47 @@ impl Vm {
         Ok(())
     }
 
+    /// Test if the top of the stack is inside of the given range, values of
+    /// other types than the bounds never match.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_match_range(
+        &mut self,
+        start: Option<InstValue>,
+        end: Option<InstValue>,
+        inclusive: bool,
+    ) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        let is_match = match value {
+            Value::Integer(n) => range_contains(n, start, end, inclusive, |v| match v {
+                InstValue::Integer(v) => Some(v),
+                _ => None,
+            }),
+            Value::Byte(n) => range_contains(n, start, end, inclusive, |v| match v {
+                InstValue::Byte(v) => Some(v),
+                _ => None,
+            }),
+            Value::Char(n) => range_contains(n, start, end, inclusive, |v| match v {
+                InstValue::Char(v) => Some(v),
+                _ => None,
+            }),
+            _ => false,
+        };
+
+        self.stack.push(is_match);
+        Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_match_type(&mut self, hash: Hash) -> Result<(), VmError> {
         let value = self.stack.pop()?;
 
         let value_hash = match value {
             Value::Variant(variant) => variant.borrow_ref()?.rtti().hash,
-            value => value.type_hash()?,
+            value => match self.native_variant(&value)? {
+                Some((variant, _)) => variant.hash,
+                None => value.type_hash()?,
+            },
         };
 
         let is_match = value_hash == hash;
@@ -2586,7 +3665,9 @@ impl Vm {
                     .function(hash)
                     .ok_or(VmErrorKind::MissingFunction { hash })?;
 
-                handler(&mut self.stack, args)?;
+                env::with_frames(self.ip, &self.call_frames, || {
+                    handler(&mut self.stack, args)
+                })?;
             }
         }
 
@@ -2599,7 +3680,8 @@ impl Vm {
         let args = args + 1;
         let instance = self.stack.at_offset_from_top(args)?;
         let type_hash = instance.type_hash()?;
-        let hash = Hash::instance_function(type_hash, hash);
+        let name = hash;
+        let hash = Hash::instance_function(type_hash, name);
 
         if let Some(UnitFn::Offset {
             offset,
@@ -2613,7 +3695,50 @@ impl Vm {
         }
 
         if let Some(handler) = self.context.function(hash) {
-            handler(&mut self.stack, args)?;
+            env::with_frames(self.ip, &self.call_frames, || {
+                handler(&mut self.stack, args)
+            })?;
+            return Ok(());
+        }
+
+        // NB: the type hash of a function value is the hash of the function
+        // itself, so instance functions of the `Function` type are looked up
+        // separately.
+        if let Value::Function(..) = instance {
+            let hash = Hash::instance_function(crate::runtime::FUNCTION_TYPE.hash, name);
+
+            if let Some(handler) = self.context.function(hash) {
+                env::with_frames(self.ip, &self.call_frames, || {
+                    handler(&mut self.stack, args)
+                })?;
+                return Ok(());
+            }
+        }
+
+        // NB: values which implement the `NEXT` protocol are iterators, so the
+        // instance functions of `std::iter::Iterator` can be used on them.
+        let iterator_hash = Hash::instance_function(crate::runtime::ITERATOR_TYPE.hash, name);
+
+        if let (Some(handler), Some(next)) = (
+            self.context.function(iterator_hash),
+            self.context
+                .function(Hash::instance_function(type_hash, Protocol::NEXT)),
+        ) {
+            let next_back = self
+                .context
+                .function(Hash::instance_function(type_hash, Protocol::NEXT_BACK));
+
+            let instance = self.stack.at_offset_from_top_mut(args)?;
+            let value = mem::take(instance);
+            *instance = Value::from(crate::runtime::Iterator::from_protocol(
+                value,
+                next.clone(),
+                next_back.cloned(),
+            ));
+
+            env::with_frames(self.ip, &self.call_frames, || {
+                handler(&mut self.stack, args)
+            })?;
             return Ok(());
         }
 
@@ -2634,6 +3759,12 @@ impl Vm {
                 return function.call_with_vm(self, args);
             }
             actual => {
+                let type_hash = actual.type_hash()?;
+
+                if let Some(callable) = Callable::resolve(&self.context, &self.unit, type_hash) {
+                    return callable.call_with_vm(self, actual, args);
+                }
+
                 let actual_type = actual.type_info()?;
                 return Err(VmError::from(VmErrorKind::UnsupportedCallFn {
                     actual_type,
@@ -2708,7 +3839,7 @@ impl Vm {
     where
         F: FnOnce() -> T,
     {
-        let _guard = crate::runtime::env::Guard::new(&self.context, &self.unit);
+        let _guard = crate::runtime::env::Guard::new(self);
         f()
     }
 
@@ -2716,13 +3847,45 @@ impl Vm {
     pub(crate) fn run(&mut self) -> Result<VmHalt, VmError> {
         // NB: set up environment so that native function can access context and
         // unit.
-        let _guard = crate::runtime::env::Guard::new(&self.context, &self.unit);
+        let _guard = crate::runtime::env::Guard::new(self);
 
         loop {
+            if !self.breakpoints.is_empty()
+                && !mem::take(&mut self.at_breakpoint)
+                && self.breakpoints.binary_search(&self.ip).is_ok()
+            {
+                self.at_breakpoint = true;
+
+                if let Some(observer) = &self.observer {
+                    let depth = self.call_frames.len();
+                    observer.with(|o| o.breakpoint(self.ip, &self.stack, depth));
+                }
+
+                return Ok(VmHalt::Breakpoint);
+            }
+
             if !budget::take() {
                 return Ok(VmHalt::Limited);
             }
 
+            self.charge_budget(1)?;
+
+            if let Some((meter, base)) = &self.meter {
+                meter.record(base + self.stack.len());
+            }
+
+            if let Some(deadline) = self.deadline {
+                self.deadline_ticks += 1;
+
+                if self.deadline_ticks >= self.deadline_interval {
+                    self.deadline_ticks = 0;
+
+                    if Instant::now() >= deadline {
+                        return Err(VmError::from(VmErrorKind::DeadlineExceeded));
+                    }
+                }
+            }
+
             let inst = *self
                 .unit
                 .instruction_at(self.ip)
@@ -2730,6 +3893,17 @@ impl Vm {
 
             tracing::trace!("{}: {}", self.ip, inst);
 
+            if let Some(observer) = &self.observer {
+                let span = self
+                    .unit
+                    .debug_info()
+                    .and_then(|debug| debug.instruction_at(self.ip))
+                    .map(|debug| debug.span);
+
+                let depth = self.call_frames.len();
+                observer.with(|o| o.instruction(self.ip, &inst, span, depth));
+            }
+
             match inst {
                 Inst::Not => {
                     self.op_not()?;
@@ -2849,8 +4023,14 @@ impl Vm {
                 Inst::JumpIfBranch { branch, offset } => {
                     self.op_jump_if_branch(branch, offset)?;
                 }
-                Inst::Vec { count } => {
-                    self.op_vec(count)?;
+                Inst::Vec { count, capacity } => {
+                    self.op_vec(count, capacity)?;
+                }
+                Inst::VecRepeat => {
+                    self.op_vec_repeat()?;
+                }
+                Inst::VecExtend => {
+                    self.op_vec_extend()?;
                 }
                 Inst::Tuple { count } => {
                     self.op_tuple(count)?;
@@ -2891,6 +4071,9 @@ impl Vm {
                 Inst::String { slot } => {
                     self.op_string(slot)?;
                 }
+                Inst::LoadGlobal { slot } => {
+                    self.op_load_global(slot)?;
+                }
                 Inst::Bytes { slot } => {
                     self.op_bytes(slot)?;
                 }
@@ -2935,6 +4118,13 @@ impl Vm {
                 } => {
                     self.op_match_sequence(type_check, len, exact)?;
                 }
+                Inst::MatchRange {
+                    start,
+                    end,
+                    inclusive,
+                } => {
+                    self.op_match_range(start, end, inclusive)?;
+                }
                 Inst::MatchType { hash } => {
                     self.op_match_type(hash)?;
                 }
@@ -3013,6 +4203,104 @@ impl CallFrame {
     }
 }
 
+/// A frame in the [backtrace][Vm::backtrace] of a virtual machine.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct BacktraceFrame<'a> {
+    /// The item of the function being executed.
+    pub fn_item: &'a Item,
+    /// The source the instruction being executed belongs to.
+    pub source_id: SourceId,
+    /// The span of the instruction being executed, for outer frames this is
+    /// the call site of the next inner frame.
+    pub span: Span,
+    /// The instruction pointer of the frame.
+    pub instruction_pointer: usize,
+}
+
+impl BacktraceFrame<'_> {
+    /// Format the frame as `<item> (<source>:<line>:<column>)`, falling back
+    /// to the bare item if the location can't be found in the given sources.
+    pub fn format_with(&self, sources: &Sources) -> String {
+        let location = sources.get(self.source_id).and_then(|source| {
+            let (line, column, _) = source.line(self.span)?;
+            Some((
+                source.name(),
+                line.saturating_add(1),
+                column.saturating_add(1),
+            ))
+        });
+
+        match location {
+            Some((name, line, column)) => {
+                format!("{} ({}:{}:{})", self.fn_item, name, line, column)
+            }
+            None => self.fn_item.to_string(),
+        }
+    }
+}
+
+/// Resolve the backtrace of a virtual machine executing `unit` at the given
+/// instruction pointer with the given call frames, see [Vm::backtrace].
+pub(crate) fn backtrace<'a>(
+    unit: &'a Unit,
+    ip: usize,
+    call_frames: &[CallFrame],
+) -> vec::Vec<BacktraceFrame<'a>> {
+    let debug = match unit.debug_info() {
+        Some(debug) => debug,
+        None => return vec::Vec::new(),
+    };
+
+    let ips = std::iter::once(ip).chain(call_frames.iter().rev().map(|f| f.ip));
+    let mut frames = vec::Vec::with_capacity(call_frames.len() + 1);
+
+    for ip in ips {
+        let (inst, (_, signature)) = match (debug.instruction_at(ip), debug.function_containing(ip))
+        {
+            (Some(inst), Some(function)) => (inst, function),
+            _ => continue,
+        };
+
+        frames.push(BacktraceFrame {
+            fn_item: &signature.path,
+            source_id: inst.source_id,
+            span: inst.span,
+            instruction_pointer: ip,
+        });
+    }
+
+    frames
+}
+
+/// Test if `n` is inside of the given range, where `f` converts a bound into a
+/// value comparable with `n` and bounds of other types never match.
+fn range_contains<T>(
+    n: T,
+    start: Option<InstValue>,
+    end: Option<InstValue>,
+    inclusive: bool,
+    f: fn(InstValue) -> Option<T>,
+) -> bool
+where
+    T: PartialOrd,
+{
+    let start = match start.map(f) {
+        Some(Some(start)) => Bound::Included(start),
+        Some(None) => return false,
+        None => Bound::Unbounded,
+    };
+
+    let end = match end.map(f) {
+        Some(Some(end)) if inclusive => Bound::Included(end),
+        Some(Some(end)) => Bound::Excluded(end),
+        Some(None) => return false,
+        None => Bound::Unbounded,
+    };
+
+    (start, end).contains(&n)
+}
+
 /// Clear stack on drop.
 struct ClearStack<'a>(&'a mut Vm);
 
@@ -66,19 +66,43 @@ impl VmError {
     }
 
     /// Convert into an unwinded vm error.
-    pub(crate) fn into_unwinded(self, unit: &Arc<Unit>, ip: usize, frames: Vec<CallFrame>) -> Self {
+    ///
+    /// At most `max_frames` of the most recent call frames are recorded, the
+    /// number of older frames which were left out is recorded separately.
+    pub(crate) fn into_unwinded(
+        self,
+        unit: &Arc<Unit>,
+        ip: usize,
+        frames: &[CallFrame],
+        max_frames: usize,
+    ) -> Self {
         if let VmErrorKind::Unwound { .. } = &*self.kind {
             return self;
         }
 
+        let elided_frames = frames.len().saturating_sub(max_frames);
+
         Self::from(VmErrorKind::Unwound {
             kind: self.kind,
             unit: unit.clone(),
             ip,
-            frames,
+            frames: frames[elided_frames..].to_vec(),
+            elided_frames,
         })
     }
 
+    /// Get the number of call frames which were left out of the recorded
+    /// backtrace of an unwound error because it exceeded the configured
+    /// maximum.
+    ///
+    /// See [Vm::set_max_error_frames][crate::runtime::Vm::set_max_error_frames].
+    pub fn elided_frames(&self) -> usize {
+        match &*self.kind {
+            VmErrorKind::Unwound { elided_frames, .. } => *elided_frames,
+            _ => 0,
+        }
+    }
+
     /// Unpack an unwinded error, if it is present.
     pub fn as_unwound(&self) -> (&VmErrorKind, Option<(&Arc<Unit>, usize, &[CallFrame])>) {
         match &*self.kind {
@@ -87,6 +111,7 @@ impl VmError {
                 unit,
                 ip,
                 frames,
+                ..
             } => (&*kind, Some((unit, *ip, frames))),
             kind => (kind, None),
         }
@@ -100,6 +125,7 @@ impl VmError {
                 unit,
                 ip,
                 frames,
+                ..
             } => {
                 let error = Self { kind };
                 (error, Some((unit, ip, frames)))
@@ -159,6 +185,9 @@ pub enum VmErrorKind {
         ip: usize,
         /// All lower call frames before the unwind trigger point
         frames: Vec<CallFrame>,
+        /// The number of older call frames which were not recorded in
+        /// `frames`.
+        elided_frames: usize,
     },
     #[error("{error}")]
     AccessError {
@@ -192,6 +221,10 @@ pub enum VmErrorKind {
     MissingEntryHash { hash: Hash },
     #[error("missing function with hash `{hash}`")]
     MissingFunction { hash: Hash },
+    #[error("function with hash `{hash}` is not a generator")]
+    NotGenerator { hash: Hash },
+    #[error("capability `{capability}` required to call function with hash `{hash}` has not been granted")]
+    CapabilityDenied { capability: Box<str>, hash: Hash },
     #[error("missing instance function `{hash}` for `{instance}`")]
     MissingInstanceFunction { hash: Hash, instance: TypeInfo },
     #[error("instruction pointer is out-of-bounds")]
@@ -315,18 +348,44 @@ pub enum VmErrorKind {
     MissingVariantName,
     #[error("no variant matching `{name}`")]
     MissingVariant { name: Box<str> },
+    #[error("`{target}` has no variant with index `{index}`")]
+    MissingVariantIndex { target: TypeInfo, index: usize },
     #[error("expected an enum variant, but got `{actual}`")]
     ExpectedVariant { actual: TypeInfo },
     #[error("{actual} can't be converted to a constant value")]
     ConstNotSupported { actual: TypeInfo },
+    #[error("callable {actual} can't be converted to a sync function")]
+    CallableNotSync { actual: TypeInfo },
     #[error("{actual} can't be converted to a hash key")]
     KeyNotSupported { actual: TypeInfo },
     #[error("missing interface environment")]
     MissingInterfaceEnvironment,
+    #[error("call stack limit of {limit} frames exceeded")]
+    CallStackLimit { limit: usize },
+    #[error("deadline exceeded")]
+    DeadlineExceeded,
+    #[error("instruction budget exceeded")]
+    BudgetExceeded,
     #[error("index out of bounds")]
     IndexOutOfBounds,
+    #[error("not enough memory to allocate {capacity} values")]
+    CapacityOverflow { capacity: usize },
     #[error("unsupported range")]
     UnsupportedRange,
+    #[error("missing global `{name}`, it has not been set on the virtual machine")]
+    MissingGlobal { name: String },
+    #[error("global `{name}` is not declared by the unit, globals have to be declared when the unit is built through `Build::with_globals`")]
+    UndeclaredGlobal { name: String },
+    #[error("virtual machines with globals can't be sent to another thread")]
+    SendGlobals,
+    #[error("slice range `{start}..{end}` is invalid for length {len}")]
+    SliceOutOfRange {
+        start: usize,
+        end: usize,
+        len: usize,
+    },
+    #[error("slice range `{start}..{end}` is not on a character boundary")]
+    NotCharBoundary { start: usize, end: usize },
     #[error("expected execution to be {expected}, but was {actual}")]
     ExpectedExecutionState {
         expected: ExecutionState,
@@ -343,6 +402,7 @@ impl VmErrorKind {
                 unit,
                 ip,
                 frames,
+                ..
             } => (&*kind, Some((unit.clone(), *ip, frames.clone()))),
             kind => (kind, None),
         }
@@ -1,6 +1,6 @@
 use crate::runtime::budget;
 use crate::runtime::{
-    Generator, GeneratorState, Stream, Value, Vm, VmError, VmErrorKind, VmHalt, VmHaltInfo,
+    Awaited, Generator, GeneratorState, Stream, Value, Vm, VmError, VmErrorKind, VmHalt, VmHaltInfo,
 };
 use crate::shared::AssertSend;
 use std::fmt;
@@ -226,7 +226,7 @@ where
             match Self::run(vm)? {
                 VmHalt::Exited => (),
                 VmHalt::Awaited(awaited) => {
-                    awaited.into_vm(vm).await?;
+                    self.await_into_vm(awaited).await?;
                     continue;
                 }
                 VmHalt::VmCall(vm_call) => {
@@ -356,7 +356,7 @@ where
         match budget::with(1, || Self::run(vm)).call()? {
             VmHalt::Exited => (),
             VmHalt::Awaited(awaited) => {
-                awaited.into_vm(vm).await?;
+                self.await_into_vm(awaited).await?;
                 return Ok(None);
             }
             VmHalt::VmCall(vm_call) => {
@@ -380,6 +380,81 @@ where
         Ok(None)
     }
 
+    /// Resume the current execution until it completes or pauses at a
+    /// breakpoint set through [Vm::set_breakpoint], without support for async
+    /// instructions.
+    ///
+    /// Returns `None` if execution paused at a breakpoint, at which point the
+    /// paused virtual machine can be inspected through [VmExecution::vm].
+    /// Calling this again continues execution from the breakpoint.
+    ///
+    /// If any async instructions are encountered, this will error. This will
+    /// also error if the execution is suspended through yielding.
+    pub fn resume_until_breakpoint(&mut self) -> Result<Option<Value>, VmError> {
+        loop {
+            let len = self.vms.len();
+            let vm = vm_mut!(self);
+
+            match Self::run(vm)? {
+                VmHalt::Exited => (),
+                VmHalt::VmCall(vm_call) => {
+                    vm_call.into_execution(self)?;
+                    continue;
+                }
+                VmHalt::Breakpoint => return Ok(None),
+                halt => {
+                    return Err(VmError::from(VmErrorKind::Halted {
+                        halt: halt.into_info(),
+                    }))
+                }
+            }
+
+            if len == 0 {
+                let value = self.end()?;
+                return Ok(Some(value));
+            }
+
+            self.pop_vm()?;
+        }
+    }
+
+    /// Resume the current execution until it completes or pauses at a
+    /// breakpoint set through [Vm::set_breakpoint], with support for async
+    /// instructions.
+    ///
+    /// See [VmExecution::resume_until_breakpoint].
+    pub async fn async_resume_until_breakpoint(&mut self) -> Result<Option<Value>, VmError> {
+        loop {
+            let len = self.vms.len();
+            let vm = vm_mut!(self);
+
+            match Self::run(vm)? {
+                VmHalt::Exited => (),
+                VmHalt::Awaited(awaited) => {
+                    self.await_into_vm(awaited).await?;
+                    continue;
+                }
+                VmHalt::VmCall(vm_call) => {
+                    vm_call.into_execution(self)?;
+                    continue;
+                }
+                VmHalt::Breakpoint => return Ok(None),
+                halt => {
+                    return Err(VmError::from(VmErrorKind::Halted {
+                        halt: halt.into_info(),
+                    }))
+                }
+            }
+
+            if len == 0 {
+                let value = self.end()?;
+                return Ok(Some(value));
+            }
+
+            self.pop_vm()?;
+        }
+    }
+
     /// End execution and perform debug checks.
     pub(crate) fn end(&mut self) -> Result<Value, VmError> {
         let vm = self.head.as_mut();
@@ -410,11 +485,55 @@ where
         Ok(())
     }
 
+    /// Wait for the given awaited value and push its result onto the current
+    /// virtual machine.
+    ///
+    /// If the future waiting for it is cancelled by being dropped, the
+    /// execution is cleared so that the awaited future and any values held by
+    /// it are dropped along with it, rather than lingering in the virtual
+    /// machine.
+    async fn await_into_vm(&mut self, awaited: Awaited) -> Result<(), VmError> {
+        let mut guard = ClearOnCancel {
+            execution: self,
+            armed: true,
+        };
+
+        let result = awaited.into_vm(vm_mut!(guard.execution)).await;
+        guard.armed = false;
+        result
+    }
+
     #[inline]
     fn run(vm: &mut Vm) -> Result<VmHalt, VmError> {
         match vm.run() {
             Ok(reason) => Ok(reason),
-            Err(error) => Err(error.into_unwinded(vm.unit(), vm.ip(), vm.call_frames().to_vec())),
+            Err(error) => Err(error.into_unwinded(
+                vm.unit(),
+                vm.ip(),
+                vm.call_frames(),
+                vm.max_error_frames(),
+            )),
+        }
+    }
+}
+
+/// Clears an execution when dropped unless disarmed.
+struct ClearOnCancel<'a, T>
+where
+    T: AsMut<Vm>,
+{
+    execution: &'a mut VmExecution<T>,
+    armed: bool,
+}
+
+impl<T> Drop for ClearOnCancel<'_, T>
+where
+    T: AsMut<Vm>,
+{
+    fn drop(&mut self) {
+        if self.armed {
+            self.execution.vms.clear();
+            self.execution.head.as_mut().clear();
         }
     }
 }
@@ -423,7 +542,8 @@ impl VmExecution<&mut Vm> {
     /// Convert the current execution into one which owns its virtual machine.
     pub fn into_owned(self) -> VmExecution<Vm> {
         let stack = take(self.head.stack_mut());
-        let head = Vm::with_stack(self.head.context().clone(), self.head.unit().clone(), stack);
+        let mut head = Vm::with_stack(self.head.context().clone(), self.head.unit().clone(), stack);
+        head.inherit(self.head);
 
         VmExecution {
             head,
@@ -448,6 +568,14 @@ pub struct VmSendExecution(pub(crate) VmExecution<Vm>);
 unsafe impl Send for VmSendExecution {}
 
 impl VmSendExecution {
+    /// Complete the current execution without support for async instructions.
+    ///
+    /// If any async instructions are encountered, this will error. This will
+    /// also error if the execution is suspended through yielding.
+    pub fn complete(mut self) -> Result<Value, VmError> {
+        self.0.complete()
+    }
+
     /// Complete the current execution with support for async instructions.
     ///
     /// This requires that the result of the Vm is converted into a
@@ -13,7 +13,9 @@ pub(crate) enum VmHalt {
     /// The virtual machine awaited on the given future.
     Awaited(Awaited),
     /// Call into a new virtual machine.
-    VmCall(VmCall),
+    VmCall(Box<VmCall>),
+    /// The virtual machine paused at a breakpoint.
+    Breakpoint,
 }
 
 impl VmHalt {
@@ -25,6 +27,7 @@ impl VmHalt {
             Self::Yielded => VmHaltInfo::Yielded,
             Self::Awaited(..) => VmHaltInfo::Awaited,
             Self::VmCall(..) => VmHaltInfo::VmCall,
+            Self::Breakpoint => VmHaltInfo::Breakpoint,
         }
     }
 }
@@ -42,6 +45,8 @@ pub enum VmHaltInfo {
     Awaited,
     /// Received instruction to push the inner virtual machine.
     VmCall,
+    /// The virtual machine paused at a breakpoint.
+    Breakpoint,
 }
 
 impl fmt::Display for VmHaltInfo {
@@ -52,6 +57,7 @@ impl fmt::Display for VmHaltInfo {
             Self::Yielded => write!(f, "yielded"),
             Self::Awaited => write!(f, "awaited"),
             Self::VmCall => write!(f, "calling into other vm"),
+            Self::Breakpoint => write!(f, "paused at breakpoint"),
         }
     }
 }
@@ -0,0 +1,66 @@
+use crate::ast::Span;
+use crate::runtime::{Inst, Stack};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// An observer which is notified about the execution of a virtual machine,
+/// configured through [Vm::set_observer][crate::Vm::set_observer].
+///
+/// Every method has a default implementation which does nothing, so only the
+/// events of interest need to be implemented.
+///
+/// The `depth` passed to each method is the number of call frames of the
+/// virtual machine, which is `0` in the function execution started from.
+pub trait VmObserver {
+    /// Called before the instruction `inst` at `ip` is executed.
+    ///
+    /// `span` is the span of the source the instruction was compiled from, if
+    /// the unit has debug information.
+    fn instruction(&mut self, _ip: usize, _inst: &Inst, _span: Option<Span>, _depth: usize) {}
+
+    /// Called when a function in the unit is entered at `ip`, after its call
+    /// frame has been pushed.
+    fn enter_call(&mut self, _ip: usize, _depth: usize) {}
+
+    /// Called when a function in the unit returns to `ip`, after its call frame
+    /// has been popped.
+    fn exit_call(&mut self, _ip: usize, _depth: usize) {}
+
+    /// Called when execution pauses at a breakpoint set through
+    /// [Vm::set_breakpoint][crate::Vm::set_breakpoint], before the instruction
+    /// at `ip` is executed.
+    fn breakpoint(&mut self, _ip: usize, _stack: &Stack, _depth: usize) {}
+}
+
+/// An observer shared between a virtual machine and the virtual machines it
+/// calls into.
+#[derive(Clone)]
+pub(crate) struct SharedObserver(Arc<Mutex<dyn VmObserver + Send>>);
+
+impl SharedObserver {
+    /// Construct a new shared observer.
+    pub(crate) fn new<O>(observer: O) -> Self
+    where
+        O: 'static + VmObserver + Send,
+    {
+        Self(Arc::new(Mutex::new(observer)))
+    }
+
+    /// Call the given closure with access to the observer.
+    ///
+    /// Nothing is called if a previous call to the observer panicked.
+    pub(crate) fn with<F>(&self, f: F)
+    where
+        F: FnOnce(&mut dyn VmObserver),
+    {
+        if let Ok(mut observer) = self.0.lock() {
+            f(&mut *observer);
+        }
+    }
+}
+
+impl fmt::Debug for SharedObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SharedObserver")
+    }
+}
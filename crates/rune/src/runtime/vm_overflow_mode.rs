@@ -0,0 +1,58 @@
+/// How the virtual machine handles integer arithmetic which overflows.
+///
+/// This applies to addition, subtraction, multiplication and negation of
+/// integers at runtime. Constant evaluation always errors on overflow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VmOverflowMode {
+    /// Overflowing operations raise [VmErrorKind::Overflow] or
+    /// [VmErrorKind::Underflow]. This is the default.
+    ///
+    /// [VmErrorKind::Overflow]: crate::runtime::VmErrorKind::Overflow
+    /// [VmErrorKind::Underflow]: crate::runtime::VmErrorKind::Underflow
+    #[default]
+    Checked,
+    /// Overflowing operations wrap around at the boundary of the integer type.
+    Wrapping,
+    /// Overflowing operations saturate at the minimum or maximum value of the
+    /// integer type.
+    Saturating,
+}
+
+impl VmOverflowMode {
+    /// Integer addition in this mode.
+    pub(crate) fn add(self) -> fn(i64, i64) -> Option<i64> {
+        match self {
+            Self::Checked => i64::checked_add,
+            Self::Wrapping => |a, b| Some(a.wrapping_add(b)),
+            Self::Saturating => |a, b| Some(a.saturating_add(b)),
+        }
+    }
+
+    /// Integer subtraction in this mode.
+    pub(crate) fn sub(self) -> fn(i64, i64) -> Option<i64> {
+        match self {
+            Self::Checked => i64::checked_sub,
+            Self::Wrapping => |a, b| Some(a.wrapping_sub(b)),
+            Self::Saturating => |a, b| Some(a.saturating_sub(b)),
+        }
+    }
+
+    /// Integer multiplication in this mode.
+    pub(crate) fn mul(self) -> fn(i64, i64) -> Option<i64> {
+        match self {
+            Self::Checked => i64::checked_mul,
+            Self::Wrapping => |a, b| Some(a.wrapping_mul(b)),
+            Self::Saturating => |a, b| Some(a.saturating_mul(b)),
+        }
+    }
+
+    /// Integer negation in this mode.
+    pub(crate) fn neg(self, value: i64) -> Option<i64> {
+        match self {
+            Self::Checked => value.checked_neg(),
+            Self::Wrapping => Some(value.wrapping_neg()),
+            Self::Saturating => Some(value.saturating_neg()),
+        }
+    }
+}
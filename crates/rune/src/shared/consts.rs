@@ -2,10 +2,15 @@
 //!
 //! This maps the item of a global constant to its value. It's also used to
 //! detect resolution cycles during constant evaluation.
+//!
+//! Immutable values produced while evaluating constants are interned here, so
+//! that identical values share storage. Interned values must never be
+//! modified, so the constant evaluator copies values which can be modified,
+//! like strings, before handing them out to a constant expression.
 
 use crate::collections::{HashMap, HashSet};
 use crate::compile::Item;
-use crate::runtime::ConstValue;
+use crate::runtime::{Bytes, ConstValue, Shared};
 
 /// State for constants processing.
 #[derive(Default)]
@@ -14,6 +19,10 @@ pub(crate) struct Consts {
     resolved: HashMap<Item, ConstValue>,
     /// Constant expressions being processed.
     processing: HashSet<Item>,
+    /// Interned strings.
+    strings: HashMap<Box<str>, Shared<String>>,
+    /// Interned byte strings.
+    bytes: HashMap<Box<[u8]>, Shared<Bytes>>,
 }
 
 impl Consts {
@@ -34,4 +43,56 @@ impl Consts {
     pub(crate) fn insert(&mut self, item: Item, value: ConstValue) -> Option<ConstValue> {
         self.resolved.insert(item, value)
     }
+
+    /// Intern the given string, sharing storage with any identical string
+    /// which has been interned before.
+    ///
+    /// The returned value must not be modified, since it might be shared.
+    /// Since strings can be modified with `String::push` in constant
+    /// expressions, they are copied when a literal is evaluated.
+    pub(crate) fn intern_string(&mut self, string: &str) -> Shared<String> {
+        if let Some(shared) = self.strings.get(string) {
+            return shared.clone();
+        }
+
+        let shared = Shared::new(string.to_owned());
+        self.strings.insert(string.into(), shared.clone());
+        shared
+    }
+
+    /// Intern the given byte string, sharing storage with any identical byte
+    /// string which has been interned before.
+    ///
+    /// The returned value must not be modified, since it might be shared. No
+    /// method modifies a byte string in constant expressions, so byte string
+    /// literals are used as-is.
+    pub(crate) fn intern_bytes(&mut self, bytes: &[u8]) -> Shared<Bytes> {
+        if let Some(shared) = self.bytes.get(bytes) {
+            return shared.clone();
+        }
+
+        let shared = Shared::new(Bytes::from_vec(bytes.to_vec()));
+        self.bytes.insert(bytes.into(), shared.clone());
+        shared
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Consts;
+
+    #[test]
+    fn test_interned_values_share_storage() {
+        let mut consts = Consts::default();
+
+        let a = consts.intern_string("hello");
+        let b = consts.intern_string("hello");
+        let c = consts.intern_string("world");
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_ne!(a.as_ptr(), c.as_ptr());
+
+        let a = consts.intern_bytes(b"hello");
+        let b = consts.intern_bytes(b"hello");
+        assert_eq!(a.as_ptr(), b.as_ptr());
+    }
 }
@@ -118,6 +118,13 @@ impl<T> Scopes<T> {
         ScopeGuard { length }
     }
 
+    /// Pop every scope pushed after the scope of the given guard, making it the
+    /// current scope again. Used when control flow like `break` skips past the
+    /// end of nested scopes.
+    pub(crate) fn unwind(&mut self, guard: &ScopeGuard) {
+        self.scopes.truncate(guard.length + 1);
+    }
+
     pub(crate) fn pop<S>(&mut self, spanned: S, guard: ScopeGuard) -> Result<(), Custom>
     where
         S: Spanned,
@@ -3,7 +3,7 @@
 use crate::ast;
 use crate::ast::Span;
 use crate::collections::HashMap;
-use crate::compile::{CompileVisitor, Item, Options, SourceLoader, UnitBuilder};
+use crate::compile::{CompileVisitor, Item, Options, Progress, SourceLoader, UnitBuilder};
 use crate::indexing::index;
 use crate::indexing::{IndexScopes, Indexer};
 use crate::macros::Storage;
@@ -11,6 +11,7 @@ use crate::query::{Query, QueryInner};
 use crate::shared::{Consts, Gen, Items};
 use crate::{Context, Diagnostics, SourceId, Sources};
 use std::collections::VecDeque;
+use std::time::Instant;
 
 mod import;
 mod task;
@@ -20,11 +21,13 @@ pub(crate) use self::import::Import;
 pub(crate) use self::task::{LoadFileKind, Task};
 pub(crate) use self::wildcard_import::WildcardImport;
 
-pub(crate) struct Worker<'a> {
+pub(crate) struct Worker<'a, 'p> {
     context: &'a Context,
     options: &'a Options,
     pub(crate) diagnostics: &'a mut Diagnostics,
     pub(crate) source_loader: &'a mut dyn SourceLoader,
+    /// Build progress reporting, if enabled.
+    pub(crate) progress: Option<&'a mut Progress<'p>>,
     /// Query engine.
     pub(crate) q: Query<'a>,
     /// Id generator.
@@ -35,7 +38,7 @@ pub(crate) struct Worker<'a> {
     pub(crate) queue: VecDeque<Task>,
 }
 
-impl<'a> Worker<'a> {
+impl<'a, 'p> Worker<'a, 'p> {
     /// Construct a new worker.
     pub(crate) fn new(
         context: &'a Context,
@@ -47,6 +50,7 @@ impl<'a> Worker<'a> {
         diagnostics: &'a mut Diagnostics,
         visitor: &'a mut dyn CompileVisitor,
         source_loader: &'a mut dyn SourceLoader,
+        progress: Option<&'a mut Progress<'p>>,
         gen: &'a Gen,
         inner: &'a mut QueryInner,
     ) -> Self {
@@ -55,7 +59,10 @@ impl<'a> Worker<'a> {
             options,
             diagnostics,
             source_loader,
-            q: Query::new(unit, consts, storage, sources, visitor, gen, inner),
+            progress,
+            q: Query::new(
+                context, unit, consts, storage, sources, options, visitor, gen, inner,
+            ),
             gen,
             loaded: HashMap::new(),
             queue: VecDeque::new(),
@@ -86,6 +93,8 @@ impl<'a> Worker<'a> {
                         }
                     };
 
+                    let start = self.progress.is_some().then(Instant::now);
+
                     let mut file = match crate::parse::parse_all::<ast::File>(
                         source.as_str(),
                         source_id,
@@ -98,6 +107,10 @@ impl<'a> Worker<'a> {
                         }
                     };
 
+                    if let (Some(start), Some(progress)) = (start, self.progress.as_deref_mut()) {
+                        progress.source_parsed(source_id, start.elapsed());
+                    }
+
                     let root = match kind {
                         LoadFileKind::Root => source.path().map(ToOwned::to_owned),
                         LoadFileKind::Module { root } => root,
@@ -121,6 +134,8 @@ impl<'a> Worker<'a> {
                         impl_item: Default::default(),
                         source_loader: self.source_loader,
                         nested_item: None,
+                        loops: Vec::new(),
+                        allow: Default::default(),
                     };
 
                     if let Err(error) = index::file(&mut file, &mut indexer) {
@@ -0,0 +1,107 @@
+use rune::compile::Variant;
+use rune::runtime::VmError;
+use rune::termcolor::{ColorChoice, StandardStream};
+use rune::{Any, ContextError, Diagnostics, FromValue, Module, ToValue, Vm};
+use std::sync::Arc;
+
+#[derive(Debug, Any)]
+#[rune(install_with = "install_external")]
+enum External {
+    First(u32),
+    Second(u32, u32),
+    Third { a: u32, b: String },
+    Fourth,
+}
+
+fn install_external(module: &mut Module) -> Result<(), ContextError> {
+    module.enum_meta(
+        &[
+            ("First", Variant::tuple(1)),
+            ("Second", Variant::tuple(2)),
+            ("Third", Variant::st(&["a", "b"])),
+            ("Fourth", Variant::unit()),
+        ],
+        |value: &External| {
+            Ok(match value {
+                External::First(a) => (0, vec![a.to_value()?]),
+                External::Second(a, b) => (1, vec![a.to_value()?, b.to_value()?]),
+                External::Third { a, b } => (2, vec![a.to_value()?, b.clone().to_value()?]),
+                External::Fourth => (3, vec![]),
+            })
+        },
+    )?;
+
+    module.variant_constructor::<External, _, _>(0, |a: u32| {
+        if a == 0 {
+            return Err(VmError::panic("first can't be zero"));
+        }
+
+        Ok(External::First(a))
+    })?;
+
+    module.variant_constructor::<External, _, _>(1, External::Second)?;
+    module.variant_constructor::<External, _, _>(3, || External::Fourth)?;
+    Ok(())
+}
+
+fn main() -> rune::Result<()> {
+    let m = module()?;
+
+    let mut context = rune_modules::default_context()?;
+    context.install(&m)?;
+    let runtime = Arc::new(context.runtime());
+
+    let mut sources = rune::sources! {
+        entry => {
+            pub fn main(external) {
+                match external {
+                    External::First(a) => a,
+                    External::Second(a, b) => a + b,
+                    External::Third { a, .. } => a,
+                    External::Fourth => 0,
+                }
+            }
+
+            pub fn make() {
+                [External::First(1), External::Second(2, 3), External::Fourth]
+            }
+        }
+    };
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    if !diagnostics.is_empty() {
+        let mut writer = StandardStream::stderr(ColorChoice::Always);
+        diagnostics.emit(&mut writer, &sources)?;
+    }
+
+    let unit = result?;
+
+    let mut vm = Vm::new(runtime, Arc::new(unit));
+
+    let output = vm.call(
+        &["main"],
+        (External::Third {
+            a: 42,
+            b: String::from("hello"),
+        },),
+    )?;
+
+    let output = u32::from_value(output)?;
+    println!("output: {}", output);
+
+    let output = Vec::<External>::from_value(vm.call(&["make"], ())?)?;
+    println!("output: {:?}", output);
+    Ok(())
+}
+
+fn module() -> Result<Module, ContextError> {
+    let mut m = Module::new();
+    m.ty::<External>()?;
+    Ok(m)
+}
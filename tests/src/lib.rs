@@ -4,7 +4,9 @@
 pub use ::rune_modules as modules;
 use rune::compile::{IntoComponent, Item};
 use rune::runtime::{Args, VmError};
-use rune::{termcolor, BuildError, Context, Diagnostics, FromValue, Source, Sources, Unit, Vm};
+use rune::{
+    termcolor, BuildError, Context, Diagnostics, FromValue, Module, Source, Sources, Unit, Vm,
+};
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -155,6 +157,19 @@ pub fn build(context: &Context, source: &str) -> rune::Result<Arc<Unit>> {
     Ok(Arc::new(result?))
 }
 
+/// Construct a virtual machine for the given source, with the default modules
+/// and the given native modules installed.
+pub fn build_vm(modules: &[Module], source: &str) -> rune::Result<Vm> {
+    let mut context = Context::with_default_modules()?;
+
+    for module in modules {
+        context.install(module)?;
+    }
+
+    let unit = build(&context, source)?;
+    Ok(Vm::new(Arc::new(context.runtime()), unit))
+}
+
 /// Construct a rune virtual machine from the given program.
 ///
 /// # Examples
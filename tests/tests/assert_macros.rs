@@ -0,0 +1,97 @@
+use rune::runtime::{Protocol, VmErrorKind};
+use rune::{Any, Context, ContextError, Module};
+use rune_tests::*;
+use std::fmt;
+use std::fmt::Write as _;
+
+#[derive(Any, Debug, Clone, Copy)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+impl Point {
+    fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+
+    fn string_debug(&self, s: &mut String) -> fmt::Result {
+        write!(s, "{:?}", self)
+    }
+}
+
+fn context() -> Result<Context, ContextError> {
+    let mut module = Module::with_item(&["point"]);
+    module.ty::<Point>()?;
+    module.function(&["Point", "new"], Point::new)?;
+    module.inst_fn(Protocol::EQ, Point::eq)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Point::string_debug)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&rune_modules::test::module(true)?)?;
+    context.install(&module)?;
+    Ok(context)
+}
+
+fn panic_reason(source: &str) -> rune::Result<String> {
+    let error = run::<_, _, ()>(&context()?, source, &["main"], ())
+        .unwrap_err()
+        .expect_vm_error("expected vm error");
+
+    match error.into_unwound().0.into_kind() {
+        VmErrorKind::Panic { reason } => Ok(reason.to_string()),
+        actual => panic!("expected panic but was `{:?}`", actual),
+    }
+}
+
+#[test]
+fn test_assert_eq_host_values() -> rune::Result<()> {
+    run::<_, _, ()>(
+        &context()?,
+        r#"
+        use point::Point;
+
+        pub fn main() {
+            assert_eq!(Point::new(1, 2), Point::new(1, 2));
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    let reason = panic_reason(
+        r#"
+        use point::Point;
+
+        pub fn main() {
+            assert_eq!(Point::new(1, 2), Point::new(3, 4));
+        }
+        "#,
+    )?;
+
+    assert_eq!(
+        reason,
+        "assertion failed (left == right):\nleft: Point { x: 1, y: 2 }\nright: Point { x: 3, y: 4 }"
+    );
+
+    let reason = panic_reason(
+        r#"
+        use point::Point;
+
+        pub fn main() {
+            assert_eq!(Point::new(1, 2), Point::new(3, 4), "points differ by {}", 2);
+        }
+        "#,
+    )?;
+
+    assert_eq!(
+        reason,
+        "assertion failed (left == right): points differ by 2\nleft: Point { x: 1, y: 2 }\nright: Point { x: 3, y: 4 }"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use rune::compile::{BuildProgress, BuildTimings, Item, Phase};
+use rune::{Context, Diagnostics, Options, Source, SourceId, Sources};
+use std::time::Duration;
+
+const SOURCE: &str = r#"
+const VALUE = 1 + 2;
+
+fn helper(n) {
+    let f = |x| x + VALUE;
+    f(n)
+}
+
+pub fn main() {
+    let s = `value: ${helper(1)}`;
+    s
+}
+"#;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Started(Phase),
+    Finished(Phase),
+    Parsed(SourceId),
+    Assembled(String),
+}
+
+#[derive(Default)]
+struct Recording {
+    events: Vec<Event>,
+    timings: Option<BuildTimings>,
+}
+
+impl BuildProgress for Recording {
+    fn phase_started(&mut self, phase: Phase, _: Option<usize>) {
+        self.events.push(Event::Started(phase));
+    }
+
+    fn phase_finished(&mut self, phase: Phase, _: Duration) {
+        self.events.push(Event::Finished(phase));
+    }
+
+    fn source_parsed(&mut self, source_id: SourceId, _: Duration) {
+        self.events.push(Event::Parsed(source_id));
+    }
+
+    fn item_assembled(&mut self, item: &Item, _: Duration) {
+        self.events.push(Event::Assembled(item.to_string()));
+    }
+
+    fn build_finished(&mut self, timings: &BuildTimings) {
+        self.timings = Some(timings.clone());
+    }
+}
+
+fn build(progress: &mut dyn BuildProgress) -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut options = Options::default();
+    options.macros(true);
+
+    let mut sources = Sources::new();
+    let source_id = sources.insert(Source::new("main", SOURCE));
+    assert_eq!(source_id, SourceId::new(0));
+
+    let mut diagnostics = Diagnostics::new();
+
+    rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .with_diagnostics(&mut diagnostics)
+        .with_progress(progress)
+        .build()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_phases_in_order() -> rune::Result<()> {
+    let mut recording = Recording::default();
+    build(&mut recording)?;
+
+    let phases = recording
+        .events
+        .iter()
+        .filter(|e| matches!(e, Event::Started(..) | Event::Finished(..)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        phases,
+        [
+            &Event::Started(Phase::Index),
+            &Event::Finished(Phase::Index),
+            &Event::Started(Phase::Assemble),
+            &Event::Finished(Phase::Assemble),
+            &Event::Started(Phase::Link),
+            &Event::Finished(Phase::Link),
+            &Event::Finished(Phase::Parse),
+            &Event::Finished(Phase::MacroExpansion),
+            &Event::Finished(Phase::ConstEval),
+        ]
+    );
+
+    let position = |event: &Event| recording.events.iter().position(|e| e == event);
+    let parsed = position(&Event::Parsed(SourceId::new(0))).expect("source was parsed");
+    let assembled = position(&Event::Assembled(String::from("main"))).expect("main was assembled");
+
+    assert!(position(&Event::Started(Phase::Index)) < Some(parsed));
+    assert!(Some(parsed) < position(&Event::Finished(Phase::Index)));
+    assert!(position(&Event::Started(Phase::Assemble)) < Some(assembled));
+    assert!(Some(assembled) < position(&Event::Finished(Phase::Assemble)));
+
+    let timings = recording.timings.expect("build finished");
+    assert_eq!(timings.phases().len(), 6);
+    Ok(())
+}
+
+#[test]
+fn test_timings_contain_every_item() -> rune::Result<()> {
+    let mut timings = BuildTimings::default();
+    build(&mut timings)?;
+
+    let mut items = timings
+        .items()
+        .iter()
+        .map(|(item, _)| item.to_string())
+        .collect::<Vec<_>>();
+
+    items.sort();
+    assert_eq!(items, ["helper", "helper::$0::$0", "main"]);
+
+    assert_eq!(timings.sources().len(), 1);
+    assert_eq!(timings.slowest(2).len(), 2);
+    assert_eq!(timings.macro_expansions(), 1);
+    assert!(timings.const_eval_budget() > 0);
+    assert!(timings.total() >= timings.phase(Phase::Assemble).unwrap_or_default());
+
+    // NB: nested phases are part of the index and assemble phases.
+    let nested = timings
+        .phases()
+        .iter()
+        .filter(|(phase, _)| phase.is_nested())
+        .map(|(_, duration)| *duration)
+        .sum::<Duration>();
+
+    assert!(nested > Duration::default());
+    assert!(nested <= timings.total());
+    assert_eq!(
+        timings.phase(Phase::Parse),
+        Some(timings.sources().iter().map(|(_, d)| *d).sum())
+    );
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use rune::runtime::Bytes;
+use rune::Context;
+use rune_tests::*;
+
+#[test]
+fn test_bytes_from_host_vec() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let out: (usize, Option<u8>) = run(
+        &context,
+        r#"pub fn main(b) { (b.len(), b.last()) }"#,
+        &["main"],
+        (Bytes::from(vec![1u8, 2, 3]),),
+    )?;
+    assert_eq!(out, (3, Some(3)));
+
+    let out: Vec<u8> = run(
+        &context,
+        r#"use std::bytes::Bytes; pub fn main(v) { let b = Bytes::from_vec(v); b.extend_str("!"); b }"#,
+        &["main"],
+        (vec![104u8, 105],),
+    )?;
+    assert_eq!(out, b"hi!");
+    Ok(())
+}
+
+#[test]
+fn test_bytes_into_host_vec() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let out: Vec<u8> = run(&context, r#"pub fn main() { b"abc" }"#, &["main"], ())?;
+    assert_eq!(out, b"abc");
+
+    let out: Vec<u8> = run(
+        &context,
+        r#"use std::bytes::Bytes; pub fn main() { let b = Bytes::from_vec([b'x', b'y']); b.to_vec() }"#,
+        &["main"],
+        (),
+    )?;
+    assert_eq!(out, b"xy");
+
+    let out: Vec<u8> = Vec::from(Bytes::from_vec(vec![1, 2]));
+    assert_eq!(out, [1, 2]);
+    Ok(())
+}
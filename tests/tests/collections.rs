@@ -39,3 +39,73 @@ fn test_hash_set_tuple() {
         }
     };
 }
+
+#[test]
+fn test_vec_deque_fifo() {
+    let out: Vec<i64> = rune! {
+        pub fn main() {
+            use std::collections::VecDeque;
+
+            let edges = [[1, 2], [3], [3, 4], [], []];
+            let seen = [false, false, false, false, false];
+            let order = [];
+
+            let queue = VecDeque::new();
+            queue.push_back(0);
+            seen[0] = true;
+
+            while !queue.is_empty() {
+                let node = queue.pop_front().unwrap();
+                order.push(node);
+
+                for next in edges[node] {
+                    if !seen[next] {
+                        seen[next] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            assert_eq!(queue.len(), 0);
+            assert_eq!(queue.pop_front(), None);
+            order
+        }
+    };
+
+    assert_eq!(out, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn test_vec_deque_front_operations() {
+    let out: (i64, Vec<i64>) = rune! {
+        pub fn main() {
+            use std::collections::VecDeque;
+
+            let queue = VecDeque::new();
+
+            for n in 0..10000 {
+                queue.push_front(n);
+            }
+
+            assert_eq!(queue.len(), 10000);
+            assert_eq!(queue.pop_front(), Some(9999));
+            let sum = 9999;
+
+            while let Some(n) = queue.pop_front() {
+                sum += n;
+            }
+
+            let queue = VecDeque::from([1, 2, 3]);
+            queue.push_front(0);
+            queue.push_back(4);
+            queue.rotate_right(1);
+            assert_eq!(queue.pop_front(), Some(4));
+            queue.rotate_left(1);
+            assert_eq!(queue.pop_back(), Some(0));
+            queue[0] = 10;
+            (sum, queue.iter().collect::<Vec>())
+        }
+    };
+
+    assert_eq!(out, (49995000, vec![10, 2, 3]));
+}
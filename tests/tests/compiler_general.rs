@@ -23,6 +23,55 @@ fn break_outside_of_loop() {
     };
 }
 
+#[test]
+fn break_and_continue_outside_of_loop_in_const() {
+    assert_compile_error! {
+        r#"const A = { break 1 }; pub fn main() { A }"#,
+        span, BreakOutsideOfLoop => {
+            assert_eq!(span, span!(12, 17));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const fn f() { continue; 1 } pub fn main() { f() }"#,
+        span, ContinueOutsideOfLoop => {
+            assert_eq!(span, span!(15, 23));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { loop { const A = { break; }; } }"#,
+        span, BreakOutsideOfLoop => {
+            assert_eq!(span, span!(35, 40));
+        }
+    };
+}
+
+#[test]
+fn break_and_continue_outside_of_loop_in_closure() {
+    assert_compile_error! {
+        r#"pub fn main() { loop { let f = || { continue; }; } }"#,
+        span, ContinueOutsideOfLoop => {
+            assert_eq!(span, span!(36, 44));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { 'outer: loop { let f = async { break 'outer; }; } }"#,
+        span, BreakOutsideOfLoop => {
+            assert_eq!(span, span!(47, 52));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { 'outer: loop { loop { let f = || { loop { break 'outer; } }; } } }"#,
+        span, MissingLoopLabel { label } => {
+            assert_eq!(span, span!(64, 70));
+            assert_eq!(&*label, "outer");
+        }
+    };
+}
+
 #[test]
 fn test_pointers() {
     assert_compile_error! {
@@ -87,3 +136,38 @@ fn test_bad_struct_declaration() {
         }
     };
 }
+
+#[test]
+fn test_labeled_block_break_targets() {
+    assert_compile_error! {
+        r#"pub fn main() { 'a: { break; } }"#,
+        span, BreakOutsideOfLoop => {
+            assert_eq!(span, span!(22, 27));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { loop { 'a: { continue 'a; } } }"#,
+        span, MissingLoopLabel { label } => {
+            assert_eq!(span, span!(38, 40));
+            assert_eq!(&*label, "a");
+        }
+    };
+}
+
+#[test]
+fn test_self_outside_of_method() {
+    assert_compile_error! {
+        r#"pub fn main() { self }"#,
+        span, UnsupportedSelfValue => {
+            assert_eq!(span, span!(16, 20));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { let f = || self; f() }"#,
+        span, UnsupportedSelfValue => {
+            assert_eq!(span, span!(27, 31));
+        }
+    };
+}
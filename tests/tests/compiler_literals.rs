@@ -1,3 +1,4 @@
+use rune::ast;
 use rune::compile::CompileErrorKind::{ParseError, ResolveError};
 use rune::parse::ParseErrorKind::*;
 use rune::parse::ResolveErrorKind::*;
@@ -43,3 +44,44 @@ fn test_number_literals() {
         }
     };
 }
+
+#[test]
+fn test_suffixed_number_literals() {
+    assert_parse!(r#"pub fn main() { 255u8 }"#);
+    assert_parse!(r#"pub fn main() { -2147483648i32 }"#);
+
+    assert_compile_error! {
+        r#"pub fn main() { 256u8 }"#,
+        span, ParseError { error: BadNumberSuffixOutOfBounds { suffix: ast::NumberSuffix::U8 } } => {
+            assert_eq!(span, span!(16, 21));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { -129i8 }"#,
+        span, ParseError { error: BadNumberSuffixOutOfBounds { suffix: ast::NumberSuffix::I8 } } => {
+            assert_eq!(span, span!(16, 22));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { -1u32 }"#,
+        span, ParseError { error: BadNumberSuffixOutOfBounds { suffix: ast::NumberSuffix::U32 } } => {
+            assert_eq!(span, span!(16, 21));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { 1e40f32 }"#,
+        span, ParseError { error: BadNumberSuffixOutOfBounds { suffix: ast::NumberSuffix::F32 } } => {
+            assert_eq!(span, span!(16, 23));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { 1.5u8 }"#,
+        span, ResolveError { error: BadNumberLiteral { .. } } => {
+            assert_eq!(span, span!(16, 21));
+        }
+    };
+}
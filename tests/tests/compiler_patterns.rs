@@ -32,3 +32,30 @@ fn illegal_pattern_in_match() {
         }
     };
 }
+
+#[test]
+fn test_let_else_must_diverge() {
+    assert_compile_error! {
+        r#"pub fn main() { let Some(x) = None else { 1 }; }"#,
+        span, LetElseMustDiverge => {
+            assert_eq!(span, span!(40, 45));
+        }
+    };
+}
+
+#[test]
+fn test_let_else_shadowed_panic() {
+    assert_compile_error! {
+        r#"fn panic(m) { 7 } pub fn main() { let Some(x) = None else { panic("no") }; x }"#,
+        span, LetElseMustDiverge => {
+            assert_eq!(span, span!(58, 73));
+        }
+    };
+
+    assert_compile_error! {
+        r#"pub fn main() { let Some(x) = None else { if true { return 1; } }; x }"#,
+        span, LetElseMustDiverge => {
+            assert_eq!(span, span!(40, 65));
+        }
+    };
+}
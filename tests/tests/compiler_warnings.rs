@@ -31,3 +31,183 @@ fn test_remove_variant_parens() {
         }
     };
 }
+
+#[test]
+fn test_unreachable_after_return() {
+    assert_warnings! {
+        r#"pub fn main() { return 1; 2 }"#,
+        Unreachable { span, cause } => {
+            assert_eq!(span, span!(26, 27));
+            assert_eq!(cause, span!(16, 24));
+        }
+    };
+}
+
+#[test]
+fn test_unreachable_reported_once() {
+    assert_warnings! {
+        r#"pub fn main() { loop { break; dbg(1); dbg(2); } }"#,
+        Unreachable { span, cause } => {
+            assert_eq!(span, span!(30, 37));
+            assert_eq!(cause, span!(23, 28));
+        }
+    };
+}
+
+#[test]
+fn test_allow_unreachable() {
+    let mut diagnostics = Default::default();
+    let _ = compile_helper(
+        r#"#[allow(unreachable)] pub fn main() { return 1; 2 }"#,
+        &mut diagnostics,
+    )
+    .expect("source should compile");
+    assert!(!diagnostics.has_warning());
+}
+
+#[test]
+fn test_non_exhaustive_match() {
+    assert_warnings! {
+        r#"enum External { First, Second } pub fn main() { match External::First { External::First => 1 } }"#,
+        NonExhaustiveMatch { span, missing } => {
+            assert_eq!(span, span!(48, 94));
+            assert_eq!(&*missing, &["Second".into()]);
+        }
+    };
+}
+
+#[test]
+fn test_non_exhaustive_match_or_pattern() {
+    assert_warnings! {
+        r#"enum E { A, B, C } pub fn main() { match E::A { E::A | E::B => 1 } }"#,
+        NonExhaustiveMatch { missing, .. } => {
+            assert_eq!(&*missing, &["C".into()]);
+        }
+    };
+}
+
+#[test]
+fn test_non_exhaustive_match_guarded() {
+    assert_warnings! {
+        r#"enum E { A, B } pub fn main(n) { match E::A { E::A => 1, E::B if n => 2 } }"#,
+        NonExhaustiveMatch { missing, .. } => {
+            assert_eq!(&*missing, &["B".into()]);
+        }
+    };
+}
+
+#[test]
+fn test_non_exhaustive_match_refutable() {
+    assert_warnings! {
+        r#"enum E { A(a), B } pub fn main() { match E::B { E::A(1) => 1, E::B => 2 } }"#,
+        NonExhaustiveMatch { missing, .. } => {
+            assert_eq!(&*missing, &["A".into()]);
+        }
+    };
+
+    let mut diagnostics = Default::default();
+    let _ = compile_helper(
+        r#"enum E { A(a), B } pub fn main() { match E::B { E::A(_) => 1, E::A(1) => 2, E::B => 3 } }"#,
+        &mut diagnostics,
+    )
+    .expect("source should compile");
+    assert!(!diagnostics.has_warning());
+}
+
+#[test]
+fn test_allow_non_exhaustive_match() {
+    let mut diagnostics = Default::default();
+    let _ = compile_helper(
+        r#"enum External { First, Second } #[allow(non_exhaustive_match)] pub fn main() { match External::First { External::First => 1 } }"#,
+        &mut diagnostics,
+    )
+    .expect("source should compile");
+    assert!(!diagnostics.has_warning());
+}
+
+#[test]
+fn test_constant_condition() {
+    assert_warnings! {
+        r#"pub fn main() { if false { 1 } else { 2 } }"#,
+        ConstantCondition { span, value, body } => {
+            assert_eq!(span, span!(19, 24));
+            assert!(!value);
+            assert_eq!(body, span!(25, 30));
+        }
+    };
+
+    assert_warnings! {
+        r#"pub fn main(n) { if n { 1 } else if !(true && false) { 2 } else { 3 } }"#,
+        ConstantCondition { span, value, .. } => {
+            assert_eq!(span, span!(36, 52));
+            assert!(value);
+        }
+    };
+
+    assert_warnings! {
+        r#"pub fn main() { while true { break; } }"#,
+        ConstantCondition { span, value, body } => {
+            assert_eq!(span, span!(22, 26));
+            assert!(value);
+            assert_eq!(body, span!(27, 37));
+        }
+    };
+}
+
+#[test]
+fn test_allow_constant_condition() {
+    let mut diagnostics = Default::default();
+    let _ = compile_helper(
+        r#"#[allow(constant_condition)] pub fn main() { if false { 1 } else { 2 } }"#,
+        &mut diagnostics,
+    )
+    .expect("source should compile");
+    assert!(!diagnostics.has_warning());
+}
+
+#[test]
+fn test_allow_in_closures() {
+    let mut diagnostics = Default::default();
+    let _ = compile_helper(
+        r#"
+        enum External { First, Second }
+
+        #[allow(non_exhaustive_match, constant_condition)]
+        pub fn main() {
+            let f = || match External::First { External::First => if false { 1 } else { 2 } };
+            let b = async { match External::First { External::First => if false { 1 } else { 2 } } };
+            (f, b)
+        }
+        "#,
+        &mut diagnostics,
+    )
+    .expect("source should compile");
+    assert!(!diagnostics.has_warning());
+}
+
+#[test]
+fn test_unreachable_range_pattern() {
+    assert_warnings! {
+        r#"pub fn main(n) { match n { 0..=10 => 1, 2..5 => 2, _ => 3 } }"#,
+        UnreachablePattern { span, cause } => {
+            assert_eq!(span, span!(40, 44));
+            assert_eq!(cause, span!(27, 33));
+        }
+    };
+
+    assert_warnings! {
+        r#"pub fn main(n) { match n { 'a'..='z' => 1, _ => 2, ..='a' => 3 } }"#,
+        UnreachablePattern { span, cause } => {
+            assert_eq!(span, span!(51, 57));
+            assert_eq!(cause, span!(43, 44));
+        }
+    };
+
+    let mut diagnostics = Default::default();
+    let _ = compile_helper(
+        r#"pub fn main(n) { match n { 0..=10 if n > 5 => 1, 2..5 => 2, 0..10 => 3, b'0'..=b'9' => 4, _ => 5 } }"#,
+        &mut diagnostics,
+    )
+    .expect("source should compile");
+    assert!(!diagnostics.has_warning());
+}
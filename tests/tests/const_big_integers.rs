@@ -0,0 +1,154 @@
+use rune::compile::CompileErrorKind::QueryError;
+use rune::compile::IrErrorKind;
+use rune::diagnostics::{Diagnostic, FatalDiagnosticKind};
+use rune::query::QueryErrorKind::IrError;
+use rune::runtime::Unit;
+use rune::{Context, Diagnostics, Options, Source, Sources, Vm};
+use std::sync::Arc;
+
+fn compile(source: &str, big_integers: bool) -> (rune::Result<Unit>, Diagnostics) {
+    let context = Context::with_default_modules().expect("failed to build context");
+
+    let mut options = Options::default();
+    options.const_big_integers(big_integers);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let mut diagnostics = Diagnostics::new();
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    (unit.map_err(Into::into), diagnostics)
+}
+
+fn run(source: &str) -> rune::Result<i64> {
+    let context = Context::with_default_modules()?;
+    let (unit, _) = compile(source, true);
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit?));
+    let output = vm.call(&["main"], ())?;
+    Ok(rune::FromValue::from_value(output)?)
+}
+
+#[test]
+fn test_const_big_integers() -> rune::Result<()> {
+    const SOURCE: &str =
+        r#"const V = 9223372036854775807 * 2 / 4; pub fn main() { V }"#;
+
+    let (unit, _) = compile(SOURCE, false);
+    assert!(unit.is_err());
+
+    assert_eq!(run(SOURCE)?, i64::MAX / 2);
+
+    let out = run(
+        r#"
+        const fn f(n) { (n * n - 1) / (n + 1) }
+        const V = f(9223372036854775807);
+        pub fn main() { V }
+        "#,
+    )?;
+
+    assert_eq!(out, i64::MAX - 1);
+    Ok(())
+}
+
+#[test]
+fn test_const_big_integers_narrowing() {
+    let (unit, diagnostics) = compile(
+        r#"const V = 9223372036854775807 + 1; pub fn main() { V }"#,
+        true,
+    );
+
+    assert!(unit.is_err());
+
+    let error = match diagnostics.into_diagnostics().into_iter().next() {
+        Some(Diagnostic::Fatal(error)) => error,
+        other => panic!("expected fatal diagnostic, but was {:?}", other),
+    };
+
+    let error = match error.into_kind() {
+        FatalDiagnosticKind::CompileError(error) => error,
+        kind => panic!("expected compile error, but was {:?}", kind),
+    };
+
+    match error.into_kind() {
+        QueryError {
+            error: IrError {
+                error: IrErrorKind::NotInteger { value },
+            },
+        } => {
+            assert_eq!(value.to_string(), "9223372036854775808");
+        }
+        kind => panic!("expected integer narrowing error, but was {:?}", kind),
+    }
+}
+
+#[test]
+fn test_const_big_integers_assign() -> rune::Result<()> {
+    const SOURCE: &str = r#"
+    const fn f() {
+        let x = 9223372036854775807;
+        x += 1;
+        x *= 4;
+        x <<= 70;
+        x >>= 70;
+        x -= 4;
+        x /= 4;
+        x
+    }
+
+    const V = f();
+    pub fn main() { V }
+    "#;
+
+    let (unit, _) = compile(SOURCE, false);
+    assert!(unit.is_err());
+
+    assert_eq!(run(SOURCE)?, i64::MAX);
+    Ok(())
+}
+
+#[test]
+fn test_const_big_integers_shift_bound() {
+    for source in [
+        r#"const V = 1 << 100000; pub fn main() { V }"#,
+        r#"const fn f() { let x = 1; x <<= 100000; x } const V = f(); pub fn main() { V }"#,
+    ] {
+        let (unit, diagnostics) = compile(source, true);
+        assert!(unit.is_err());
+
+        let error = match diagnostics.into_diagnostics().into_iter().next() {
+            Some(Diagnostic::Fatal(error)) => error,
+            other => panic!("expected fatal diagnostic, but was {:?}", other),
+        };
+
+        let error = match error.into_kind() {
+            FatalDiagnosticKind::CompileError(error) => error,
+            kind => panic!("expected compile error, but was {:?}", kind),
+        };
+
+        let error = match error.into_kind() {
+            QueryError {
+                error: IrError { error },
+            } => error,
+            kind => panic!("expected ir error, but was {:?}", kind),
+        };
+
+        // NB: errors raised inside of const fn calls include the calls.
+        let error = match error {
+            IrErrorKind::ConstEval { error, .. } => *error,
+            error => error,
+        };
+
+        match error {
+            IrErrorKind::Custom { message } => {
+                assert_eq!(message, "cannot be converted to shift operand")
+            }
+            error => panic!("expected shift error, but was {:?}", error),
+        }
+    }
+}
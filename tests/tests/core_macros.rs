@@ -54,6 +54,92 @@ fn test_format() {
     assert_eq!(format!("Hello, {} {0} {}", "John", "Doe"), out);
 }
 
+#[test]
+fn test_struct_debug() {
+    let out: (String, String, String, String) = rune!(
+        struct Point { x, y }
+        struct Pair(a, b);
+        enum Shape { Line { from, to }, Empty }
+
+        #[no_debug]
+        struct Secret { key }
+
+        pub fn main() {
+            let point = Point { x: 1, y: "two" };
+            let line = Shape::Line { from: Pair(0, 0), to: point };
+
+            (
+                format!("{:?}", point),
+                format!("{:?}", line),
+                format!("{:?}", Shape::Empty),
+                format!("{:?}", Secret { key: 42 }),
+            )
+        }
+    );
+
+    assert_eq!(out.0, "Point { x: 1, y: \"two\" }");
+    assert_eq!(out.1, "Shape::Line { from: Pair(0, 0), to: Point { x: 1, y: \"two\" } }");
+    assert_eq!(out.2, "Shape::Empty");
+    assert_eq!(out.3, "Secret { .. }");
+}
+
+#[test]
+fn test_enum_no_debug() {
+    let out: (String, String, String, String) = rune!(
+        #[no_debug]
+        enum Secret { Key(key), Pair { a, b } }
+
+        enum Token { Public(value), #[no_debug] Private(value) }
+
+        pub fn main() {
+            (
+                format!("{:?}", Secret::Key(42)),
+                format!("{:?}", Secret::Pair { a: 1, b: 2 }),
+                format!("{:?}", Token::Public(1)),
+                format!("{:?}", Token::Private(2)),
+            )
+        }
+    );
+
+    assert_eq!(out.0, "Secret::Key(..)");
+    assert_eq!(out.1, "Secret::Pair { .. }");
+    assert_eq!(out.2, "Token::Public(1)");
+    assert_eq!(out.3, "Token::Private(..)");
+}
+
+#[test]
+fn test_struct_debug_cycle() {
+    let out: (String, String, String) = rune!(
+        struct Node { next }
+        struct Pair(a, b);
+        enum List { Cons(head, tail) }
+
+        pub fn main() {
+            let node = Node { next: None };
+            node.next = node;
+            let a = format!("{:?}", node);
+            node.next = None;
+
+            let pair = Pair(1, None);
+            pair.1 = Some(pair);
+            let b = format!("{:?}", pair);
+            pair.1 = None;
+
+            let tail = [];
+            let list = List::Cons(1, tail);
+            tail.push(list);
+            let c = format!("{:?}", list);
+            tail.clear();
+
+            (a, b, c)
+        }
+    );
+
+    assert_eq!(out.0, "Node { next: *recursive* }");
+    assert_eq!(out.1, "Pair(1, Some(*recursive*))");
+    assert_eq!(out.2, "List::Cons(1, [*recursive*])");
+}
+
 #[test]
 fn test_strings() {
     test_case!("{}", "test\tstring");
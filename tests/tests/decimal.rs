@@ -0,0 +1,270 @@
+use rune::runtime::VmError;
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_decimal_exact_arithmetic() {
+    let out: bool = rune! {
+        use decimal::Decimal;
+
+        pub fn main() {
+            let a = Decimal::parse("0.1")?;
+            let b = Decimal::parse("0.2")?;
+            a + b == Decimal::parse("0.3")?
+        }
+    };
+    assert!(out);
+
+    let out: String = rune! {
+        use decimal::Decimal;
+
+        pub fn main() {
+            let a = Decimal::parse("10.25")?;
+            ((a * 2 - 1) / 4).to_string()
+        }
+    };
+    assert_eq!(out, "4.8750");
+
+    let out: String = rune_s! { r#"
+        use decimal::Decimal;
+
+        pub fn main() {
+            let a = Decimal::parse("-1.5")?;
+            `${a + 1} ${a * 2} ${a.abs()} ${a.min(1)} ${a.max(Decimal::from_int(2))}`
+        }
+    "#};
+    assert_eq!(out, "-0.5 -3.0 1.5 -1.5 2");
+}
+
+#[test]
+fn test_decimal_comparisons() {
+    let out: (bool, bool, bool, bool, bool) = rune! {
+        use decimal::Decimal;
+
+        pub fn main() {
+            let a = Decimal::parse("1.50")?;
+            let b = Decimal::parse("1.5")?;
+            let c = Decimal::parse("2.25")?;
+            (a == b, a < c, c >= a, c > 2, a > 2)
+        }
+    };
+    assert_eq!(out, (true, true, true, true, false));
+}
+
+#[test]
+fn test_decimal_rounding() {
+    let out: (String, String, String, String, String, String) = rune! {
+        use decimal::Decimal;
+
+        pub fn main() {
+            let a = Decimal::parse("2.345")?;
+            let b = Decimal::parse("2.355")?;
+            let c = Decimal::parse("-2.341")?;
+
+            (
+                a.round_dp(2).to_string(),
+                b.round_dp(2).to_string(),
+                a.round_dp_with(2, "half_up").to_string(),
+                a.round_dp_with(2, "half_down").to_string(),
+                c.round_dp_with(2, "floor").to_string(),
+                c.round_dp_with(2, "ceil").to_string(),
+            )
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            String::from("2.34"),
+            String::from("2.36"),
+            String::from("2.35"),
+            String::from("2.34"),
+            String::from("-2.35"),
+            String::from("-2.34"),
+        )
+    );
+}
+
+#[test]
+fn test_decimal_map_keys() {
+    let out: (i64, i64, bool, String) = rune! {
+        use decimal::Decimal;
+        use std::collections::{HashMap, HashSet};
+
+        pub fn main() {
+            let totals = HashMap::new();
+            let prices = ["1.50", "1.5", "2.00", "1.500"];
+
+            for price in prices {
+                let key = Decimal::parse(price)?;
+
+                if let Some(count) = totals.get(key) {
+                    totals[key] = count + 1;
+                } else {
+                    totals[key] = 1;
+                }
+            }
+
+            let seen = HashSet::new();
+            seen.insert(Decimal::parse("3.0")?);
+
+            (
+                totals[Decimal::parse("1.5")?],
+                totals[Decimal::from_int(2)],
+                seen.contains(Decimal::from_int(3)),
+                totals.keys().next().unwrap().to_string(),
+            )
+        }
+    };
+    assert_eq!(out.0, 3);
+    assert_eq!(out.1, 1);
+    assert!(out.2);
+    assert!(out.3 == "1.50" || out.3 == "2.00");
+}
+
+#[test]
+fn test_decimal_errors() {
+    assert_vm_error!(
+        r#"
+        use decimal::Decimal;
+
+        pub fn main() {
+            Decimal::from_int(1) / 0
+        }
+        "#,
+        DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        use decimal::Decimal;
+
+        pub fn main() {
+            Decimal::from_int(1) + 0.5
+        }
+        "#,
+        Panic { reason } => {
+            assert!(reason.to_string().contains("convert explicitly"));
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        use decimal::Decimal;
+
+        pub fn main() {
+            0.5 * Decimal::from_int(1)
+        }
+        "#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "*");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        use decimal::Decimal;
+
+        pub fn main() {
+            2 + Decimal::from_int(1)
+        }
+        "#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(op, "+");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        use decimal::Decimal;
+
+        pub fn main() {
+            Decimal::parse("79228162514264337593543950335")? - Decimal::from_int(-1)
+        }
+        "#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        use decimal::Decimal;
+
+        pub fn main() {
+            Decimal::parse("-79228162514264337593543950335")? - 1
+        }
+        "#,
+        Underflow => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        use decimal::Decimal;
+
+        pub fn main() {
+            Decimal::parse("79228162514264337593543950335")? * -2
+        }
+        "#,
+        Underflow => {}
+    );
+}
+
+#[test]
+fn test_decimal_key_is_a_copy() {
+    let out: (i64, bool, bool) = rune! {
+        use decimal::Decimal;
+        use std::collections::HashMap;
+
+        pub fn main() {
+            let totals = HashMap::new();
+            let key = Decimal::parse("1.5")?;
+            totals[key] = 1;
+            key = key + 1;
+
+            (
+                totals[Decimal::parse("1.5")?],
+                totals.contains_key(key),
+                totals.keys().next().unwrap() == Decimal::parse("1.5")?,
+            )
+        }
+    };
+    assert_eq!(out, (1, false, true));
+}
+
+#[test]
+fn test_decimal_integer_promotion() {
+    let mut context = rune_modules::default_context().expect("failed to build context");
+    context
+        .install(&rune_modules::decimal::integer_module().expect("failed to build module"))
+        .expect("failed to install module");
+
+    let source = r#"
+    use decimal::Decimal;
+
+    pub fn main() {
+        let d = Decimal::parse("0.5")?;
+        ((2 + d).to_string(), (2 - d).to_string(), (3 * d).to_string(), 1 == Decimal::from_int(1), 1 < d + 1)
+    }
+    "#;
+
+    let out: (String, String, String, bool, bool) =
+        rune_tests::run(&context, source, &["main"], ()).expect("program to run successfully");
+    assert_eq!(
+        out,
+        ("2.5".into(), "1.5".into(), "1.5".into(), true, true)
+    );
+}
+
+#[test]
+fn test_decimal_native_roundtrip() -> Result<(), VmError> {
+    use rune::runtime::{FromValue, ToValue};
+    use rune_modules::decimal::Decimal;
+
+    let value = Decimal::from(rust_decimal::Decimal::new(1999, 2)).to_value()?;
+    let out = rust_decimal::Decimal::from(Decimal::from_value(value)?);
+    assert_eq!(out, rust_decimal::Decimal::new(1999, 2));
+
+    let json = serde_json::to_string(&Decimal::from(out)).expect("serialize");
+    let back: Decimal = serde_json::from_str(&json).expect("deserialize");
+    assert_eq!(rust_decimal::Decimal::from(back), out);
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use rune::compile::CompileErrorKind;
+use rune::diagnostics::{Diagnostic, FatalDiagnosticKind};
+use rune::{Context, Diagnostics, Source, Sources};
+
+/// Build the given source with the `std::io` module denied, returning the
+/// first compile error.
+fn denied_error(source: &str) -> rune::Result<CompileErrorKind> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .deny_module(["std", "io"])
+        .build();
+
+    assert!(result.is_err());
+
+    let error = match diagnostics.into_diagnostics().into_iter().next() {
+        Some(Diagnostic::Fatal(error)) => error,
+        other => panic!("expected fatal diagnostic but was {:?}", other),
+    };
+
+    match error.into_kind() {
+        FatalDiagnosticKind::CompileError(error) => Ok(error.into_kind()),
+        kind => panic!("expected compile error but was {:?}", kind),
+    }
+}
+
+#[test]
+fn test_denied_module_path() -> rune::Result<()> {
+    let error = denied_error(r#"pub fn main() { std::io::println("hello") }"#)?;
+
+    assert_eq!(
+        error.to_string(),
+        "cannot reference `::std::io::println` since the module `::std::io` is denied"
+    );
+
+    match error {
+        CompileErrorKind::DeniedModule { item, module } => {
+            assert_eq!(item.to_string(), "::std::io::println");
+            assert_eq!(module.to_string(), "::std::io");
+        }
+        kind => panic!("expected denied module but was {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_denied_module_import_and_prelude() -> rune::Result<()> {
+    let error = denied_error(r#"use std::io; pub fn main() { io::println("hello") }"#)?;
+    assert!(matches!(error, CompileErrorKind::DeniedModule { .. }));
+
+    let error = denied_error(r#"pub fn main() { println("hello") }"#)?;
+    assert!(matches!(error, CompileErrorKind::DeniedModule { .. }));
+    Ok(())
+}
+
+#[test]
+fn test_other_modules_allowed() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "main",
+        r#"pub fn main() { std::string::String::from_str("io") }"#,
+    ));
+
+    rune::prepare(&mut sources)
+        .with_context(&context)
+        .deny_module(["std", "io"])
+        .build()?;
+
+    Ok(())
+}
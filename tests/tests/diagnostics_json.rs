@@ -0,0 +1,114 @@
+use rune::diagnostics::JSON_DIAGNOSTICS_VERSION;
+use rune::Diagnostics;
+use rune_tests::*;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Document {
+    version: u32,
+    truncated: u32,
+    diagnostics: Vec<JsonDiagnostic>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonDiagnostic {
+    kind: String,
+    message: String,
+    source_id: u32,
+    span_start: u32,
+    span_end: u32,
+    filename: Option<String>,
+    labels: Vec<Label>,
+    notes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    primary: bool,
+    source_id: u32,
+    span: Span,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Span {
+    start: u32,
+    end: u32,
+}
+
+#[test]
+fn test_emit_json() -> rune::Result<()> {
+    let source = r#"
+    fn a() { missing1 }
+    fn b() { missing2 }
+    pub fn main() { a(); b(); }
+    "#;
+
+    let mut sources = sources(source);
+    let mut diagnostics = Diagnostics::new().with_max_errors(1);
+
+    let result = rune::prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_err());
+
+    let mut out = Vec::new();
+    diagnostics.emit_json(&mut out, &sources)?;
+    let document: Document = serde_json::from_slice(&out)?;
+
+    assert_eq!(document.version, JSON_DIAGNOSTICS_VERSION);
+    assert_eq!(document.truncated, 1);
+    assert_eq!(document.diagnostics.len(), 1);
+
+    let error = &document.diagnostics[0];
+    assert_eq!(error.kind, "error");
+    assert_eq!(error.message, "compile error");
+    assert_eq!(error.source_id, 0);
+    assert_eq!(error.filename.as_deref(), Some("main"));
+    assert!(error.notes.is_empty());
+
+    let (start, end) = (error.span_start, error.span_end);
+    assert_eq!(&source[start as usize..end as usize], "missing1");
+
+    assert_eq!(error.labels.len(), 1);
+    let label = &error.labels[0];
+    assert!(label.primary);
+    assert_eq!(label.source_id, 0);
+    assert_eq!((label.span.start, label.span.end), (start, end));
+    assert_eq!(label.message, "no local variable `missing1`");
+    Ok(())
+}
+
+#[test]
+fn test_emit_json_warning() -> rune::Result<()> {
+    let source = r#"
+    pub fn main(n) {
+        match n { 0..=10 => 1, 2..5 => 2, _ => 3 }
+    }
+    "#;
+
+    let mut sources = sources(source);
+    let mut diagnostics = Diagnostics::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_ok());
+
+    let mut out = Vec::new();
+    diagnostics.emit_json(&mut out, &sources)?;
+    let document: Document = serde_json::from_slice(&out)?;
+
+    assert_eq!(document.truncated, 0);
+    assert_eq!(document.diagnostics.len(), 1);
+
+    let warning = &document.diagnostics[0];
+    assert_eq!(warning.kind, "warning");
+    assert_eq!(warning.message, "unreachable pattern");
+
+    let (start, end) = (warning.span_start, warning.span_end);
+    assert_eq!(&source[start as usize..end as usize], "2..5");
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use rune::diagnostics::Diagnostic;
+use rune::Diagnostics;
+use rune_tests::*;
+
+#[test]
+fn test_max_errors() -> rune::Result<()> {
+    let mut sources = sources(
+        r#"
+        fn a() { missing1 }
+        fn b() { missing2 }
+        fn c() { missing3 }
+        fn d() { missing4 }
+        fn e() { missing5 }
+        pub fn main() { a(); b(); c(); d(); e(); }
+        "#,
+    );
+
+    let mut diagnostics = Diagnostics::new().with_max_errors(2);
+
+    let result = rune::prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_err());
+    assert!(diagnostics.has_error());
+
+    let errors = diagnostics
+        .diagnostics()
+        .iter()
+        .filter(|d| matches!(d, Diagnostic::Fatal(..)))
+        .count();
+
+    assert_eq!(errors, 2);
+    assert_eq!(diagnostics.truncated_errors(), 3);
+
+    let mut buffer = rune::termcolor::Buffer::no_color();
+    diagnostics.emit(&mut buffer, &sources)?;
+    let output = String::from_utf8(buffer.into_inner())?;
+
+    assert!(output.contains("3 more errors were not reported"), "{}", output);
+    Ok(())
+}
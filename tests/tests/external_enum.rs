@@ -0,0 +1,195 @@
+//! Tests for matching over and constructing native enums registered with
+//! `Module::enum_meta`.
+
+use rune::compile::Variant;
+use rune::runtime::{VmError, VmErrorKind};
+use rune::{Any, Context, ContextError, Module, ToValue};
+use rune_tests::*;
+
+#[derive(Debug, PartialEq, Any)]
+#[rune(install_with = "install_external")]
+enum External {
+    First(u32),
+    Second(u32, u32),
+    Third { a: u32, b: String },
+    Fourth,
+}
+
+const VARIANTS: &[(&str, Variant)] = &[
+    ("First", Variant::tuple(1)),
+    ("Second", Variant::tuple(2)),
+    ("Third", Variant::st(&["a", "b"])),
+    ("Fourth", Variant::unit()),
+];
+
+fn install_external(module: &mut Module) -> Result<(), ContextError> {
+    module.enum_meta(VARIANTS, |value: &External| {
+        Ok(match value {
+            External::First(a) => (0, vec![a.to_value()?]),
+            External::Second(a, b) => (1, vec![a.to_value()?, b.to_value()?]),
+            External::Third { a, b } => (2, vec![a.to_value()?, b.clone().to_value()?]),
+            External::Fourth => (3, vec![]),
+        })
+    })?;
+
+    module.variant_constructor::<External, _, _>(0, |a: u32| {
+        if a == 0 {
+            return Err(VmError::panic("zero"));
+        }
+
+        Ok(External::First(a))
+    })?;
+
+    module.variant_constructor::<External, _, _>(1, External::Second)?;
+    module.variant_constructor::<External, _, _>(3, || External::Fourth)?;
+    Ok(())
+}
+
+fn context() -> rune::Result<Context> {
+    let mut module = Module::new();
+    module.ty::<External>()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    Ok(context)
+}
+
+#[test]
+fn test_external_enum_match() -> rune::Result<()> {
+    let context = context()?;
+
+    let source = r#"
+    pub fn main(external) {
+        match external {
+            External::First(a) => `first ${a}`,
+            External::Second(a, b) => `second ${a + b}`,
+            External::Third { a, b } => `third ${a} ${b}`,
+            External::Fourth => "fourth",
+        }
+    }
+    "#;
+
+    let values = vec![
+        External::First(1),
+        External::Second(2, 3),
+        External::Third {
+            a: 4,
+            b: String::from("five"),
+        },
+        External::Fourth,
+    ];
+
+    let mut outputs = Vec::new();
+
+    for value in values {
+        outputs.push(run::<_, _, String>(&context, source, &["main"], (value,))?);
+    }
+
+    assert_eq!(outputs, vec!["first 1", "second 5", "third 4 five", "fourth"]);
+
+    let value: u32 = run(
+        &context,
+        r#"
+        pub fn main(external) {
+            match external {
+                External::Third { b: "five", a } => a,
+                External::Third { .. } => 1,
+                _ => 0,
+            }
+        }
+        "#,
+        &["main"],
+        (External::Third {
+            a: 4,
+            b: String::from("five"),
+        },),
+    )?;
+
+    assert_eq!(value, 4);
+    Ok(())
+}
+
+#[test]
+fn test_external_enum_construct() -> rune::Result<()> {
+    let context = context()?;
+
+    let values: Vec<External> = run(
+        &context,
+        r#"
+        pub fn main() {
+            [External::First(1), External::Second(2, 3), External::Fourth]
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(
+        values,
+        vec![
+            External::First(1),
+            External::Second(2, 3),
+            External::Fourth
+        ]
+    );
+
+    let error = run::<_, _, External>(
+        &context,
+        r#"pub fn main() { External::First(0) }"#,
+        &["main"],
+        (),
+    )
+    .unwrap_err();
+
+    match error {
+        RunError::VmError(error) => {
+            let (kind, _) = error.into_unwound();
+            assert!(matches!(kind.into_kind(), VmErrorKind::Panic { .. }));
+        }
+        error => panic!("expected vm error but got {:?}", error),
+    }
+
+    // NB: struct variants have no constructor.
+    assert!(build(&context, r#"pub fn main() { External::Third { a: 1, b: "b" } }"#).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_external_enum_errors() -> rune::Result<()> {
+    #[derive(Any)]
+    struct Unregistered;
+
+    let mut module = Module::new();
+    module.ty::<External>()?;
+
+    assert!(matches!(
+        module.variant_constructor::<External, _, _>(2, External::First),
+        Err(ContextError::UnsupportedVariantConstructor { name: "Third", .. })
+    ));
+
+    assert!(matches!(
+        module.variant_constructor::<External, _, _>(1, External::First),
+        Err(ContextError::BadVariantConstructorArgs {
+            expected: 2,
+            actual: 1,
+            ..
+        })
+    ));
+
+    assert!(matches!(
+        module.variant_constructor::<External, _, _>(4, || External::Fourth),
+        Err(ContextError::MissingEnumVariant { index: 4, .. })
+    ));
+
+    assert!(matches!(
+        module.variant_constructor::<External, _, _>(3, || External::Fourth),
+        Err(ContextError::ConflictingVariantConstructor { name: "Fourth", .. })
+    ));
+
+    assert!(matches!(
+        module.enum_meta(VARIANTS, |_: &Unregistered| Ok((0, vec![]))),
+        Err(ContextError::MissingType { .. })
+    ));
+
+    Ok(())
+}
@@ -92,3 +92,118 @@ fn test_external_ops() -> rune::Result<()> {
     test_case!([%=], REM_ASSIGN, rem_assign, 25, 10, 5);
     Ok(())
 }
+
+#[test]
+fn test_install_ops() -> rune::Result<()> {
+    #[derive(Debug, Clone, Copy, PartialEq, Any)]
+    struct Number(i64);
+
+    impl Number {
+        fn new(value: i64) -> Self {
+            Self(value)
+        }
+    }
+
+    impl std::ops::Add for Number {
+        type Output = Number;
+
+        fn add(self, rhs: Number) -> Number {
+            Number(self.0 + rhs.0)
+        }
+    }
+
+    impl std::ops::Sub for Number {
+        type Output = Number;
+
+        fn sub(self, rhs: Number) -> Number {
+            Number(self.0 - rhs.0)
+        }
+    }
+
+    impl std::ops::Mul for Number {
+        type Output = Number;
+
+        fn mul(self, rhs: Number) -> Number {
+            Number(self.0 * rhs.0)
+        }
+    }
+
+    impl std::ops::Div for Number {
+        type Output = Number;
+
+        fn div(self, rhs: Number) -> Number {
+            Number(self.0 / rhs.0)
+        }
+    }
+
+    let mut module = Module::new();
+    module.ty::<Number>()?;
+    module.function(&["Number", "new"], Number::new)?;
+    module.install_ops::<Number>()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let out: (Number, Number, Number, Number) = run(
+        &context,
+        r#"
+        pub fn main() {
+            let a = Number::new(6);
+            let b = Number::new(3);
+            let c = Number::new(6);
+            c *= b;
+            c -= Number::new(4);
+            (a + b, a - b, a * b / Number::new(2), c)
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(out, (Number(9), Number(3), Number(9), Number(14)));
+    Ok(())
+}
+
+#[test]
+fn test_install_add_assign_self() -> rune::Result<()> {
+    #[derive(Debug, Clone, Copy, PartialEq, Any)]
+    struct Meters(i64);
+
+    impl Meters {
+        fn new(value: i64) -> Self {
+            Self(value)
+        }
+    }
+
+    impl std::ops::Add for Meters {
+        type Output = Meters;
+
+        fn add(self, rhs: Meters) -> Meters {
+            Meters(self.0 + rhs.0)
+        }
+    }
+
+    let mut module = Module::new();
+    module.ty::<Meters>()?;
+    module.function(&["Meters", "new"], Meters::new)?;
+    module.install_add::<Meters>()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let out: (Meters, Meters) = run(
+        &context,
+        r#"
+        pub fn main() {
+            let a = Meters::new(3);
+            a += a;
+            (a, a + a)
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(out, (Meters(6), Meters(12)));
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use rune::runtime::Protocol;
+use rune::{Any, Context, FromValue, Module, Vm};
+use rune_tests::*;
+use std::fmt;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A value which counts the number of times it has been displayed.
+#[derive(Any, Clone, Default)]
+struct Tracked {
+    displayed: Arc<AtomicUsize>,
+}
+
+impl Tracked {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        self.displayed.fetch_add(1, Ordering::SeqCst);
+        write!(buf, "tracked")
+    }
+}
+
+#[test]
+fn test_format_args_lazy() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.ty::<Tracked>()?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Tracked::display)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub fn capture(value) {
+            format_args!("{}, {}!", value, 42)
+        }
+
+        pub fn render(args) {
+            `<${args}>`
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+
+    let tracked = Tracked::default();
+    let args = vm.call(["capture"], (tracked.clone(),))?;
+    assert_eq!(tracked.displayed.load(Ordering::SeqCst), 0);
+
+    let out = String::from_value(vm.call(["render"], (args,))?)?;
+    assert_eq!(out, "<tracked, 42!>");
+    assert_eq!(tracked.displayed.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[test]
+fn test_format_args() {
+    let out: String = rune! {
+        pub fn main() {
+            let empty = format_args!("");
+            let args = format_args!("{} + {b} = {:03}", 1, 3, b = 2);
+            format!("{}{}", empty, args)
+        }
+    };
+
+    assert_eq!(out, "1 + 2 = 003");
+}
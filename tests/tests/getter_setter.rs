@@ -1,5 +1,6 @@
+use rune::runtime::{Protocol, VmErrorKind};
 use rune::{Any, Module, Value, Vm};
-use rune_tests::*;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Any, Debug, Default)]
@@ -44,3 +45,68 @@ fn test_getter_setter() -> rune::Result<()> {
     assert!(matches!(output, Value::Unit));
     Ok(())
 }
+
+#[derive(Any, Debug, Default)]
+struct Config {
+    #[rune(get, set, copy)]
+    version: i64,
+    values: HashMap<String, i64>,
+}
+
+impl Config {
+    fn get(&self, field: &str) -> Option<i64> {
+        self.values.get(field).copied()
+    }
+
+    fn set(&mut self, field: &str, value: i64) {
+        self.values.insert(field.to_owned(), value);
+    }
+}
+
+#[test]
+fn test_dynamic_fields() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.ty::<Config>()?;
+    module.inst_fn(Protocol::DYNAMIC_FIELD_GET, Config::get)?;
+    module.inst_fn(Protocol::DYNAMIC_FIELD_SET, Config::set)?;
+
+    let mut context = rune_modules::default_context()?;
+    context.install(&module)?;
+
+    let mut sources = rune::sources! {
+        entry => {
+            pub fn main(cfg) {
+                cfg.version = 2;
+                cfg.width = cfg.height * 2;
+                cfg.height = cfg.height + cfg.version;
+            }
+
+            pub fn missing(cfg) {
+                cfg.depth
+            }
+        }
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+
+    let mut cfg = Config::default();
+    cfg.values.insert(String::from("height"), 10);
+
+    vm.call(&["main"], (&mut cfg,))?;
+
+    assert_eq!(cfg.version, 2);
+    assert!(!cfg.values.contains_key("version"));
+    assert_eq!(cfg.values.get("width"), Some(&20));
+    assert_eq!(cfg.values.get("height"), Some(&12));
+
+    let error = vm.call(&["missing"], (&mut cfg,)).unwrap_err();
+
+    match error.into_unwound().0.into_kind() {
+        VmErrorKind::MissingField { field, .. } => assert_eq!(field, "depth"),
+        kind => panic!("expected missing field error but got {:?}", kind),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_vec_range_index() {
+    let out: (Vec<i64>, Vec<i64>, Vec<i64>, Vec<i64>) = rune! {
+        pub fn main() {
+            let v = [1, 2, 3, 4];
+            (v[1..3], v[1..=2], v[..2], v[2..])
+        }
+    };
+    assert_eq!(out, (vec![2, 3], vec![2, 3], vec![1, 2], vec![3, 4]));
+}
+
+#[test]
+fn test_string_range_index() {
+    let out: (String, String, String) = rune! {
+        pub fn main() {
+            let s = "hello";
+            (s[1..3], s[1..=3], s[..])
+        }
+    };
+    assert_eq!(
+        out,
+        (
+            String::from("el"),
+            String::from("ell"),
+            String::from("hello")
+        )
+    );
+}
+
+#[test]
+fn test_bytes_range_index() {
+    let out: (Vec<u8>, Vec<u8>) = rune! {
+        pub fn main() {
+            let b = b"abcd";
+            (b[..2], b[1..=3])
+        }
+    };
+    assert_eq!(out, (b"ab".to_vec(), b"bcd".to_vec()));
+}
+
+#[test]
+fn test_range_index_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2, 3][2..5] }"#,
+        SliceOutOfRange { start, end, len } => {
+            assert_eq!((start, end, len), (2, 5, 3));
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2, 3][2..1] }"#,
+        SliceOutOfRange { start, end, len } => {
+            assert_eq!((start, end, len), (2, 1, 3));
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { b"abc"[1..=3] }"#,
+        SliceOutOfRange { start, end, len } => {
+            assert_eq!((start, end, len), (1, 4, 3));
+        }
+    );
+}
+
+#[test]
+fn test_string_range_index_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { "hello"[3..9] }"#,
+        SliceOutOfRange { start, end, len } => {
+            assert_eq!((start, end, len), (3, 9, 5));
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "höh"[0..2] }"#,
+        NotCharBoundary { start, end } => {
+            assert_eq!((start, end), (0, 2));
+        }
+    );
+}
@@ -1,5 +1,7 @@
-use rune::runtime::VmErrorKind::*;
+use rune::runtime::{Iterator, Protocol};
+use rune::{Any, Context, Module};
 use rune_tests::*;
+use std::cell::Cell;
 
 #[test]
 fn test_range_iter() {
@@ -58,13 +60,59 @@ fn test_next_back() {
 }
 
 #[test]
-fn test_object_rev_error() {
-    assert_vm_error!(
-        r#"pub fn main() { #{}.iter().rev() }"#,
-        Panic { reason } => {
-            assert_eq!(reason.to_string(), "`std::object::Iter` is not a double-ended iterator");
+fn test_object_rev_buffered() {
+    let values: Vec<(String, i64)> = rune! {
+        pub fn main() {
+            #{a: 1}.iter().rev().collect::<Vec>()
         }
-    );
+    };
+
+    assert_eq!(values, vec![(String::from("a"), 1)]);
+}
+
+thread_local! {
+    static PULLED: Cell<usize> = Cell::new(0);
+}
+
+/// Construct an iterator which counts the number of values pulled out of it.
+fn counting(n: i64, double_ended: bool) -> Iterator {
+    let iter = (0..n).map(|n| {
+        PULLED.with(|pulled| pulled.set(pulled.get() + 1));
+        n
+    });
+
+    if double_ended {
+        Iterator::from_double_ended("counting", iter)
+    } else {
+        Iterator::from("counting", iter)
+    }
+}
+
+#[test]
+fn test_rev_next_back() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.function(&["counting"], counting)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let source = r#"
+    pub fn main(double_ended) {
+        let it = counting(100, double_ended).rev();
+        [it.next(), it.next()]
+    }
+    "#;
+
+    PULLED.with(|pulled| pulled.set(0));
+    let values: Vec<Option<i64>> = run(&context, source, &["main"], (true,))?;
+    assert_eq!(values, vec![Some(99), Some(98)]);
+    assert_eq!(PULLED.with(|pulled| pulled.get()), 2);
+
+    PULLED.with(|pulled| pulled.set(0));
+    let values: Vec<Option<i64>> = run(&context, source, &["main"], (false,))?;
+    assert_eq!(values, vec![Some(99), Some(98)]);
+    assert_eq!(PULLED.with(|pulled| pulled.get()), 100);
+    Ok(())
 }
 
 #[test]
@@ -134,6 +182,70 @@ fn test_peekable_take() {
     assert_eq!(actual, expected);
 }
 
+/// A native type which implements the iterator protocols.
+#[derive(Any)]
+struct Span {
+    front: i64,
+    back: i64,
+}
+
+impl Span {
+    fn new(front: i64, back: i64) -> Self {
+        Self { front, back }
+    }
+
+    fn next(&mut self) -> Option<i64> {
+        if self.front == self.back {
+            return None;
+        }
+
+        PULLED.with(|pulled| pulled.set(pulled.get() + 1));
+        self.front += 1;
+        Some(self.front - 1)
+    }
+
+    fn next_back(&mut self) -> Option<i64> {
+        if self.front == self.back {
+            return None;
+        }
+
+        PULLED.with(|pulled| pulled.set(pulled.get() + 1));
+        self.back -= 1;
+        Some(self.back)
+    }
+}
+
+#[test]
+fn test_rev_native_next_back() -> rune::Result<()> {
+    let source = r#"
+    pub fn main() {
+        let it = Span::new(0, 100).rev();
+        [it.next(), it.next()]
+    }
+    "#;
+
+    for (double_ended, pulled) in [(true, 2), (false, 100)] {
+        let mut module = Module::new();
+        module.ty::<Span>()?;
+        module.function(&["Span", "new"], Span::new)?;
+        module.inst_fn(Protocol::NEXT, Span::next)?;
+
+        if double_ended {
+            module.inst_fn(Protocol::NEXT_BACK, Span::next_back)?;
+        }
+
+        let mut context = Context::with_default_modules()?;
+        context.install(&module)?;
+
+        PULLED.with(|pulled| pulled.set(0));
+        let values: Vec<Option<i64>> = run(&context, source, &["main"], ())?;
+        assert_eq!(values, vec![Some(99), Some(98)]);
+        assert_eq!(PULLED.with(|pulled| pulled.get()), pulled);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_flat_map() {
     let actual: Vec<i64> = rune! {
@@ -0,0 +1,157 @@
+use rune::compile::LinkerError;
+use rune::diagnostics::{Diagnostic, FatalDiagnosticKind};
+use rune::{Any, Context, Diagnostics, Module};
+use rune_tests::*;
+
+#[derive(Any)]
+struct Square {
+    side: i64,
+}
+
+impl Square {
+    fn draw(&self) -> String {
+        format!("square({})", self.side)
+    }
+
+    fn area(&self) -> i64 {
+        self.side * self.side
+    }
+}
+
+fn shapes() -> rune::Result<Module> {
+    let mut module = Module::with_crate("shapes");
+    module.interface(&["Shape"], &[("draw", 0)])?;
+    module.ty::<Square>()?;
+    module.function(&["Square", "new"], |side: i64| Square { side })?;
+    module.inst_fn("draw", Square::draw)?;
+    module.inst_fn("area", Square::area)?;
+    module.implements::<Square, _>(&["shapes", "Shape"])?;
+    Ok(module)
+}
+
+#[test]
+fn test_interface_dispatch() -> rune::Result<()> {
+    let mut context = Context::with_default_modules()?;
+    context.install(&shapes()?)?;
+
+    let value: Vec<String> = run(
+        &context,
+        r#"
+        struct Circle { radius }
+
+        impl Circle {
+            fn draw(self) {
+                `circle(${self.radius})`
+            }
+        }
+
+        fn render(shape) {
+            shape.draw()
+        }
+
+        pub fn main() {
+            [render(shapes::Square::new(2)), render(Circle { radius: 3 })]
+        }
+        "#,
+        &["main"],
+        (),
+    )?;
+
+    assert_eq!(value, vec!["square(2)", "circle(3)"]);
+    Ok(())
+}
+
+/// Link an empty script against the given context, returning the linker error
+/// it fails with, if any.
+fn link(context: &Context) -> Option<LinkerError> {
+    let mut sources = sources("pub fn main() {}");
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = rune::prepare(&mut sources)
+        .with_context(context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    diagnostics
+        .into_diagnostics()
+        .into_iter()
+        .find_map(|diagnostic| match diagnostic {
+            Diagnostic::Fatal(fatal) => match fatal.into_kind() {
+                FatalDiagnosticKind::LinkError(error) => Some(error),
+                _ => None,
+            },
+            _ => None,
+        })
+}
+
+#[test]
+fn test_interface_missing_method() -> rune::Result<()> {
+    let mut context = Context::with_default_modules()?;
+    context.install(&shapes()?)?;
+
+    let mut module = Module::with_crate("more_shapes");
+    module.interface(&["Measured"], &[("area", 0), ("perimeter", 0)])?;
+    module.implements::<Square, _>(&["more_shapes", "Measured"])?;
+    context.install(&module)?;
+
+    match link(&context) {
+        Some(LinkerError::MissingInterfaceMethod {
+            interface,
+            name,
+            args,
+            ..
+        }) => {
+            assert_eq!(interface.to_string(), "::more_shapes::Measured");
+            assert_eq!(&*name, "perimeter");
+            assert_eq!(args, 0);
+        }
+        other => panic!("expected missing interface method but got {:?}", other),
+    }
+
+    // NB: the arity of the method is part of the interface.
+    let mut context = Context::with_default_modules()?;
+    context.install(&shapes()?)?;
+
+    let mut module = Module::with_crate("scaled_shapes");
+    module.interface(&["Scaled"], &[("draw", 1)])?;
+    module.implements::<Square, _>(&["scaled_shapes", "Scaled"])?;
+    context.install(&module)?;
+
+    assert!(matches!(
+        link(&context),
+        Some(LinkerError::MissingInterfaceMethod { args: 1, .. })
+    ));
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&shapes()?)?;
+
+    let mut module = Module::new();
+    module.implements::<Square, _>(&["shapes", "Missing"])?;
+    context.install(&module)?;
+
+    assert!(matches!(
+        link(&context),
+        Some(LinkerError::MissingInterface { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_interface_install_order() -> rune::Result<()> {
+    let mut implementation = Module::with_crate("squares");
+    implementation.implements::<Square, _>(&["measures", "Measured"])?;
+
+    let mut interface = Module::with_crate("measures");
+    interface.interface(&["Measured"], &[("area", 0)])?;
+
+    // NB: the implementation is installed before both the interface and the
+    // instance functions of the type it refers to.
+    let mut context = Context::with_default_modules()?;
+    context.install(&implementation)?;
+    context.install(&interface)?;
+    context.install(&shapes()?)?;
+
+    assert!(link(&context).is_none());
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use rune::compile::ContextError;
+use rune::{Context, Module};
+use rune_tests::*;
+
+#[test]
+fn test_reexport() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.reexport(&["fast_max"], &["std", "int", "max"])?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let value: i64 = run(&context, "pub fn main() { fast_max(4, 9) }", &["main"], ())?;
+    assert_eq!(value, 9);
+    Ok(())
+}
+
+#[test]
+fn test_reexport_missing_target() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.reexport(&["fast_max"], &["std", "int", "missing"])?;
+
+    let mut context = Context::with_default_modules()?;
+
+    assert!(matches!(
+        context.install(&module),
+        Err(ContextError::MissingReexportTarget { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_reexport_before_target() -> rune::Result<()> {
+    let mut target = Module::with_crate("extra");
+    target.function(&["answer"], || 42i64)?;
+
+    let mut module = Module::new();
+    module.reexport(&["answer"], &["extra", "answer"])?;
+
+    let mut context = Context::with_default_modules()?;
+
+    match context.install(&module) {
+        Err(ContextError::MissingReexportTarget { item, target }) => {
+            assert_eq!(item.to_string(), "answer");
+            assert_eq!(target.to_string(), "::extra::answer");
+        }
+        other => panic!("expected missing reexport target, got {:?}", other),
+    }
+
+    context.install(&target)?;
+    context.install(&module)?;
+
+    let value: i64 = run(&context, "pub fn main() { answer() }", &["main"], ())?;
+    assert_eq!(value, 42);
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use rune::compile::CompileErrorKind::*;
+use rune::runtime::{AccessError, Vec as RuneVec, VmErrorKind};
 use rune::span;
+use rune::{Context, Module, Vm};
 use rune_tests::*;
+use std::sync::Arc;
 
 #[test]
 fn test_closure_moved() {
@@ -45,3 +48,53 @@ fn test_async_moved() {
         }
     )
 }
+
+#[test]
+fn test_use_after_take() -> rune::Result<()> {
+    fn consume(_: RuneVec) {}
+
+    let mut module = Module::new();
+    module.function(&["consume"], consume)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let mut sources = rune::sources! {
+        entry => {
+            pub fn main() {
+                let v = [1, 2];
+                consume(v);
+                v.len()
+            }
+        }
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+
+    let error = vm.call(&["main"], ()).unwrap_err();
+
+    let mut buffer = rune::termcolor::Buffer::no_color();
+    error.emit(&mut buffer, &sources)?;
+    let output = String::from_utf8(buffer.into_inner())?;
+    assert!(output.contains("use of moved value"));
+    assert!(output.contains("value used here after being moved"));
+
+    let (error, unwound) = error.into_unwound();
+    let (unit, ip, _) = unwound.expect("expected unwound error");
+    let debug = unit.debug_info().expect("expected debug info");
+    let inst = debug.instruction_at(ip).expect("expected debug instruction");
+    assert_eq!(inst.span, span!(44, 51));
+
+    match error.into_kind() {
+        VmErrorKind::BadArgument { error, arg: 0 } => match error.into_kind() {
+            VmErrorKind::AccessError {
+                error: AccessError::NotAccessibleRef { error },
+            } => assert!(error.is_moved()),
+            kind => panic!("unexpected access error: {:?}", kind),
+        },
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    Ok(())
+}
@@ -1,4 +1,10 @@
+use rune::compile::CompileErrorKind::{
+    QueryError, UnsupportedOrPatternBinding, UnsupportedPatternExpr,
+};
+use rune::compile::IrErrorKind::{ConstEval, Custom, NoMatchingArm};
+use rune::query::QueryErrorKind::IrError;
 use rune::runtime::VmErrorKind::*;
+use rune::span;
 use rune_tests::*;
 
 #[test]
@@ -258,3 +264,368 @@ fn test_bad_pattern() {
         }
     );
 }
+
+#[test]
+fn test_fixed_length_vec_patterns() {
+    let out: (i64, i64, i64, i64) = rune!(
+        fn len(v) {
+            match v {
+                [] => 0,
+                [_] => 1,
+                [a, b, c] => a + b + c,
+                [_, _, ..] => -1,
+            }
+        }
+
+        pub fn main() {
+            (len([]), len([1]), len([1, 2, 3]), len([1, 2, 3, 4]))
+        }
+    );
+    assert_eq!(out, (0, 1, 6, -1));
+}
+
+#[test]
+fn test_const_fixed_length_vec_patterns() {
+    let out: (i64, i64, i64, i64) = rune!(
+        const fn len(v) {
+            match v {
+                [] => 0,
+                [_] => 1,
+                [a, b, c] => a + b + c,
+                [_, _, ..] => 0 - 1,
+            }
+        }
+
+        const A = len([]);
+        const B = len([1]);
+        const C = len([1, 2, 3]);
+        const D = len([1, 2, 3, 4]);
+
+        pub fn main() {
+            (A, B, C, D)
+        }
+    );
+    assert_eq!(out, (0, 1, 6, -1));
+
+    let out: (bool, bool) = rune!(
+        const EXACT = if let [a, b] = [1, 2, 3] { true } else { false };
+        const OPEN = if let [a, b, ..] = [1, 2, 3] { a + 1 == b } else { false };
+
+        pub fn main() {
+            (EXACT, OPEN)
+        }
+    );
+    assert_eq!(out, (false, true));
+}
+
+#[test]
+fn test_const_vec_pattern_rest_must_be_last() {
+    assert_compile_error! {
+        r#"const A = if let [.., a] = [1, 2] { a } else { 0 }; pub fn main() { A }"#,
+        span, QueryError { error: IrError { error: Custom { message } } } => {
+            assert_eq!(message, "`..` is not supported in this location");
+            assert_eq!(span, span!(18, 20));
+        }
+    };
+}
+
+#[test]
+fn test_range_patterns() {
+    let out: Vec<String> = rune!(
+        fn classify(n) {
+            match n {
+                -10..=-1 => "negative",
+                0 => "zero",
+                1..=9 => "digit",
+                'a'..='z' => "lower",
+                b'0'..=b'9' => "byte digit",
+                _ => "other",
+            }
+        }
+
+        pub fn main() {
+            [-11, -10, -1, 0, 9, 10, 'a', 'z', 'A', b'5', b'a', 5.0].iter().map(classify).collect::<Vec>()
+        }
+    );
+
+    assert_eq!(
+        out,
+        [
+            "other",
+            "negative",
+            "negative",
+            "zero",
+            "digit",
+            "other",
+            "lower",
+            "lower",
+            "other",
+            "byte digit",
+            "other",
+            "other",
+        ]
+    );
+
+    let out: bool = rune!(
+        pub fn main() {
+            let n = 4;
+            if let 5..=1 = n { true } else { false }
+        }
+    );
+    assert!(!out);
+}
+
+#[test]
+fn test_open_range_patterns() {
+    let out: i64 = rune!(pub fn main() { match 5 { 1..=10 => 1, _ => 2 } });
+    assert_eq!(out, 1);
+
+    let out: i64 = rune!(pub fn main() { match 'z' { 'a'..='m' => 1, _ => 2 } });
+    assert_eq!(out, 2);
+
+    let out: Vec<String> = rune!(
+        fn classify(n) {
+            match n {
+                ..0 => "negative",
+                0..10 => "digit",
+                10.. => "large",
+                'a'..'m' => "early",
+                ..=b'9' => "byte",
+                _ => "other",
+            }
+        }
+
+        pub fn main() {
+            [-1, 0, 9, 10, 'a', 'l', 'm', b'9', b'a'].iter().map(classify).collect::<Vec>()
+        }
+    );
+
+    assert_eq!(
+        out,
+        ["negative", "digit", "digit", "large", "early", "early", "other", "byte", "other"]
+    );
+}
+
+#[test]
+fn test_const_range_patterns() {
+    let out: (String, String, String, String, String) = rune!(
+        const fn classify(n) {
+            match n {
+                -9..=-1 => "negative",
+                0..10 => "digit",
+                ..-9 => "small",
+                'a'..='z' => "lower",
+                _ => "other",
+            }
+        }
+
+        const A = classify(0 - 3);
+        const B = classify(9);
+        const C = classify('q');
+        const D = classify(10);
+        const E = classify(0 - 10);
+
+        pub fn main() {
+            (A, B, C, D, E)
+        }
+    );
+
+    assert_eq!(
+        out,
+        (
+            "negative".to_owned(),
+            "digit".to_owned(),
+            "lower".to_owned(),
+            "other".to_owned(),
+            "small".to_owned()
+        )
+    );
+}
+
+#[test]
+fn test_range_pattern_mismatched_bounds() {
+    assert_compile_error! {
+        r#"pub fn main() { match 1 { 0..='a' => true, _ => false } }"#,
+        span, UnsupportedPatternExpr => {
+            assert_eq!(span, span!(26, 33));
+        }
+    };
+}
+
+#[test]
+fn test_or_patterns() {
+    let out: (String, String, String, String) = rune!(
+        fn classify(n) {
+            match n {
+                1 | 2 | 3 => "small",
+                'a' | 'b' => "letter",
+                Option::Some(_) | Option::None => "option",
+                _ => "other",
+            }
+        }
+
+        pub fn main() {
+            (classify(2), classify('b'), classify(None), classify(Some(4)))
+        }
+    );
+
+    assert_eq!(
+        out,
+        (
+            "small".to_owned(),
+            "letter".to_owned(),
+            "option".to_owned(),
+            "option".to_owned()
+        )
+    );
+
+    let out: std::vec::Vec<i64> = rune!(
+        fn classify(value) {
+            match value {
+                (1 | 2, [_, 3 | 4]) => 1,
+                (_, [Some(1) | None, _]) => 2,
+                _ => 3,
+            }
+        }
+
+        pub fn main() {
+            let values = [(2, [0, 4]), (1, [0, 5]), (3, [None, 0]), (3, [Some(1), 0])];
+            values.iter().map(classify).collect::<Vec>()
+        }
+    );
+
+    assert_eq!(out, [1, 3, 2, 2]);
+
+    let out: bool = rune!(
+        pub fn main() {
+            let f = |n| if let 1 | 2 = n { true } else { false };
+            f(2) && !f(3)
+        }
+    );
+
+    assert!(out);
+}
+
+#[test]
+fn test_const_or_patterns() {
+    let out: (String, String, String, String) = rune!(
+        enum Shape {
+            Circle(r),
+            Square(s),
+            Point,
+        }
+
+        const fn classify(n) {
+            match n {
+                0..=2 | 5.. => "outer",
+                Shape::Circle(_) | Shape::Point => "round",
+                _ => "other",
+            }
+        }
+
+        const A = classify(6);
+        const B = classify(Shape::Point);
+        const C = classify(Shape::Square(1));
+        const D = classify(3);
+
+        pub fn main() {
+            (A, B, C, D)
+        }
+    );
+
+    assert_eq!(
+        out,
+        (
+            "outer".to_owned(),
+            "round".to_owned(),
+            "other".to_owned(),
+            "other".to_owned()
+        )
+    );
+}
+
+#[test]
+fn test_const_or_patterns_variants_and_literals() {
+    let out: (String, String, String, String, String) = rune!(
+        const fn classify(value) {
+            match value {
+                Some(_) | None => "option",
+                (1, 2) | (3, 4) => "pair",
+                "a" | "b" | true => "literal",
+                (_, ..) => "tuple",
+                _ => "other",
+            }
+        }
+
+        const A = classify(None);
+        const B = classify((3, 4));
+        const C = classify("b");
+        const D = classify((5, 6, 7));
+        const E = classify(false);
+
+        pub fn main() {
+            (A, B, C, D, E)
+        }
+    );
+
+    assert_eq!(
+        out,
+        (
+            "option".to_owned(),
+            "pair".to_owned(),
+            "literal".to_owned(),
+            "tuple".to_owned(),
+            "other".to_owned()
+        )
+    );
+
+    let out: (i64, i64) = rune!(
+        const fn value(option) {
+            match option {
+                None => 0,
+                n => n,
+            }
+        }
+
+        const A = value(None);
+        const B = value(2);
+
+        pub fn main() {
+            (A, B)
+        }
+    );
+
+    assert_eq!(out, (0, 2));
+}
+
+#[test]
+fn test_const_or_pattern_binding() {
+    assert_compile_error! {
+        r#"const fn f(n) { match n { 1 | m => m, _ => 0 } } const A = f(2); pub fn main() { A }"#,
+        span, QueryError { error: IrError { error: ConstEval { error, .. } } } => {
+            assert!(matches!(*error, Custom { message } if message == "bindings are not supported in or-patterns"));
+            assert_eq!(span, span!(26, 31));
+        }
+    };
+}
+
+#[test]
+fn test_or_pattern_binding() {
+    assert_compile_error! {
+        r#"pub fn main() { match 1 { 1 | n => n, _ => 0 } }"#,
+        span, UnsupportedOrPatternBinding { name } => {
+            assert_eq!(name, "n");
+            assert_eq!(span, span!(30, 31));
+        }
+    };
+}
+
+#[test]
+fn test_const_match_no_matching_arm() {
+    assert_compile_error! {
+        r#"const A = match [1, 2] { [] => 0, [_] => 1 }; pub fn main() { A }"#,
+        span, QueryError { error: IrError { error: NoMatchingArm } } => {
+            assert_eq!(span, span!(10, 44));
+        }
+    };
+}
@@ -0,0 +1,106 @@
+use rune::compile::CompileErrorKind;
+use rune::diagnostics::{Diagnostic, FatalDiagnosticKind};
+use rune::{Context, Diagnostics, FromValue, Source, Sources, Vm};
+use std::sync::Arc;
+
+fn run_with_prelude<T>(prelude: &[&str], source: &str) -> rune::Result<T>
+where
+    T: FromValue,
+{
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_prelude(prelude)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    Ok(T::from_value(vm.call(["main"], ())?)?)
+}
+
+#[test]
+fn test_prelude_function_without_use() -> rune::Result<()> {
+    let out: i64 = run_with_prelude(
+        &["std::int::parse"],
+        r#"
+        pub fn main() {
+            parse("42")?
+        }
+        "#,
+    )?;
+
+    assert_eq!(out, 42);
+    Ok(())
+}
+
+#[test]
+fn test_prelude_shadowed_by_script() -> rune::Result<()> {
+    let out: i64 = run_with_prelude(
+        &["std::int::parse"],
+        r#"
+        fn parse(value) {
+            value.len()
+        }
+
+        pub fn main() {
+            parse("42")
+        }
+        "#,
+    )?;
+
+    assert_eq!(out, 2);
+
+    let out: i64 = run_with_prelude(
+        &["std::int::parse"],
+        r#"
+        use std::string::parse_int as parse;
+
+        pub fn main() {
+            parse("7")? + 1
+        }
+        "#,
+    )?;
+
+    assert_eq!(out, 8);
+    Ok(())
+}
+
+#[test]
+fn test_prelude_invalid_path() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    for path in ["", "std::int::", "std::::parse"] {
+        let mut sources = Sources::new();
+        sources.insert(Source::new("main", "pub fn main() { 42 }"));
+
+        let mut diagnostics = Diagnostics::new();
+
+        let result = rune::prepare(&mut sources)
+            .with_context(&context)
+            .with_diagnostics(&mut diagnostics)
+            .with_prelude([path])
+            .build();
+
+        assert!(result.is_err());
+
+        let error = match diagnostics.into_diagnostics().into_iter().next() {
+            Some(Diagnostic::Fatal(error)) => error,
+            other => panic!("expected fatal diagnostic but was {:?}", other),
+        };
+
+        match error.into_kind() {
+            FatalDiagnosticKind::CompileError(error) => match error.into_kind() {
+                CompileErrorKind::InvalidPreludeItem { path: actual } => {
+                    assert_eq!(actual.as_ref(), path);
+                }
+                kind => panic!("expected invalid prelude item but was {:?}", kind),
+            },
+            kind => panic!("expected compile error but was {:?}", kind),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use rune::compile::Item;
+use rune::runtime::VmErrorKind;
+use rune::{Context, ContextError, Module};
+use rune_modules::testing::{Cassette, Matching, Recorder, Replayer};
+use rune_tests::*;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+pub fn main(url) {
+    let start = time::now();
+    let body = http::get(url);
+    let end = time::now();
+    (body, end - start)
+}
+"#;
+
+/// Construct a context with fake `time` and `http` modules. If `live` is
+/// `false` the fake functions panic when called, since they are expected to be
+/// replaced.
+fn fake_context(live: bool) -> Result<Context, ContextError> {
+    let clock = Arc::new(AtomicI64::new(1000));
+
+    let mut time = Module::with_crate("time");
+
+    time.function(&["now"], move || {
+        assert!(live, "time::now should have been replayed");
+        clock.fetch_add(250, Ordering::SeqCst)
+    })?;
+
+    let mut http = Module::with_crate("http");
+
+    http.function(&["get"], move |url: &str| {
+        assert!(live, "http::get should have been replayed");
+        format!("body of {}", url)
+    })?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&time)?;
+    context.install(&http)?;
+    Ok(context)
+}
+
+fn record(url: &str) -> rune::Result<Cassette> {
+    let (context, recorder) = Recorder::wrap(
+        fake_context(true)?,
+        [Item::with_crate("time"), Item::with_crate("http")],
+    );
+
+    let output: (String, i64) = run(&context, SOURCE, &["main"], (url,))?;
+    assert_eq!(output, (format!("body of {}", url), 250));
+    Ok(recorder.cassette())
+}
+
+fn replay_error(cassette: Cassette, matching: Matching, url: &str) -> rune::Result<String> {
+    let (context, _) = Replayer::install_with(fake_context(false)?, cassette, matching);
+
+    let error = run::<_, _, (String, i64)>(&context, SOURCE, &["main"], (url,))
+        .unwrap_err()
+        .expect_vm_error("expected vm error");
+
+    match error.into_unwound().0.into_kind() {
+        VmErrorKind::Panic { reason } => Ok(reason.to_string()),
+        actual => panic!("expected panic but was `{:?}`", actual),
+    }
+}
+
+#[test]
+fn test_record_replay() -> rune::Result<()> {
+    let cassette = record("https://example.com")?;
+    assert_eq!(cassette.calls.len(), 3);
+    assert_eq!(cassette.calls[0].function, "::time::now");
+    assert_eq!(cassette.calls[1].function, "::http::get");
+
+    let json = serde_json::to_string(&cassette)?;
+    let cassette: Cassette = serde_json::from_str(&json)?;
+
+    for matching in [Matching::Ordered, Matching::Keyed] {
+        let (context, replayer) =
+            Replayer::install_with(fake_context(false)?, cassette.clone(), matching);
+        assert_eq!(replayer.remaining(), 3);
+
+        let output: (String, i64) = run(&context, SOURCE, &["main"], ("https://example.com",))?;
+        assert_eq!(output, (String::from("body of https://example.com"), 250));
+        assert_eq!(replayer.remaining(), 0);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_replay_mismatch() -> rune::Result<()> {
+    let cassette = record("https://example.com")?;
+
+    let reason = replay_error(cassette.clone(), Matching::Ordered, "https://example.org")?;
+    assert!(reason.contains("call at position 1 diverged"), "{}", reason);
+    assert!(
+        reason.contains("\n-         \"https://example.com\",\n+         \"https://example.org\","),
+        "{}",
+        reason
+    );
+
+    let reason = replay_error(cassette, Matching::Keyed, "https://example.org")?;
+    assert!(
+        reason.contains("no unused recording of `::http::get`"),
+        "{}",
+        reason
+    );
+    Ok(())
+}
@@ -58,3 +58,20 @@ fn test_float_fns() {
     };
     assert_eq!(n, 1728.0);
 }
+
+#[test]
+fn test_float_to_string() {
+    let s: String = rune! {
+        pub fn main() {
+            3.25.to_string()
+        }
+    };
+    assert_eq!(s, "3.25");
+
+    let s: String = rune! {
+        pub fn main() {
+            2.0.to_string()
+        }
+    };
+    assert_eq!(s, "2.0");
+}
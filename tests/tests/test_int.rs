@@ -58,3 +58,20 @@ fn test_int_fns() {
     };
     assert_eq!(n, 1728);
 }
+
+#[test]
+fn test_int_to_string() {
+    let s: String = rune! {
+        pub fn main() {
+            42.to_string()
+        }
+    };
+    assert_eq!(s, "42");
+
+    let s: String = rune! {
+        pub fn main() {
+            (-7).to_string()
+        }
+    };
+    assert_eq!(s, "-7");
+}
@@ -1,4 +1,5 @@
 use rune::runtime::VmErrorKind::*;
+use rune::{FromValue, Value};
 use rune_tests::*;
 
 #[test]
@@ -21,6 +22,40 @@ fn test_and_then() {
     assert_eq!(out, Some(2))
 }
 
+#[test]
+fn test_filter() {
+    let out: (Option<i64>, Option<i64>, Option<i64>) = rune! {
+        pub fn main() {
+            (
+                Some(2).filter(|v| v % 2 == 0),
+                Some(3).filter(|v| v % 2 == 0),
+                None.filter(|v| v % 2 == 0),
+            )
+        }
+    };
+    assert_eq!(out, (Some(2), None, None));
+}
+
+#[test]
+fn test_zip() {
+    let out: (
+        Option<(i64, String)>,
+        Option<(i64, String)>,
+        Option<(i64, String)>,
+        Option<(i64, String)>,
+    ) = rune! {
+        pub fn main() {
+            (
+                Some(1).zip(Some("a")),
+                Some(1).zip(None),
+                None.zip(Some("a")),
+                None.zip(None),
+            )
+        }
+    };
+    assert_eq!(out, (Some((1, String::from("a"))), None, None, None));
+}
+
 #[test]
 fn test_expect_some() {
     let out: i32 = rune! {
@@ -70,3 +105,44 @@ fn test_unwrap() {
         }
     );
 }
+
+#[test]
+fn test_from_value() -> rune::Result<()> {
+    let value: Value = rune!(pub fn main() { Some(1) });
+    assert_eq!(Option::<i64>::from_value(value)?, Some(1));
+
+    let value: Value = rune!(pub fn main() { None });
+    assert_eq!(Option::<i64>::from_value(value)?, None);
+
+    let value: Value = rune!(pub fn main() { Some(()) });
+    assert_eq!(Option::<()>::from_value(value)?, Some(()));
+
+    let out: (Option<i64>, Option<i64>) = rune! {
+        pub fn main() {
+            let option = Some(1);
+            (option, option)
+        }
+    };
+    assert_eq!(out, (Some(1), Some(1)));
+    Ok(())
+}
+
+#[test]
+fn test_from_value_not_option() {
+    for (value, actual) in [
+        (rune!(pub fn main() { () }), "unit"),
+        (rune!(pub fn main() { 1 }), "integer"),
+        (rune!(pub fn main() { Ok(1) }), "Result"),
+    ] {
+        let value: Value = value;
+        let error = Option::<i64>::from_value(value).unwrap_err();
+
+        match error.into_kind() {
+            Expected { expected, actual: a } => {
+                assert_eq!(expected.to_string(), "Option");
+                assert_eq!(a.to_string(), actual);
+            }
+            kind => panic!("expected type error but was {:?}", kind),
+        }
+    }
+}
@@ -42,6 +42,35 @@ fn test_range_iter() {
     };
 }
 
+#[test]
+fn test_inclusive_range_loop() {
+    let out: (Vec<i64>, i64, i64) = rune_s! { r#"
+        pub fn main() {
+            let values = [];
+
+            for n in 0..=5 {
+                values.push(n);
+            }
+
+            let max = 0;
+
+            for n in 9223372036854775807..=9223372036854775807 {
+                max += 1;
+            }
+
+            let reversed = 0;
+
+            for n in 5..=1 {
+                reversed += 1;
+            }
+
+            (values, max, reversed)
+        }
+    "# };
+
+    assert_eq!(out, (vec![0, 1, 2, 3, 4, 5], 1, 0));
+}
+
 #[test]
 fn test_non_numeric_ranges() {
     let _: () = rune! {
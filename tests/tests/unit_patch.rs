@@ -0,0 +1,302 @@
+use rune::runtime::{Unit, UnitFn};
+use rune::{Context, FromValue, Hash, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+
+fn compile(context: &Context, source: &str) -> rune::Result<Unit> {
+    Ok(Arc::try_unwrap(build(context, source)?).expect("unit should not be shared"))
+}
+
+#[test]
+fn test_patch_changed_function() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut unit = compile(
+        &context,
+        r#"
+        fn a() { "a" }
+        fn b() { 2 }
+        pub fn removed() { 3 }
+        pub fn main() { (a(), b()) }
+        "#,
+    )?;
+
+    let patch = compile(
+        &context,
+        r#"
+        fn a() { "a" }
+        fn b() { 20 }
+        pub fn added() { 4 }
+        pub fn main() { (a(), b()) }
+        "#,
+    )?;
+
+    let a = Hash::type_hash(["a"]);
+    let b = Hash::type_hash(["b"]);
+    let main = Hash::type_hash(["main"]);
+
+    let before = (unit.function(a), unit.function(b), unit.function(main));
+
+    let result = unit.apply_patch(&patch);
+
+    assert_eq!(result.changed, vec![b]);
+    assert_eq!(result.added, vec![Hash::type_hash(["added"])]);
+    assert_eq!(result.removed, vec![Hash::type_hash(["removed"])]);
+    assert!(result.retained.is_empty());
+
+    assert_eq!(unit.function(a), before.0);
+    assert_eq!(unit.function(main), before.2);
+    assert_ne!(unit.function(b), before.1);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = <(String, i64)>::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, (String::from("a"), 20));
+    Ok(())
+}
+
+#[test]
+fn test_patch_unchanged_nan() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let source = r#"
+    const NAN = 0.0 / 0.0;
+    pub fn main() { NAN }
+    "#;
+
+    let mut unit = compile(&context, source)?;
+    let patch = compile(&context, source)?;
+
+    let result = unit.apply_patch(&patch);
+    assert!(result.changed.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_patch_relocates_field_assignments() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut unit = compile(
+        &context,
+        r#"
+        pub fn main() {
+            let o = #{count: 1};
+            o.count += 1;
+            o.count
+        }
+        "#,
+    )?;
+
+    // NB: the new string comes first, so the static strings of the patch are
+    // ordered differently from the ones in the unit.
+    let patch = compile(
+        &context,
+        r#"
+        pub fn main() {
+            let name = "other";
+            let o = #{count: 1};
+            o.count += 41;
+            o.count
+        }
+        "#,
+    )?;
+
+    unit.apply_patch(&patch);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 42);
+    Ok(())
+}
+
+#[test]
+fn test_patch_without_debug_info() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut unit = compile(
+        &context,
+        r#"
+        pub fn a() { 1 }
+        pub fn main() { a() }
+        "#,
+    )?;
+
+    let patch = compile(
+        &context,
+        r#"
+        pub fn a() { 2 }
+        pub fn main() { a() }
+        "#,
+    )?;
+
+    // NB: strip the debug information from the patch.
+    let mut patch = serde_json::to_value(&patch)?;
+    patch["debug"] = serde_json::Value::Null;
+    let patch = serde_json::from_value::<Unit>(patch)?;
+
+    assert!(patch.debug_info().is_none());
+
+    let main = Hash::type_hash(["main"]);
+    let before = unit.function(main);
+    unit.apply_patch(&patch);
+    assert_eq!(unit.function(main), before);
+
+    // NB: the debug information of the unit is kept, so functions which
+    // weren't changed by the patch can still be resolved.
+    let debug = unit.debug_info().expect("debug info should be kept");
+
+    let offset = match unit.function(main) {
+        Some(UnitFn::Offset { offset, .. }) => offset,
+        _ => panic!("expected main to be a function"),
+    };
+
+    let (hash, signature) = debug.function_at(offset).expect("main should have debug info");
+    assert_eq!(hash, main);
+    assert_eq!(signature.path.to_string(), "main");
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 2);
+    Ok(())
+}
+
+#[test]
+fn test_patch_retains_functions_on_call_stack() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let unit = compile(
+        &context,
+        r#"
+        fn helper() { let n = 0; while n < 10 { n += 1; } n }
+        pub fn main() { helper() }
+        "#,
+    )?;
+
+    let patch = compile(&context, r#"pub fn main() { 0 }"#)?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let mut execution = vm.execute(["main"], ())?;
+
+    while execution.vm().call_frames().is_empty() {
+        assert!(execution.step()?.is_none());
+    }
+
+    let result = execution.vm_mut().apply_patch(&patch);
+    assert_eq!(result.retained, vec![Hash::type_hash(["helper"])]);
+    assert!(result.removed.is_empty());
+
+    let output = i64::from_value(execution.complete()?)?;
+    assert_eq!(output, 10);
+
+    let output = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 0);
+    Ok(())
+}
+
+#[test]
+fn test_patch_appends_changed_functions() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let source = r#"
+        fn a() { let n = 0; while n < 10 { n += 1; } n }
+        pub fn main() { a() + 1 }
+    "#;
+
+    let mut unit = compile(&context, source)?;
+    let len = unit.iter_instructions().count();
+
+    for _ in 0..10 {
+        let result = unit.apply_patch(&compile(&context, source)?);
+        assert!(result.changed.is_empty());
+        assert!(result.added.is_empty());
+    }
+
+    assert_eq!(unit.iter_instructions().count(), len);
+
+    let patch = compile(
+        &context,
+        r#"
+        fn a() { let n = 0; while n < 10 { n += 1; } n }
+        pub fn main() { a() + 2 }
+        "#,
+    )?;
+
+    let main = Hash::type_hash(["main"]);
+    let main_len = function_len(&patch, main);
+
+    assert_eq!(unit.apply_patch(&patch).changed, vec![main]);
+    assert_eq!(unit.iter_instructions().count(), len + main_len);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 12);
+    Ok(())
+}
+
+#[test]
+fn test_patch_retains_replaced_functions_on_call_stack() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let unit = compile(
+        &context,
+        r#"
+        fn helper() { let n = 0; while n < 10 { n += 1; } n }
+        pub fn main() { helper() }
+        "#,
+    )?;
+
+    let changed = compile(
+        &context,
+        r#"
+        fn helper() { let n = 0; while n < 20 { n += 1; } n }
+        pub fn main() { helper() }
+        "#,
+    )?;
+
+    let removed = compile(&context, r#"pub fn main() { 0 }"#)?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let mut execution = vm.execute(["main"], ())?;
+
+    while execution.vm().call_frames().is_empty() {
+        assert!(execution.step()?.is_none());
+    }
+
+    let helper = Hash::type_hash(["helper"]);
+
+    let result = execution.vm_mut().apply_patch(&changed);
+    assert_eq!(result.changed, vec![helper]);
+
+    // NB: the old body of `helper` is still executing, so it has to be kept
+    // even though it's no longer the current implementation.
+    let result = execution.vm_mut().apply_patch(&removed);
+    assert_eq!(result.retained, vec![helper]);
+    assert!(result.removed.is_empty());
+
+    let output = i64::from_value(execution.complete()?)?;
+    assert_eq!(output, 10);
+    Ok(())
+}
+
+/// Get the number of instructions in the body of the given function.
+fn function_len(unit: &Unit, hash: Hash) -> usize {
+    let offsets = unit
+        .iter_functions()
+        .filter_map(|(_, f)| match f {
+            UnitFn::Offset { offset, .. } => Some(*offset),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let start = match unit.function(hash) {
+        Some(UnitFn::Offset { offset, .. }) => offset,
+        _ => panic!("missing function"),
+    };
+
+    let end = offsets
+        .into_iter()
+        .filter(|offset| *offset > start)
+        .min()
+        .unwrap_or_else(|| unit.iter_instructions().count());
+
+    end - start
+}
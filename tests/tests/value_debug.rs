@@ -0,0 +1,68 @@
+use rune::runtime::{Shared, Vec};
+use rune::Value;
+use rune_tests::*;
+
+#[test]
+fn test_pretty_debug() {
+    let value: Value = rune! {
+        pub fn main() {
+            #{
+                list: [1, "two"],
+                pair: (true, ()),
+            }
+        }
+    };
+
+    assert_eq!(
+        format!("{:?}", value),
+        r#"{"list": [1, "two"], "pair": (true, ())}"#
+    );
+
+    let expected = r#"{
+    "list": [
+        1,
+        "two",
+    ],
+    "pair": (
+        true,
+        (),
+    ),
+}"#;
+
+    assert_eq!(format!("{:#?}", value), expected);
+}
+
+#[test]
+fn test_debug_cycle() {
+    let vec = Shared::new(Vec::new());
+    let value = Value::from(vec.clone());
+
+    vec.borrow_mut().unwrap().push(Value::from(1i64));
+    vec.borrow_mut().unwrap().push(value.clone());
+
+    assert_eq!(format!("{:?}", value), "[1, *recursive*]");
+    assert_eq!(format!("{:#?}", value), "[\n    1,\n    *recursive*,\n]");
+
+    // Clear the cycle so that the vector can be freed.
+    vec.borrow_mut().unwrap().clear();
+}
+
+#[test]
+fn test_debug_struct_cycle() {
+    let value: Value = rune! {
+        struct Node { next }
+
+        pub fn main() {
+            let node = Node { next: None };
+            node.next = Some([node]);
+            node
+        }
+    };
+
+    let debug = format!("{:?}", value);
+    assert!(debug.contains("*recursive*"), "{}", debug);
+
+    let mut string = String::new();
+    value.string_debug(&mut string).unwrap().unwrap();
+    assert_eq!(string, "Node { next: Some([*recursive*]) }");
+}
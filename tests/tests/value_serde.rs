@@ -0,0 +1,67 @@
+use rune::runtime::{BorrowRef, Object, Shared};
+use rune::{FromValue, Value};
+use rune_tests::*;
+
+#[test]
+fn test_serialize_value() -> rune::Result<()> {
+    let value: Value = rune! {
+        pub fn main() {
+            #{
+                unit: (),
+                number: 42,
+                float: 1.5,
+                flag: true,
+                string: "hello",
+                vec: [1, 2, 3],
+                tuple: (1, "two"),
+                option: Some(1),
+            }
+        }
+    };
+
+    let json = serde_json::to_value(&value)?;
+
+    assert_eq! {
+        json,
+        serde_json::json!({
+            "unit": null,
+            "number": 42,
+            "float": 1.5,
+            "flag": true,
+            "string": "hello",
+            "vec": [1, 2, 3],
+            "tuple": [1, "two"],
+            "option": 1,
+        })
+    };
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_value() -> rune::Result<()> {
+    let value: Value = serde_json::from_str(
+        r#"{"number": 42, "float": 1.5, "flag": false, "list": [null, "a"], "nested": {"a": 1}}"#,
+    )?;
+
+    let object = Object::from_value(value)?;
+    assert!(matches!(object.get("number"), Some(Value::Integer(42))));
+    assert!(matches!(object.get("float"), Some(Value::Float(f)) if *f == 1.5));
+    assert!(matches!(object.get("flag"), Some(Value::Bool(false))));
+
+    let list = <Vec<Value>>::from_value(object.get("list").unwrap().clone())?;
+    assert!(matches!(list[0], Value::Unit));
+    assert_eq!(String::from_value(list[1].clone())?, "a");
+
+    let nested = Object::from_value(object.get("nested").unwrap().clone())?;
+    assert!(matches!(nested.get("a"), Some(Value::Integer(1))));
+    Ok(())
+}
+
+#[test]
+fn test_serialize_borrow_ref() -> rune::Result<()> {
+    let shared = Shared::new(vec![1i64, 2, 3]);
+    let vec: BorrowRef<'_, Vec<i64>> = shared.borrow_ref()?;
+    assert_eq!(serde_json::to_string(&vec)?, "[1,2,3]");
+    Ok(())
+}
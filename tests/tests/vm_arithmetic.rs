@@ -138,6 +138,16 @@ fn test_bit_ops() {
     error_test!(0b1 << 64 = Overflow);
 }
 
+#[test]
+fn test_shift_operand_range() {
+    op_tests!(0b1 << 63 = i64::MIN);
+    op_tests!(-9223372036854775807i64 >> 63 = -1);
+    error_test!(0b1 >> 64 = Overflow);
+    error_test!(0b1 << -1 = Overflow);
+    error_test!(0b1 >> -1 = Overflow);
+    error_test!(0b1 >> 4294967296i64 = Overflow);
+}
+
 #[test]
 fn test_bitwise_not() {
     let out: i64 = rune! {
@@ -0,0 +1,109 @@
+use futures_executor::block_on;
+use rune::{Context, Module, Vm};
+use rune_tests::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context as TaskContext, Poll};
+
+/// Sets the flag when the host future it is held by is dropped.
+struct DropFlag(Arc<AtomicBool>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Poll the given future once, reporting if it is still pending.
+struct PollOnce<F>(Pin<Box<F>>);
+
+impl<F> Future for PollOnce<F>
+where
+    F: Future,
+{
+    type Output = bool;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<bool> {
+        Poll::Ready(self.0.as_mut().poll(cx).is_pending())
+    }
+}
+
+fn vm(source: &str) -> rune::Result<(Vm, Arc<AtomicBool>, Arc<AtomicUsize>)> {
+    let dropped = Arc::new(AtomicBool::new(false));
+    let started = Arc::new(AtomicUsize::new(0));
+
+    let mut module = Module::new();
+
+    module.async_function(&["pending"], {
+        let dropped = dropped.clone();
+        let started = started.clone();
+
+        move || {
+            let guard = DropFlag(dropped.clone());
+            started.fetch_add(1, Ordering::SeqCst);
+
+            async move {
+                let _guard = guard;
+                std::future::pending::<()>().await
+            }
+        }
+    })?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let unit = build(&context, source)?;
+    let vm = Vm::new(Arc::new(context.runtime()), unit);
+    Ok((vm, dropped, started))
+}
+
+#[test]
+fn test_drop_async_call_drops_host_future() -> rune::Result<()> {
+    let sources = [
+        r#"pub async fn main() { pending().await }"#,
+        r#"pub async fn main() { let future = pending(); future.await }"#,
+        r#"pub async fn main() { let a = pending(); let b = pending(); select { _ = a => 1, _ = b => 2 } }"#,
+    ];
+
+    for source in sources {
+        let (mut vm, dropped, started) = vm(source)?;
+
+        let future = vm.async_call(["main"], ());
+        assert!(block_on(PollOnce(Box::pin(future))));
+
+        assert!(started.load(Ordering::SeqCst) > 0);
+        assert!(dropped.load(Ordering::SeqCst), "{}", source);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_drop_execution_drops_host_future() -> rune::Result<()> {
+    const SOURCE: &str = r#"
+    pub async fn main() { let future = pending(); future.await }
+    pub fn other() { 42 }
+    "#;
+
+    let (mut vm, dropped, _) = vm(SOURCE)?;
+
+    {
+        let mut execution = vm.execute(["main"], ())?;
+        assert!(block_on(PollOnce(Box::pin(execution.async_complete()))));
+    }
+
+    assert!(dropped.load(Ordering::SeqCst));
+    assert!(vm.stack().is_empty());
+
+    // NB: the virtual machine can be used again after being cancelled.
+    let value = vm.call(["other"], ())?;
+    assert_eq!(value.into_integer()?, 42);
+
+    let (send_vm, dropped, _) = self::vm(SOURCE)?;
+    let execution = send_vm.send_execute(["main"], ())?;
+    assert!(block_on(PollOnce(Box::pin(execution.async_complete()))));
+    assert!(dropped.load(Ordering::SeqCst));
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use rune::compile::Item;
+use rune::runtime::{env, Inst, VmError, VmErrorKind};
+use rune::{Context, FromValue, Hash, Module, Sources, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+fn inner() {
+    native()
+}
+
+fn outer() {
+    inner()
+}
+
+pub fn main() {
+    outer()
+}
+"#;
+
+#[test]
+fn test_backtrace() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.function(&["native"], || ())?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let unit = build(&context, SOURCE)?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+
+    let native = Hash::type_hash(&["native"]);
+
+    let ip = vm
+        .unit()
+        .iter_instructions()
+        .position(|inst| matches!(inst, Inst::Call { hash, .. } if hash == native))
+        .expect("missing call to native function");
+
+    vm.set_breakpoint(ip);
+
+    let mut execution = vm.execute(["main"], ())?;
+    assert!(execution.resume_until_breakpoint()?.is_none());
+
+    let backtrace = execution.vm().backtrace();
+    assert_eq!(backtrace.len(), 3);
+
+    let items = backtrace
+        .iter()
+        .map(|frame| frame.fn_item.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        items,
+        [
+            Item::with_item(&["inner"]),
+            Item::with_item(&["outer"]),
+            Item::with_item(&["main"]),
+        ]
+    );
+
+    let sources = rune_tests::sources(SOURCE);
+    let main = sources.get(backtrace[0].source_id).expect("missing source");
+    assert_eq!(main.name(), "main");
+
+    let calls = backtrace
+        .iter()
+        .map(|frame| &SOURCE[frame.span.range()])
+        .collect::<Vec<_>>();
+
+    assert_eq!(calls, ["native()", "inner()", "outer()"]);
+    assert_eq!(backtrace[0].instruction_pointer, ip);
+    assert_eq!(backtrace[0].format_with(&sources), "inner (main:3:5)");
+    assert_eq!(backtrace[2].format_with(&Sources::new()), "main");
+    Ok(())
+}
+
+#[test]
+fn test_backtrace_from_native() -> rune::Result<()> {
+    let mut module = Module::new();
+
+    module.function(&["native"], || -> Result<std::vec::Vec<String>, VmError> {
+        env::with_backtrace(|backtrace| {
+            backtrace
+                .iter()
+                .map(|frame| format!("{} {}", frame.fn_item, &SOURCE[frame.span.range()]))
+                .collect()
+        })
+    })?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let unit = build(&context, SOURCE)?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+
+    let output = std::vec::Vec::<String>::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(
+        output,
+        ["inner native()", "outer inner()", "main outer()"]
+    );
+
+    let error = env::with_backtrace(|backtrace| backtrace.len()).unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::MissingInterfaceEnvironment
+    ));
+    Ok(())
+}
@@ -11,3 +11,75 @@ fn test_anonymous_type_precedence() {
     };
     assert_eq!(out, 3);
 }
+
+#[test]
+fn test_labeled_block_break() {
+    let out: (i64, i64) = rune! {
+        fn check(n) {
+            'done: {
+                if n < 0 {
+                    break 'done -1;
+                }
+
+                let doubled = n * 2;
+
+                if doubled > 10 {
+                    break 'done 10;
+                }
+
+                doubled
+            }
+        }
+
+        pub fn main() {
+            (check(-5) + check(3), check(100))
+        }
+    };
+    assert_eq!(out, (5, 10));
+}
+
+#[test]
+fn test_labeled_block_inside_loop() {
+    let out: Vec<i64> = rune! {
+        pub fn main() {
+            let out = [];
+
+            'outer: for n in 0..6 {
+                let value = 'block: {
+                    if n % 2 == 0 {
+                        break 'block n * 10;
+                    }
+
+                    if n == 5 {
+                        break 'outer;
+                    }
+
+                    n
+                };
+
+                out.push(value);
+            }
+
+            out
+        }
+    };
+    assert_eq!(out, vec![0, 1, 20, 3, 40]);
+}
+
+#[test]
+fn test_labeled_block_without_value() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = 0;
+
+            'block: {
+                n += 1;
+                break 'block;
+                n += 1;
+            }
+
+            n
+        }
+    };
+    assert_eq!(out, 1);
+}
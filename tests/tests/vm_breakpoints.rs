@@ -0,0 +1,92 @@
+use rune::runtime::{Inst, Stack, VmErrorKind, VmHaltInfo, VmObserver};
+use rune::{Context, FromValue, Vm};
+use rune_tests::*;
+use std::sync::{Arc, Mutex};
+
+/// Records the arguments of the call frame every time a breakpoint is hit.
+#[derive(Clone, Default)]
+struct Breakpoints(Arc<Mutex<Vec<(usize, Vec<i64>)>>>);
+
+impl VmObserver for Breakpoints {
+    fn breakpoint(&mut self, ip: usize, stack: &Stack, _: usize) {
+        let frame = stack.get(stack.stack_bottom()..).unwrap_or_default();
+
+        let frame = frame
+            .iter()
+            .take(2)
+            .map(|value| i64::from_value(value.clone()).unwrap())
+            .collect();
+
+        self.0.lock().unwrap().push((ip, frame));
+    }
+}
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    add(1, 2) + add(3, 4)
+}
+"#;
+
+/// Find the instruction performing the addition in `add`.
+fn find_add(vm: &Vm) -> usize {
+    let debug = vm.unit().debug_info().expect("unit should have debug info");
+
+    vm.unit()
+        .iter_instructions()
+        .enumerate()
+        .find(|(ip, inst)| {
+            let span = debug.instruction_at(*ip).unwrap().span;
+            matches!(inst, Inst::Op { .. }) && &SOURCE[span.range()] == "a + b"
+        })
+        .map(|(ip, _)| ip)
+        .expect("missing addition")
+}
+
+#[test]
+fn test_breakpoints() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, SOURCE)?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+
+    let ip = find_add(&vm);
+    vm.set_breakpoint(ip);
+
+    let breakpoints = Breakpoints::default();
+    vm.set_observer(breakpoints.clone());
+
+    let mut execution = vm.execute(["main"], ())?;
+
+    assert!(execution.resume_until_breakpoint()?.is_none());
+    assert_eq!(execution.vm().ip(), ip);
+
+    assert!(execution.resume_until_breakpoint()?.is_none());
+    assert_eq!(execution.vm().ip(), ip);
+
+    let out = execution.resume_until_breakpoint()?.expect("should complete");
+    assert_eq!(i64::from_value(out)?, 10);
+
+    let hits = breakpoints.0.lock().unwrap().clone();
+    assert_eq!(hits, vec![(ip, vec![1, 2]), (ip, vec![3, 4])]);
+
+    // Running without a way to resume reports the breakpoint.
+    let error = vm.call(["main"], ()).unwrap_err();
+
+    match error.into_unwound().0.into_kind() {
+        VmErrorKind::Halted {
+            halt: VmHaltInfo::Breakpoint,
+        } => {}
+        kind => panic!("expected breakpoint but got {:?}", kind),
+    }
+
+    assert!(vm.clear_breakpoint(ip));
+    assert!(!vm.clear_breakpoint(ip));
+
+    let out = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, 10);
+    assert_eq!(breakpoints.0.lock().unwrap().len(), 3);
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use rune::runtime::{VmError, VmErrorKind};
+use rune::Value;
+use rune_tests::*;
+
+fn is_budget_exceeded(result: Result<Value, VmError>) -> bool {
+    matches!(
+        result.map_err(|e| e.into_unwound().0.into_kind()),
+        Err(VmErrorKind::BudgetExceeded)
+    )
+}
+
+#[test]
+fn test_budget_exceeded() {
+    let mut vm = rune_vm! {
+        pub fn main(n) {
+            let count = 0;
+
+            while count != n {
+                count += 1;
+            }
+
+            count
+        }
+    };
+
+    assert_eq!(vm.budget(), None);
+    vm.set_budget(10_000);
+
+    let value = vm.call(["main"], (10,)).expect("call within budget");
+    assert_eq!(value.into_integer().expect("integer"), 10);
+
+    let remaining = vm.budget().expect("budget");
+    assert!(remaining > 0 && remaining < 10_000);
+
+    assert!(is_budget_exceeded(vm.call(["main"], (-1,))));
+    assert_eq!(vm.budget(), Some(0));
+
+    vm.clear_budget();
+    let value = vm.call(["main"], (100,)).expect("call without budget");
+    assert_eq!(value.into_integer().expect("integer"), 100);
+}
+
+#[test]
+fn test_budget_async() {
+    let mut vm = rune_vm! {
+        async fn spin() {
+            loop {}
+        }
+
+        pub async fn main() {
+            spin().await
+        }
+    };
+
+    vm.set_budget(1000);
+    let result = futures_executor::block_on(vm.async_call(["main"], ()));
+    assert!(is_budget_exceeded(result));
+}
+
+#[test]
+fn test_budget_generator() {
+    let mut vm = rune_vm! {
+        fn count() {
+            let n = 0;
+
+            loop {
+                n += 1;
+
+                if n % 100 == 0 {
+                    yield n;
+                }
+            }
+        }
+
+        pub fn main() {
+            count()
+        }
+    };
+
+    vm.set_budget(100_000);
+
+    let mut generator = vm
+        .call(["main"], ())
+        .expect("generator")
+        .into_generator()
+        .expect("generator")
+        .take()
+        .expect("owned generator");
+
+    let mut yielded = 0;
+
+    // NB: the generator shares the budget of the virtual machine which
+    // started it, so resuming it eventually runs out.
+    let error = loop {
+        match generator.next() {
+            Ok(Some(..)) => yielded += 1,
+            Ok(None) => panic!("generator should not complete"),
+            Err(error) => break error,
+        }
+    };
+
+    assert!(yielded > 0);
+    assert!(matches!(
+        error.into_unwound().0.into_kind(),
+        VmErrorKind::BudgetExceeded
+    ));
+    assert_eq!(vm.budget(), Some(0));
+}
+
+#[test]
+fn test_budget_native_closure() {
+    let mut vm = rune_vm! {
+        pub fn main(n) {
+            [n].iter().map(|n| {
+                let count = 0;
+
+                while count != n {
+                    count += 1;
+                }
+
+                count
+            }).collect::<Vec>()
+        }
+    };
+
+    vm.set_budget(1000);
+
+    // NB: the closure is called from a native iterator adaptor, which must
+    // still respect the budget of the calling virtual machine.
+    assert!(is_budget_exceeded(vm.call(["main"], (100_000,))));
+    assert_eq!(vm.budget(), Some(0));
+}
+
+#[test]
+fn test_budget_vec_repeat() {
+    let mut vm = rune_vm! {
+        pub fn main(n) {
+            [0; n].len()
+        }
+    };
+
+    vm.set_budget(1000);
+
+    let value = vm.call(["main"], (10,)).expect("call within budget");
+    assert_eq!(value.into_integer().expect("integer"), 10);
+
+    let remaining = vm.budget().expect("budget");
+    assert!(remaining < 990);
+
+    assert!(is_budget_exceeded(vm.call(["main"], (1_000_000,))));
+}
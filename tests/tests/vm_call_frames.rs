@@ -0,0 +1,71 @@
+use rune::runtime::VmErrorKind;
+use rune_tests::*;
+
+#[test]
+fn test_max_call_frames() {
+    let mut vm = rune_vm! {
+        fn recurse(n) {
+            if n == 0 {
+                return 0;
+            }
+
+            recurse(n - 1) + 1
+        }
+
+        pub fn main(n) {
+            recurse(n)
+        }
+    };
+
+    assert_eq!(vm.max_call_frames(), None);
+    vm.set_max_call_frames(16);
+    assert_eq!(vm.max_call_frames(), Some(16));
+
+    let value = vm.call(["main"], (10,)).expect("call within limit");
+    assert_eq!(value.into_integer().expect("integer"), 10);
+
+    let error = vm
+        .call(["main"], (100,))
+        .expect_err("call should exceed limit");
+
+    assert!(
+        matches!(
+            error.into_unwound().0.into_kind(),
+            VmErrorKind::CallStackLimit { limit: 16 }
+        ),
+        "expected call stack limit error"
+    );
+}
+
+#[test]
+fn test_max_call_frames_native_closure() {
+    let mut vm = rune_vm! {
+        fn recurse(n) {
+            if n == 0 {
+                return 0;
+            }
+
+            recurse(n - 1) + 1
+        }
+
+        pub fn main(n) {
+            [n].iter().map(|n| recurse(n)).collect::<Vec>()
+        }
+    };
+
+    vm.set_max_call_frames(10);
+
+    // NB: the function is called from a native iterator adaptor, which must
+    // still respect the limit of the calling virtual machine.
+    let error = vm
+        .call(["main"], (100,))
+        .expect_err("call should exceed limit");
+
+    assert!(
+        matches!(
+            error.into_unwound().0.into_kind(),
+            VmErrorKind::CallStackLimit { limit: 10 }
+        ),
+        "expected call stack limit error"
+    );
+}
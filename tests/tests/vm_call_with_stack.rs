@@ -0,0 +1,47 @@
+use rune::runtime::{Stack, VmErrorKind};
+use rune::{Context, FromValue, Hash, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+
+#[test]
+fn test_call_with_stack() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub fn join(a, b, c) {
+            (a, b, c)
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+
+    let mut stack = Stack::new();
+    stack.push(1i64);
+    stack.push(String::from("two"));
+    stack.push(3.0f64);
+
+    let value = vm.call_with_stack(Hash::type_hash(&["join"]), stack)?;
+    let value = <(i64, String, f64)>::from_value(value)?;
+    assert_eq!(value, (1, String::from("two"), 3.0));
+    assert!(vm.stack().is_empty());
+
+    let mut stack = Stack::new();
+    stack.push(1i64);
+
+    let error = vm
+        .call_with_stack(Hash::type_hash(&["join"]), stack)
+        .expect_err("argument count should mismatch");
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 1,
+            expected: 3
+        }
+    ));
+
+    Ok(())
+}
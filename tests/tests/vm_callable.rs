@@ -0,0 +1,227 @@
+use rune::compile::CompileErrorKind::Custom;
+use rune::runtime::{Function, Protocol, Tuple, TypeOf, VmErrorKind::*};
+use rune::{span, Any, ContextError, Module, Params};
+use rune_tests::*;
+
+#[derive(Any)]
+struct Query {
+    offset: i64,
+}
+
+impl Query {
+    fn new(offset: i64) -> Self {
+        Self { offset }
+    }
+
+    fn call(&self, value: i64) -> i64 {
+        value + self.offset
+    }
+}
+
+#[derive(Any)]
+struct Count;
+
+impl Count {
+    fn call(&self, args: Tuple) -> usize {
+        args.len()
+    }
+}
+
+fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Query>()?;
+    module.function(&["Query", "new"], Query::new)?;
+    module.inst_fn(Protocol::CALL, Query::call)?;
+    module.ty::<Count>()?;
+    module.function(&["Count", "new"], || Count)?;
+    module.inst_fn(Params(Protocol::CALL, [Tuple::type_hash()]), Count::call)?;
+    Ok(module)
+}
+
+#[test]
+fn test_external_callable() -> rune::Result<()> {
+    let out: (i64, usize, usize) = rune_n! {
+        module()?,
+        (),
+        (i64, usize, usize) => pub fn main() {
+            let q = Query::new(10);
+            let count = Count::new();
+            (q(32), count(), count(1, "two", 3.0))
+        }
+    };
+
+    assert_eq!(out, (42, 0, 3));
+    Ok(())
+}
+
+#[test]
+fn test_script_struct_callable() {
+    let out: (i64, i64) = rune! {
+        struct Adder { amount }
+
+        impl Adder {
+            #[call]
+            fn call(self, a, b) {
+                self.amount + a + b
+            }
+        }
+
+        pub fn main() {
+            let adder = Adder { amount: 10 };
+            let f = adder;
+            (adder(1, 2), f(3, 4))
+        }
+    };
+
+    assert_eq!(out, (13, 17));
+}
+
+#[test]
+fn test_callable_in_iterator_map() -> rune::Result<()> {
+    let out: Vec<i64> = rune! {
+        struct Double;
+
+        impl Double {
+            #[call]
+            fn apply(self, value) {
+                value * 2
+            }
+        }
+
+        pub fn main() {
+            [1, 2, 3].iter().map(Double).collect::<Vec>()
+        }
+    };
+
+    assert_eq!(out, vec![2, 4, 6]);
+
+    let out: Vec<i64> = rune_n! {
+        module()?,
+        (),
+        Vec<i64> => pub fn main() {
+            [1, 2, 3].iter().map(Query::new(1)).collect::<Vec>()
+        }
+    };
+
+    assert_eq!(out, vec![2, 3, 4]);
+    Ok(())
+}
+
+/// Try to convert the given function into a sync function, returning the type
+/// reported in the error.
+fn sync_error(function: Function) -> String {
+    let error = match function.into_sync() {
+        Ok(..) => panic!("expected function to not be convertible"),
+        Err(error) => error,
+    };
+
+    match error.into_kind() {
+        CallableNotSync { actual } => actual.to_string(),
+        kind => panic!("expected callable not sync but was {:?}", kind),
+    }
+}
+
+#[test]
+fn test_callable_into_sync() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.function(&["sync_error"], sync_error)?;
+
+    let out: String = rune_n! {
+        module,
+        (),
+        String =>
+        struct Double;
+
+        impl Double {
+            #[call]
+            fn apply(self, value) {
+                value * 2
+            }
+        }
+
+        pub fn main() {
+            sync_error(Double)
+        }
+    };
+
+    assert_eq!(out, "Double");
+    Ok(())
+}
+
+#[test]
+fn test_not_callable() {
+    assert_vm_error!(
+        r#"
+        struct Point { x, y }
+
+        pub fn main() {
+            let p = Point { x: 1, y: 2 };
+            p(1)
+        }
+        "#,
+        UnsupportedCallFn { actual_type } => {
+            assert_eq!(actual_type.to_string(), "Point");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        struct Adder { amount }
+
+        impl Adder {
+            fn call(self, value) {
+                self.amount + value
+            }
+        }
+
+        pub fn main() {
+            let adder = Adder { amount: 1 };
+            adder(1)
+        }
+        "#,
+        UnsupportedCallFn { actual_type } => {
+            assert_eq!(actual_type.to_string(), "Adder");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            [1, 2, 3].iter().map(42).collect::<Vec>()
+        }
+        "#,
+        BadArgument { error, arg: 1 } => {
+            assert_eq!(error.to_string(), "expected `Function`, but found `integer`");
+        }
+    );
+}
+
+#[test]
+fn test_call_attribute_errors() {
+    assert_compile_error! {
+        r#"#[call] fn main() {}"#,
+        span, Custom { message } => {
+            assert_eq!(span, span!(0, 7));
+            assert_eq!(message, "#[call] is only supported on member functions");
+        }
+    };
+
+    assert_compile_error! {
+        r#"
+        struct Adder;
+
+        impl Adder {
+            #[call]
+            fn a(self) { 1 }
+
+            #[call]
+            fn b(self) { 2 }
+        }
+
+        pub fn main() { Adder() }
+        "#,
+        span, Custom { message } => {
+            assert_eq!(message, "only one function can be marked with #[call]");
+        }
+    };
+}
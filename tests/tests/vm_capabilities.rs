@@ -0,0 +1,148 @@
+use futures_executor::block_on;
+use rune::compile::Item;
+use rune::runtime::{Capabilities, VmError, VmErrorKind};
+use rune::{Any, Context, ContextError, FromValue, Hash, Module, Value, Vm};
+use rune_tests::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(Any)]
+struct Client;
+
+impl Client {
+    fn fetch(&self, value: i64) -> i64 {
+        value * 3
+    }
+}
+
+fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("host");
+    module.function(&["fetch"], |value: i64| value * 2)?;
+    module.capability(&["fetch"], "net")?;
+    module.function(&["free"], |value: i64| value + 1)?;
+    module.ty::<Client>()?;
+    module.function(&["Client", "new"], || Client)?;
+    module.inst_fn("fetch", Client::fetch)?;
+    module.inst_capability::<Client, _>("fetch", "net")?;
+    Ok(module)
+}
+
+/// Get the capability which was denied by the given call, if any.
+fn denied(result: Result<Value, VmError>) -> Option<Box<str>> {
+    let (error, _) = result.err()?.into_unwound();
+
+    match error.into_kind() {
+        VmErrorKind::CapabilityDenied { capability, .. } => Some(capability),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_capability_denied() -> rune::Result<()> {
+    let mut vm = build_vm(&[module()?], r#"pub fn main() { host::free(1) + host::fetch(1) }"#)?;
+
+    let (error, _) = vm.call(&["main"], ()).unwrap_err().into_unwound();
+
+    match error.into_kind() {
+        VmErrorKind::CapabilityDenied { capability, hash } => {
+            assert_eq!(&*capability, "net");
+            assert_eq!(hash, Hash::type_hash(&Item::with_crate_item("host", &["fetch"])));
+        }
+        kind => panic!("expected capability denied but was {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_capability_granted() -> rune::Result<()> {
+    let mut vm = build_vm(&[module()?], r#"pub fn main() { host::free(1) + host::fetch(1) }"#)?;
+    vm.grant("net");
+    assert_eq!(i64::from_value(vm.call(&["main"], ())?)?, 4);
+
+    vm.capabilities_mut().revoke("net");
+    assert_eq!(denied(vm.call(&["main"], ())).as_deref(), Some("net"));
+    Ok(())
+}
+
+#[test]
+fn test_capability_inherited() -> rune::Result<()> {
+    let source = r#"
+    async fn fetch_async(n) {
+        host::fetch(n)
+    }
+
+    pub async fn main() {
+        let a = [1, 2].iter().map(|n| host::fetch(n)).collect::<Vec>();
+        let b = [3].iter().map(host::fetch).collect::<Vec>();
+        (a, b, fetch_async(4).await)
+    }
+    "#;
+
+    let mut vm = build_vm(&[module()?], source)?;
+    assert_eq!(
+        denied(block_on(vm.async_call(&["main"], ()))).as_deref(),
+        Some("net")
+    );
+
+    vm.grant("net");
+    let out = <(Vec<i64>, Vec<i64>, i64)>::from_value(block_on(vm.async_call(&["main"], ()))?)?;
+    assert_eq!(out, (vec![2, 4], vec![6], 8));
+    Ok(())
+}
+
+#[test]
+fn test_instance_capability() -> rune::Result<()> {
+    let mut vm = build_vm(&[module()?], r#"pub fn main() { host::Client::new().fetch(2) }"#)?;
+    assert_eq!(denied(vm.call(&["main"], ())).as_deref(), Some("net"));
+
+    vm.grant("net");
+    assert_eq!(i64::from_value(vm.call(&["main"], ())?)?, 6);
+    Ok(())
+}
+
+#[test]
+fn test_empty_capabilities_deny_everything() -> rune::Result<()> {
+    let source = r#"
+    pub fn main() {
+        host::child()
+    }
+
+    pub fn fetch() {
+        host::fetch(1)
+    }
+    "#;
+
+    let slot = Arc::new(Mutex::new(None));
+
+    let mut child_module = Module::with_crate("host");
+    let child_slot = slot.clone();
+
+    // Call `fetch` in a new virtual machine with the capabilities in the
+    // slot, while the virtual machine calling `child` is running.
+    child_module.function(&["child"], move || -> Result<Value, VmError> {
+        let (runtime, unit, capabilities) = child_slot.lock().unwrap().take().expect("child");
+        let mut vm = Vm::new(runtime, unit);
+        vm.set_capabilities(capabilities);
+        vm.call(&["fetch"], ())
+    })?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module()?)?;
+    context.install(&child_module)?;
+    let unit = build(&context, source)?;
+    let runtime = Arc::new(context.runtime());
+
+    let mut vm = Vm::new(runtime.clone(), unit.clone());
+    vm.grant("net");
+
+    // A virtual machine without its own capabilities uses the ones of the
+    // virtual machine which called into it.
+    *slot.lock().unwrap() = Some((runtime.clone(), unit.clone(), None));
+    assert_eq!(i64::from_value(vm.call(&["main"], ())?)?, 2);
+
+    // An empty set of capabilities grants nothing, even when called from a
+    // virtual machine which has been granted more.
+    *slot.lock().unwrap() = Some((runtime, unit, Some(Capabilities::new())));
+    assert_eq!(denied(vm.call(&["main"], ())).as_deref(), Some("net"));
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use rune::runtime::{Function, VecTuple};
-use rune::FromValue;
+use rune::{Context, FromValue, Vm};
 use rune_tests::*;
+use std::sync::Arc;
 
 /// Test that we don't accidentally capture `a` as part of its own declaration.
 #[test]
@@ -229,3 +230,121 @@ fn test_closure_in_lit_object() -> rune::Result<()> {
     assert_eq!(3, proxy.d);
     Ok(())
 }
+
+#[test]
+fn test_call_returned_closure() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources(
+        r#"
+        pub fn main(base) {
+            let seen = [];
+
+            |n| {
+                seen.push(n);
+                base + n * seen.len()
+            }
+        }
+        "#,
+    );
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let closure = vm.call(&["main"], (10i64,))?;
+
+    let first = vm.call_value(closure.clone(), (1i64,))?;
+    assert_eq!(first.into_integer()?, 11);
+
+    let second = vm.call_value(closure, (2i64,))?;
+    assert_eq!(second.into_integer()?, 14);
+    Ok(())
+}
+
+#[test]
+fn test_closure_captures_self() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources(
+        r#"
+        struct Counter { base }
+
+        impl Counter {
+            fn adder(self) {
+                |n| || self.base + n
+            }
+        }
+
+        pub fn main(base) {
+            let counter = Counter { base };
+            counter.adder()
+        }
+        "#,
+    );
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let adder = vm.call(&["main"], (40i64,))?;
+
+    let inner = vm.call_value(adder, (2i64,))?;
+    let output = vm.call_value(inner, ())?;
+    assert_eq!(output.into_integer()?, 42);
+    Ok(())
+}
+
+#[test]
+fn test_call_value_inherits_settings() -> rune::Result<()> {
+    use rune::Module;
+    use std::io::{self, Write};
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut module = Module::with_crate("host");
+    module.function(&["fetch"], |value: i64| value * 2)?;
+    module.capability(&["fetch"], "net")?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub fn main() {
+            |n| {
+                println(`fetching ${n}`);
+                host::fetch(n)
+            }
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+    let buffer = Buffer::default();
+    vm.set_output(buffer.clone());
+    vm.grant("net");
+
+    let closure = vm.call(&["main"], ())?;
+    let output = vm.call_value(closure, (21i64,))?;
+    assert_eq!(output.into_integer()?, 42);
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, "fetching 21\n");
+    Ok(())
+}
@@ -1,5 +1,10 @@
-use rune::runtime::{Object, Tuple, Vec};
+use rune::compile::CompileErrorKind::{ContinueOutsideOfLoop, QueryError};
+use rune::compile::IrErrorKind;
+use rune::query::QueryErrorKind::IrError;
+use rune::runtime::{ConstValue, FromValue, Object, Tuple, Vec, VmError, VmErrorKind};
+use rune::{span, Context, Module, Source, Sources, Vm};
 use rune_tests::*;
+use std::sync::Arc;
 
 macro_rules! test_op {
     ($ty:ty => $lhs:literal $op:tt $rhs:literal = $result:literal) => {{
@@ -37,6 +42,15 @@ fn test_const_values() {
     "#
     );
     assert_eq!(out, "Hello World 1 1.0 true");
+
+    let out: String = rune_s!(
+        r#"
+        const VALUE = `${'x'}${SEP}${'😊'}`;
+        const SEP = '-';
+        pub fn main() { VALUE }
+    "#
+    );
+    assert_eq!(out, "x-😊");
 }
 
 #[test]
@@ -45,8 +59,13 @@ fn test_integer_ops() {
     test_op!(i64 => 2 - 1 = 1);
     test_op!(i64 => 8 / 2 = 4);
     test_op!(i64 => 8 * 2 = 16);
+    test_op!(i64 => 10 % 3 = 1);
+    test_op!(i64 => 9 % 3 = 0);
     test_op!(i64 => 0b1010 << 2 = 0b101000);
     test_op!(i64 => 0b1010 >> 2 = 0b10);
+    test_op!(i64 => 0b1100 & 0b1010 = 0b1000);
+    test_op!(i64 => 0b1100 | 0b1010 = 0b1110);
+    test_op!(i64 => 0b1100 ^ 0b1010 = 0b0110);
     test_op!(bool => 1 < 2 = true);
     test_op!(bool => 2 < 2 = false);
     test_op!(bool => 1 <= 1 = true);
@@ -57,6 +76,307 @@ fn test_integer_ops() {
     test_op!(bool => 0 >= 2 = false);
 }
 
+#[test]
+fn test_const_bitwise_ops() {
+    let out: (i64, i64, i64, bool, bool, bool) = rune! {
+        const MASK = 0xFF & 0x0F;
+        const FLAGS = A | B;
+        const A = 1 << 2;
+        const B = 1 << 4;
+        const MIXED = (MASK + 1) * 2 ^ FLAGS & !0b100;
+        const NOT_TRUE = !true;
+        const BOOLS = (true & false) | (true ^ false);
+
+        const fn clear(flags, flag) {
+            let out = flags;
+            out &= !flag;
+            out |= 1;
+            out ^= 0b11;
+            out
+        }
+
+        const CLEARED = clear(FLAGS, A);
+
+        pub fn main() {
+            (MASK, FLAGS, MIXED, NOT_TRUE, BOOLS, CLEARED == 0b10010)
+        }
+    };
+
+    assert_eq!(out, (0x0F, 0b10100, 32 ^ 0b10000, false, true, true));
+
+    let out: i64 = rune!(const V = !0; pub fn main() { V });
+    assert_eq!(out, -1);
+
+    let out: i64 = rune!(const V = (1 << 62) >> 10; pub fn main() { V });
+    assert_eq!(out, 1 << 52);
+
+    assert_compile_error! {
+        r#"const V = 1 << 63; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::NotInteger { value } } } => {
+            assert_eq!(value.to_string(), "9223372036854775808");
+            assert_eq!(span, span!(10, 17));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const V = 1 & true; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::NotConst } } => {
+            assert_eq!(span, span!(10, 18));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const V = false ^ 0; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::NotConst } } => {
+            assert_eq!(span, span!(10, 19));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const V = !"hello"; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::NotConst } } => {
+            assert_eq!(span, span!(10, 18));
+        }
+    };
+}
+
+#[test]
+fn test_const_remainder() {
+    let out: (i64, i64, i64, i64) = rune! {
+        const SEED = (0x5f3759df % 1000) ^ (0xff & 0x0f | 0x30);
+        const NEGATIVE = (0 - 7) % 3;
+
+        const fn wrap(n, size) {
+            let out = n;
+            out %= size;
+            out & 0xff
+        }
+
+        const WRAPPED = wrap(1000, 7 % 4 + 1);
+        const NESTED = wrap(SEED, 10) % (2 + 3);
+
+        pub fn main() {
+            (SEED, NEGATIVE, WRAPPED, NESTED)
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            (0x5f3759df % 1000) ^ (0xff & 0x0f | 0x30),
+            -7 % 3,
+            1000 % 4,
+            ((0x5f3759df % 1000) ^ (0xff & 0x0f | 0x30)) % 10 % 5,
+        )
+    );
+
+    assert_compile_error! {
+        r#"const V = 10 % 0; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::Custom { message } } } => {
+            assert_eq!(message, "division by zero");
+            assert_eq!(span, span!(10, 16));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const fn f(n) { n %= 0; n } const V = f(1); pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::ConstEval { error, .. } } } => {
+            assert!(matches!(*error, IrErrorKind::Custom { message: "division by zero" }));
+            assert_eq!(span, span!(16, 22));
+        }
+    };
+}
+
+#[test]
+fn test_const_integer_overflow() {
+    let out: i64 = rune!(const V = 9223372036854775806 + 1; pub fn main() { V });
+    assert_eq!(out, i64::MAX);
+
+    assert_compile_error! {
+        r#"const V = 9223372036854775807 + 1; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::Custom { message } } } => {
+            assert_eq!(message, "integer overflow");
+            assert_eq!(span, span!(10, 33));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const V = 0 - 4611686018427387904 * 3; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::Custom { message } } } => {
+            assert_eq!(message, "integer overflow");
+            assert_eq!(span, span!(14, 37));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const fn f(n) { n += 9223372036854775807; n } const V = f(1); pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::ConstEval { error, .. } } } => {
+            assert!(matches!(*error, IrErrorKind::Custom { message: "integer overflow" }));
+            assert_eq!(span, span!(16, 40));
+        }
+    };
+}
+
+#[test]
+fn test_const_logical_operators() {
+    let out: (bool, bool, bool, bool) = rune! {
+        const A = true && false;
+        const B = false || true;
+        const C = false && 1 / 0 == 0;
+        const D = true || 1 / 0 == 0;
+        pub fn main() { (A, B, C, D) }
+    };
+    assert_eq!(out, (false, true, false, true));
+
+    let out: (bool, bool) = rune! {
+        fn not_const() { true }
+        const A = true || not_const();
+        const B = false && not_const();
+        pub fn main() { (A, B) }
+    };
+    assert_eq!(out, (true, false));
+
+    let out: (i64, i64) = rune! {
+        const fn f(n) { if n > 0 && 10 / n > 2 { 1 } else { 0 } }
+        const A = f(0);
+        const B = f(2);
+        pub fn main() { (A, B) }
+    };
+    assert_eq!(out, (0, 1));
+
+    assert_compile_error! {
+        r#"const V = true && 1 / 0 == 0; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::Custom { message } } } => {
+            assert_eq!(message, "division by zero");
+            assert_eq!(span, span!(18, 23));
+        }
+    };
+}
+
+#[test]
+fn test_const_continue() {
+    let out: i64 = rune! {
+        const fn f() {
+            let n = 0;
+            let out = 0;
+
+            while n < 10 {
+                n += 1;
+
+                if n % 2 == 0 {
+                    continue;
+                }
+
+                out += n;
+            }
+
+            out
+        }
+
+        const V = f();
+        pub fn main() { V }
+    };
+    assert_eq!(out, 25);
+
+    let out: i64 = rune! {
+        const fn f() {
+            let n = 0;
+            let out = 0;
+
+            'outer: while n < 5 {
+                n += 1;
+                let m = 0;
+
+                'inner: while m < 5 {
+                    m += 1;
+
+                    if m > n {
+                        continue 'outer;
+                    }
+
+                    if m == 2 {
+                        continue 'inner;
+                    }
+
+                    out += 1;
+                }
+            }
+
+            out
+        }
+
+        const V = f();
+        pub fn main() { V }
+    };
+    assert_eq!(out, 11);
+
+    let out: i64 = rune! {
+        const fn f() {
+            let n = 0;
+            let out = 0;
+
+            loop {
+                n += 1;
+
+                {
+                    let m = n * 2;
+
+                    if m == 6 {
+                        continue;
+                    }
+
+                    if m == 12 {
+                        break;
+                    }
+                }
+
+                out += n;
+            }
+
+            out
+        }
+
+        const V = f();
+        pub fn main() { V }
+    };
+    assert_eq!(out, 12);
+
+    let out: i64 = rune! {
+        const fn f() {
+            let n = 0;
+            let out = 0;
+
+            'outer: loop {
+                n += 1;
+
+                if n > 4 {
+                    break 'outer out;
+                }
+
+                loop {
+                    if n % 2 == 0 {
+                        continue 'outer;
+                    }
+
+                    out += n;
+                    break;
+                }
+            }
+        }
+
+        const V = f();
+        pub fn main() { V }
+    };
+    assert_eq!(out, 4);
+
+    assert_compile_error! {
+        r#"const V = { continue; 1 }; pub fn main() { V }"#,
+        span, ContinueOutsideOfLoop => {
+            assert_eq!(span, span!(12, 20));
+        }
+    };
+}
+
 macro_rules! test_float_op {
     ($ty:ty => $lhs:literal $op:tt $rhs:literal = $result:literal) => {{
         let program = format!(
@@ -80,6 +400,8 @@ fn test_float_ops() {
     test_float_op!(f64 => 2 - 1 = 1f64);
     test_float_op!(f64 => 8 / 2 = 4f64);
     test_float_op!(f64 => 8 * 2 = 16f64);
+    test_float_op!(f64 => 7 % 2 = 1f64);
+    test_float_op!(f64 => 8 % 3 = 2f64);
     test_float_op!(bool => 1 < 2 = true);
     test_float_op!(bool => 2 < 2 = false);
     test_float_op!(bool => 1 <= 1 = true);
@@ -265,3 +587,614 @@ fn test_const_block() {
 
     assert_eq!(result, "Hello World");
 }
+
+#[test]
+fn test_const_structural_eq() {
+    test_op!(bool => "(1, 2)" == "(1, 2)" = true);
+    test_op!(bool => "(1, 2)" == "(2, 1)" = false);
+    test_op!(bool => "(1, \"a\", (true, 'b'))" == "(1, \"a\", (true, 'b'))" = true);
+    test_op!(bool => "(1, 2)" == "(1, 2, 3)" = false);
+    test_op!(bool => "[1, 2, 3]" == "[1, 2, 3]" = true);
+    test_op!(bool => "[1, 2, 3]" == "[1, 2, 4]" = false);
+    test_op!(bool => "[]" == "[1]" = false);
+    test_op!(bool => "[(1, 2.5), (3, 4.5)]" == "[(1, 2.5), (3, 4.5)]" = true);
+
+    let out: bool = rune! {
+        const SAME = (1, 2) == (1, 2);
+        pub fn main() { SAME }
+    };
+
+    assert!(out);
+}
+
+#[test]
+fn test_const_eval_call_path() -> rune::Result<()> {
+    let mut sources = sources(
+        r#"
+        const fn divide(a) { a / 0 }
+        const fn outer(a) { divide(a + 1) }
+        const VALUE = outer(10);
+        pub fn main() { VALUE }
+        "#,
+    );
+
+    let mut diagnostics = rune::Diagnostics::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_err());
+
+    let mut buffer = rune::termcolor::Buffer::no_color();
+    diagnostics.emit(&mut buffer, &sources)?;
+    let output = String::from_utf8(buffer.into_inner())?;
+
+    assert!(output.contains("division by zero"), "{}", output);
+    assert!(output.contains("in call to `outer`"), "{}", output);
+    assert!(output.contains("in call to `divide`"), "{}", output);
+    assert!(output.contains("while evaluating const `VALUE`"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_const_string_methods() {
+    let out: (i64, String, String) = rune! {
+        const N = "hello".len();
+        const UPPER = "  Hello ".trim().to_uppercase();
+        const LOWER = "HeLLo".to_lowercase();
+
+        pub fn main() {
+            (N, UPPER, LOWER)
+        }
+    };
+
+    assert_eq!(out, (5, String::from("HELLO"), String::from("hello")));
+
+    assert_compile_error! {
+        r#"const N = "hello".split(" "); pub fn main() { N }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::NotConst } } => {
+            assert_eq!(span, span!(10, 28));
+        }
+    };
+}
+
+#[test]
+fn test_const_break_label_value() {
+    let out: i64 = rune! {
+        const fn find(target) {
+            let n = 0;
+
+            'outer: loop {
+                let m = 0;
+
+                'inner: loop {
+                    let k = 0;
+
+                    while k < 10 {
+                        if n * 100 + m * 10 + k == target {
+                            break 'outer n + m + k;
+                        }
+
+                        k += 1;
+                    }
+
+                    m += 1;
+
+                    if m == 10 {
+                        break 'inner;
+                    }
+                }
+
+                n += 1;
+            }
+        }
+
+        const V = find(345);
+        pub fn main() { V }
+    };
+    assert_eq!(out, 12);
+
+    assert_compile_error! {
+        r#"const fn f() { 'a: while true { break 'a 1; } } const V = f(); pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::ConstEval { error, .. } } } => {
+            assert!(matches!(*error, IrErrorKind::Custom { message: "break with value is not supported for conditional loops" }));
+            assert_eq!(span, span!(32, 42));
+        }
+    };
+}
+
+#[test]
+fn test_const_nested_label_break_value() {
+    let out: i64 = rune! {
+        const N = 'outer: loop { loop { break 'outer 42; } };
+        pub fn main() { N }
+    };
+    assert_eq!(out, 42);
+
+    let out: (i64, i64) = rune! {
+        const A = 'a: loop {
+            let b = 'b: loop {
+                'c: loop {
+                    break 'b 10;
+                }
+            };
+
+            loop {
+                loop {
+                    break 'a b + 1;
+                }
+            }
+        };
+
+        const B = 'a: loop {
+            let v = 'b: loop {
+                loop {
+                    break 'b 2;
+                }
+            };
+
+            break v * 3;
+        };
+
+        pub fn main() { (A, B) }
+    };
+    assert_eq!(out, (11, 6));
+}
+
+#[test]
+fn test_const_index() {
+    let out: (i64, String, bool, i64, i64) = rune! {
+        const TABLE = [10, 20, 30];
+        const PAIR = ("a", true);
+        const CONFIG = #{ limits: #{ max: 5 } };
+
+        const A = TABLE[1];
+        const B = PAIR.0;
+        const C = PAIR[1];
+        const D = CONFIG.limits.max;
+        const E = lookup(2);
+
+        const fn lookup(n) {
+            let table = [[1, 2], [3, 4], [5, 6]];
+            table[n][1] + CONFIG["limits"]["max"]
+        }
+
+        pub fn main() {
+            (A, B, C, D, E)
+        }
+    };
+    assert_eq!(out, (20, String::from("a"), true, 5, 11));
+
+    assert_compile_error! {
+        r#"const V = [1, 2, 3][3]; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::MissingIndex { index: 3 } } } => {
+            assert_eq!(span, span!(20, 21));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const V = #{a: 1}.b; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::MissingField { field } } } => {
+            assert_eq!(&*field, "b");
+            assert_eq!(span, span!(18, 19));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const V = "abc"[0]; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::NotConst } } => {
+            assert_eq!(span, span!(10, 18));
+        }
+    };
+}
+
+#[test]
+fn test_const_string_comparisons() {
+    let out: (bool, bool, bool, bool, bool, bool) = rune! {
+        const EQ = "abc" == "abc";
+        const NE = "abc" == "abd";
+        const LT = "abc" < "abd";
+        const LTE = "abc" <= "abc";
+        const GT = "b" > "abc";
+        const GTE = "ab" >= "abc";
+
+        pub fn main() {
+            (EQ, NE, LT, LTE, GT, GTE)
+        }
+    };
+
+    assert_eq!(out, (true, false, true, true, true, false));
+
+    let out: String = rune! {
+        const fn max(a, b) { if a < b { b } else { a } }
+        const MAX = max("apple", "banana");
+
+        pub fn main() {
+            MAX
+        }
+    };
+
+    assert_eq!(out, "banana");
+}
+
+#[test]
+fn test_const_collection_len() {
+    let out: (i64, i64, i64, i64, i64, bool) = rune! {
+        const STRING = "hello".len();
+        const VEC = [1, 2, 3].len();
+        const TUPLE = (1, 2).len();
+        const OBJECT = #{a: 1, b: 2, c: 3, d: 4}.len();
+        const BYTES = b"abcde".len();
+        const EMPTY = b"".is_empty();
+
+        pub fn main() {
+            (STRING, VEC, TUPLE, OBJECT, BYTES, EMPTY)
+        }
+    };
+
+    assert_eq!(out, (5, 3, 2, 4, 5, true));
+
+    let out: std::vec::Vec<i64> = rune! {
+        const NAME = "rune";
+        const SIZE = NAME.len() * 2;
+
+        pub fn main() {
+            [0; SIZE]
+        }
+    };
+
+    assert_eq!(out, [0; 8]);
+}
+
+#[test]
+fn test_const_instance_fns() {
+    let out: (i64, bool, bool, Option<char>, String, i64, bool, i64, bool) = rune! {
+        const LEN = [1, 2, 3].len();
+        const EMPTY = "".is_empty();
+        const CONTAINS = "hello world".contains("o w") & "hello".contains('e');
+        const CHAR = "hello".char_at(1);
+        const UPPER = "hello".to_uppercase();
+        const TUPLE = (1, 2).len();
+        const OBJECT = #{a: 1}.contains_key("a") & !#{}.contains_key("a");
+        const PUSHED = pushed(4);
+        const FOUND = [[1], [2, 3]].contains([2, 3]) & ![1, 2].contains(3);
+
+        const fn pushed(n) {
+            let values = [];
+            let name = "";
+
+            while values.len() < n {
+                values.push(values.len());
+                name.push('a');
+            }
+
+            if values.is_empty() { 0 } else { values.len() + name.len() }
+        }
+
+        pub fn main() {
+            (LEN, EMPTY, CONTAINS, CHAR, UPPER, TUPLE, OBJECT, PUSHED, FOUND)
+        }
+    };
+
+    assert_eq!(
+        out,
+        (3, true, true, Some('e'), String::from("HELLO"), 2, true, 8, true)
+    );
+
+    assert_compile_error! {
+        r#"const N = "hello".contains(1); pub fn main() { N }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::Expected { expected, actual } } } => {
+            assert_eq!(expected.to_string(), "String");
+            assert_eq!(actual.to_string(), "integer");
+            assert_eq!(span, span!(27, 28));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const fn f(v) { v.push(1); v.iter() } const N = f([]); pub fn main() { N }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::ConstEval { error, .. } } } => {
+            assert!(matches!(*error, IrErrorKind::NotConst));
+            assert_eq!(span, span!(27, 35));
+        }
+    };
+}
+
+#[test]
+fn test_const_push_copies_literals() {
+    let out: (String, String, String) = rune! {
+        const fn f() { let s = "a"; s.push('b'); s }
+        const A = f();
+        const B = f();
+        const C = "a";
+
+        pub fn main() {
+            (A, B, C)
+        }
+    };
+
+    assert_eq!(out, (String::from("ab"), String::from("ab"), String::from("a")));
+
+    let out: (String, String) = rune! {
+        const X = { let s = "q"; s.push('x'); s };
+        const Y = "q";
+
+        pub fn main() {
+            (X, Y)
+        }
+    };
+
+    assert_eq!(out, (String::from("qx"), String::from("q")));
+}
+
+#[test]
+fn test_const_recursion_limit() -> rune::Result<()> {
+    let source = |n: usize| {
+        sources(&format!(
+            r#"
+            const fn count(n) {{ if n == 0 {{ 0 }} else {{ 1 + count(n - 1) }} }}
+            const COUNT = count({});
+            pub fn main() {{ COUNT }}
+            "#,
+            n
+        ))
+    };
+
+    let mut sources = source(5);
+
+    let unit = rune::prepare(&mut sources)
+        .with_const_recursion_limit(10)
+        .build()?;
+
+    let mut vm = rune::Vm::without_runtime(std::sync::Arc::new(unit));
+    let out = <i64 as rune::FromValue>::from_value(vm.call(&["main"], ())?)?;
+    assert_eq!(out, 5);
+
+    let mut sources = source(100);
+    let mut diagnostics = rune::Diagnostics::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .with_const_recursion_limit(10)
+        .build();
+
+    assert!(result.is_err());
+
+    let mut buffer = rune::termcolor::Buffer::no_color();
+    diagnostics.emit(&mut buffer, &sources)?;
+    let output = String::from_utf8(buffer.into_inner())?;
+
+    assert!(output.contains("const recursion limit reached"), "{}", output);
+    assert!(!output.contains("evaluation budget exceeded"), "{}", output);
+    Ok(())
+}
+
+#[test]
+fn test_const_shared_literals() {
+    let out: (String, String, String, std::vec::Vec<u8>, std::vec::Vec<u8>) = rune! {
+        const fn name() { "shared" }
+        const A = name();
+        const B = name();
+        const C = "shared";
+        const D = b"bytes";
+        const E = b"bytes";
+
+        pub fn main() {
+            (A, B, C, D, E)
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            String::from("shared"),
+            String::from("shared"),
+            String::from("shared"),
+            b"bytes".to_vec(),
+            b"bytes".to_vec()
+        )
+    );
+}
+
+#[test]
+fn test_const_variant() {
+    let out: (i64, i64, i64, bool) = rune! {
+        enum External {
+            First(a),
+            Second(a, b),
+            Third,
+        }
+
+        const FIRST = External::First(5);
+        const SECOND = External::Second(FIRST, 2);
+        const THIRD = External::Third;
+
+        const fn value(e) {
+            match e {
+                External::First(n) => n * 2,
+                External::Second(External::First(a), b) => a + b,
+                External::Third => 100,
+                _ => 0,
+            }
+        }
+
+        const A = value(FIRST);
+        const B = value(SECOND);
+        const C = value(THIRD);
+
+        pub fn main() {
+            let is_first = match FIRST {
+                External::First(5) => true,
+                _ => false,
+            };
+
+            (A, B, C, is_first)
+        }
+    };
+    assert_eq!(out, (10, 7, 100, true));
+}
+
+#[test]
+fn test_const_variant_not_const_argument() {
+    assert_compile_error! {
+        r#"enum E { A(a) } fn f() { 1 } const V = E::A(f()); pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::UnsupportedMeta { .. } } } => {
+            assert_eq!(span, span!(44, 47));
+        }
+    };
+}
+
+#[test]
+fn test_const_builtin_variants() {
+    let out: (Option<i64>, Option<i64>, Option<i64>, Result<i64, String>, Result<i64, String>) = rune! {
+        const A = Option::Some(5);
+        const B = Some(A);
+        const C = None;
+        const D = Ok(1);
+        const E = Result::Err("bad");
+
+        pub fn main() {
+            (A, B.unwrap(), C, D, E)
+        }
+    };
+    assert_eq!(
+        out,
+        (Some(5), Some(5), None, Ok(1), Err(String::from("bad")))
+    );
+
+    let out: (i64, i64, i64, i64) = rune! {
+        const fn value(v) {
+            match v {
+                Some(Ok(n)) => n,
+                Some(Err(n)) => n * 10,
+                Option::None => 0,
+            }
+        }
+
+        const A = value(Some(Ok(2)));
+        const B = value(Some(Result::Err(3)));
+        const C = value(None);
+
+        const D = if let Some(n) = Some(4) { n } else { 0 };
+
+        pub fn main() {
+            (A, B, C, D)
+        }
+    };
+    assert_eq!(out, (2, 30, 0, 4));
+
+    assert_compile_error! {
+        r#"const V = Some(1, 2); pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::ArgumentCountMismatch { actual: 2, expected: 1 } } } => {
+            assert_eq!(span, span!(10, 20));
+        }
+    };
+}
+
+#[test]
+fn test_const_native_fn() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.function(&["double"], |n: i64| n * 2)?;
+    module.const_eval(&["double"], |args| match args {
+        [ConstValue::Integer(n)] => Ok(ConstValue::Integer(n * 2)),
+        _ => Err(VmError::panic("expected an integer")),
+    })?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "test",
+        r#"const VALUE = double(double(5) + 11); pub fn main() { VALUE }"#,
+    ));
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = i64::from_value(vm.call(&["main"], ())?)?;
+    assert_eq!(output, 42);
+    Ok(())
+}
+
+#[test]
+fn test_const_native_fn_in_crate() -> rune::Result<()> {
+    let mut module = Module::with_crate("host");
+    module.function(&["double"], |n: i64| n * 2)?;
+    module.const_eval(&["double"], |args| match args {
+        [ConstValue::Integer(n)] => Ok(ConstValue::Integer(n * 2)),
+        _ => Err(VmError::panic("expected an integer")),
+    })?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let sources = [
+        r#"const VALUE = host::double(5); pub fn main() { VALUE }"#,
+        r#"use host::double; const VALUE = double(5); pub fn main() { VALUE }"#,
+        r#"use host::double as twice; const VALUE = twice(5); pub fn main() { VALUE }"#,
+        r#"mod inner { use host::double; pub const VALUE = double(5); } pub fn main() { inner::VALUE }"#,
+    ];
+
+    for source in sources {
+        let mut sources = Sources::new();
+        sources.insert(Source::new("test", source));
+
+        let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+        let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+        let output = i64::from_value(vm.call(&["main"], ())?)?;
+        assert_eq!(output, 10, "{}", source);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_const_vec_spread() {
+    let out: (std::vec::Vec<i64>, std::vec::Vec<String>) = rune! {
+        const A = [1, 2];
+        const B = [0, ..A, ..[], 3, ..[4, 5]];
+
+        const fn twice(items) {
+            [..items, ..items]
+        }
+
+        const C = twice(["a"]);
+        pub fn main() { (B, C) }
+    };
+    assert_eq!(out, (vec![0, 1, 2, 3, 4, 5], vec![String::from("a"); 2]));
+
+    assert_compile_error! {
+        r#"const V = [0, ..(1, 2)]; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::Expected { actual, .. } } } => {
+            assert_eq!(actual.to_string(), "Tuple");
+            assert_eq!(span, span!(16, 22));
+        }
+    };
+}
+
+#[test]
+fn test_const_shift_operand_range() {
+    let out: i64 = rune!(const V = (1 << 62) >> 62; pub fn main() { V });
+    assert_eq!(out, 1);
+
+    assert_vm_error!(
+        r#"pub fn main() { let a = 1; let b = 64; a >> b }"#,
+        VmErrorKind::Overflow => {}
+    );
+
+    assert_compile_error! {
+        r#"const V = 1 >> 64; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::Custom { message } } } => {
+            assert_eq!(message, "numerical overflow");
+            assert_eq!(span, span!(15, 17));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const fn f() { let a = 1; a <<= 64; a } const V = f(); pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::ConstEval { error, .. } } } => {
+            assert!(matches!(*error, IrErrorKind::Custom { message } if message == "numerical overflow"));
+            assert_eq!(span, span!(26, 34));
+        }
+    };
+}
@@ -0,0 +1,77 @@
+use rune::runtime::VmErrorKind;
+use rune::Vm;
+use rune_tests::*;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_deadline_exceeded() {
+    let mut vm = rune_vm! {
+        pub fn main(n) {
+            let count = 0;
+
+            loop {
+                count += 1;
+
+                if count == n {
+                    break count;
+                }
+            }
+        }
+    };
+
+    assert_eq!(vm.deadline_interval(), Vm::DEFAULT_DEADLINE_INTERVAL);
+    vm.set_deadline_interval(16);
+    assert_eq!(vm.deadline_interval(), 16);
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let value = vm
+        .call_with_deadline(["main"], (10,), deadline)
+        .expect("call within deadline");
+    assert_eq!(value.into_integer().expect("integer"), 10);
+
+    let deadline = Instant::now() + Duration::from_millis(10);
+    let error = vm
+        .call_with_deadline(["main"], (-1,), deadline)
+        .expect_err("call should exceed deadline");
+
+    assert!(
+        matches!(
+            error.into_unwound().0.into_kind(),
+            VmErrorKind::DeadlineExceeded
+        ),
+        "expected deadline exceeded error"
+    );
+
+    // NB: the deadline only applies to the call it was provided to.
+    let value = vm.call(["main"], (100,)).expect("call without deadline");
+    assert_eq!(value.into_integer().expect("integer"), 100);
+}
+
+#[test]
+fn test_deadline_native_closure() {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            [0].iter().map(|n| {
+                loop {
+                    n += 1;
+                }
+            }).collect::<Vec>()
+        }
+    };
+
+    vm.set_deadline_interval(16);
+
+    // NB: the closure is called from a native iterator adaptor, which must
+    // still respect the deadline of the calling virtual machine.
+    let error = vm
+        .call_with_deadline(["main"], (), Instant::now())
+        .expect_err("call should exceed deadline");
+
+    assert!(
+        matches!(
+            error.into_unwound().0.into_kind(),
+            VmErrorKind::DeadlineExceeded
+        ),
+        "expected deadline exceeded error"
+    );
+}
@@ -0,0 +1,132 @@
+use rune::runtime::{Protocol, Value, VmErrorKind};
+use rune::{Any, Context, ContextError, FromValue, Module, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+
+#[derive(Any, Clone)]
+struct Counter {
+    #[rune(get, set)]
+    count: i64,
+}
+
+#[derive(Any)]
+struct Opaque;
+
+fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Counter>()?;
+    module.function(&["Counter", "new"], || Counter { count: 0 })?;
+    module.inst_fn(Protocol::CLONE, Counter::clone)?;
+    module.ty::<Opaque>()?;
+    module.function(&["Opaque", "new"], || Opaque)?;
+    Ok(module)
+}
+
+fn vm(source: &str) -> rune::Result<Vm> {
+    let mut context = Context::with_default_modules()?;
+    context.install(&module()?)?;
+    let unit = build(&context, source)?;
+    Ok(Vm::new(Arc::new(context.runtime()), unit))
+}
+
+#[test]
+fn test_deep_clone_nested_object() -> rune::Result<()> {
+    let value: Value = rune! {
+        pub fn main() {
+            #{ name: "a", inner: #{ values: [1, 2], pair: (3, Some(4)) } }
+        }
+    };
+
+    let copy = value.deep_clone()?;
+
+    let modify: Value = rune! {
+        pub fn main() {
+            |object| {
+                object.name = "b";
+                object.inner.values.push(3);
+                object.inner.pair.0 = 5;
+            }
+        }
+    };
+
+    let modify = rune::runtime::Function::from_value(modify)?;
+    modify.call::<_, ()>((copy.clone(),))?;
+
+    let inspect: Value = rune! {
+        pub fn main() {
+            |object| (object.name, object.inner.values, object.inner.pair)
+        }
+    };
+
+    let inspect = rune::runtime::Function::from_value(inspect)?;
+
+    let original: (String, Vec<i64>, (i64, Option<i64>)) = inspect.call((value,))?;
+    assert_eq!(original, (String::from("a"), vec![1, 2], (3, Some(4))));
+
+    let copy: (String, Vec<i64>, (i64, Option<i64>)) = inspect.call((copy,))?;
+    assert_eq!(copy, (String::from("b"), vec![1, 2, 3], (5, Some(4))));
+    Ok(())
+}
+
+#[test]
+fn test_deep_clone_preserves_sharing() -> rune::Result<()> {
+    let value: Value = rune! {
+        pub fn main() {
+            let shared = [1];
+            let cycle = [shared, shared];
+            cycle.push(cycle);
+            cycle
+        }
+    };
+
+    let copy = value.deep_clone()?;
+
+    let check: Value = rune! {
+        pub fn main() {
+            |original, copy| {
+                copy[0].push(2);
+                (original[0].len(), copy[1].len(), copy[2][0].len())
+            }
+        }
+    };
+
+    let check = rune::runtime::Function::from_value(check)?;
+    let out: (usize, usize, usize) = check.call((value, copy))?;
+    assert_eq!(out, (1, 2, 2));
+    Ok(())
+}
+
+#[test]
+fn test_deep_clone_native() -> rune::Result<()> {
+    let mut vm = vm(r#"
+    pub fn main() {
+        let counter = Counter::new();
+        [counter, counter]
+    }
+    "#)?;
+
+    let value = vm.call(["main"], ())?;
+    let copy = vm.with(|| value.deep_clone())?;
+
+    let copy = Vec::<Value>::from_value(copy)?;
+    copy[0].clone().into_any()?.downcast_borrow_mut::<Counter>()?.count = 42;
+    assert_eq!(copy[1].clone().into_any()?.downcast_borrow_ref::<Counter>()?.count, 42);
+
+    let value = Vec::<Value>::from_value(value)?;
+    assert_eq!(value[0].clone().into_any()?.downcast_borrow_ref::<Counter>()?.count, 0);
+    Ok(())
+}
+
+#[test]
+fn test_deep_clone_missing_protocol() -> rune::Result<()> {
+    let mut vm = vm(r#"pub fn main() { [Opaque::new()] }"#)?;
+
+    let value = vm.call(["main"], ())?;
+    let error = vm.with(|| value.deep_clone()).unwrap_err();
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::MissingProtocol { protocol, .. } if protocol == Protocol::CLONE
+    ));
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use rune::{Context, FromValue, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+
+#[test]
+fn test_env_var() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = sources(
+        r#"
+        pub fn main() {
+            use std::env;
+
+            let name = match env::var("name") {
+                Some(name) => name,
+                None => "unknown",
+            };
+
+            (name, env::var("PATH"))
+        }
+        "#,
+    );
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_env([("name", "rune")])
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = <(String, Option<String>)>::from_value(vm.call(&["main"], ())?)?;
+
+    assert_eq!(output, (String::from("rune"), None));
+    Ok(())
+}
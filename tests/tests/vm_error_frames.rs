@@ -0,0 +1,67 @@
+use rune::runtime::Vm;
+use rune_tests::*;
+
+fn deep_recursion_error(vm: &mut Vm) -> rune::runtime::VmError {
+    let result = vm.execute(&["main"], ()).and_then(|mut e| e.complete());
+
+    match result {
+        Ok(value) => panic!("expected error, but got {:?}", value),
+        Err(error) => error,
+    }
+}
+
+#[test]
+fn test_error_frames_capped() {
+    let mut vm = rune_vm! {
+        fn recurse(n) {
+            if n == 0 {
+                panic("bottom");
+            }
+
+            recurse(n - 1)
+        }
+
+        pub fn main() {
+            recurse(200)
+        }
+    };
+
+    assert_eq!(vm.max_error_frames(), Vm::DEFAULT_MAX_ERROR_FRAMES);
+
+    let error = deep_recursion_error(&mut vm);
+    let (_, unwound) = error.as_unwound();
+    let (_, _, frames) = unwound.expect("expected unwound error");
+    assert_eq!(frames.len(), Vm::DEFAULT_MAX_ERROR_FRAMES);
+    assert_eq!(error.elided_frames(), 201 - Vm::DEFAULT_MAX_ERROR_FRAMES);
+
+    vm.set_max_error_frames(8);
+
+    let error = deep_recursion_error(&mut vm);
+    let (_, unwound) = error.as_unwound();
+    let (_, _, frames) = unwound.expect("expected unwound error");
+    assert_eq!(frames.len(), 8);
+    assert_eq!(error.elided_frames(), 201 - 8);
+}
+
+#[test]
+fn test_error_frames_not_elided() {
+    let mut vm = rune_vm! {
+        fn recurse(n) {
+            if n == 0 {
+                panic("bottom");
+            }
+
+            recurse(n - 1)
+        }
+
+        pub fn main() {
+            recurse(10)
+        }
+    };
+
+    let error = deep_recursion_error(&mut vm);
+    let (_, unwound) = error.as_unwound();
+    let (_, _, frames) = unwound.expect("expected unwound error");
+    assert_eq!(frames.len(), 11);
+    assert_eq!(error.elided_frames(), 0);
+}
@@ -69,3 +69,37 @@ fn test_function() {
     let value: Value = function.call(()).unwrap();
     assert!(matches!(value, Value::Integer(3)));
 }
+
+#[test]
+fn test_function_bind() {
+    let out: (i64, i64, i64) = rune! {
+        fn add(a, b) {
+            a + b
+        }
+
+        pub fn main() {
+            let add5 = add.bind(5);
+            let sub = |a, b, c| a - b - c;
+            let sub10 = sub.bind(10).bind(3);
+            (add5(1), [1, 2, 3].iter().map(add5).fold(0, |a, b| a + b), sub10(2))
+        }
+    };
+
+    assert_eq!(out, (6, 21, 5));
+
+    let function: Function = rune! {
+        fn add(a, b) {
+            a + b
+        }
+
+        pub fn main() { add }
+    };
+
+    let add5 = function.bind((5i64,)).unwrap();
+    assert_eq!(add5.call::<_, i64>((1i64,)).unwrap(), 6);
+    assert!(add5.call::<_, Value>((1i64, 2i64)).is_err());
+    assert!(add5.bind((1i64, 2i64)).is_err());
+
+    let add = add5.bind((1i64,)).unwrap();
+    assert_eq!(add.call::<_, i64>(()).unwrap(), 6);
+}
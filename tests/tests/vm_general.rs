@@ -426,6 +426,37 @@ fn test_break_label() {
     assert_eq!(out, 77);
 }
 
+#[test]
+fn test_break_label_value() {
+    let out: (i64, i64, i64) = rune! {
+        pub fn main() {
+            let a = 1;
+
+            let out = 'outer: loop {
+                let b = 2;
+
+                'inner: loop {
+                    let c = 3;
+
+                    for n in 0..10 {
+                        let d = 4;
+
+                        if n == 5 {
+                            break 'outer a + b + c + d + n;
+                        }
+                    }
+
+                    break 'inner;
+                }
+            };
+
+            let e = 10;
+            (out, a, e)
+        }
+    };
+    assert_eq!(out, (15, 1, 10));
+}
+
 #[test]
 fn test_string_concat() {
     let out: String = rune! {
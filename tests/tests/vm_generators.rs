@@ -1,3 +1,4 @@
+use rune::runtime::VmErrorKind::*;
 use rune_tests::*;
 
 #[test]
@@ -53,3 +54,92 @@ fn test_resume() {
     };
     assert_eq!(out, 6);
 }
+
+#[test]
+fn test_generator_adaptors() {
+    let out: Vec<i64> = rune! {
+        fn naturals() { let n = 0; loop { yield n; n += 1; } }
+
+        pub fn main() {
+            naturals().filter(|n| n % 2 == 0).map(|n| n * 10).take(3).collect::<Vec>()
+        }
+    };
+    assert_eq!(out, vec![0, 20, 40]);
+
+    let out: i64 = rune! {
+        fn foo() { yield 1; yield 2; yield 3; 42 }
+
+        pub fn main() {
+            let result = 0;
+
+            for value in foo().map(|n| n + 1) {
+                result += value;
+            }
+
+            result
+        }
+    };
+    assert_eq!(out, 9);
+
+    let out: (usize, Option<usize>) = rune! {
+        fn foo() { yield 1; }
+
+        pub fn main() {
+            foo().size_hint()
+        }
+    };
+    assert_eq!(out, (0, None));
+}
+
+#[test]
+fn test_generator_rev() {
+    let out: Vec<i64> = rune! {
+        fn foo() { yield 1; yield 2; }
+
+        pub fn main() {
+            foo().rev().collect::<Vec>()
+        }
+    };
+    assert_eq!(out, vec![2, 1]);
+
+    let out: Vec<i64> = rune! {
+        fn foo() { yield 1; yield 2; }
+
+        pub fn main() {
+            foo().iter().rev().collect::<Vec>()
+        }
+    };
+    assert_eq!(out, vec![2, 1]);
+}
+
+#[test]
+fn test_call_generator() {
+    let mut vm = rune_vm! {
+        pub fn main(n) {
+            yield n;
+            yield n * 2;
+            yield n * 3;
+        }
+
+        pub fn not_generator() {
+            1
+        }
+    };
+
+    let mut generator = vm
+        .call_generator(["main"], (2i64,))
+        .expect("generator to be called");
+
+    let mut values = Vec::new();
+
+    while let Some(value) = generator.next().expect("generator to resume") {
+        values.push(value.into_integer().expect("integer"));
+    }
+
+    assert_eq!(values, [2, 4, 6]);
+
+    let error = vm
+        .call_generator(["not_generator"], ())
+        .expect_err("not a generator");
+    assert!(matches!(error.into_kind(), NotGenerator { .. }));
+}
@@ -0,0 +1,172 @@
+use rune::runtime::{Object, Range, RangeLimits, VmErrorKind};
+use rune::{Any, Context, FromValue, Module, ToValue, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+
+#[test]
+fn test_global_object() -> rune::Result<()> {
+    let mut sources = sources(
+        r#"
+        pub fn main() {
+            let f = || env.config.name;
+            (env.config.level, f())
+        }
+        "#,
+    );
+
+    let unit = rune::prepare(&mut sources).with_globals(["env"]).build()?;
+
+    let mut config = Object::new();
+    config.insert(String::from("level"), 3i64.to_value()?);
+    config.insert(String::from("name"), "prod".to_value()?);
+
+    let mut env = Object::new();
+    env.insert(String::from("config"), config.to_value()?);
+
+    let mut vm = Vm::without_runtime(Arc::new(unit));
+    vm.set_global("env", env)?;
+
+    let output = <(i64, String)>::from_value(vm.call(&["main"], ())?)?;
+    assert_eq!(output, (3, String::from("prod")));
+    Ok(())
+}
+
+#[test]
+fn test_unset_global() -> rune::Result<()> {
+    let mut sources = sources(r#"pub fn main() { env }"#);
+    let unit = rune::prepare(&mut sources).with_globals(["env"]).build()?;
+
+    let mut vm = Vm::without_runtime(Arc::new(unit));
+    let error = vm.call(&["main"], ()).unwrap_err();
+
+    match error.into_unwound().0.into_kind() {
+        VmErrorKind::MissingGlobal { name } => assert_eq!(name, "env"),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_undeclared_global() -> rune::Result<()> {
+    let mut undeclared = sources(r#"pub fn main() { env }"#);
+    assert!(rune::prepare(&mut undeclared).build().is_err());
+
+    let mut sources = sources(r#"pub fn main() { 42 }"#);
+    let unit = rune::prepare(&mut sources).with_globals(["env"]).build()?;
+
+    let mut vm = Vm::without_runtime(Arc::new(unit));
+    vm.set_global("env", 1i64)?;
+
+    match vm.set_global("other", 2i64).unwrap_err().into_kind() {
+        VmErrorKind::UndeclaredGlobal { name } => assert_eq!(name, "other"),
+        kind => panic!("unexpected error: {:?}", kind),
+    }
+
+    assert!(vm.global("other").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_global_native_closure() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut sources =
+        sources(r#"pub fn main() { [1, 2].iter().map(|n| n + offset).collect::<Vec>() }"#);
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_globals(["offset"])
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    vm.set_global("offset", 10i64)?;
+
+    let output = std::vec::Vec::<i64>::from_value(vm.call(&["main"], ())?)?;
+    assert_eq!(output, [11, 12]);
+    Ok(())
+}
+
+#[test]
+fn test_global_is_shared() -> rune::Result<()> {
+    let mut sources = sources(
+        r#"
+        pub fn main() {
+            let first = env;
+            first.push_str(" world");
+            env
+        }
+        "#,
+    );
+
+    let context = Context::with_default_modules()?;
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_globals(["env"])
+        .build()?;
+
+    let value = String::from("hello").to_value()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    vm.set_global("env", value.clone())?;
+
+    let output = String::from_value(vm.call(&["main"], ())?)?;
+    assert_eq!(output, "hello world");
+    assert_eq!(String::from_value(value)?, "hello world");
+    Ok(())
+}
+
+#[test]
+fn test_global_native_value() -> rune::Result<()> {
+    #[derive(Any)]
+    struct Config {
+        #[rune(get)]
+        level: i64,
+    }
+
+    let mut module = Module::new();
+    module.ty::<Config>()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let mut sources = sources(
+        r#"
+        pub fn main() {
+            (config.level, range.start)
+        }
+        "#,
+    );
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_globals(["config", "range"])
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    vm.set_global("config", Config { level: 3 })?;
+    vm.set_global(
+        "range",
+        Range::new(Some(1i64.to_value()?), None, RangeLimits::HalfOpen),
+    )?;
+
+    let output = <(i64, Option<i64>)>::from_value(vm.call(&["main"], ())?)?;
+    assert_eq!(output, (3, Some(1)));
+    Ok(())
+}
+
+#[test]
+fn test_global_send_execute() -> rune::Result<()> {
+    let mut sources = sources(r#"pub fn main() { env }"#);
+    let unit = rune::prepare(&mut sources).with_globals(["env"]).build()?;
+
+    let mut vm = Vm::without_runtime(Arc::new(unit));
+    vm.set_global("env", 42i64)?;
+
+    match vm.send_execute(&["main"], ()) {
+        Err(error) => assert!(matches!(error.into_kind(), VmErrorKind::SendGlobals)),
+        Ok(..) => panic!("expected sending a virtual machine with globals to fail"),
+    }
+
+    Ok(())
+}
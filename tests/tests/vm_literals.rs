@@ -175,3 +175,56 @@ fn test_byte_string_literals() {
     );
     assert_eq!(out, b"a b"[..]);
 }
+
+#[test]
+fn test_suffixed_number_literals() {
+    let out: u8 = rune!(
+        pub fn main() {
+            255u8
+        }
+    );
+    assert_eq!(out, 255u8);
+
+    let out: i64 = rune!(
+        pub fn main() {
+            -128i8
+        }
+    );
+    assert_eq!(out, -128);
+
+    let out: i64 = rune!(
+        pub fn main() {
+            0xffu16
+        }
+    );
+    assert_eq!(out, 0xff);
+
+    let out: i64 = rune!(
+        pub fn main() {
+            4294967295u32
+        }
+    );
+    assert_eq!(out, 4294967295);
+
+    let out: f64 = rune!(
+        pub fn main() {
+            3f64
+        }
+    );
+    assert_eq!(out, 3.0);
+
+    let out: f64 = rune!(
+        pub fn main() {
+            0.1f32
+        }
+    );
+    assert_eq!(out, 0.1f32 as f64);
+
+    let out: u8 = rune!(
+        const VALUE = 42u8;
+        pub fn main() {
+            VALUE
+        }
+    );
+    assert_eq!(out, 42u8);
+}
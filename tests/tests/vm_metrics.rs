@@ -0,0 +1,81 @@
+use rune::runtime::{Metrics, VmErrorKind};
+use rune::FromValue;
+use rune_tests::*;
+
+fn metered_sum(n: i64) -> (i64, Metrics) {
+    let mut vm = rune_vm! {
+        pub fn main(n) {
+            let sum = 0;
+
+            for i in 0..n {
+                sum += i;
+            }
+
+            sum
+        }
+    };
+
+    let (output, metrics) = vm.call_metered(&["main"], (n,));
+    let output = output.expect("call to succeed").into_integer().unwrap();
+    (output, metrics)
+}
+
+#[test]
+fn test_metered_loop() {
+    let (output, small) = metered_sum(10);
+    assert_eq!(output, 45);
+
+    let (output, large) = metered_sum(110);
+    assert_eq!(output, 5995);
+
+    // Every loop iteration executes the same instructions, so the difference
+    // is a whole number of iterations each with a handful of instructions.
+    let delta = large.instructions - small.instructions;
+    assert_eq!(delta % 100, 0, "{:?} {:?}", small, large);
+    assert!((3..=20).contains(&(delta / 100)), "{:?} {:?}", small, large);
+
+    // The loop doesn't grow the stack.
+    assert!(small.peak_stack_depth > 0);
+    assert_eq!(small.peak_stack_depth, large.peak_stack_depth);
+}
+
+#[test]
+fn test_metered_error() {
+    let mut vm = rune_vm! {
+        pub fn main() {
+            let a = 1;
+            let b = 2;
+            panic("boom");
+        }
+    };
+
+    let (output, metrics) = vm.call_metered(&["main"], ());
+
+    let error = output.expect_err("call to fail");
+    assert!(matches!(error.into_unwound().0.into_kind(), VmErrorKind::Panic { .. }));
+    assert!(metrics.instructions > 0);
+    assert!(metrics.peak_stack_depth >= 2);
+}
+
+#[test]
+fn test_metered_native_closure() {
+    let mut vm = rune_vm! {
+        pub fn plain() {
+            [1, 2, 3].iter().collect::<Vec>()
+        }
+
+        pub fn main() {
+            [1, 2, 3].iter().map(|x| x + 1).collect::<Vec>()
+        }
+    };
+
+    let (output, metrics) = vm.call_metered(&["main"], ());
+    let output = std::vec::Vec::<i64>::from_value(output.expect("call to succeed")).unwrap();
+    assert_eq!(output, [2, 3, 4]);
+
+    // NB: instructions executed by the closure, which is called from a native
+    // iterator adaptor, are counted as well.
+    let (output, plain) = vm.call_metered(&["plain"], ());
+    assert!(output.is_ok());
+    assert!(metrics.instructions > plain.instructions + 3);
+}
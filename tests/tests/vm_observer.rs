@@ -0,0 +1,102 @@
+use rune::ast::Span;
+use rune::runtime::{Inst, VmObserver};
+use rune::{Context, FromValue, Vm};
+use rune_tests::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone)]
+enum Event {
+    Instruction(Inst, Option<Span>, usize),
+    Enter(usize),
+    Exit(usize),
+}
+
+#[derive(Clone, Default)]
+struct Recording(Arc<Mutex<Vec<Event>>>);
+
+impl VmObserver for Recording {
+    fn instruction(&mut self, _: usize, inst: &Inst, span: Option<Span>, depth: usize) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(Event::Instruction(*inst, span, depth));
+    }
+
+    fn enter_call(&mut self, _: usize, depth: usize) {
+        self.0.lock().unwrap().push(Event::Enter(depth));
+    }
+
+    fn exit_call(&mut self, _: usize, depth: usize) {
+        self.0.lock().unwrap().push(Event::Exit(depth));
+    }
+}
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    add(1, 2)
+}
+"#;
+
+#[test]
+fn test_observer_events() -> rune::Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, SOURCE)?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+
+    let recording = Recording::default();
+    vm.set_observer(recording.clone());
+
+    let out = i64::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, 3);
+
+    let events = recording.0.lock().unwrap().clone();
+
+    let depths = events
+        .iter()
+        .filter_map(|e| match e {
+            Event::Instruction(_, _, depth) => Some(*depth),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(depths.first(), Some(&0));
+    assert_eq!(depths.last(), Some(&0));
+    assert!(depths.contains(&1));
+
+    let enter = events.iter().position(|e| matches!(e, Event::Enter(1)));
+    let exit = events.iter().position(|e| matches!(e, Event::Exit(0)));
+    assert!(enter.is_some() && enter < exit);
+
+    let calls = events
+        .iter()
+        .filter(|e| matches!(e, Event::Enter(..) | Event::Exit(..)))
+        .count();
+    assert_eq!(calls, 2);
+
+    // Every instruction inside of `add` happens between entering and exiting.
+    for (index, event) in events.iter().enumerate() {
+        if let Event::Instruction(inst, span, depth) = event {
+            let span = span.expect("instruction should have a span");
+            assert!(span.end.into_usize() <= SOURCE.len());
+
+            if *depth == 1 {
+                assert!(Some(index) > enter && Some(index) < exit);
+            }
+
+            if let Inst::Op { .. } = inst {
+                assert_eq!(*depth, 1);
+                assert_eq!(&SOURCE[span.range()], "a + b");
+            }
+        }
+    }
+
+    vm.clear_observer();
+    recording.0.lock().unwrap().clear();
+    vm.call(["main"], ())?;
+    assert!(recording.0.lock().unwrap().is_empty());
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use futures_executor::block_on;
+use rune::{Context, Vm};
+use rune_tests::*;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Buffer {
+    fn take(&self) -> String {
+        String::from_utf8(std::mem::take(&mut *self.0.lock().unwrap())).unwrap()
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn vm(source: &str) -> rune::Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, source)?;
+    Ok(Vm::new(Arc::new(context.runtime()), unit))
+}
+
+#[test]
+fn test_capture_println() -> rune::Result<()> {
+    let mut vm = vm(r#"
+    pub fn main() {
+        print("Hello");
+        println(" World");
+        dbg(1, "two");
+    }
+    "#)?;
+
+    let buffer = Buffer::default();
+    vm.set_output(buffer.clone());
+    vm.call(["main"], ())?;
+
+    assert_eq!(buffer.take(), "Hello World\n1\n\"two\"\n");
+    Ok(())
+}
+
+#[test]
+fn test_capture_nested_output() -> rune::Result<()> {
+    let mut vm = vm(r#"
+    async fn hello(n) {
+        println(`async ${n}`);
+    }
+
+    pub async fn main() {
+        [1, 2].iter().map(|n| println(`closure ${n}`)).collect::<Vec>();
+        hello(3).await;
+    }
+    "#)?;
+
+    let buffer = Buffer::default();
+    vm.set_output(buffer.clone());
+    block_on(vm.async_call(["main"], ()))?;
+
+    assert_eq!(buffer.take(), "closure 1\nclosure 2\nasync 3\n");
+    Ok(())
+}
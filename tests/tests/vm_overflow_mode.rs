@@ -0,0 +1,84 @@
+use rune::runtime::{VmErrorKind, VmOverflowMode};
+use rune::{Context, FromValue, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+pub fn add(a, b) { a + b }
+pub fn sub(a, b) { a - b }
+pub fn mul(a, b) { a * b }
+pub fn neg(a) { -a }
+pub fn add_assign(a, b) { a += b; a }
+pub fn map_add(a, b) { [a].iter().map(|a| a + b).collect::<Vec>() }
+"#;
+
+fn vm(mode: VmOverflowMode) -> rune::Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, SOURCE)?;
+    Ok(Vm::new(Arc::new(context.runtime()), unit).with_overflow_mode(mode))
+}
+
+#[test]
+fn test_checked() -> rune::Result<()> {
+    let mut vm = vm(VmOverflowMode::Checked)?;
+    assert_eq!(vm.overflow_mode(), VmOverflowMode::Checked);
+
+    let error = vm.call(["add"], (i64::MAX, 1i64)).unwrap_err();
+    assert!(matches!(error.into_unwound().0.into_kind(), VmErrorKind::Overflow));
+
+    let error = vm.call(["sub"], (i64::MIN, 1i64)).unwrap_err();
+    assert!(matches!(error.into_unwound().0.into_kind(), VmErrorKind::Underflow));
+
+    let error = vm.call(["neg"], (i64::MIN,)).unwrap_err();
+    assert!(matches!(error.into_unwound().0.into_kind(), VmErrorKind::Overflow));
+
+    let error = vm.call(["add_assign"], (i64::MAX, 1i64)).unwrap_err();
+    assert!(matches!(error.into_unwound().0.into_kind(), VmErrorKind::Overflow));
+
+    let out = i64::from_value(vm.call(["neg"], (i64::MAX,))?)?;
+    assert_eq!(out, -i64::MAX);
+    Ok(())
+}
+
+#[test]
+fn test_wrapping() -> rune::Result<()> {
+    let mut vm = vm(VmOverflowMode::Wrapping)?;
+
+    let out = i64::from_value(vm.call(["neg"], (i64::MIN,))?)?;
+    assert_eq!(out, i64::MIN);
+
+    let out = i64::from_value(vm.call(["add"], (i64::MAX, 1i64))?)?;
+    assert_eq!(out, i64::MIN);
+
+    let out = i64::from_value(vm.call(["sub"], (i64::MIN, 1i64))?)?;
+    assert_eq!(out, i64::MAX);
+
+    let out = i64::from_value(vm.call(["mul"], (i64::MAX, 2i64))?)?;
+    assert_eq!(out, -2);
+
+    let out = i64::from_value(vm.call(["add_assign"], (i64::MAX, 1i64))?)?;
+    assert_eq!(out, i64::MIN);
+    Ok(())
+}
+
+#[test]
+fn test_saturating() -> rune::Result<()> {
+    let mut vm = vm(VmOverflowMode::Saturating)?;
+
+    let out = i64::from_value(vm.call(["add"], (i64::MAX, 1i64))?)?;
+    assert_eq!(out, i64::MAX);
+
+    let out = i64::from_value(vm.call(["sub"], (i64::MIN, 1i64))?)?;
+    assert_eq!(out, i64::MIN);
+
+    let out = i64::from_value(vm.call(["mul"], (i64::MIN, 2i64))?)?;
+    assert_eq!(out, i64::MIN);
+
+    let out = i64::from_value(vm.call(["neg"], (i64::MIN,))?)?;
+    assert_eq!(out, i64::MAX);
+
+    // NB: closures called from native functions use the same mode.
+    let out = Vec::<i64>::from_value(vm.call(["map_add"], (i64::MAX, 1i64))?)?;
+    assert_eq!(out, [i64::MAX]);
+    Ok(())
+}
@@ -41,3 +41,74 @@ fn test_match_binding() {
     };
     assert_eq!(out, true);
 }
+
+#[test]
+fn test_let_else() {
+    let out: i64 = rune! {
+        fn unwrap_or_zero(opt) {
+            let Some(x) = opt else {
+                let zero = 0;
+                return zero;
+            };
+
+            let y = x + 1;
+            y
+        }
+
+        pub fn main() {
+            unwrap_or_zero(Some(41)) + unwrap_or_zero(None)
+        }
+    };
+    assert_eq!(out, 42);
+}
+
+#[test]
+fn test_let_else_break() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let values = [Some(1), Some(2), None, Some(4)];
+            let sum = 0;
+
+            for value in values {
+                let Some(n) = value else { break; };
+                sum += n;
+            }
+
+            sum
+        }
+    };
+    assert_eq!(out, 3);
+}
+
+#[test]
+fn test_let_else_branches() {
+    let out: i64 = rune! {
+        fn sign(opt, negative) {
+            let Some(x) = opt else {
+                if negative {
+                    return -1;
+                } else {
+                    return 1;
+                }
+            };
+
+            x
+        }
+
+        fn unwrap_or(opt, fallback) {
+            let Some(x) = opt else {
+                match fallback {
+                    Some(fallback) => return fallback,
+                    None => panic("no default"),
+                }
+            };
+
+            x
+        }
+
+        pub fn main() {
+            sign(Some(10), true) + sign(None, true) + sign(None, false) + unwrap_or(None, Some(100))
+        }
+    };
+    assert_eq!(out, 110);
+}
@@ -0,0 +1,98 @@
+use rune::runtime::{Value, VmError};
+use rune::{Context, FromValue, Module, Vm};
+use rune_tests::*;
+use std::cell::RefCell;
+use std::sync::Arc;
+
+thread_local! {
+    static VM: RefCell<Option<Vm>> = RefCell::new(None);
+    static OTHER: RefCell<Option<Vm>> = RefCell::new(None);
+}
+
+/// Host function which tries to call back into the virtual machine stored in
+/// [VM], which can't be done while it's executing.
+fn reenter() -> Result<Value, VmError> {
+    VM.with(|vm| match vm.try_borrow_mut() {
+        Ok(mut vm) => vm.as_mut().expect("missing vm").call(["inner"], ()),
+        Err(..) => Ok(Value::from(String::from("busy"))),
+    })
+}
+
+/// Host function which runs the virtual machine stored in [OTHER].
+fn into_other() -> Result<Value, VmError> {
+    OTHER.with(|vm| {
+        vm.borrow_mut()
+            .as_mut()
+            .expect("missing vm")
+            .call(["other"], ())
+    })
+}
+
+fn context() -> rune::Result<Context> {
+    let mut module = Module::new();
+    module.function(&["reenter"], reenter)?;
+    module.function(&["into_other"], into_other)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    Ok(context)
+}
+
+#[test]
+fn test_reentrancy() -> rune::Result<()> {
+    let context = context()?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub fn inner() { 42 }
+        pub fn main() { reenter() }
+        "#,
+    )?;
+
+    VM.with(|vm| *vm.borrow_mut() = Some(Vm::new(Arc::new(context.runtime()), unit)));
+
+    // NB: the virtual machine is borrowed for as long as it's executing, so
+    // the host function can't call back into it.
+    let value = VM.with(|vm| vm.borrow_mut().as_mut().unwrap().call(["main"], ()))?;
+    assert_eq!(String::from_value(value)?, "busy");
+
+    // Once the call has completed it can be called again.
+    let value = reenter()?;
+    assert_eq!(i64::from_value(value)?, 42);
+
+    VM.with(|vm| vm.borrow_mut().take());
+    Ok(())
+}
+
+#[test]
+fn test_nested_reentrancy() -> rune::Result<()> {
+    let context = context()?;
+
+    let unit = build(
+        &context,
+        r#"
+        pub fn inner() { 42 }
+        pub fn main() { into_other() }
+        pub fn other() { (reenter(), 1) }
+        "#,
+    )?;
+
+    let runtime = Arc::new(context.runtime());
+    VM.with(|vm| *vm.borrow_mut() = Some(Vm::new(runtime.clone(), unit.clone())));
+    OTHER.with(|vm| *vm.borrow_mut() = Some(Vm::new(runtime, unit)));
+
+    // NB: the first virtual machine calls into the second one through a host
+    // function, which can run while the first one is executing, but can't
+    // call back into the first one.
+    let value = VM.with(|vm| vm.borrow_mut().as_mut().unwrap().call(["main"], ()))?;
+    let (busy, one) = <(String, i64)>::from_value(value)?;
+    assert_eq!((busy.as_str(), one), ("busy", 1));
+
+    let value = reenter()?;
+    assert_eq!(i64::from_value(value)?, 42);
+
+    VM.with(|vm| vm.borrow_mut().take());
+    OTHER.with(|vm| vm.borrow_mut().take());
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use rune::compile::CompileErrorKind::QueryError;
+use rune::compile::IrErrorKind;
+use rune::query::QueryErrorKind::IrError;
+use rune::runtime::VmErrorKind::*;
+use rune::span;
+use rune_tests::*;
+
+#[test]
+fn test_repeat() {
+    let out: Vec<i64> = rune!(pub fn main() { [0; 4] });
+    assert_eq!(out, vec![0, 0, 0, 0]);
+
+    let out: Vec<i64> = rune!(pub fn main() { [1; 0] });
+    assert_eq!(out, Vec::<i64>::new());
+
+    let out: Vec<String> = rune! {
+        const N = 2;
+
+        pub fn main() {
+            let count = N + 1;
+            ["a"; count]
+        }
+    };
+    assert_eq!(out, vec!["a", "a", "a"]);
+
+    let out: (usize, i64) = rune! {
+        pub fn main() {
+            let v = [[1, 2]; 2];
+            (v.len(), v[1][1])
+        }
+    };
+    assert_eq!(out, (2, 2));
+}
+
+#[test]
+fn test_const_repeat() {
+    let out: Vec<i64> = rune! {
+        const V = [0; 4];
+        pub fn main() { V }
+    };
+    assert_eq!(out, vec![0, 0, 0, 0]);
+
+    let out: Vec<Vec<String>> = rune! {
+        const N = 1 + 1;
+        const V = [["x"; N]; 3];
+        pub fn main() { V }
+    };
+    assert_eq!(out, vec![vec!["x", "x"]; 3]);
+}
+
+#[test]
+fn test_repeat_bad_count() {
+    assert_vm_error!(
+        r#"pub fn main() { let n = -1; [0; n] }"#,
+        ValueToIntegerCoercionError { from, to: "usize" } => {
+            assert_eq!(from.to_string(), "-1");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let n = 1.0; [0; n] }"#,
+        Expected { expected, actual } => {
+            assert_eq!(expected.to_string(), "integer");
+            assert_eq!(actual.to_string(), "float");
+        }
+    );
+
+    assert_compile_error! {
+        r#"const V = [0; 0 - 1]; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::NotInteger { value } } } => {
+            assert_eq!(value.to_string(), "-1");
+            assert_eq!(span, span!(14, 19));
+        }
+    };
+
+    assert_compile_error! {
+        r#"const V = [0; "two"]; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::Expected { .. } } } => {
+            assert_eq!(span, span!(14, 19));
+        }
+    };
+}
+
+#[test]
+fn test_repeat_distinct_elements() {
+    let out: Vec<Vec<i64>> = rune! {
+        pub fn main() {
+            let v = [[]; 3];
+            v[0].push(1);
+            v
+        }
+    };
+    assert_eq!(out, vec![vec![1], vec![], vec![]]);
+
+    let out: Vec<Vec<i64>> = rune! {
+        const V = [[1]; 2];
+
+        pub fn main() {
+            let v = V;
+            v[1].push(2);
+            v
+        }
+    };
+    assert_eq!(out, vec![vec![1], vec![1, 2]]);
+}
+
+#[test]
+fn test_repeat_native_elements() {
+    // NB: `HashMap` doesn't implement the clone protocol, so it's shared
+    // between the elements, including when nested in another value.
+    let out: (i64, i64, i64) = rune! {
+        use std::collections::HashMap;
+
+        pub fn main() {
+            let v = [HashMap::new(); 2];
+            v[0].insert("a", 1);
+
+            let nested = [[HashMap::new()]; 2];
+            nested[0][0].insert("b", 2);
+            nested[0].push(HashMap::new());
+
+            (v[1].len(), nested[1][0].len(), nested[1].len())
+        }
+    };
+    assert_eq!(out, (1, 1, 1));
+}
+
+#[test]
+fn test_repeat_too_large() {
+    assert_vm_error!(
+        r#"pub fn main() { let n = 1 << 62; [0; n].len() }"#,
+        CapacityOverflow { capacity } => {
+            assert_eq!(capacity, 1 << 62);
+        }
+    );
+
+    assert_compile_error! {
+        r#"const V = [0; 1 << 62]; pub fn main() { V }"#,
+        span, QueryError { error: IrError { error: IrErrorKind::BudgetExceeded } } => {
+            assert_eq!(span, span!(10, 22));
+        }
+    };
+}
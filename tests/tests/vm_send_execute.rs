@@ -0,0 +1,121 @@
+use futures_executor::block_on;
+use rune::runtime::{Args, Object, Shared, Value, VmErrorKind, VmSendExecution};
+use rune::{Context, FromValue, Vm};
+use rune_tests::*;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::thread;
+
+const SOURCE: &str = r#"
+pub fn main(a, b) { a + b.len() }
+pub async fn delayed(a) { a * 2 }
+pub fn object(name, values) { let total = 0; for v in values { total += v; } #{ name, total } }
+"#;
+
+fn vm() -> rune::Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, SOURCE)?;
+    Ok(Vm::new(Arc::new(context.runtime()), unit))
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+/// Tests if arguments satisfy the bounds of [Vm::send_execute], falling back
+/// to the trait constant if they don't.
+struct SendArgs<A>(PhantomData<A>);
+
+trait Rejected {
+    const ACCEPTED: bool = false;
+}
+
+impl<A> Rejected for SendArgs<A> {}
+
+impl<A> SendArgs<A>
+where
+    A: Send + Args,
+{
+    const ACCEPTED: bool = true;
+}
+
+#[test]
+fn test_send_execute_thread() -> rune::Result<()> {
+    let vm = vm()?;
+
+    let execution = vm
+        .clone()
+        .send_execute(["main"], (40i64, String::from("hi")))?;
+    assert_send(&execution);
+
+    let output = thread::spawn(move || -> rune::Result<i64> {
+        Ok(i64::from_value(execution.complete()?)?)
+    })
+    .join()
+    .expect("thread panicked")?;
+
+    assert_eq!(output, 42);
+
+    let executions = (0..4i64)
+        .map(|n| vm.clone().send_execute(["delayed"], (n,)))
+        .collect::<Result<Vec<VmSendExecution>, _>>()?;
+
+    let handles = executions
+        .into_iter()
+        .map(|execution| {
+            thread::spawn(move || -> rune::Result<i64> {
+                Ok(i64::from_value(block_on(execution.async_complete())?)?)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut outputs = Vec::new();
+
+    for handle in handles {
+        outputs.push(handle.join().expect("thread panicked")?);
+    }
+
+    assert_eq!(outputs, [0, 2, 4, 6]);
+    Ok(())
+}
+
+#[test]
+fn test_send_execute_missing_function() -> rune::Result<()> {
+    let error = match vm()?.send_execute(["missing"], ()) {
+        Ok(..) => panic!("expected missing function error"),
+        Err(error) => error,
+    };
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::MissingEntry { .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_send_execute_returns_value() -> rune::Result<()> {
+    let vm = vm()?;
+    let execution = vm.send_execute(["object"], (String::from("sum"), vec![1i64, 2, 3]))?;
+
+    let (name, total) = thread::spawn(move || -> rune::Result<(String, i64)> {
+        let object = Object::from_value(execution.complete()?)?;
+        let name = String::from_value(object.get("name").expect("missing name").clone())?;
+        let total = i64::from_value(object.get("total").expect("missing total").clone())?;
+        Ok((name, total))
+    })
+    .join()
+    .expect("thread panicked")?;
+
+    assert_eq!(name, "sum");
+    assert_eq!(total, 6);
+    Ok(())
+}
+
+#[test]
+fn test_send_execute_rejects_shared() {
+    assert!(SendArgs::<(i64, String)>::ACCEPTED);
+    assert!(SendArgs::<(std::vec::Vec<i64>,)>::ACCEPTED);
+    assert!(!SendArgs::<(Shared<String>,)>::ACCEPTED);
+    assert!(!SendArgs::<(i64, Value)>::ACCEPTED);
+    assert!(!SendArgs::<(Object,)>::ACCEPTED);
+}
@@ -63,3 +63,22 @@ fn test_resume() {
     };
     assert_eq!(out, 6);
 }
+
+#[test]
+fn test_stream_adaptors() {
+    let out: Vec<i64> = rune! {
+        async fn foo() { yield 1; yield 2; yield 3; yield 4; }
+
+        pub async fn main() {
+            let stream = foo().filter(|n| n % 2 == 0).map(|n| n * 10);
+            let result = [];
+
+            while let Some(value) = stream.next().await {
+                result.push(value);
+            }
+
+            result
+        }
+    };
+    assert_eq!(out, vec![20, 40]);
+}
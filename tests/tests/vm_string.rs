@@ -0,0 +1,133 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_string_chars() {
+    let out: Vec<char> = rune!(pub fn main() { "aö€😀".chars().collect::<Vec>() });
+    assert_eq!(out, vec!['a', 'ö', '€', '😀']);
+
+    let out: Vec<char> = rune!(pub fn main() { "aö€".chars().rev().collect::<Vec>() });
+    assert_eq!(out, vec!['€', 'ö', 'a']);
+}
+
+#[test]
+fn test_string_char_indices() {
+    let out: Vec<(usize, char)> = rune! {
+        pub fn main() {
+            "aö€😀".char_indices().collect::<Vec>()
+        }
+    };
+
+    assert_eq!(out, vec![(0, 'a'), (1, 'ö'), (3, '€'), (6, '😀')]);
+}
+
+#[test]
+fn test_string_bytes() {
+    let out: Vec<u8> = rune!(pub fn main() { "aö".bytes().collect::<Vec>() });
+    assert_eq!(out, vec![b'a', 0xc3, 0xb6]);
+
+    let out: (usize, usize, usize) = rune! {
+        pub fn main() {
+            let s = "aö€😀";
+            (s.bytes().count(), s.len(), s.chars().count())
+        }
+    };
+
+    assert_eq!(out, (10, 10, 4));
+}
+
+#[test]
+fn test_string_mutation() {
+    let out: String = rune! {
+        pub fn main() {
+            let s = String::new();
+            s.push('a');
+            s.push_str("cd");
+            s.insert(1, 'b');
+            s.insert_str(0, "€");
+            s.push('😀');
+            let removed = s.remove(3);
+            s.push(removed);
+            s
+        }
+    };
+
+    assert_eq!(out, "€bcd😀a");
+}
+
+#[test]
+fn test_string_bad_char_boundary() {
+    assert_vm_error!(
+        r#"pub fn main() { let s = String::from_str("€"); s.insert(1, 'a') }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "index outside of character boundary");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let s = String::from_str("aö"); s.remove(2) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "index outside of character boundary");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let s = String::from_str("ab"); s.insert(3, 'c') }"#,
+        OutOfRange { index, len } => {
+            assert_eq!(index.to_string(), "3");
+            assert_eq!(len.to_string(), "3");
+        }
+    );
+}
+
+#[test]
+fn test_string_parse() {
+    let n: i64 = rune! {
+        pub fn main() {
+            "42".parse::<int>()?
+        }
+    };
+    assert_eq!(n, 42);
+
+    let n: f64 = rune! {
+        pub fn main() {
+            "3.14".parse::<float>()?
+        }
+    };
+    assert_eq!(n, 3.14);
+
+    let n: i64 = rune! {
+        pub fn main() {
+            let s = String::from_str("-7");
+            s.parse::<int>()?
+        }
+    };
+    assert_eq!(n, -7);
+
+    let out: (String, String, String) = rune! {
+        pub fn main() {
+            match "4x2".parse::<int>() {
+                Err(e) => (e.input, e.expected, format!("{}", e)),
+                Ok(..) => panic("expected parse to fail"),
+            }
+        }
+    };
+    assert_eq!(
+        out,
+        (
+            String::from("4x2"),
+            String::from("int"),
+            String::from("failed to parse `4x2` as int")
+        )
+    );
+
+    let out: (String, String) = rune! {
+        pub fn main() {
+            match "pi".parse::<float>() {
+                Err(e) => (e.input, e.expected),
+                Ok(..) => panic("expected parse to fail"),
+            }
+        }
+    };
+    assert_eq!(out, (String::from("pi"), String::from("float")));
+}
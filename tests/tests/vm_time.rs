@@ -0,0 +1,94 @@
+use rune::runtime::VmErrorKind;
+use rune::{FromValue, Module, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[test]
+fn test_duration_round_trip() -> rune::Result<()> {
+    let mut module = Module::new();
+    module.function(&["double"], |d: Duration| d * 2)?;
+
+    let mut context = rune_modules::default_context()?;
+    context.install(&module)?;
+
+    let mut sources = sources(
+        r#"
+        pub fn main(input) {
+            let output = double(input);
+            assert_eq!(output.as_millis(), 3000);
+            (output, double(time::Duration::from_millis(250)))
+        }
+        "#,
+    );
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = vm.call(&["main"], (Duration::from_millis(1500),))?;
+    let (a, b): (Duration, Duration) = FromValue::from_value(output)?;
+    assert_eq!(a, Duration::from_secs(3));
+    assert_eq!(b, Duration::from_millis(500));
+    Ok(())
+}
+
+#[test]
+fn test_system_time_argument() -> rune::Result<()> {
+    let context = rune_modules::default_context()?;
+
+    let mut sources = sources(
+        r#"
+        pub fn main(time) {
+            time.unix_timestamp()
+        }
+        "#,
+    );
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let time = UNIX_EPOCH + Duration::from_secs(1_000_000);
+    let output: i64 = FromValue::from_value(vm.call(&["main"], (time,))?)?;
+    assert_eq!(output, 1_000_000);
+
+    let before = UNIX_EPOCH - Duration::from_secs(60);
+    let output: i64 = FromValue::from_value(vm.call(&["main"], (before,))?)?;
+    assert_eq!(output, -60);
+
+    let output = vm.call(&["main"], (SystemTime::now(),))?;
+    assert!(i64::from_value(output)? > 1_000_000);
+    Ok(())
+}
+
+#[test]
+fn test_duration_as_millis_overflow() -> rune::Result<()> {
+    let context = rune_modules::default_context()?;
+
+    let mut sources = sources(
+        r#"
+        pub fn main(duration) {
+            duration.as_millis()
+        }
+        "#,
+    );
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+
+    let output: u64 = FromValue::from_value(vm.call(&["main"], (Duration::from_secs(2),))?)?;
+    assert_eq!(output, 2000);
+
+    let error = vm.call(&["main"], (Duration::MAX,)).unwrap_err();
+    assert!(matches!(
+        error.into_unwound().0.into_kind(),
+        VmErrorKind::Overflow
+    ));
+    Ok(())
+}
@@ -12,3 +12,25 @@ fn test_mutate_tuples() {
     "# };
     assert_eq!(out, "Now You Don't !");
 }
+
+#[test]
+fn test_tuple_len() {
+    let out: (usize, usize, bool) = rune! {
+        pub fn main() {
+            let t = (1, "two", 3.0);
+            (t.len(), (1,).len(), t.is_empty())
+        }
+    };
+    assert_eq!(out, (3, 1, false));
+}
+
+#[test]
+fn test_tuple_get() {
+    let out: (Option<i64>, Option<String>, Option<f64>, Option<i64>) = rune! {
+        pub fn main() {
+            let t = (1, "two", 3.0);
+            (t.get(0), t.get(1), t.get(2), t.get(3))
+        }
+    };
+    assert_eq!(out, (Some(1), Some(String::from("two")), Some(3.0), None));
+}
@@ -0,0 +1,110 @@
+use rune::runtime::{Protocol, VmError, VmErrorKind};
+use rune::{Any, Context, ContextError, FromValue, Module, Vm};
+use rune_tests::*;
+use std::sync::Arc;
+
+#[derive(Any)]
+struct Tagged {
+    tag: String,
+}
+
+impl Tagged {
+    fn new(tag: &str) -> Self {
+        Self { tag: tag.into() }
+    }
+
+    fn type_name(&self) -> String {
+        format!("Tagged::{}", self.tag)
+    }
+}
+
+#[derive(Any)]
+struct Plain;
+
+#[derive(Any)]
+struct Broken;
+
+fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+    module.ty::<Tagged>()?;
+    module.function(&["Tagged", "new"], Tagged::new)?;
+    module.inst_fn(Protocol::INTO_TYPE_NAME, Tagged::type_name)?;
+    module.ty::<Plain>()?;
+    module.function(&["Plain", "new"], || Plain)?;
+    module.ty::<Broken>()?;
+    module.function(&["Broken", "new"], || Broken)?;
+    module.inst_fn(Protocol::INTO_TYPE_NAME, |_: &Broken| -> Result<String, VmError> {
+        Err(VmError::panic("no name"))
+    })?;
+    module.function(&["double"], |value: i64| value * 2)?;
+    Ok(module)
+}
+
+fn vm(source: &str) -> rune::Result<Vm> {
+    let mut context = Context::with_default_modules()?;
+    context.install(&module()?)?;
+    let unit = build(&context, source)?;
+    Ok(Vm::new(Arc::new(context.runtime()), unit))
+}
+
+#[test]
+fn test_dynamic_type_name() -> rune::Result<()> {
+    let mut vm = vm(r#"
+    pub fn main() {
+        let a = std::any::type_name_of_val(Tagged::new("circle"));
+        let b = std::any::type_name_of_val(Plain::new());
+        (a, b)
+    }
+    "#)?;
+
+    let out = <(String, String)>::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, (String::from("Tagged::circle"), String::from("Plain")));
+    Ok(())
+}
+
+#[test]
+fn test_dynamic_type_name_in_errors() -> rune::Result<()> {
+    // NB: errors fall back to the registered name of the type if the type name
+    // handler is missing or fails.
+    for (source, name) in [
+        (r#"pub fn main() { double(Tagged::new("square")) }"#, "Tagged::square"),
+        (r#"pub fn main() { double(Plain::new()) }"#, "Plain"),
+        (r#"pub fn main() { double(Broken::new()) }"#, "Broken"),
+    ] {
+        let mut vm = vm(source)?;
+        let (error, _) = vm.call(["main"], ()).unwrap_err().into_unwound();
+
+        match error.into_kind() {
+            VmErrorKind::BadArgument { error, arg: 0 } => {
+                assert_eq!(
+                    error.to_string(),
+                    format!("expected `integer`, but found `{}`", name)
+                );
+            }
+            kind => panic!("expected bad argument but was {:?}", kind),
+        }
+    }
+
+    let mut vm = vm(r#"pub fn main() { Broken::new() + 1 }"#)?;
+    let (error, _) = vm.call(["main"], ()).unwrap_err().into_unwound();
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::UnsupportedBinaryOperation { .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_type_name_handler_error() -> rune::Result<()> {
+    let mut vm = vm(r#"pub fn main() { std::any::type_name_of_val(Broken::new()) }"#)?;
+    let (error, _) = vm.call(["main"], ()).unwrap_err().into_unwound();
+
+    match error.into_kind() {
+        VmErrorKind::Panic { reason } => assert_eq!(reason.to_string(), "no name"),
+        kind => panic!("expected panic but was {:?}", kind),
+    }
+
+    Ok(())
+}
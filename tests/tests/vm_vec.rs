@@ -0,0 +1,125 @@
+use rune::runtime::VmErrorKind::*;
+use rune_tests::*;
+
+#[test]
+fn test_vec_with_capacity() {
+    let out: (usize, bool, usize) = rune! {
+        pub fn main() {
+            let v = Vec::with_capacity(16);
+            let capacity = v.capacity();
+
+            for n in 0..16 {
+                v.push(n);
+            }
+
+            (v.len(), v.capacity() == capacity, capacity)
+        }
+    };
+
+    let (len, unchanged, capacity) = out;
+    assert_eq!(len, 16);
+    assert!(unchanged);
+    assert!(capacity >= 16);
+}
+
+#[test]
+fn test_vec_reserve() {
+    let out: (usize, bool) = rune! {
+        pub fn main() {
+            let v = [1, 2];
+            v.reserve(10);
+            let capacity = v.capacity();
+
+            for n in 0..10 {
+                v.push(n);
+            }
+
+            (v.len(), capacity >= 12 && v.capacity() == capacity)
+        }
+    };
+
+    assert_eq!(out, (12, true));
+}
+
+#[test]
+fn test_vec_negative_capacity() {
+    assert_vm_error!(
+        "pub fn main() { Vec::with_capacity(-1) }",
+        BadArgument { arg: 0, error } => {
+            assert!(matches!(error.kind(), ValueToIntegerCoercionError { to: "usize", .. }));
+        }
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let v = []; v.reserve(-1) }",
+        BadArgument { arg: 1, .. } => {}
+    );
+
+    assert_vm_error!(
+        "pub fn main() { let v = [1]; v.reserve(9223372036854775807) }",
+        CapacityOverflow { capacity } => {
+            assert_eq!(capacity, 1 << 63);
+        }
+    );
+}
+
+#[test]
+fn test_vec_capacity_grows() {
+    let out: (usize, usize, usize) = rune! {
+        pub fn main() {
+            let v = [];
+            let empty = v.capacity();
+
+            for n in 0..100 {
+                v.push(n);
+            }
+
+            (empty, v.len(), v.capacity())
+        }
+    };
+
+    let (empty, len, capacity) = out;
+    assert_eq!(empty, 0);
+    assert_eq!(len, 100);
+    assert!(capacity >= 100);
+}
+
+#[test]
+fn test_vec_shrink_to_fit() {
+    let out: (usize, usize, usize) = rune! {
+        pub fn main() {
+            let v = Vec::with_capacity(64);
+
+            for n in 0..64 {
+                v.push(n);
+            }
+
+            while v.len() > 4 {
+                v.pop();
+            }
+
+            let before = v.capacity();
+            v.shrink_to_fit();
+            (v.len(), before, v.capacity())
+        }
+    };
+
+    let (len, before, after) = out;
+    assert_eq!(len, 4);
+    assert!(before >= 64);
+    assert!(after >= 4 && after < before);
+}
+
+#[test]
+fn test_vec_literal_exact_capacity() {
+    let out: (usize, usize, usize, usize) = rune! {
+        pub fn main() {
+            let empty = [];
+            let v = [1, 2, 3, 4, 5];
+            let spread = [1, 2, ..[], 3];
+            (empty.capacity(), v.capacity(), spread.len(), spread.capacity())
+        }
+    };
+
+    assert_eq!(out, (0, 5, 3, 3));
+}